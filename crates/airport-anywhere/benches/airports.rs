@@ -0,0 +1,63 @@
+//! Lookup and search latency on the full embedded airport dataset.
+//!
+//! Run with `cargo bench -p airport-anywhere`.
+
+use airport_anywhere::models::AirportsService;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn loading(c: &mut Criterion) {
+    c.bench_function("load_and_index", |b| {
+        b.iter(|| AirportsService::embedded().unwrap())
+    });
+}
+
+fn lookups(c: &mut Criterion) {
+    let airports = AirportsService::embedded().unwrap();
+    let mut group = c.benchmark_group("find_by_code");
+
+    group.bench_function("iata", |b| {
+        b.iter(|| airports.find_by_code(black_box("FRA")).unwrap())
+    });
+    group.bench_function("icao", |b| {
+        b.iter(|| airports.find_by_code(black_box("EDDF")).unwrap())
+    });
+    group.bench_function("prefix", |b| {
+        b.iter(|| airports.find_by_code(black_box("EDD")).unwrap())
+    });
+    group.bench_function("missing", |b| {
+        b.iter(|| airports.find_by_code(black_box("QQQQ")).is_err())
+    });
+    group.finish();
+}
+
+fn search(c: &mut Criterion) {
+    let airports = AirportsService::embedded().unwrap();
+    let mut group = c.benchmark_group("search");
+
+    for query in [
+        "frankfurt",
+        "international",
+        "zz",
+        "LIS",
+        "frankfrt am main",
+    ] {
+        group.bench_function(query, |b| b.iter(|| airports.search(black_box(query), 50)));
+    }
+    group.finish();
+}
+
+fn nearby(c: &mut Criterion) {
+    let airports = AirportsService::embedded().unwrap();
+    let mut group = c.benchmark_group("nearby");
+
+    group.bench_function("frankfurt_100km", |b| {
+        b.iter(|| airports.nearby(black_box((50.0333, 8.5706)), 100.0, 10))
+    });
+    group.bench_function("mid_atlantic_2000km", |b| {
+        b.iter(|| airports.nearby(black_box((40.0, -40.0)), 2000.0, 10))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, loading, lookups, search, nearby);
+criterion_main!(benches);
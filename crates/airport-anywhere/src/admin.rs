@@ -0,0 +1,92 @@
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+use crate::routes::ApiError;
+
+/// Environment variable with the bearer token for administrative endpoints
+pub const AIRPORTS_ADMIN_TOKEN_ENV: &str = "AIRPORTS_ADMIN_TOKEN";
+
+/// Bearer token guarding administrative endpoints.
+///
+/// Without a configured token the endpoints are disabled and answer `503`,
+/// so a deployment that forgot the token does not expose them.
+#[derive(Debug, Clone, Default)]
+pub struct AdminToken(Option<Arc<str>>);
+
+impl AdminToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(Some(token.into().into()))
+    }
+
+    /// The token configured in `AIRPORTS_ADMIN_TOKEN`, if any
+    pub fn from_env() -> Self {
+        match std::env::var(AIRPORTS_ADMIN_TOKEN_ENV) {
+            Ok(token) if !token.trim().is_empty() => Self::new(token.trim()),
+            _ => Self::default(),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn permits(&self, headers: &HeaderMap) -> bool {
+        let Some(expected) = &self.0 else {
+            return false;
+        };
+        // Compared in constant time, so timing does not reveal the token
+        headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| bool::from(token.trim().as_bytes().ct_eq(expected.as_bytes())))
+    }
+}
+
+/// Rejects requests without the admin bearer token, and all of them if no
+/// token is configured
+pub async fn require_admin(
+    State(token): State<AdminToken>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !token.is_configured() {
+        ApiError::AdminDisabled.into_response()
+    } else if token.permits(request.headers()) {
+        next.run(request).await
+    } else {
+        ApiError::Unauthorized.into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(authorization: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static(authorization));
+        headers
+    }
+
+    #[test]
+    fn test_token_check() {
+        let token = AdminToken::new("secret");
+
+        assert!(token.permits(&headers("Bearer secret")));
+        assert!(!token.permits(&headers("Bearer wrong")));
+        assert!(!token.permits(&headers("secret")));
+        assert!(!token.permits(&HeaderMap::new()));
+        assert!(!token.permits(&headers("Bearer secrets")));
+        // Without a token nothing is let through
+        assert!(!AdminToken::default().permits(&HeaderMap::new()));
+        assert!(!AdminToken::default().permits(&headers("Bearer ")));
+    }
+}
@@ -0,0 +1,92 @@
+use axum::{
+    extract::{Request, State},
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use sky_tracer::protocol::airports::ExportFormat;
+
+use crate::export;
+use crate::services::AirportService;
+
+/// Conditional GETs for the airport API
+///
+/// Every response is derived from the loaded dataset alone, so its version
+/// is a valid entity tag for all of them. Successful GETs are tagged and
+/// requests already holding the current tag are answered with
+/// `304 Not Modified` without running the handler. GeoJSON and KML asked
+/// for by `Accept` get tags of their own.
+pub async fn etag(State(service): State<AirportService>, request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    // Representations negotiated by `Accept` share the URL, tell them apart
+    let tag = match export::negotiate(None, request.headers()) {
+        ExportFormat::Json => format!("\"{:016x}\"", service.airports().version()),
+        format => format!(
+            "\"{:016x}-{}\"",
+            service.airports().version(),
+            format_name(format)
+        ),
+    };
+    let tag =
+        HeaderValue::from_str(&tag).expect("hex digits and format names are a valid header value");
+
+    if matches_any(request.headers(), &tag) {
+        return (StatusCode::NOT_MODIFIED, [(ETAG, tag)]).into_response();
+    }
+
+    let mut response = next.run(request).await;
+    if response.status() == StatusCode::OK {
+        response.headers_mut().insert(ETAG, tag);
+    }
+    response
+}
+
+fn format_name(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Json => "json",
+        ExportFormat::GeoJson => "geojson",
+        ExportFormat::Kml => "kml",
+    }
+}
+
+/// Weak comparison as required for `If-None-Match`, `W/` prefixes are ignored
+fn matches_any(headers: &HeaderMap, tag: &HeaderValue) -> bool {
+    let Ok(tag) = tag.to_str() else {
+        return false;
+    };
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_if_none_match() {
+        let tag = HeaderValue::from_static("\"00ff\"");
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(IF_NONE_MATCH, HeaderValue::from_static(value));
+            headers
+        };
+
+        assert!(matches_any(&headers("\"00ff\""), &tag));
+        assert!(matches_any(&headers("\"abcd\", W/\"00ff\""), &tag));
+        assert!(matches_any(&headers("*"), &tag));
+        assert!(!matches_any(&headers("\"abcd\""), &tag));
+        assert!(!matches_any(&HeaderMap::new(), &tag));
+    }
+}
@@ -0,0 +1,374 @@
+use axum::{
+    http::{
+        header::{ACCEPT, CONTENT_TYPE, VARY},
+        HeaderMap, HeaderValue,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use geojson::{feature::Id, Feature, FeatureCollection, Geometry, JsonValue};
+use serde::Serialize;
+use sky_tracer::protocol::airports::{
+    AirportResponse, ExportFormat, ListAirportsResponse, NearbyAirportResponse,
+    NearbyAirportsResponse, Position, SearchAirportsResponse,
+};
+use std::fmt::Write;
+
+use crate::routes::ApiError;
+
+/// Name of the KML document holding the placemarks
+const KML_DOCUMENT_NAME: &str = "Airports";
+
+/// Airport entry that can be placed on a map
+pub trait AsFeature: Serialize {
+    fn id(&self) -> u32;
+    fn name(&self) -> &str;
+    fn position(&self) -> &Position;
+
+    /// Point feature whose properties are the JSON representation of the entry
+    fn to_feature(&self) -> Feature {
+        let properties = match serde_json::to_value(self) {
+            Ok(JsonValue::Object(properties)) => Some(properties),
+            _ => None,
+        };
+        let position = self.position();
+        Feature {
+            bbox: None,
+            geometry: Some(Geometry::new_point([position.longitude, position.latitude])),
+            id: Some(Id::Number(self.id().into())),
+            properties,
+            foreign_members: None,
+        }
+    }
+}
+
+impl AsFeature for AirportResponse {
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+}
+
+impl AsFeature for NearbyAirportResponse {
+    fn id(&self) -> u32 {
+        self.airport.id
+    }
+
+    fn name(&self) -> &str {
+        &self.airport.name
+    }
+
+    fn position(&self) -> &Position {
+        &self.airport.position
+    }
+}
+
+/// Response body listing airports under an `airports` field
+pub trait AirportCollection: Serialize {
+    type Airport: AsFeature;
+
+    fn airports(&self) -> &[Self::Airport];
+}
+
+impl AirportCollection for ListAirportsResponse {
+    type Airport = AirportResponse;
+
+    fn airports(&self) -> &[AirportResponse] {
+        &self.airports
+    }
+}
+
+impl AirportCollection for SearchAirportsResponse {
+    type Airport = AirportResponse;
+
+    fn airports(&self) -> &[AirportResponse] {
+        &self.airports
+    }
+}
+
+impl AirportCollection for NearbyAirportsResponse {
+    type Airport = NearbyAirportResponse;
+
+    fn airports(&self) -> &[NearbyAirportResponse] {
+        &self.airports
+    }
+}
+
+/// Format asked for by the `format` parameter or else the `Accept` header.
+///
+/// The first media type of the header that is known wins, JSON is the
+/// fallback for anything else including `*/*`.
+pub fn negotiate(format: Option<ExportFormat>, headers: &HeaderMap) -> ExportFormat {
+    format.unwrap_or_else(|| {
+        headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find_map(ExportFormat::from_media_type)
+            .unwrap_or_default()
+    })
+}
+
+/// GeoJSON `FeatureCollection` of the airports.
+///
+/// All other fields of the body, like the paging of the list, become
+/// foreign members of the collection.
+pub fn feature_collection<C: AirportCollection>(body: &C) -> FeatureCollection {
+    let foreign_members = match serde_json::to_value(body) {
+        Ok(JsonValue::Object(mut members)) => {
+            members.remove("airports");
+            Some(members).filter(|members| !members.is_empty())
+        }
+        _ => None,
+    };
+
+    FeatureCollection {
+        bbox: None,
+        features: body.airports().iter().map(AsFeature::to_feature).collect(),
+        foreign_members,
+    }
+}
+
+/// KML document with a placemark per airport.
+///
+/// The JSON fields of an airport are kept as `ExtendedData`, nested values
+/// like the position are left out as they are part of the point.
+pub fn kml<T: AsFeature>(airports: &[T]) -> String {
+    let mut kml = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "\n",
+        r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#,
+        "\n<Document>\n"
+    ));
+    let _ = writeln!(kml, "<name>{}</name>", escape(KML_DOCUMENT_NAME));
+
+    for airport in airports {
+        let position = airport.position();
+        let _ = writeln!(kml, "<Placemark id=\"airport-{}\">", airport.id());
+        let _ = writeln!(kml, "<name>{}</name>", escape(airport.name()));
+
+        if let Ok(JsonValue::Object(properties)) = serde_json::to_value(airport) {
+            kml.push_str("<ExtendedData>\n");
+            for (name, value) in properties {
+                let value = match value {
+                    JsonValue::String(value) => value,
+                    JsonValue::Object(_) | JsonValue::Array(_) | JsonValue::Null => continue,
+                    value => value.to_string(),
+                };
+                let _ = writeln!(
+                    kml,
+                    "<Data name=\"{}\"><value>{}</value></Data>",
+                    escape(&name),
+                    escape(&value)
+                );
+            }
+            kml.push_str("</ExtendedData>\n");
+        }
+
+        let _ = writeln!(
+            kml,
+            "<Point><coordinates>{},{}</coordinates></Point>",
+            position.longitude, position.latitude
+        );
+        kml.push_str("</Placemark>\n");
+    }
+
+    kml.push_str("</Document>\n</kml>\n");
+    kml
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders `body` in `format`.
+///
+/// The representation depends on `Accept`, which caches learn from `Vary`.
+pub fn respond<C: AirportCollection>(format: ExportFormat, body: C) -> Response {
+    let mut response = match format {
+        ExportFormat::Json => Json(body).into_response(),
+        ExportFormat::GeoJson => match serde_json::to_vec(&feature_collection(&body)) {
+            Ok(geojson) => geojson.into_response(),
+            Err(e) => return ApiError::ServiceError(e.to_string()).into_response(),
+        },
+        ExportFormat::Kml => kml(body.airports()).into_response(),
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    headers.insert(VARY, HeaderValue::from_static("accept"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geojson::JsonObject;
+    use sky_tracer::model::airport::Airport;
+
+    fn airports() -> Vec<AirportResponse> {
+        let mut odd = AirportResponse::from(&Airport::paris());
+        odd.name = "Tom & Jerry's <\"Airfield\">".to_string();
+        odd.elevation_ft = None;
+        vec![AirportResponse::from(&Airport::frankfurt()), odd]
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let accept = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(ACCEPT, HeaderValue::from_static(value));
+            headers
+        };
+
+        assert_eq!(negotiate(None, &HeaderMap::new()), ExportFormat::Json);
+        assert_eq!(negotiate(None, &accept("*/*")), ExportFormat::Json);
+        assert_eq!(
+            negotiate(None, &accept("text/html, application/geo+json;q=0.9")),
+            ExportFormat::GeoJson
+        );
+        assert_eq!(
+            negotiate(None, &accept(ExportFormat::Kml.content_type())),
+            ExportFormat::Kml
+        );
+        // The parameter beats the header
+        assert_eq!(
+            negotiate(Some(ExportFormat::Json), &accept("application/geo+json")),
+            ExportFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_geojson_round_trip() {
+        let page = ListAirportsResponse {
+            airports: airports(),
+            total: 10,
+            offset: 0,
+            limit: 2,
+            next_offset: Some(2),
+            next_cursor: None,
+        };
+        let json = serde_json::to_string(&feature_collection(&page)).unwrap();
+
+        let collection: FeatureCollection = json.parse().unwrap();
+        assert_eq!(collection.features.len(), 2);
+        let members = collection.foreign_members.as_ref().unwrap();
+        assert_eq!(members["total"], 10);
+        assert_eq!(members["next_offset"], 2);
+        assert!(!members.contains_key("airports"));
+
+        for (feature, airport) in collection.features.into_iter().zip(&page.airports) {
+            let geojson::GeometryValue::Point { coordinates } = feature.geometry.unwrap().value
+            else {
+                panic!("airports are points");
+            };
+            assert_eq!(
+                coordinates.as_slice(),
+                [airport.position.longitude, airport.position.latitude]
+            );
+            assert_eq!(feature.id, Some(Id::Number(airport.id.into())));
+
+            let properties = JsonValue::Object(feature.properties.unwrap());
+            let parsed: AirportResponse = serde_json::from_value(properties).unwrap();
+            assert_eq!(&parsed, airport);
+        }
+    }
+
+    #[test]
+    fn test_geojson_keeps_distance() {
+        let nearby = NearbyAirportsResponse {
+            airports: vec![NearbyAirportResponse {
+                airport: AirportResponse::from(&Airport::frankfurt()),
+                distance_km: 3.5,
+            }],
+        };
+        let collection = feature_collection(&nearby);
+
+        assert!(collection.foreign_members.is_none());
+        let properties = collection.features[0].properties.as_ref().unwrap();
+        assert_eq!(properties["distance_km"], 3.5);
+        assert_eq!(properties["code"], "FRA");
+    }
+
+    #[test]
+    fn test_kml_round_trip() {
+        let airports = airports();
+        let kml = kml(&airports);
+
+        let document = roxmltree::Document::parse(&kml).unwrap();
+        let placemarks: Vec<_> = document
+            .descendants()
+            .filter(|node| node.has_tag_name("Placemark"))
+            .collect();
+        assert_eq!(placemarks.len(), airports.len());
+
+        for (placemark, airport) in placemarks.into_iter().zip(&airports) {
+            let child_text = |name: &str| {
+                placemark
+                    .descendants()
+                    .find(|node| node.has_tag_name(name))
+                    .and_then(|node| node.text())
+                    .unwrap()
+                    .to_string()
+            };
+            let data: JsonObject = placemark
+                .descendants()
+                .filter(|node| node.has_tag_name("Data"))
+                .map(|node| {
+                    let value = node
+                        .first_element_child()
+                        .unwrap()
+                        .text()
+                        .unwrap_or_default();
+                    (node.attribute("name").unwrap().to_string(), value.into())
+                })
+                .collect();
+            let coordinates: Vec<f64> = child_text("coordinates")
+                .split(',')
+                .map(|value| value.parse().unwrap())
+                .collect();
+
+            assert_eq!(child_text("name"), airport.name);
+            assert_eq!(
+                coordinates,
+                [airport.position.longitude, airport.position.latitude]
+            );
+            assert_eq!(data["id"], airport.id.to_string());
+            assert_eq!(data["code"], airport.code);
+            assert_eq!(data["country"], airport.country);
+            assert_eq!(
+                data.get("elevation_ft").and_then(JsonValue::as_str),
+                airport.elevation_ft.map(|e| e.to_string()).as_deref()
+            );
+            assert_eq!(
+                serde_json::from_value::<sky_tracer::model::AirportType>(
+                    data["airport_type"].clone()
+                )
+                .unwrap(),
+                airport.airport_type
+            );
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! Airport data shared by the tests of this crate
+
+/// Airport `TST` in the OpenFlights format
+pub const LINE: &str = r#"1,"Test Airport","Test City","Test Country","TST","TTST",1.0,2.0,0,0,"E","UTC","airport","test""#;
+/// Airport `OTH` in the OpenFlights format
+pub const OTHER_LINE: &str = r#"2,"Other Airport","Other City","Test Country","OTH","TOTH",3.0,4.0,0,0,"E","UTC","airport","test""#;
@@ -1,30 +1,402 @@
+pub mod admin;
+pub mod etag;
+pub mod export;
 pub mod models;
 pub mod openapi;
 pub mod routes;
 pub mod services;
+pub mod watcher;
+
+#[cfg(test)]
+mod fixtures;
 
 #[cfg(feature = "ssr")]
 pub mod ui;
 
-use axum::{routing::get, Router};
-use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
-use sky_tracer::protocol::{AIRPORTS_API_PATH, AIRPORTS_SEARCH_API_PATH};
+use admin::AdminToken;
+use axum::{
+    http::header::ETAG,
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+use services::AirportService;
+use sky_tracer::protocol::health::health_routes;
+use sky_tracer::protocol::metrics::{metrics_routes, observe};
+use sky_tracer::protocol::problem::problem_instance;
+use sky_tracer::protocol::{
+    AIRPORTS_API_PATH, AIRPORTS_AUTOCOMPLETE_API_PATH, AIRPORTS_FREQUENCIES_API_PATH,
+    AIRPORTS_NEARBY_API_PATH, AIRPORTS_OVERLAY_API_PATH, AIRPORTS_RELOAD_API_PATH,
+    AIRPORTS_RUNWAYS_API_PATH, AIRPORTS_SEARCH_API_PATH, AIRPORT_API_PATH,
+};
 use tower_http::cors::{Any, CorsLayer};
 
+/// Application backed by the airport data compiled into the binary
 pub fn app() -> Router {
+    app_with_service(AirportService::embedded().expect("embedded airport data is valid"))
+}
+
+/// Application over `airport_service`, guarded by the token in
+/// `AIRPORTS_ADMIN_TOKEN` and without admin endpoints while it is unset
+pub fn app_with_service(airport_service: AirportService) -> Router {
+    app_with_admin_token(airport_service, AdminToken::from_env())
+}
+
+/// Application with the airport search page at `/`
+#[cfg(feature = "ssr")]
+pub fn web_app(airport_service: AirportService, admin_token: AdminToken) -> Router {
+    let page = Router::new().route("/", get(ui::render_page));
+    observe("airport-anywhere", page, Router::new())
+        .with_state(airport_service.clone())
+        .merge(app_with_admin_token(airport_service, admin_token))
+}
+
+pub fn app_with_admin_token(airport_service: AirportService, admin_token: AdminToken) -> Router {
+    let admin = || middleware::from_fn_with_state(admin_token.clone(), admin::require_admin);
+
+    // The token is checked before conditional GETs, a `304` would reveal
+    // the state of the overlay
+    let overlay_router = Router::new()
+        .route(
+            AIRPORTS_OVERLAY_API_PATH,
+            get(routes::airport_overlay).delete(routes::clear_airport_overlay),
+        )
+        .layer(middleware::from_fn_with_state(
+            airport_service.clone(),
+            etag::etag,
+        ))
+        .route_layer(admin());
+
     let api_router = Router::new()
-        .route(AIRPORTS_API_PATH, get(routes::list_airports))
+        .route(
+            AIRPORTS_API_PATH,
+            get(routes::list_airports).merge(post(routes::create_airport).route_layer(admin())),
+        )
         .route(AIRPORTS_SEARCH_API_PATH, get(routes::search_airports))
+        .route(AIRPORTS_NEARBY_API_PATH, get(routes::nearby_airports))
+        .route(
+            AIRPORTS_AUTOCOMPLETE_API_PATH,
+            get(routes::autocomplete_airports),
+        )
+        .route(
+            AIRPORT_API_PATH,
+            get(routes::get_airport).merge(
+                put(routes::update_airport)
+                    .delete(routes::delete_airport)
+                    .route_layer(admin()),
+            ),
+        )
+        .route(AIRPORTS_RUNWAYS_API_PATH, get(routes::airport_runways))
+        .route(
+            AIRPORTS_FREQUENCIES_API_PATH,
+            get(routes::airport_frequencies),
+        )
+        .route(
+            AIRPORTS_RELOAD_API_PATH,
+            post(routes::reload_airports).route_layer(admin()),
+        )
+        .layer(middleware::from_fn_with_state(
+            airport_service.clone(),
+            etag::etag,
+        ))
+        .merge(overlay_router)
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
-                .allow_headers(Any),
+                .allow_headers(Any)
+                .expose_headers([ETAG]),
         );
 
-    Router::new()
+    let app = Router::new()
         .merge(openapi::routes())
         .merge(api_router)
-        .layer(OtelInResponseLayer)
-        .layer(OtelAxumLayer::default())
+        .layer(middleware::from_fn(problem_instance));
+    let probes = health_routes("airport-anywhere", |service: AirportService| async move {
+        vec![service.data_health()]
+    })
+    .merge(metrics_routes(|_: AirportService| async {}));
+    observe("airport-anywhere", app, probes).with_state(airport_service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{
+            header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY},
+            Request, StatusCode,
+        },
+    };
+    use sky_tracer::protocol::airports::ReloadAirportsResponse;
+    use sky_tracer::protocol::health::HealthResponse;
+    use sky_tracer::protocol::problem::{ProblemDetails, PROBLEM_JSON};
+    use tower::ServiceExt;
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reload_requires_admin_token() {
+        let app = app_with_admin_token(
+            AirportService::embedded().unwrap(),
+            AdminToken::new("secret"),
+        );
+        let reload = |token: Option<&str>| {
+            let mut request = Request::post("/api/v1/airports/reload");
+            if let Some(token) = token {
+                request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(reload(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app.clone().oneshot(reload(Some("wrong"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.oneshot(reload(Some("secret"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: ReloadAirportsResponse = serde_json::from_slice(&body).unwrap();
+        assert!(report.reloaded);
+        assert!(!report.changed);
+        assert_eq!(report.source, "embedded");
+    }
+
+    #[tokio::test]
+    async fn test_admin_endpoints_are_disabled_without_token() {
+        let app = app_with_admin_token(AirportService::embedded().unwrap(), AdminToken::default());
+
+        for request in [
+            Request::post("/api/v1/airports")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap(),
+            Request::post("/api/v1/airports/reload")
+                .body(Body::empty())
+                .unwrap(),
+            Request::delete("/api/v1/airports/overlay")
+                .header(AUTHORIZATION, "Bearer ")
+                .body(Body::empty())
+                .unwrap(),
+        ] {
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        }
+        let response = app.oneshot(get("/api/v1/airports/FRA")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_overlay_is_not_revealed_by_conditional_requests() {
+        let app = app_with_admin_token(
+            AirportService::embedded().unwrap(),
+            AdminToken::new("secret"),
+        );
+        let overlay = |token: Option<&str>| {
+            let mut request = get("/api/v1/airports/overlay");
+            request
+                .headers_mut()
+                .insert(IF_NONE_MATCH, "*".parse().unwrap());
+            if let Some(token) = token {
+                request
+                    .headers_mut()
+                    .insert(AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+            }
+            request
+        };
+
+        let response = app.clone().oneshot(overlay(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app.oneshot(overlay(Some("secret"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn test_list_supports_conditional_requests() {
+        let app = app();
+
+        let response = app
+            .clone()
+            .oneshot(get("/api/v1/airports?limit=2&sort=name"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let tag = response.headers().get(ETAG).unwrap().clone();
+
+        let mut request = get("/api/v1/airports?limit=2&sort=name");
+        request.headers_mut().insert(IF_NONE_MATCH, tag.clone());
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(ETAG), Some(&tag));
+
+        // Rejected requests are not tagged
+        let response = app
+            .oneshot(get("/api/v1/airports?limit=many"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response.headers().get(ETAG).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_nearby_negotiates_format() {
+        let app = app();
+        let uri = "/api/v1/airports/nearby?lat=50.03&lon=8.57&limit=3";
+
+        let json = app.clone().oneshot(get(uri)).await.unwrap();
+        assert_eq!(json.headers()[CONTENT_TYPE], "application/json");
+
+        let mut request = get(uri);
+        request.headers_mut().insert(
+            ACCEPT,
+            "application/vnd.google-earth.kml+xml".parse().unwrap(),
+        );
+        let kml = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(kml.status(), StatusCode::OK);
+        assert_eq!(
+            kml.headers()[CONTENT_TYPE],
+            "application/vnd.google-earth.kml+xml"
+        );
+        assert_eq!(kml.headers()[VARY], "accept");
+        assert_ne!(kml.headers()[ETAG], json.headers()[ETAG]);
+        let body = axum::body::to_bytes(kml.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8(body.to_vec())
+            .unwrap()
+            .contains("<Placemark"));
+
+        let response = app
+            .oneshot(get(&format!("{uri}&format=shapefile")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_airport_writes_require_admin_token() {
+        let app = app_with_admin_token(
+            AirportService::new(
+                models::AirportsService::from_csv_str(
+                    r#"1,"Test Airport","Test City","Test Country","TST","TTST",1.0,2.0,0,0,"E","UTC","airport","test""#,
+                )
+                .unwrap(),
+            ),
+            AdminToken::new("secret"),
+        );
+        let write = |method: &str, uri: &str, token: Option<&str>, body: &str| {
+            let mut request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+            }
+            request.body(Body::from(body.to_string())).unwrap()
+        };
+        let strip = r#"{"name": "Private Strip", "code": "XPS", "position": {"latitude": 1.5, "longitude": 2.5}}"#;
+
+        let response = app
+            .clone()
+            .oneshot(write("POST", "/api/v1/airports", None, strip))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        // Reading stays open
+        let response = app.clone().oneshot(get("/api/v1/airports")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(write("POST", "/api/v1/airports", Some("secret"), strip))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let response = app
+            .clone()
+            .oneshot(write("POST", "/api/v1/airports", Some("secret"), strip))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let response = app
+            .clone()
+            .oneshot(write("POST", "/api/v1/airports", Some("secret"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = app
+            .clone()
+            .oneshot(get("/api/v1/airports/xps"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(write("DELETE", "/api/v1/airports/TST", Some("secret"), ""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app
+            .clone()
+            .oneshot(get("/api/v1/airports/TST"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .clone()
+            .oneshot(get("/api/v1/airports/overlay"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app
+            .oneshot(write(
+                "DELETE",
+                "/api/v1/airports/overlay",
+                Some("secret"),
+                "",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_ready_reports_loaded_data() {
+        let response = app().oneshot(get("/health/ready")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: HealthResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report.service, "airport-anywhere");
+        assert_eq!(report.dependencies[0].name, "airport-data");
+        assert!(report.is_up());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parameters_are_problems() {
+        let response = app()
+            .oneshot(get("/api/v1/airports/nearby?lat=north&lon=8.57"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()[CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code.as_deref(), Some("INVALID_PARAMETER"));
+        assert_eq!(problem.instance.as_deref(), Some("/api/v1/airports/nearby"));
+    }
 }
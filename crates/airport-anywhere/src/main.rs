@@ -1,78 +1,10 @@
 use airport_anywhere::{
+    admin::AdminToken,
+    models::{overlay_path_from_env, AirportSource},
     services::AirportService,
-    ui::pages::{Home, HomeProps},
+    watcher,
 };
-use axum::{extract::Query, response::Html, routing::get, Router};
-use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
-use serde::Deserialize;
-use sky_tracer::protocol::airports::AirportResponse;
-use tracing::{error, info, instrument};
-
-#[derive(Debug, Deserialize)]
-struct SearchParams {
-    q: Option<String>,
-}
-
-#[instrument(skip(params))]
-async fn render_page(Query(params): Query<SearchParams>) -> Html<String> {
-    info!(?params, "Rendering page with search parameters");
-
-    let airports: Vec<AirportResponse> = if let Some(query) = &params.q {
-        info!(?query, "Searching for airports");
-
-        // Try searching by code first, then by name
-        let code_results = AirportService::search_by_code(query)
-            .await
-            .unwrap_or_default();
-
-        if !code_results.is_empty() {
-            info!("Found airports by code");
-            code_results
-        } else {
-            info!("Searching airports by name");
-            AirportService::search_by_name(query)
-                .await
-                .unwrap_or_default()
-        }
-    } else {
-        info!("Listing all airports");
-        match AirportService::get_all_airports().await {
-            Ok(airports) => airports,
-            Err(e) => {
-                error!(error = %e, "Failed to load airports");
-                vec![]
-            }
-        }
-    };
-
-    info!(airports_found = airports.len(), "Found airports");
-
-    let renderer = yew::ServerRenderer::<Home>::with_props(move || HomeProps {
-        airports,
-        query: params.q,
-    });
-
-    let html = renderer.render().await;
-
-    Html(format!(
-        r#"<!DOCTYPE html>
-        <html>
-            <head>
-                <meta charset="utf-8" />
-                <meta name="viewport" content="width=device-width, initial-scale=1.0">
-                <title>Airport Anywhere</title>
-                <style>
-                    {}
-                </style>
-            </head>
-            <body>
-                {}
-            </body>
-        </html>"#,
-        include_str!("../assets/styles.css"),
-        html
-    ))
-}
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
@@ -89,11 +21,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
 
     info!("Starting {} service on port {}", service_name, service_port);
 
-    let app = Router::new()
-        .route("/", get(render_page))
-        .merge(airport_anywhere::app())
-        .layer(OtelInResponseLayer)
-        .layer(OtelAxumLayer::default());
+    // Parse and index the dataset once, all requests share it
+    let source = AirportSource::from_env();
+    let mut airport_service = AirportService::from_source(source.clone())?;
+    match overlay_path_from_env() {
+        Some(path) => airport_service = airport_service.with_overlay_file(path)?,
+        None => warn!("AIRPORTS_OVERLAY_PATH is not set, airport changes are kept in memory only"),
+    }
+    info!(
+        source = %source,
+        airports = airport_service.airports().len(),
+        "Airport data loaded"
+    );
+
+    // Keep the watcher alive for the lifetime of the server
+    let _watcher = watcher::watch(airport_service.clone()).unwrap_or_else(|e| {
+        warn!(error = %e, "Cannot watch airport data file, reload it via the API");
+        None
+    });
+
+    let admin_token = AdminToken::from_env();
+    if !admin_token.is_configured() {
+        warn!("AIRPORTS_ADMIN_TOKEN is not set, admin endpoints are disabled");
+    }
+
+    let app = airport_anywhere::web_app(airport_service, admin_token);
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", service_port)).await?;
     info!("Server running on http://localhost:{}", service_port);
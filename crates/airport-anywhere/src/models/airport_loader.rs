@@ -1,35 +1,136 @@
 use csv::ReaderBuilder;
-use sky_tracer::model::airport::{Airport, AirportError};
+use sky_tracer::model::airport::{Airport, AirportError, AirportType, Frequency, Runway};
 use std::sync::Arc;
 
-pub fn load_airports_from_csv(data: &str) -> Result<Vec<Arc<Airport>>, AirportError> {
-    let mut rdr = ReaderBuilder::new()
+/// Placeholder OpenFlights uses for missing values
+const MISSING: &str = "\\N";
+
+/// A data line that could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    /// Name of the data file, when known
+    pub file: Option<String>,
+    /// 1-based line number in the data
+    pub line: u64,
+    pub message: String,
+}
+
+impl LineError {
+    pub fn new(line: u64, message: impl Into<String>) -> Self {
+        Self {
+            file: None,
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+/// Result of a lenient load, every line ends up in exactly one of the lists
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    pub airports: Vec<Arc<Airport>>,
+    pub runways: Vec<Runway>,
+    pub frequencies: Vec<Frequency>,
+    pub errors: Vec<LineError>,
+}
+
+fn reader(data: &str) -> csv::Reader<&[u8]> {
+    ReaderBuilder::new()
         .has_headers(false)
         .delimiter(b',')
-        .from_reader(data.as_bytes());
+        .from_reader(data.as_bytes())
+}
 
+/// Parses all airports, failing on the first invalid line
+pub fn load_airports_from_csv(data: &str) -> Result<Vec<Arc<Airport>>, AirportError> {
+    let mut rdr = reader(data);
     let mut airports = Vec::new();
     let mut record = csv::ByteRecord::new();
 
     while rdr.read_byte_record(&mut record)? {
-        let id = parse_field(&record, 0, "id")?;
-        let name = parse_utf8(&record, 1, "name")?;
-        let iata = parse_utf8(&record, 4, "iata")?.trim().to_string();
-        let icao = parse_utf8(&record, 5, "icao")?.trim().to_string();
-        let latitude = parse_field(&record, 6, "latitude")?;
-        let longitude = parse_field(&record, 7, "longitude")?;
-
-        // Store just the IATA code as the primary code
-        let code = if !iata.is_empty() { iata } else { icao.clone() };
-
-        let airport = Airport::new(id, latitude, longitude, name, code);
-        airports.push(Arc::new(airport));
+        airports.push(Arc::new(parse_record(&record)?));
     }
 
     tracing::info!("Parsed {} airports", airports.len());
     Ok(airports)
 }
 
+/// Parses all valid airports and reports the invalid lines instead of failing
+pub fn load_airports_lenient(data: &str) -> LoadReport {
+    let mut rdr = reader(data);
+    let mut report = LoadReport::default();
+    let mut record = csv::ByteRecord::new();
+
+    loop {
+        let line = rdr.position().line();
+        match rdr.read_byte_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => match parse_record(&record) {
+                Ok(airport) => report.airports.push(Arc::new(airport)),
+                Err(e) => report.errors.push(LineError::new(
+                    record.position().map_or(line, |position| position.line()),
+                    e.to_string(),
+                )),
+            },
+            Err(e) => {
+                let io_error = e.is_io_error();
+                report.errors.push(LineError::new(
+                    e.position().map_or(line, |position| position.line()),
+                    e.to_string(),
+                ));
+                if io_error {
+                    break;
+                }
+            }
+        }
+    }
+
+    tracing::info!(
+        airports = report.airports.len(),
+        invalid_lines = report.errors.len(),
+        "Parsed airports"
+    );
+    report
+}
+
+fn parse_record(record: &csv::ByteRecord) -> Result<Airport, AirportError> {
+    let id = parse_field(record, 0, "id")?;
+    let name = parse_utf8(record, 1, "name")?;
+    let city = parse_optional(record, 2, "city")?.unwrap_or_default();
+    let country = parse_optional(record, 3, "country")?.unwrap_or_default();
+    let iata = parse_optional(record, 4, "iata")?;
+    let icao = parse_optional(record, 5, "icao")?;
+    let latitude = parse_field(record, 6, "latitude")?;
+    let longitude = parse_field(record, 7, "longitude")?;
+    let elevation_ft = parse_optional(record, 8, "altitude")?
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| AirportError::invalid_value("altitude", value))
+        })
+        .transpose()?;
+    let timezone = parse_optional(record, 11, "tz")?;
+    let airport_type = parse_optional(record, 12, "type")?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(AirportType::Unknown);
+
+    // The IATA code is the primary code, ICAO only fills in where none is assigned.
+    // Some entries carry local identifiers like "07FA" in the ICAO column, those
+    // are kept as primary code but not taken for ICAO indicators.
+    let code = iata.clone().or_else(|| icao.clone()).unwrap_or_default();
+
+    Ok(Airport {
+        iata: iata.and_then(|code| code.parse().ok()),
+        icao: icao.and_then(|code| code.parse().ok()),
+        city,
+        country,
+        elevation_ft,
+        timezone,
+        airport_type,
+        ..Airport::new(id, latitude, longitude, name, code)
+    })
+}
+
 fn parse_utf8(record: &csv::ByteRecord, index: usize, field: &str) -> Result<String, AirportError> {
     let bytes = record
         .get(index)
@@ -38,6 +139,20 @@ fn parse_utf8(record: &csv::ByteRecord, index: usize, field: &str) -> Result<Str
         .map_err(|err| AirportError::invalid_utf8(field, err.to_string()))
 }
 
+/// Read a trimmed column that may be absent, empty or `\N`
+fn parse_optional(
+    record: &csv::ByteRecord,
+    index: usize,
+    field: &str,
+) -> Result<Option<String>, AirportError> {
+    if record.get(index).is_none() {
+        return Ok(None);
+    }
+    let value = parse_utf8(record, index, field)?;
+    let value = value.trim();
+    Ok((!value.is_empty() && value != MISSING).then(|| value.to_string()))
+}
+
 fn parse_field<T: std::str::FromStr>(
     record: &csv::ByteRecord,
     index: usize,
@@ -52,6 +167,7 @@ fn parse_field<T: std::str::FromStr>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sky_tracer::model::{IataCode, IcaoCode};
 
     #[test]
     fn test_frankfurt_airport() {
@@ -65,6 +181,13 @@ mod tests {
         assert_eq!(airport.name, "Frankfurt am Main Airport");
         assert_eq!(airport.latitude, 50.033333);
         assert_eq!(airport.longitude, 8.570556);
+        assert_eq!(airport.iata.as_ref().map(IataCode::as_str), Some("FRA"));
+        assert_eq!(airport.icao.as_ref().map(IcaoCode::as_str), Some("EDDF"));
+        assert_eq!(airport.city, "Frankfurt");
+        assert_eq!(airport.country, "Germany");
+        assert_eq!(airport.elevation_ft, Some(364));
+        assert_eq!(airport.timezone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(airport.airport_type, AirportType::Airport);
     }
 
     #[test]
@@ -90,6 +213,32 @@ mod tests {
 
         let airport = airports.first().unwrap();
         assert_eq!(airport.code, "XXXX"); // Should use ICAO when IATA is empty
+        assert_eq!(airport.iata, None);
+    }
+
+    #[test]
+    fn test_openflights_missing_values() {
+        let data = r#"5562,"Hof-Plauen Airport","Hof","Germany",\N,"EDQM",50.288612,11.856389,1959,1,"E",\N,"airport","OurAirports""#;
+
+        let airports = load_airports_from_csv(data).unwrap();
+        let airport = airports.first().unwrap();
+
+        assert_eq!(airport.code, "EDQM");
+        assert_eq!(airport.iata, None);
+        assert_eq!(airport.icao.as_ref().map(IcaoCode::as_str), Some("EDQM"));
+        assert_eq!(airport.timezone, None);
+    }
+
+    #[test]
+    fn test_local_identifier_is_no_icao_code() {
+        let data = r#"8007,"Flying Ten Airport","Fellsmere","United States",\N,"07FA",27.8,-80.5,61,-5,"A","America/New_York","airport","OurAirports""#;
+
+        let airports = load_airports_from_csv(data).unwrap();
+        let airport = airports.first().unwrap();
+
+        assert_eq!(airport.code, "07FA");
+        assert_eq!(airport.icao, None);
+        assert!(airport.has_code("07fa"));
     }
 
     #[test]
@@ -97,4 +246,24 @@ mod tests {
         let data = r#"invalid,"Test Airport","Test City","Test Country","TST","TTST",12.345,-67.890,1234,2,"E","Europe/Test","airport","Test Source""#;
         assert!(load_airports_from_csv(data).is_err());
     }
+
+    #[test]
+    fn test_lenient_load_reports_lines() {
+        let data = r#"1,"Good One","City","Country","AAA","AAAA",1.0,1.0,0,0,"E","UTC","airport","test"
+x,"Bad Id","City","Country","BBB","BBBB",1.0,1.0,0,0,"E","UTC","airport","test"
+3,"Short Line","City"
+4,"Good Two","City","Country","DDD","DDDD",2.0,2.0,0,0,"E","UTC","airport","test"
+5,"Bad Latitude","City","Country","EEE","EEEE",north,2.0,0,0,"E","UTC","airport","test""#;
+
+        let report = load_airports_lenient(data);
+
+        let codes: Vec<_> = report.airports.iter().map(|a| a.code.as_str()).collect();
+        assert_eq!(codes, ["AAA", "DDD"]);
+        let lines: Vec<_> = report.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [2, 3, 5]);
+        assert!(report.errors[0].message.contains("id"));
+        assert!(report.errors[2].message.contains("latitude"));
+        // The strict loader gives up on the first of them
+        assert!(load_airports_from_csv(data).is_err());
+    }
 }
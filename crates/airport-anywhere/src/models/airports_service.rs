@@ -1,70 +1,367 @@
 use super::airport_loader::load_airports_from_csv;
-use sky_tracer::model::airport::{Airport, AirportError};
-use std::collections::HashMap;
+use super::listing::{fingerprint, sort_airports, AirportFilter};
+use super::overlay::Overlay;
+use super::search::{fold_words, normalize_name, MatchKind, SearchTerms, Vocabulary, WordScores};
+use super::spatial_index::SpatialIndex;
+use sky_tracer::model::airport::{Airport, AirportError, Frequency, Runway};
+use sky_tracer::model::{IataCode, IcaoCode};
+use sky_tracer::protocol::airports::{AirportSortField, SortOrder};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::ops::Bound;
 use std::sync::Arc;
 
-const AIRPORTS_DATA: &str = include_str!("../../../../assets/airports.dat");
+pub(crate) const AIRPORTS_DATA: &str = include_str!("../../../../assets/airports.dat");
 
-/// Service for looking up airports
-#[derive(Debug, Clone)]
+/// Indexed, read-only airport dataset
+///
+/// Codes are indexed upper case and names in their [normalized](normalize_name)
+/// form. The indexes are ordered maps so prefix lookups are range scans.
+/// Positions are kept in a [`SpatialIndex`] for proximity searches and the
+/// words of name, city and country in a [`Vocabulary`] for text search.
+/// Runways and frequencies, where the data has them, are kept per airport id.
+/// The [`version`](Self::version) identifies the contents, e.g. for ETags.
+#[derive(Debug, Clone, Default)]
 pub struct AirportsService {
-    airports_by_code: HashMap<String, Arc<Airport>>,
+    airports: Vec<Arc<Airport>>,
+    by_code: BTreeMap<String, usize>,
+    by_iata: BTreeMap<String, usize>,
+    by_icao: BTreeMap<String, usize>,
+    by_name: BTreeMap<String, Vec<usize>>,
+    spatial: SpatialIndex,
+    vocabulary: Vocabulary,
+    terms: Vec<SearchTerms>,
+    runways: HashMap<u32, Vec<Runway>>,
+    frequencies: HashMap<u32, Vec<Frequency>>,
+    version: u64,
+}
+
+/// An airport found by [`AirportsService::search`]
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub airport: Arc<Airport>,
+    pub kind: MatchKind,
+    /// Quality of the word matches, higher is better
+    pub score: u32,
 }
 
 impl AirportsService {
     /// Creates a new AirportsService by parsing CSV data
     pub fn from_csv_str(data: &str) -> Result<Self, AirportError> {
-        let airports = load_airports_from_csv(data)?;
-        let mut airports_by_code = HashMap::new();
+        Ok(Self::from_airports(load_airports_from_csv(data)?))
+    }
+
+    /// Builds the indexes over already loaded airports
+    pub fn from_airports(airports: Vec<Arc<Airport>>) -> Self {
+        let mut by_code = BTreeMap::new();
+        let mut by_iata = BTreeMap::new();
+        let mut by_icao = BTreeMap::new();
+        let mut by_name: BTreeMap<String, Vec<usize>> = BTreeMap::new();
 
-        for airport in airports {
-            if !airport.code.is_empty() {
-                airports_by_code.insert(airport.code.clone(), airport);
+        for (index, airport) in airports.iter().enumerate() {
+            // The first airport claiming a code wins
+            by_code.entry(airport.code.to_uppercase()).or_insert(index);
+            if let Some(iata) = &airport.iata {
+                by_iata.entry(iata.to_string()).or_insert(index);
+            }
+            if let Some(icao) = &airport.icao {
+                by_icao.entry(icao.to_string()).or_insert(index);
             }
+            by_name
+                .entry(normalize_name(&airport.name))
+                .or_default()
+                .push(index);
+        }
+
+        let mut vocabulary = Vocabulary::default();
+        let terms = airports
+            .iter()
+            .map(|airport| vocabulary.terms(airport))
+            .collect();
+        let spatial = SpatialIndex::new(
+            airports
+                .iter()
+                .enumerate()
+                .map(|(index, airport)| (index, airport.position())),
+        );
+
+        let version = fingerprint(&airports);
+
+        tracing::info!(
+            airports = airports.len(),
+            iata = by_iata.len(),
+            icao = by_icao.len(),
+            words = vocabulary.len(),
+            version = format!("{version:016x}"),
+            "Indexed airports"
+        );
+
+        Self {
+            airports,
+            by_code,
+            by_iata,
+            by_icao,
+            by_name,
+            spatial,
+            vocabulary,
+            terms,
+            runways: HashMap::new(),
+            frequencies: HashMap::new(),
+            version,
+        }
+    }
+
+    /// Attaches runways and frequencies, linked to airports by id
+    pub fn with_facilities(mut self, runways: Vec<Runway>, frequencies: Vec<Frequency>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        self.version.hash(&mut hasher);
+        // Serialization covers every field, including the floating point ones
+        serde_json::to_vec(&runways)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        serde_json::to_vec(&frequencies)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        self.version = hasher.finish();
+
+        self.runways.clear();
+        for runway in runways {
+            self.runways
+                .entry(runway.airport_id)
+                .or_default()
+                .push(runway);
+        }
+        self.frequencies.clear();
+        for frequency in frequencies {
+            self.frequencies
+                .entry(frequency.airport_id)
+                .or_default()
+                .push(frequency);
         }
+        self
+    }
+
+    /// This dataset with `overlay` applied, runways and frequencies are kept
+    pub fn with_overlay(&self, overlay: &Overlay) -> Self {
+        let mut overlaid = Self::from_airports(overlay.apply(self.airports.iter()));
+        overlaid.runways = self.runways.clone();
+        overlaid.frequencies = self.frequencies.clone();
 
-        Ok(Self { airports_by_code })
+        // Facilities are only part of the base version
+        let mut hasher = DefaultHasher::new();
+        self.version.hash(&mut hasher);
+        overlaid.version.hash(&mut hasher);
+        overlaid.version = hasher.finish();
+        overlaid
     }
 
-    /// Gets instance of AirportsService
-    pub fn instance() -> Result<Self, AirportError> {
+    /// Parses the airport data compiled into the binary
+    pub fn embedded() -> Result<Self, AirportError> {
         Self::from_csv_str(AIRPORTS_DATA)
     }
 
-    /// Find an airport by its code (IATA or combined IATA/ICAO)
+    /// Number of airports in the dataset
+    pub fn len(&self) -> usize {
+        self.airports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.airports.is_empty()
+    }
+
+    /// Fingerprint of the dataset, equal for equal contents
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Find an airport by its IATA, ICAO or primary code, ignoring case.
+    ///
+    /// Without an exact match the first airport whose code starts with the
+    /// given code is returned.
     pub fn find_by_code(&self, code: &str) -> Result<Arc<Airport>, AirportError> {
         tracing::debug!("Searching for airport with code: {}", code);
+        let key = code.trim().to_uppercase();
 
-        // First try exact match
-        if let Some(airport) = self.airports_by_code.get(code) {
-            return Ok(airport.clone());
-        }
-
-        // If no exact match, try matching just the IATA part
-        self.airports_by_code
-            .values()
-            .find(|airport| airport.code.starts_with(code))
-            .cloned()
+        self.by_iata
+            .get(&key)
+            .or_else(|| self.by_icao.get(&key))
+            .or_else(|| self.by_code.get(&key))
+            .or_else(|| prefix_match(&self.by_iata, &key))
+            .or_else(|| prefix_match(&self.by_icao, &key))
+            .map(|&index| self.airports[index].clone())
             .ok_or_else(|| {
                 tracing::debug!("Airport not found with code: {}", code);
                 AirportError::NotFound(code.to_string())
             })
     }
 
-    /// Get all airports
-    pub fn all(&self) -> impl Iterator<Item = &Arc<Airport>> {
-        self.airports_by_code.values()
+    /// Find an airport by its exact primary, IATA or ICAO code, ignoring case
+    pub fn find_exact(&self, code: &str) -> Option<Arc<Airport>> {
+        let key = code.trim().to_uppercase();
+        self.by_code
+            .get(&key)
+            .or_else(|| self.by_iata.get(&key))
+            .or_else(|| self.by_icao.get(&key))
+            .map(|&index| self.airports[index].clone())
     }
 
-    /// Search airports by name (case-insensitive partial match)
-    pub fn search_by_name(&self, query: &str) -> Vec<Arc<Airport>> {
-        let query = query.to_lowercase();
-        self.airports_by_code
-            .values()
-            .filter(|airport| airport.name.to_lowercase().contains(&query))
+    /// Find an airport by its id
+    pub fn find_by_id(&self, id: u32) -> Option<Arc<Airport>> {
+        self.airports
+            .iter()
+            .find(|airport| airport.id == id)
             .cloned()
+    }
+
+    /// Runways of the airport with the given id
+    pub fn runways(&self, airport_id: u32) -> &[Runway] {
+        self.runways
+            .get(&airport_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Radio frequencies of the airport with the given id
+    pub fn frequencies(&self, airport_id: u32) -> &[Frequency] {
+        self.frequencies
+            .get(&airport_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Find an airport by its IATA code only
+    pub fn find_by_iata(&self, iata: &str) -> Option<Arc<Airport>> {
+        self.by_iata
+            .get(&iata.trim().to_uppercase())
+            .map(|&index| self.airports[index].clone())
+    }
+
+    /// Find an airport by its ICAO code only
+    pub fn find_by_icao(&self, icao: &str) -> Option<Arc<Airport>> {
+        self.by_icao
+            .get(&icao.trim().to_uppercase())
+            .map(|&index| self.airports[index].clone())
+    }
+
+    /// Up to `limit` airports within `radius_km` of `position` with their
+    /// great-circle distance in kilometers, nearest first
+    pub fn nearby(
+        &self,
+        position: (f64, f64),
+        radius_km: f64,
+        limit: usize,
+    ) -> Vec<(Arc<Airport>, f64)> {
+        self.spatial
+            .nearby(position, radius_km, limit)
+            .into_iter()
+            .map(|(index, distance_km)| (self.airports[index].clone(), distance_km))
             .collect()
     }
+
+    /// Get all airports in dataset order
+    pub fn all(&self) -> impl Iterator<Item = &Arc<Airport>> {
+        self.airports.iter()
+    }
+
+    /// All airports matching `filter`, ordered by `field` and then id
+    pub fn list(
+        &self,
+        filter: &AirportFilter,
+        field: AirportSortField,
+        order: SortOrder,
+    ) -> Vec<Arc<Airport>> {
+        let mut airports: Vec<Arc<Airport>> = self
+            .airports
+            .iter()
+            .filter(|airport| filter.matches(airport))
+            .cloned()
+            .collect();
+        sort_airports(&mut airports, field, order);
+        airports
+    }
+
+    /// Ranked search over codes, name, city and country.
+    ///
+    /// Matching ignores case, accents and punctuation and tolerates typos in
+    /// longer words. Hits are ordered by [`MatchKind`], then word match
+    /// quality, then airports with an IATA code first, then name.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let code = query.trim().to_uppercase();
+        let words = fold_words(query);
+        let normalized = words.join(" ");
+        if normalized.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+        let scores: Vec<WordScores> = words
+            .iter()
+            .map(|word| self.vocabulary.score(word))
+            .collect();
+        let same_name = self
+            .by_name
+            .get(&normalized)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let mut hits: Vec<SearchHit> = self
+            .airports
+            .iter()
+            .zip(&self.terms)
+            .enumerate()
+            .filter_map(|(index, (airport, terms))| {
+                let word_match = terms.match_words(&scores);
+                let score = word_match.map(|(_, score)| score).unwrap_or_default();
+                let mut codes = airport
+                    .iata
+                    .iter()
+                    .map(IataCode::as_str)
+                    .chain(airport.icao.iter().map(IcaoCode::as_str));
+
+                let kind = if codes.clone().any(|c| c.eq_ignore_ascii_case(&code)) {
+                    MatchKind::ExactCode
+                } else if code.len() >= 2 && codes.any(|c| starts_with_ignore_case(c, &code)) {
+                    MatchKind::CodePrefix
+                } else if same_name.contains(&index) || terms.city() == normalized {
+                    MatchKind::Name
+                } else {
+                    word_match?.0
+                };
+
+                Some(SearchHit {
+                    airport: airport.clone(),
+                    kind,
+                    score,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.kind
+                .cmp(&a.kind)
+                .then(b.score.cmp(&a.score))
+                .then(b.airport.iata.is_some().cmp(&a.airport.iata.is_some()))
+                .then_with(|| a.airport.name.cmp(&b.airport.name))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+fn starts_with_ignore_case(value: &str, prefix: &str) -> bool {
+    value
+        .get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+fn prefix_match<'a>(index: &'a BTreeMap<String, usize>, prefix: &str) -> Option<&'a usize> {
+    if prefix.is_empty() {
+        return None;
+    }
+    index
+        .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+        .next()
+        .filter(|(code, _)| code.starts_with(prefix))
+        .map(|(_, index)| index)
 }
 
 #[cfg(test)]
@@ -87,13 +384,146 @@ mod tests {
 2,"Frankfurt-Hahn","City2","Country2","HHN","EDFH",49.945,7.264,0,1,"E","Europe/Berlin","airport","test""#;
 
         let service = AirportsService::from_csv_str(data).unwrap();
-        let results = service.search_by_name("frankfurt");
+        let results: Vec<_> = service
+            .search("frankfurt", 10)
+            .into_iter()
+            .map(|hit| hit.airport)
+            .collect();
 
         assert_eq!(results.len(), 2);
         assert!(results.iter().any(|a| a.code == "FRA"));
         assert!(results.iter().any(|a| a.code == "HHN"));
     }
 
+    #[test]
+    fn test_find_by_icao() {
+        let data = r#"1,"Frankfurt Airport","Frankfurt","Germany","FRA","EDDF",50.033,8.571,364,1,"E","Europe/Berlin","airport","test""#;
+        let service = AirportsService::from_csv_str(data).unwrap();
+
+        let airport = service.find_by_code("eddf").unwrap();
+        assert_eq!(airport.code, "FRA");
+        assert_eq!(airport.country, "Germany");
+    }
+
+    #[test]
+    fn test_find_by_code_prefers_exact_match() {
+        let data = r#"1,"Airport One","City1","Country1","AB","ABCD",1.0,1.0,0,0,"E","UTC","airport","test"
+2,"Airport Two","City2","Country2","ABC","EABC",2.0,2.0,0,0,"E","UTC","airport","test""#;
+        let service = AirportsService::from_csv_str(data).unwrap();
+
+        assert_eq!(service.find_by_code("ABC").unwrap().id, 2);
+        assert_eq!(service.find_by_code("abcd").unwrap().id, 1);
+        // Prefix fallback, IATA codes first
+        assert_eq!(service.find_by_code("EAB").unwrap().id, 2);
+        assert!(service.find_by_iata("ABCD").is_none());
+        assert_eq!(service.find_by_icao("EABC").unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_search_by_name_is_normalized() {
+        let data = r#"1,"Frankfurt am Main Airport","Frankfurt","Germany","FRA","EDDF",50.033,8.571,0,1,"E","Europe/Berlin","airport","test"
+2,"Frankfurt-Hahn Airport","Hahn","Germany","HHN","EDFH",49.945,7.264,0,1,"E","Europe/Berlin","airport","test""#;
+        let service = AirportsService::from_csv_str(data).unwrap();
+
+        let results = service.search("  FRANKFURT   hahn ", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].airport.code, "HHN");
+        assert!(service.search("--", 10).is_empty());
+    }
+
+    #[test]
+    fn test_embedded_dataset() {
+        let service = AirportsService::embedded().unwrap();
+
+        assert!(service.len() > 7000);
+        assert_eq!(service.find_by_code("EDDF").unwrap().code, "FRA");
+        assert_eq!(
+            service.find_by_code("fra").unwrap().icao.clone().unwrap(),
+            "EDDF"
+        );
+
+        let nearby = service.nearby((50.0, 8.6), 50.0, 3);
+        assert_eq!(nearby.len(), 3);
+        assert_eq!(nearby[0].0.code, "FRA");
+        assert!(nearby.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_list() {
+        let data = r#"1,"Zurich Airport","Zurich","Switzerland","ZRH","LSZH",47.46,8.55,1416,1,"E","Europe/Zurich","airport","test"
+2,"Basel SBB","Basel","Switzerland",\N,\N,47.55,7.59,\N,1,"E","Europe/Zurich","station","test"
+3,"Geneva Airport","Geneva","Switzerland","GVA","LSGG",46.24,6.11,1411,1,"E","Europe/Zurich","airport","test"
+4,"Frankfurt Airport","Frankfurt","Germany","FRA","EDDF",50.03,8.57,364,1,"E","Europe/Berlin","airport","test""#;
+        let service = AirportsService::from_csv_str(data).unwrap();
+        let ids = |airports: Vec<Arc<Airport>>| airports.iter().map(|a| a.id).collect::<Vec<_>>();
+
+        let swiss = AirportFilter::default().with_country("switzerland");
+        assert_eq!(
+            ids(service.list(&swiss, AirportSortField::Elevation, SortOrder::Desc)),
+            [1, 3, 2]
+        );
+        assert_eq!(
+            ids(service.list(
+                &swiss.with_airport_type(sky_tracer::model::AirportType::Airport),
+                AirportSortField::Name,
+                SortOrder::Asc
+            )),
+            [3, 1]
+        );
+        assert_eq!(
+            service
+                .list(
+                    &AirportFilter::default(),
+                    AirportSortField::Id,
+                    SortOrder::Asc
+                )
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_facilities() {
+        use super::super::ourairports::{
+            self,
+            tests::{AIRPORTS, FREQUENCIES, RUNWAYS},
+        };
+
+        let airports = ourairports::load_airports(AIRPORTS).airports;
+        let plain = AirportsService::from_airports(airports.clone());
+        let service = AirportsService::from_airports(airports).with_facilities(
+            ourairports::load_runways(RUNWAYS).0,
+            ourairports::load_frequencies(FREQUENCIES).0,
+        );
+
+        let frankfurt = service.find_exact("eddf").unwrap();
+        assert_eq!(service.runways(frankfurt.id).len(), 2);
+        assert_eq!(service.frequencies(frankfurt.id).len(), 2);
+        let heliport = service.find_exact("00A").unwrap();
+        assert_eq!(service.runways(heliport.id)[0].designation(), "H1");
+        assert!(service.frequencies(heliport.id).is_empty());
+        // No prefix matching
+        assert!(service.find_exact("EDD").is_none());
+
+        assert_ne!(service.version(), plain.version());
+    }
+
+    #[test]
+    fn test_version_follows_contents() {
+        let data = r#"1,"Test Airport","Test City","Test Country","TST","TTST",12.345,-67.890,1234,2,"E","Europe/Test","airport","Test Source""#;
+        let renamed = data.replace("Test Airport", "Renamed Airport");
+
+        let version = AirportsService::from_csv_str(data).unwrap().version();
+        assert_eq!(
+            AirportsService::from_csv_str(data).unwrap().version(),
+            version
+        );
+        assert_ne!(
+            AirportsService::from_csv_str(&renamed).unwrap().version(),
+            version
+        );
+    }
+
     #[test]
     fn test_not_found() {
         let data = r#"1,"Test Airport","Test City","Test Country","TST","TTST",12.345,-67.890,1234,2,"E","Europe/Test","airport","Test Source""#;
@@ -114,4 +544,31 @@ mod tests {
         assert!(airports.iter().any(|a| a.code == "AA1"));
         assert!(airports.iter().any(|a| a.code == "AA2"));
     }
+
+    #[test]
+    fn test_overlay_is_indexed() {
+        let data = r#"1,"Airport1","City1","Country1","AA1","AAA1",1.0,1.0,0,0,"E","UTC","airport","test"
+2,"Airport2","City2","Country2","AA2","AAA2",2.0,2.0,0,0,"E","UTC","airport","test""#;
+        let base = AirportsService::from_csv_str(data).unwrap();
+
+        let mut overlay = Overlay::default();
+        overlay.upsert(Airport {
+            id: overlay.next_id().unwrap(),
+            name: "Private Strip".to_string(),
+            code: "XPS".to_string(),
+            latitude: 1.01,
+            longitude: 1.01,
+            ..Default::default()
+        });
+        overlay.remove(2, true);
+        let overlaid = base.with_overlay(&overlay);
+
+        assert_eq!(overlaid.len(), 2);
+        assert!(overlaid.find_exact("AA2").is_none());
+        assert_eq!(overlaid.find_exact("xps").unwrap().name, "Private Strip");
+        assert_eq!(overlaid.nearby((1.0, 1.0), 10.0, 5).len(), 2);
+        assert_eq!(overlaid.search("private", 5)[0].airport.code, "XPS");
+        assert_ne!(overlaid.version(), base.version());
+        assert_eq!(overlaid.version(), base.with_overlay(&overlay).version());
+    }
 }
@@ -0,0 +1,248 @@
+use super::search::normalize_name;
+use sky_tracer::model::airport::{Airport, AirportError, AirportType};
+use sky_tracer::protocol::airports::{AirportSortField, SortOrder};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Area given as `min_lon,min_lat,max_lon,max_lat`, the GeoJSON order.
+///
+/// A `min_lon` greater than `max_lon` describes a box crossing the
+/// antimeridian, e.g. `170,-50,-170,-30` around New Zealand's east.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lon: f64,
+    pub min_lat: f64,
+    pub max_lon: f64,
+    pub max_lat: f64,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, (latitude, longitude): (f64, f64)) -> bool {
+        let in_lon = if self.min_lon <= self.max_lon {
+            (self.min_lon..=self.max_lon).contains(&longitude)
+        } else {
+            longitude >= self.min_lon || longitude <= self.max_lon
+        };
+        in_lon && (self.min_lat..=self.max_lat).contains(&latitude)
+    }
+}
+
+impl FromStr for BoundingBox {
+    type Err = AirportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AirportError::invalid_value("bbox", s);
+        let values = s
+            .split(',')
+            .map(|part| part.trim().parse::<f64>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [min_lon, min_lat, max_lon, max_lat] = values[..] else {
+            return Err(invalid());
+        };
+
+        let lon_ok = |lon: f64| (-180.0..=180.0).contains(&lon);
+        let lat_ok = |lat: f64| (-90.0..=90.0).contains(&lat);
+        if !(lon_ok(min_lon) && lon_ok(max_lon) && lat_ok(min_lat) && lat_ok(max_lat))
+            || min_lat > max_lat
+        {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            min_lon,
+            min_lat,
+            max_lon,
+            max_lat,
+        })
+    }
+}
+
+/// Restricts the airport list, unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct AirportFilter {
+    /// Country in its [normalized](normalize_name) form
+    country: Option<String>,
+    bbox: Option<BoundingBox>,
+    airport_type: Option<AirportType>,
+}
+
+impl AirportFilter {
+    pub fn with_country(mut self, country: &str) -> Self {
+        self.country = Some(normalize_name(country)).filter(|country| !country.is_empty());
+        self
+    }
+
+    pub fn with_bbox(mut self, bbox: BoundingBox) -> Self {
+        self.bbox = Some(bbox);
+        self
+    }
+
+    pub fn with_airport_type(mut self, airport_type: AirportType) -> Self {
+        self.airport_type = Some(airport_type);
+        self
+    }
+
+    pub fn matches(&self, airport: &Airport) -> bool {
+        self.airport_type
+            .is_none_or(|airport_type| airport.airport_type == airport_type)
+            && self
+                .bbox
+                .is_none_or(|bbox| bbox.contains(airport.position()))
+            && self
+                .country
+                .as_ref()
+                .is_none_or(|country| normalize_name(&airport.country) == *country)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Number(i64),
+    Text(String),
+}
+
+fn sort_key(airport: &Airport, field: AirportSortField) -> SortKey {
+    match field {
+        AirportSortField::Id => SortKey::Number(airport.id.into()),
+        // Unknown elevations count as the lowest
+        AirportSortField::Elevation => {
+            SortKey::Number(airport.elevation_ft.map_or(i64::MIN, i64::from))
+        }
+        AirportSortField::Name => SortKey::Text(normalize_name(&airport.name)),
+        AirportSortField::Code => SortKey::Text(airport.code.to_uppercase()),
+        AirportSortField::City => SortKey::Text(normalize_name(&airport.city)),
+        AirportSortField::Country => SortKey::Text(normalize_name(&airport.country)),
+    }
+}
+
+/// Orders airports by `field`, ties keep ascending id order
+pub fn sort_airports(airports: &mut [Arc<Airport>], field: AirportSortField, order: SortOrder) {
+    let mut keyed: Vec<(SortKey, Arc<Airport>)> = airports
+        .iter()
+        .map(|airport| (sort_key(airport, field), airport.clone()))
+        .collect();
+    keyed.sort_by(|(a_key, a), (b_key, b)| {
+        let ordering = match order {
+            SortOrder::Asc => a_key.cmp(b_key),
+            SortOrder::Desc => b_key.cmp(a_key),
+        };
+        ordering.then(a.id.cmp(&b.id))
+    });
+
+    for (slot, (_, airport)) in airports.iter_mut().zip(keyed) {
+        *slot = airport;
+    }
+}
+
+/// Fingerprint of the dataset contents, changes whenever any airport does
+pub fn fingerprint(airports: &[Arc<Airport>]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    airports.len().hash(&mut hasher);
+    for airport in airports {
+        airport.id.hash(&mut hasher);
+        airport.latitude.to_bits().hash(&mut hasher);
+        airport.longitude.to_bits().hash(&mut hasher);
+        airport.name.hash(&mut hasher);
+        airport.code.hash(&mut hasher);
+        airport.iata.hash(&mut hasher);
+        airport.icao.hash(&mut hasher);
+        airport.city.hash(&mut hasher);
+        airport.country.hash(&mut hasher);
+        airport.elevation_ft.hash(&mut hasher);
+        airport.timezone.hash(&mut hasher);
+        airport.airport_type.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn airport(id: u32, name: &str, country: &str, position: (f64, f64)) -> Arc<Airport> {
+        Arc::new(Airport {
+            id,
+            name: name.to_string(),
+            code: format!("A{id:02}"),
+            country: country.to_string(),
+            latitude: position.0,
+            longitude: position.1,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_bbox_parsing() {
+        let bbox: BoundingBox = "5.8, 47.2, 15.1, 55.1".parse().unwrap();
+        assert!(bbox.contains((50.03, 8.57)));
+        assert!(!bbox.contains((48.85, 2.35)));
+
+        for invalid in ["1,2,3", "a,b,c,d", "0,10,1,5", "0,0,190,1", "1,2,3,4,5"] {
+            assert!(invalid.parse::<BoundingBox>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_bbox_across_antimeridian() {
+        let bbox: BoundingBox = "170,-50,-170,-30".parse().unwrap();
+
+        assert!(bbox.contains((-40.0, 175.0)));
+        assert!(bbox.contains((-40.0, -175.0)));
+        assert!(!bbox.contains((-40.0, 0.0)));
+        assert!(!bbox.contains((0.0, 175.0)));
+    }
+
+    #[test]
+    fn test_filter() {
+        let zurich = airport(1, "Zürich", "Switzerland", (47.46, 8.55));
+        let station = Arc::new(Airport {
+            airport_type: AirportType::Station,
+            ..(*airport(2, "Basel SBB", "Switzerland", (47.55, 7.59))).clone()
+        });
+
+        let filter = AirportFilter::default().with_country("  SWITZERLAND ");
+        assert!(filter.matches(&zurich) && filter.matches(&station));
+
+        let filter = filter.with_airport_type(AirportType::Airport);
+        assert!(filter.matches(&zurich) && !filter.matches(&station));
+
+        let filter = AirportFilter::default().with_bbox("8,47,9,48".parse().unwrap());
+        assert!(filter.matches(&zurich) && !filter.matches(&station));
+
+        // Blank country does not filter
+        assert!(AirportFilter::default().with_country(" ").matches(&zurich));
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut airports = vec![
+            airport(3, "Ålesund", "Norway", (0.0, 0.0)),
+            airport(1, "Zagreb", "Croatia", (0.0, 0.0)),
+            airport(2, "Bergen", "Norway", (0.0, 0.0)),
+        ];
+        let ids = |airports: &[Arc<Airport>]| airports.iter().map(|a| a.id).collect::<Vec<_>>();
+
+        sort_airports(&mut airports, AirportSortField::Id, SortOrder::Asc);
+        assert_eq!(ids(&airports), [1, 2, 3]);
+
+        // Accents fold away, "Ålesund" sorts as "alesund"
+        sort_airports(&mut airports, AirportSortField::Name, SortOrder::Asc);
+        assert_eq!(ids(&airports), [3, 2, 1]);
+
+        // Ties stay in id order in both directions
+        sort_airports(&mut airports, AirportSortField::Country, SortOrder::Desc);
+        assert_eq!(ids(&airports), [2, 3, 1]);
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_contents() {
+        let airports = vec![airport(1, "One", "X", (1.0, 2.0))];
+        let moved = vec![airport(1, "One", "X", (1.0, 2.5))];
+
+        assert_eq!(fingerprint(&airports), fingerprint(&airports.clone()));
+        assert_ne!(fingerprint(&airports), fingerprint(&moved));
+        assert_ne!(fingerprint(&airports), fingerprint(&[]));
+    }
+}
@@ -1,5 +1,17 @@
 pub mod airport_loader;
 pub mod airports_service;
+pub mod listing;
+pub mod ourairports;
+pub mod overlay;
+pub mod search;
+pub mod source;
+pub mod spatial_index;
 
-pub use airport_loader::load_airports_from_csv;
-pub use airports_service::AirportsService;
+pub use airport_loader::{load_airports_from_csv, load_airports_lenient, LineError, LoadReport};
+pub use airports_service::{AirportsService, SearchHit};
+pub use listing::{AirportFilter, BoundingBox};
+pub use ourairports::DataFormat;
+pub use overlay::{overlay_path_from_env, Overlay, AIRPORTS_OVERLAY_PATH_ENV};
+pub use search::MatchKind;
+pub use source::{AirportSource, AIRPORTS_DATA_PATH_ENV};
+pub use spatial_index::SpatialIndex;
@@ -0,0 +1,365 @@
+//! Loader for the OurAirports CSV family
+//!
+//! Unlike OpenFlights, OurAirports files carry a header row, so columns are
+//! matched by name and the header tells the files apart. Airports link to
+//! runways and frequencies by their numeric id (`airport_ref`).
+
+use super::airport_loader::{LineError, LoadReport};
+use csv::{ReaderBuilder, StringRecord, Trim};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sky_tracer::model::airport::{Airport, AirportType, Frequency, Runway, RunwayEnd};
+use sky_tracer::model::{IataCode, IcaoCode};
+use std::fmt;
+use std::sync::Arc;
+
+/// File name OurAirports publishes runways under
+pub const RUNWAYS_FILE: &str = "runways.csv";
+/// File name OurAirports publishes frequencies under
+pub const FREQUENCIES_FILE: &str = "airport-frequencies.csv";
+
+/// Layout of an airport data file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    /// Headerless `airports.dat` from OpenFlights
+    OpenFlights,
+    /// `airports.csv` from OurAirports
+    OurAirports,
+    /// `runways.csv` from OurAirports
+    OurAirportsRunways,
+    /// `airport-frequencies.csv` from OurAirports
+    OurAirportsFrequencies,
+}
+
+impl DataFormat {
+    /// Tells the formats apart by the first line
+    pub fn detect(data: &str) -> Self {
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(false)
+            .trim(Trim::All)
+            .from_reader(data.as_bytes());
+        let mut first = StringRecord::new();
+        if !rdr.read_record(&mut first).unwrap_or(false) {
+            return Self::OpenFlights;
+        }
+        let has = |column: &str| first.iter().any(|field| field == column);
+
+        if has("ident") && has("latitude_deg") {
+            Self::OurAirports
+        } else if has("airport_ref") && has("le_ident") {
+            Self::OurAirportsRunways
+        } else if has("airport_ref") && has("frequency_mhz") {
+            Self::OurAirportsFrequencies
+        } else {
+            Self::OpenFlights
+        }
+    }
+}
+
+impl fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::OpenFlights => "OpenFlights airports",
+            Self::OurAirports => "OurAirports airports",
+            Self::OurAirportsRunways => "OurAirports runways",
+            Self::OurAirportsFrequencies => "OurAirports frequencies",
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AirportRow {
+    id: u32,
+    ident: String,
+    #[serde(rename = "type")]
+    kind: String,
+    name: String,
+    latitude_deg: f64,
+    longitude_deg: f64,
+    elevation_ft: Option<i32>,
+    #[serde(default)]
+    iso_country: String,
+    #[serde(default)]
+    municipality: String,
+    #[serde(default)]
+    gps_code: Option<String>,
+    #[serde(default)]
+    iata_code: Option<String>,
+    /// Only present in newer exports
+    #[serde(default)]
+    icao_code: Option<String>,
+}
+
+impl From<AirportRow> for Airport {
+    fn from(row: AirportRow) -> Self {
+        let airport_type = match row.kind.as_str() {
+            "closed" => AirportType::Unknown,
+            _ => AirportType::Airport,
+        };
+        let iata: Option<IataCode> = row.iata_code.and_then(|code| code.parse().ok());
+        // Older exports only carry the ICAO code as GPS code
+        let icao: Option<IcaoCode> = row
+            .icao_code
+            .and_then(|code| code.parse().ok())
+            .or_else(|| row.gps_code.and_then(|code| code.parse().ok()));
+        let code = iata
+            .as_ref()
+            .map(IataCode::to_string)
+            .or_else(|| icao.as_ref().map(IcaoCode::to_string))
+            .unwrap_or(row.ident);
+
+        Self {
+            iata,
+            icao,
+            city: row.municipality,
+            country: row.iso_country,
+            elevation_ft: row.elevation_ft,
+            airport_type,
+            ..Airport::new(row.id, row.latitude_deg, row.longitude_deg, row.name, code)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RunwayRow {
+    airport_ref: u32,
+    length_ft: Option<u32>,
+    width_ft: Option<u32>,
+    #[serde(default)]
+    surface: String,
+    #[serde(default)]
+    lighted: u8,
+    #[serde(default)]
+    closed: u8,
+    le_ident: String,
+    le_latitude_deg: Option<f64>,
+    le_longitude_deg: Option<f64>,
+    le_elevation_ft: Option<i32>,
+    #[serde(rename = "le_heading_degT")]
+    le_heading_deg: Option<f64>,
+    le_displaced_threshold_ft: Option<u32>,
+    he_ident: Option<String>,
+    he_latitude_deg: Option<f64>,
+    he_longitude_deg: Option<f64>,
+    he_elevation_ft: Option<i32>,
+    #[serde(rename = "he_heading_degT")]
+    he_heading_deg: Option<f64>,
+    he_displaced_threshold_ft: Option<u32>,
+}
+
+impl From<RunwayRow> for Runway {
+    fn from(row: RunwayRow) -> Self {
+        let high_end = row
+            .he_ident
+            .filter(|ident| !ident.is_empty())
+            .map(|ident| RunwayEnd {
+                ident,
+                heading_deg: row.he_heading_deg,
+                latitude: row.he_latitude_deg,
+                longitude: row.he_longitude_deg,
+                elevation_ft: row.he_elevation_ft,
+                displaced_threshold_ft: row.he_displaced_threshold_ft,
+            });
+
+        Self {
+            airport_id: row.airport_ref,
+            length_ft: row.length_ft,
+            width_ft: row.width_ft,
+            surface: row.surface,
+            lighted: row.lighted != 0,
+            closed: row.closed != 0,
+            low_end: RunwayEnd {
+                ident: row.le_ident,
+                heading_deg: row.le_heading_deg,
+                latitude: row.le_latitude_deg,
+                longitude: row.le_longitude_deg,
+                elevation_ft: row.le_elevation_ft,
+                displaced_threshold_ft: row.le_displaced_threshold_ft,
+            },
+            high_end,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FrequencyRow {
+    airport_ref: u32,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    description: String,
+    frequency_mhz: f64,
+}
+
+impl From<FrequencyRow> for Frequency {
+    fn from(row: FrequencyRow) -> Self {
+        Self {
+            airport_id: row.airport_ref,
+            frequency_type: row.kind,
+            description: row.description,
+            frequency_mhz: row.frequency_mhz,
+        }
+    }
+}
+
+/// Parses every row after the header, collecting the ones that do not fit `T`
+fn parse_rows<T, U>(data: &str) -> (Vec<U>, Vec<LineError>)
+where
+    T: DeserializeOwned + Into<U>,
+{
+    let mut rdr = ReaderBuilder::new()
+        .trim(Trim::All)
+        .flexible(true)
+        .from_reader(data.as_bytes());
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    let headers = match rdr.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => {
+            errors.push(LineError::new(1, e.to_string()));
+            return (rows, errors);
+        }
+    };
+
+    let mut record = StringRecord::new();
+    loop {
+        let line = rdr.position().line();
+        match rdr.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => match record.deserialize::<T>(Some(&headers)) {
+                Ok(row) => rows.push(row.into()),
+                Err(e) => errors.push(LineError::new(
+                    record.position().map_or(line, |position| position.line()),
+                    e.to_string(),
+                )),
+            },
+            Err(e) => {
+                let io_error = e.is_io_error();
+                errors.push(LineError::new(
+                    e.position().map_or(line, |position| position.line()),
+                    e.to_string(),
+                ));
+                if io_error {
+                    break;
+                }
+            }
+        }
+    }
+    (rows, errors)
+}
+
+/// Parses an OurAirports `airports.csv`, skipping invalid lines
+pub fn load_airports(data: &str) -> LoadReport {
+    let (airports, errors) = parse_rows::<AirportRow, Airport>(data);
+    tracing::info!(
+        airports = airports.len(),
+        invalid_lines = errors.len(),
+        "Parsed OurAirports airports"
+    );
+    LoadReport {
+        airports: airports.into_iter().map(Arc::new).collect(),
+        errors,
+        ..Default::default()
+    }
+}
+
+/// Parses an OurAirports `runways.csv`, skipping invalid lines
+pub fn load_runways(data: &str) -> (Vec<Runway>, Vec<LineError>) {
+    parse_rows::<RunwayRow, Runway>(data)
+}
+
+/// Parses an OurAirports `airport-frequencies.csv`, skipping invalid lines
+pub fn load_frequencies(data: &str) -> (Vec<Frequency>, Vec<LineError>) {
+    parse_rows::<FrequencyRow, Frequency>(data)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const AIRPORTS: &str = r#""id","ident","type","name","latitude_deg","longitude_deg","elevation_ft","continent","iso_country","iso_region","municipality","scheduled_service","gps_code","iata_code","local_code","home_link","wikipedia_link","keywords"
+2212,"EDDF","large_airport","Frankfurt am Main Airport",50.036249,8.559294,364,"EU","DE","DE-HE","Frankfurt am Main","yes","EDDF","FRA","","https://www.frankfurt-airport.com/",,"EDDF, Frankfurt, Rhein-Main"
+6523,"00A","heliport","Total RF Heliport",40.070985,-74.933689,11,"NA","US","US-PA","Bensalem","no","","","00A",,,
+9999,"XBAD","small_airport","Broken Field",north,8.0,100,"EU","DE","DE-HE","Nowhere","no","","","",,,"#;
+
+    pub(crate) const RUNWAYS: &str = r#""id","airport_ref","airport_ident","length_ft","width_ft","surface","lighted","closed","le_ident","le_latitude_deg","le_longitude_deg","le_elevation_ft","le_heading_degT","le_displaced_threshold_ft","he_ident","he_latitude_deg","he_longitude_deg","he_elevation_ft","he_heading_degT","he_displaced_threshold_ft"
+240024,2212,"EDDF",13123,148,"ASP",1,0,"07C",50.0325,8.53463,334,69.9,,"25C",50.0457,8.58725,364,249.9,
+240025,2212,"EDDF",9240,148,"CON",1,0,"18",50.0345,8.52606,328,180,,,,,,,
+1,6523,"00A",80,80,"ASPH-G",1,0,"H1",,,,,,,,,,,"#;
+
+    pub(crate) const FREQUENCIES: &str = r#""id","airport_ref","airport_ident","type","description","frequency_mhz"
+60822,2212,"EDDF","TWR","Frankfurt Tower",119.9
+60823,2212,"EDDF","ATIS","ATIS",118.025
+60824,2212,"EDDF","GND","broken",ground"#;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(DataFormat::detect(AIRPORTS), DataFormat::OurAirports);
+        assert_eq!(DataFormat::detect(RUNWAYS), DataFormat::OurAirportsRunways);
+        assert_eq!(
+            DataFormat::detect(FREQUENCIES),
+            DataFormat::OurAirportsFrequencies
+        );
+        assert_eq!(
+            DataFormat::detect(
+                r#"340,"Frankfurt am Main Airport","Frankfurt","Germany","FRA","EDDF",50.03,8.57,364,1,"E","Europe/Berlin","airport","OurAirports""#
+            ),
+            DataFormat::OpenFlights
+        );
+        assert_eq!(DataFormat::detect(""), DataFormat::OpenFlights);
+    }
+
+    #[test]
+    fn test_load_airports() {
+        let report = load_airports(AIRPORTS);
+
+        assert_eq!(report.airports.len(), 2);
+        let frankfurt = &report.airports[0];
+        assert_eq!(frankfurt.id, 2212);
+        assert_eq!(frankfurt.code, "FRA");
+        assert_eq!(frankfurt.icao.as_ref().unwrap(), "EDDF");
+        assert_eq!(frankfurt.city, "Frankfurt am Main");
+        assert_eq!(frankfurt.country, "DE");
+        assert_eq!(frankfurt.elevation_ft, Some(364));
+
+        // Neither IATA nor ICAO code, the ident stands in
+        let heliport = &report.airports[1];
+        assert_eq!(heliport.code, "00A");
+        assert_eq!(heliport.iata, None);
+
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, 4);
+    }
+
+    #[test]
+    fn test_load_runways() {
+        let (runways, errors) = load_runways(RUNWAYS);
+
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(runways.len(), 3);
+        assert_eq!(runways[0].designation(), "07C/25C");
+        assert_eq!(runways[0].length_ft, Some(13123));
+        assert_eq!(runways[0].surface, "ASP");
+        assert!(runways[0].lighted && !runways[0].closed);
+        assert_eq!(runways[0].low_end.heading_deg, Some(69.9));
+        assert_eq!(
+            runways[0].high_end.as_ref().unwrap().elevation_ft,
+            Some(364)
+        );
+        // Single-ended
+        assert_eq!(runways[1].designation(), "18");
+        assert_eq!(runways[2].airport_id, 6523);
+    }
+
+    #[test]
+    fn test_load_frequencies() {
+        let (frequencies, errors) = load_frequencies(FREQUENCIES);
+
+        assert_eq!(frequencies.len(), 2);
+        assert_eq!(frequencies[0].frequency_type, "TWR");
+        assert_eq!(frequencies[0].frequency_mhz, 119.9);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 4);
+    }
+}
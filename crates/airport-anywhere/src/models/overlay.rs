@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use sky_tracer::model::airport::{Airport, AirportError};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Environment variable with the path of the overlay file
+pub const AIRPORTS_OVERLAY_PATH_ENV: &str = "AIRPORTS_OVERLAY_PATH";
+
+/// The overlay file configured in `AIRPORTS_OVERLAY_PATH`, if any
+pub fn overlay_path_from_env() -> Option<PathBuf> {
+    std::env::var(AIRPORTS_OVERLAY_PATH_ENV)
+        .ok()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// First id given to airports added through the overlay.
+///
+/// Far above the ids of OpenFlights and OurAirports, so a reloaded base
+/// dataset does not collide with added airports.
+pub const FIRST_OVERLAY_ID: u32 = 1 << 30;
+
+/// Local changes laid over the base airport dataset
+///
+/// Airports are keyed by id. One with the id of a base airport replaces it,
+/// any other is added. Hidden base airports are left out of the served data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Overlay {
+    #[serde(default)]
+    airports: BTreeMap<u32, Airport>,
+    #[serde(default)]
+    hidden: BTreeSet<u32>,
+}
+
+impl Overlay {
+    /// Reads the overlay file, a missing file is an empty overlay
+    pub fn load(path: &Path) -> Result<Self, AirportError> {
+        match std::fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data).map_err(|e| {
+                AirportError::LoadError(format!("invalid overlay {}: {e}", path.display()))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the overlay file, replacing the previous one in a single step
+    pub fn save(&self, path: &Path) -> Result<(), AirportError> {
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| AirportError::LoadError(format!("cannot serialize overlay: {e}")))?;
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, data)?;
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.airports.is_empty() && self.hidden.is_empty()
+    }
+
+    /// Added and replacing airports in id order
+    pub fn airports(&self) -> impl Iterator<Item = &Airport> {
+        self.airports.values()
+    }
+
+    /// Ids of hidden base airports
+    pub fn hidden(&self) -> impl Iterator<Item = u32> + '_ {
+        self.hidden.iter().copied()
+    }
+
+    /// Id for the next added airport, `None` once the ids are used up
+    pub fn next_id(&self) -> Option<u32> {
+        match self.airports.range(FIRST_OVERLAY_ID..).next_back() {
+            Some((&id, _)) => id.checked_add(1),
+            None => Some(FIRST_OVERLAY_ID),
+        }
+    }
+
+    /// Adds `airport` or replaces the entry with its id, showing it again
+    /// if it was hidden
+    pub fn upsert(&mut self, airport: Airport) {
+        self.hidden.remove(&airport.id);
+        self.airports.insert(airport.id, airport);
+    }
+
+    /// Takes the airport with `id` out of the served data.
+    ///
+    /// Added airports are dropped from the overlay, base airports are hidden.
+    pub fn remove(&mut self, id: u32, in_base: bool) {
+        self.airports.remove(&id);
+        if in_base {
+            self.hidden.insert(id);
+        }
+    }
+
+    /// The base airports with the overlay applied.
+    ///
+    /// Replaced airports keep their position in the base order, added
+    /// airports follow in id order.
+    pub fn apply<'a>(&self, base: impl Iterator<Item = &'a Arc<Airport>>) -> Vec<Arc<Airport>> {
+        let mut replaced = HashSet::new();
+        let mut airports: Vec<Arc<Airport>> = base
+            .filter(|airport| !self.hidden.contains(&airport.id))
+            .map(|airport| match self.airports.get(&airport.id) {
+                Some(replacement) => {
+                    replaced.insert(airport.id);
+                    Arc::new(replacement.clone())
+                }
+                None => airport.clone(),
+            })
+            .collect();
+        airports.extend(
+            self.airports
+                .values()
+                .filter(|airport| !replaced.contains(&airport.id))
+                .map(|airport| Arc::new(airport.clone())),
+        );
+        airports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn airport(id: u32, code: &str) -> Airport {
+        Airport {
+            id,
+            code: code.to_string(),
+            name: format!("{code} Airfield"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let base: Vec<Arc<Airport>> = [airport(1, "AAA"), airport(2, "BBB"), airport(3, "CCC")]
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+        let mut overlay = Overlay::default();
+        let added = airport(overlay.next_id().unwrap(), "ZZZ");
+        overlay.upsert(added.clone());
+        overlay.upsert(Airport {
+            latitude: 10.0,
+            ..airport(2, "BBB")
+        });
+        overlay.remove(1, true);
+
+        let merged = overlay.apply(base.iter());
+        let codes: Vec<&str> = merged.iter().map(|a| a.code.as_str()).collect();
+        assert_eq!(codes, ["BBB", "CCC", "ZZZ"]);
+        assert_eq!(merged[0].latitude, 10.0);
+        assert_eq!(merged[2].id, FIRST_OVERLAY_ID);
+        assert_eq!(overlay.next_id(), Some(FIRST_OVERLAY_ID + 1));
+
+        // Removing an added airport forgets it instead of hiding it
+        overlay.remove(added.id, false);
+        assert_eq!(overlay.hidden().collect::<Vec<_>>(), [1]);
+        assert_eq!(overlay.apply(base.iter()).len(), 2);
+
+        // Writing a hidden airport shows it again
+        overlay.upsert(airport(1, "AAA"));
+        assert_eq!(overlay.hidden().count(), 0);
+
+        overlay.upsert(airport(u32::MAX, "MAX"));
+        assert_eq!(overlay.next_id(), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("overlay.json");
+        assert_eq!(Overlay::load(&path).unwrap(), Overlay::default());
+
+        let mut overlay = Overlay::default();
+        overlay.upsert(airport(FIRST_OVERLAY_ID, "ZZZ"));
+        overlay.remove(7, true);
+        overlay.save(&path).unwrap();
+        assert_eq!(Overlay::load(&path).unwrap(), overlay);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            Overlay::load(&path),
+            Err(AirportError::LoadError(_))
+        ));
+    }
+}
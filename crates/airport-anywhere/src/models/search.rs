@@ -0,0 +1,269 @@
+//! Ranked, typo-tolerant airport search.
+//!
+//! Text is folded to lower case ASCII words before matching, so "Zürich",
+//! "ZURICH" and "zurich" are the same query. The distinct words of all
+//! airports form a [`Vocabulary`]; a query word is scored against the
+//! vocabulary once and airports only look up the scores of their words.
+//! Matches are ranked by [`MatchKind`] first and by how well the individual
+//! words matched second.
+
+use deunicode::deunicode;
+use sky_tracer::model::Airport;
+use std::collections::HashMap;
+use strsim::osa_distance;
+
+/// How an airport matched a query, from least to most relevant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MatchKind {
+    /// At least one query word only matched with typos
+    Fuzzy,
+    /// Every query word matched a word of the name, city or country
+    Word,
+    /// The whole query equals the airport's name or city
+    Name,
+    /// An IATA or ICAO code starts with the query
+    CodePrefix,
+    /// The query is the airport's IATA or ICAO code
+    ExactCode,
+}
+
+/// Lower case ASCII words of a text, accents and punctuation removed
+pub fn fold_words(text: &str) -> Vec<String> {
+    deunicode(text)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// Folded words of a name joined by single spaces
+pub fn normalize_name(name: &str) -> String {
+    fold_words(name).join(" ")
+}
+
+/// Distinct folded words of all indexed airports
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    words: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+/// Scores of one query word against every vocabulary word, 0 for no match
+#[derive(Debug, Clone)]
+pub struct WordScores(Vec<u8>);
+
+impl Vocabulary {
+    /// Collect the words of an airport's name, city and country
+    pub fn terms(&mut self, airport: &Airport) -> SearchTerms {
+        let mut words: Vec<u32> = [&airport.name, &airport.city, &airport.country]
+            .into_iter()
+            .flat_map(|text| fold_words(text))
+            .map(|word| self.intern(word))
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        SearchTerms {
+            words,
+            city: normalize_name(&airport.city),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Score a folded query word against every word of the vocabulary
+    pub fn score(&self, query: &str) -> WordScores {
+        WordScores(
+            self.words
+                .iter()
+                .map(|word| word_score(query, word))
+                .collect(),
+        )
+    }
+
+    fn intern(&mut self, word: String) -> u32 {
+        if let Some(&id) = self.ids.get(&word) {
+            return id;
+        }
+        let id = self.words.len() as u32;
+        self.words.push(word.clone());
+        self.ids.insert(word, id);
+        id
+    }
+}
+
+/// Words an airport can be found by, taken from its name, city and country
+#[derive(Debug, Clone, Default)]
+pub struct SearchTerms {
+    words: Vec<u32>,
+    city: String,
+}
+
+impl SearchTerms {
+    /// Normalized name of the city served
+    pub fn city(&self) -> &str {
+        &self.city
+    }
+
+    /// Match all query words, `None` if any of them has no counterpart.
+    ///
+    /// Returns [`MatchKind::Word`] or [`MatchKind::Fuzzy`] with a score that
+    /// grows with the quality of the individual word matches.
+    pub fn match_words(&self, query: &[WordScores]) -> Option<(MatchKind, u32)> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let mut kind = MatchKind::Word;
+        let mut score = 0;
+        for scores in query {
+            let best = self
+                .words
+                .iter()
+                .map(|&id| scores.0[id as usize])
+                .max()
+                .filter(|&best| best > 0)?;
+            if best == FUZZY_SCORE {
+                kind = MatchKind::Fuzzy;
+            }
+            score += u32::from(best);
+        }
+        Some((kind, score))
+    }
+}
+
+const FUZZY_SCORE: u8 = 1;
+
+fn word_score(query: &str, term: &str) -> u8 {
+    if term == query {
+        return 4;
+    }
+    if term.starts_with(query) {
+        return 3;
+    }
+    if query.len() >= 3 && term.contains(query) {
+        return 2;
+    }
+
+    // Typos are tolerated in longer words, as long as the first letter is right
+    let allowed = max_typos(query.len());
+    if allowed == 0 || query.as_bytes()[0] != term.as_bytes()[0] {
+        return 0;
+    }
+    let within = |other: &str| {
+        other.len().abs_diff(query.len()) <= allowed && osa_distance(query, other) <= allowed
+    };
+    // Also compare against the start of the term so words still being typed match
+    let prefix = &term[..term.len().min(query.len())];
+    if within(term) || within(prefix) {
+        FUZZY_SCORE
+    } else {
+        0
+    }
+}
+
+/// Number of typos tolerated in a query word of the given length
+fn max_typos(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(vocabulary: &mut Vocabulary, name: &str, city: &str, country: &str) -> SearchTerms {
+        vocabulary.terms(&Airport {
+            name: name.to_string(),
+            city: city.to_string(),
+            country: country.to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn query(vocabulary: &Vocabulary, text: &str) -> Vec<WordScores> {
+        fold_words(text)
+            .iter()
+            .map(|word| vocabulary.score(word))
+            .collect()
+    }
+
+    #[test]
+    fn test_fold_words() {
+        assert_eq!(fold_words("Zürich  Airport"), vec!["zurich", "airport"]);
+        assert_eq!(fold_words("Frankfurt-Hahn"), vec!["frankfurt", "hahn"]);
+        assert_eq!(
+            normalize_name(" São Paulo/Guarulhos "),
+            "sao paulo guarulhos"
+        );
+    }
+
+    #[test]
+    fn test_vocabulary_is_shared() {
+        let mut vocabulary = Vocabulary::default();
+        terms(
+            &mut vocabulary,
+            "Frankfurt am Main Airport",
+            "Frankfurt",
+            "Germany",
+        );
+        terms(&mut vocabulary, "Munich Airport", "Munich", "Germany");
+
+        // frankfurt, am, main, airport, germany, munich
+        assert_eq!(vocabulary.len(), 6);
+    }
+
+    #[test]
+    fn test_word_matches() {
+        let mut vocabulary = Vocabulary::default();
+        let fra = terms(
+            &mut vocabulary,
+            "Frankfurt am Main Airport",
+            "Frankfurt",
+            "Germany",
+        );
+        let matches = |text: &str| fra.match_words(&query(&vocabulary, text));
+
+        assert_eq!(matches("frankfurt"), Some((MatchKind::Word, 4)));
+        assert_eq!(matches("frank"), Some((MatchKind::Word, 3)));
+        assert_eq!(matches("Germany main"), Some((MatchKind::Word, 8)));
+        assert_eq!(matches("frankfurt paris"), None);
+    }
+
+    #[test]
+    fn test_typos_and_accents() {
+        let mut vocabulary = Vocabulary::default();
+        let fra = terms(
+            &mut vocabulary,
+            "Frankfurt am Main Airport",
+            "Frankfurt",
+            "Germany",
+        );
+        let zrh = terms(&mut vocabulary, "Zürich Airport", "Zurich", "Switzerland");
+
+        assert_eq!(
+            fra.match_words(&query(&vocabulary, "Frankfrt")),
+            Some((MatchKind::Fuzzy, 1))
+        );
+        assert_eq!(
+            fra.match_words(&query(&vocabulary, "frnakfu")),
+            Some((MatchKind::Fuzzy, 1))
+        );
+        assert_eq!(
+            zrh.match_words(&query(&vocabulary, "ZÜRICH")),
+            Some((MatchKind::Word, 4))
+        );
+        // Short words need to match exactly, longer ones need the first letter
+        assert_eq!(fra.match_words(&query(&vocabulary, "fro")), None);
+        assert_eq!(fra.match_words(&query(&vocabulary, "grankfurt")), None);
+    }
+}
@@ -0,0 +1,179 @@
+use super::airport_loader::{load_airports_lenient, LineError, LoadReport};
+use super::airports_service::AIRPORTS_DATA;
+use super::ourairports::{self, DataFormat, FREQUENCIES_FILE, RUNWAYS_FILE};
+use sky_tracer::model::airport::AirportError;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Environment variable with the path of an OpenFlights `airports.dat` or
+/// OurAirports `airports.csv` file
+pub const AIRPORTS_DATA_PATH_ENV: &str = "AIRPORTS_DATA_PATH";
+
+/// Where the airport dataset is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AirportSource {
+    /// The copy compiled into the binary
+    Embedded,
+    /// A data file that may be replaced while the service runs.
+    ///
+    /// The format is detected from the contents. Next to an OurAirports
+    /// `airports.csv` the `runways.csv` and `airport-frequencies.csv` of the
+    /// same export are loaded when present.
+    File(PathBuf),
+}
+
+impl AirportSource {
+    /// The file configured in `AIRPORTS_DATA_PATH`, the embedded copy otherwise
+    pub fn from_env() -> Self {
+        match std::env::var(AIRPORTS_DATA_PATH_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::File(PathBuf::from(path.trim())),
+            _ => Self::Embedded,
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Embedded => None,
+            Self::File(path) => Some(path),
+        }
+    }
+
+    /// Files whose changes affect the loaded data
+    pub fn watched_paths(&self) -> Vec<PathBuf> {
+        match self {
+            Self::Embedded => Vec::new(),
+            Self::File(path) => vec![
+                path.clone(),
+                path.with_file_name(RUNWAYS_FILE),
+                path.with_file_name(FREQUENCIES_FILE),
+            ],
+        }
+    }
+
+    /// Reads and parses the source, collecting invalid lines
+    pub fn load(&self) -> Result<LoadReport, AirportError> {
+        let path = match self {
+            Self::Embedded => return Ok(load_airports_lenient(AIRPORTS_DATA)),
+            Self::File(path) => path,
+        };
+
+        let data = std::fs::read_to_string(path)?;
+        let format = DataFormat::detect(&data);
+        let mut report = match format {
+            DataFormat::OpenFlights => load_airports_lenient(&data),
+            DataFormat::OurAirports => ourairports::load_airports(&data),
+            format => {
+                return Err(AirportError::LoadError(format!(
+                    "{} holds {}, not airports",
+                    path.display(),
+                    format
+                )))
+            }
+        };
+        tag_errors(&mut report.errors, path);
+
+        if format == DataFormat::OurAirports {
+            if let Some(data) = read_sibling(path, RUNWAYS_FILE)? {
+                let (runways, mut errors) = ourairports::load_runways(&data);
+                tag_errors(&mut errors, &path.with_file_name(RUNWAYS_FILE));
+                report.runways = runways;
+                report.errors.extend(errors);
+            }
+            if let Some(data) = read_sibling(path, FREQUENCIES_FILE)? {
+                let (frequencies, mut errors) = ourairports::load_frequencies(&data);
+                tag_errors(&mut errors, &path.with_file_name(FREQUENCIES_FILE));
+                report.frequencies = frequencies;
+                report.errors.extend(errors);
+            }
+        }
+        Ok(report)
+    }
+}
+
+fn read_sibling(path: &Path, file_name: &str) -> Result<Option<String>, AirportError> {
+    match std::fs::read_to_string(path.with_file_name(file_name)) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn tag_errors(errors: &mut [LineError], path: &Path) {
+    let file = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned());
+    for error in errors {
+        error.file.clone_from(&file);
+    }
+}
+
+impl fmt::Display for AirportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Embedded => f.write_str("embedded"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("airports.dat");
+        std::fs::write(
+            &path,
+            r#"1,"Test Airport","Test City","Test Country","TST","TTST",1.0,2.0,0,0,"E","UTC","airport","test"
+not,"a valid line""#,
+        )
+        .unwrap();
+
+        let report = AirportSource::File(path).load().unwrap();
+        assert_eq!(report.airports.len(), 1);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, 2);
+
+        let missing = AirportSource::File(dir.path().join("missing.dat"));
+        assert!(matches!(missing.load(), Err(AirportError::IoError(_))));
+    }
+
+    #[test]
+    fn test_load_ourairports_export() {
+        use super::super::ourairports::tests::{AIRPORTS, FREQUENCIES, RUNWAYS};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("airports.csv");
+        std::fs::write(&path, AIRPORTS).unwrap();
+        std::fs::write(dir.path().join(RUNWAYS_FILE), RUNWAYS).unwrap();
+
+        let report = AirportSource::File(path.clone()).load().unwrap();
+        assert_eq!(report.airports.len(), 2);
+        assert_eq!(report.runways.len(), 3);
+        // No frequencies file in this export
+        assert!(report.frequencies.is_empty());
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].file.as_deref(), Some("airports.csv"));
+
+        std::fs::write(dir.path().join(FREQUENCIES_FILE), FREQUENCIES).unwrap();
+        let report = AirportSource::File(path).load().unwrap();
+        assert_eq!(report.frequencies.len(), 2);
+        assert_eq!(
+            report.errors[1].file.as_deref(),
+            Some("airport-frequencies.csv")
+        );
+
+        // Pointing at a file of the export other than airports is an error
+        let runways = AirportSource::File(dir.path().join(RUNWAYS_FILE));
+        assert!(matches!(runways.load(), Err(AirportError::LoadError(_))));
+    }
+
+    #[test]
+    fn test_embedded_is_valid() {
+        let report = AirportSource::Embedded.load().unwrap();
+        assert!(report.airports.len() > 7000);
+        assert!(report.errors.is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Spatial index for nearest-airport queries.
+//!
+//! Positions are mapped onto the unit sphere and stored in an R*-tree. The
+//! straight-line (chord) distance between two points on the sphere grows
+//! monotonically with their great-circle distance, so the tree's euclidean
+//! nearest-neighbour order is great-circle order as well, without special
+//! cases for the poles or the antimeridian.
+
+use rstar::{primitives::GeomWithData, RTree};
+use sky_tracer::geo::EARTH_RADIUS_KM;
+
+type IndexedPoint = GeomWithData<[f64; 3], usize>;
+
+#[derive(Debug, Clone, Default)]
+pub struct SpatialIndex {
+    tree: RTree<IndexedPoint>,
+}
+
+impl SpatialIndex {
+    /// Index `(index, (latitude, longitude))` pairs
+    pub fn new(positions: impl IntoIterator<Item = (usize, (f64, f64))>) -> Self {
+        let points = positions
+            .into_iter()
+            .map(|(index, position)| IndexedPoint::new(unit_vector(position), index))
+            .collect();
+        Self {
+            tree: RTree::bulk_load(points),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.size() == 0
+    }
+
+    /// Up to `limit` indexes within `radius_km` of `position` with their
+    /// great-circle distance in kilometers, nearest first
+    pub fn nearby(&self, position: (f64, f64), radius_km: f64, limit: usize) -> Vec<(usize, f64)> {
+        self.tree
+            .nearest_neighbor_iter_with_distance_2(&unit_vector(position))
+            .map(|(point, distance_2)| (point.data, arc_length_km(distance_2.sqrt())))
+            .take_while(|&(_, distance_km)| distance_km <= radius_km)
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Position as a point on the unit sphere
+fn unit_vector((latitude, longitude): (f64, f64)) -> [f64; 3] {
+    let (lat, lon) = (latitude.to_radians(), longitude.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Great-circle distance spanned by a chord on the unit sphere
+fn arc_length_km(chord: f64) -> f64 {
+    2.0 * (chord / 2.0).clamp(0.0, 1.0).asin() * EARTH_RADIUS_KM
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sky_tracer::geo::distance_km;
+
+    const FRA: (f64, f64) = (50.033333, 8.570556);
+    const HHN: (f64, f64) = (49.945, 7.264);
+    const CDG: (f64, f64) = (49.012798, 2.55);
+    // Either side of the antimeridian in Fiji
+    const SVU: (f64, f64) = (-16.8028, 179.341);
+    const TVU: (f64, f64) = (-16.6906, -179.877);
+
+    fn index() -> SpatialIndex {
+        SpatialIndex::new([FRA, HHN, CDG, SVU, TVU].into_iter().enumerate())
+    }
+
+    #[test]
+    fn test_nearest_first_with_great_circle_distance() {
+        let results = index().nearby(FRA, 1000.0, 10);
+
+        let order: Vec<usize> = results.iter().map(|&(index, _)| index).collect();
+        assert_eq!(order, vec![0, 1, 2]);
+        assert!(results[0].1 < 1e-6);
+        assert!((results[2].1 - distance_km(FRA, CDG)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_radius_and_limit() {
+        let index = index();
+
+        assert_eq!(index.nearby(FRA, 50.0, 10).len(), 1);
+        assert_eq!(index.nearby(FRA, 1000.0, 2).len(), 2);
+        assert!(index.nearby((0.0, 0.0), 100.0, 10).is_empty());
+        assert_eq!(index.nearby((0.0, 0.0), 20_000.0, 10).len(), 5);
+    }
+
+    #[test]
+    fn test_antimeridian() {
+        let results = index().nearby(SVU, 200.0, 10);
+
+        let order: Vec<usize> = results.iter().map(|&(index, _)| index).collect();
+        assert_eq!(order, vec![3, 4]);
+        assert!((results[1].1 - distance_km(SVU, TVU)).abs() < 1e-6);
+    }
+}
@@ -1,20 +1,70 @@
 use crate::routes;
 use axum::{response::Redirect, routing::get, Router};
+use sky_tracer::model::AirportType;
 use sky_tracer::protocol::airports::{
-    AirportResponse, Position, SearchAirportsRequest, SearchAirportsResponse,
+    AirportOverlayResponse, AirportRequest, AirportResponse, AirportSortField, AirportSuggestion,
+    AutocompleteRequest, AutocompleteResponse, ExportFormat, ExportFormatRequest,
+    FrequenciesResponse, FrequencyResponse, LineErrorResponse, ListAirportsRequest,
+    ListAirportsResponse, NearbyAirportResponse, NearbyAirportsRequest, NearbyAirportsResponse,
+    Position, ReloadAirportsResponse, RunwayEndResponse, RunwayResponse, RunwaysResponse,
+    SearchAirportsRequest, SearchAirportsResponse, SortOrder,
 };
-use utoipa::OpenApi;
+use sky_tracer::protocol::flights::FieldError;
+use sky_tracer::protocol::problem::ProblemDetails;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         routes::list_airports,
-        routes::search_airports
+        routes::create_airport,
+        routes::get_airport,
+        routes::update_airport,
+        routes::delete_airport,
+        routes::airport_overlay,
+        routes::clear_airport_overlay,
+        routes::search_airports,
+        routes::nearby_airports,
+        routes::autocomplete_airports,
+        routes::airport_runways,
+        routes::airport_frequencies,
+        routes::reload_airports
     ),
     components(
-        schemas(AirportResponse, Position, SearchAirportsRequest, SearchAirportsResponse)
+        schemas(
+            AirportOverlayResponse,
+            AirportRequest,
+            AirportResponse,
+            AirportSortField,
+            AirportSuggestion,
+            AirportType,
+            AutocompleteRequest,
+            AutocompleteResponse,
+            ProblemDetails,
+            FieldError,
+            ExportFormat,
+            ExportFormatRequest,
+            FrequenciesResponse,
+            FrequencyResponse,
+            LineErrorResponse,
+            ListAirportsRequest,
+            ListAirportsResponse,
+            NearbyAirportResponse,
+            NearbyAirportsRequest,
+            NearbyAirportsResponse,
+            Position,
+            ReloadAirportsResponse,
+            RunwayEndResponse,
+            RunwayResponse,
+            RunwaysResponse,
+            SearchAirportsRequest,
+            SearchAirportsResponse,
+            SortOrder
+        )
     ),
+    modifiers(&SecurityAddon),
     tags(
         (name = "airports", description = "Airport management API")
     ),
@@ -25,6 +75,20 @@ use utoipa_swagger_ui::SwaggerUi;
 )]
 pub struct ApiDoc;
 
+/// Bearer token of the admin endpoints, see [`crate::admin::AdminToken`]
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "admin_token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+        }
+    }
+}
+
 pub fn routes<S>() -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
@@ -1,28 +1,128 @@
-use axum::{extract::Query, Json};
-use sky_tracer::protocol::airports::{SearchAirportsRequest, SearchAirportsResponse};
-use sky_tracer::protocol::{AIRPORTS_API_PATH, AIRPORTS_SEARCH_API_PATH};
-use tracing::{error, info, instrument};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use sky_tracer::model::airport::AirportError;
+use sky_tracer::protocol::airports::{
+    AirportOverlayResponse, AirportRequest, AirportResponse, AutocompleteRequest,
+    AutocompleteResponse, ExportFormatRequest, FrequenciesResponse, ListAirportsRequest,
+    ListAirportsResponse, NearbyAirportsRequest, NearbyAirportsResponse, ReloadAirportsResponse,
+    RunwaysResponse, SearchAirportsRequest, SearchAirportsResponse,
+};
+use sky_tracer::protocol::problem::{
+    ProblemDetails, ProblemJson, ProblemPath, ProblemQuery, PROBLEM_JSON,
+};
+use sky_tracer::protocol::{
+    AIRPORTS_API_PATH, AIRPORTS_AUTOCOMPLETE_API_PATH, AIRPORTS_FREQUENCIES_API_PATH,
+    AIRPORTS_NEARBY_API_PATH, AIRPORTS_OVERLAY_API_PATH, AIRPORTS_RELOAD_API_PATH,
+    AIRPORTS_RUNWAYS_API_PATH, AIRPORTS_SEARCH_API_PATH, AIRPORT_API_PATH,
+};
+use tracing::{error, info, instrument, warn};
 
+use crate::admin::AIRPORTS_ADMIN_TOKEN_ENV;
+use crate::export;
 use crate::services::AirportService;
 
-/// List all airports
+#[derive(Debug)]
+pub enum ApiError {
+    InvalidParameter(String),
+    NotFound(String),
+    Conflict(String),
+    Unauthorized,
+    AdminDisabled,
+    ServiceError(String),
+}
+
+impl From<AirportError> for ApiError {
+    fn from(error: AirportError) -> Self {
+        match error {
+            AirportError::InvalidValue { .. } | AirportError::MissingField(_) => {
+                ApiError::InvalidParameter(error.to_string())
+            }
+            AirportError::NotFound(_) => ApiError::NotFound(error.to_string()),
+            AirportError::AlreadyExists(_) | AirportError::NoIdsLeft => {
+                ApiError::Conflict(error.to_string())
+            }
+            _ => ApiError::ServiceError(error.to_string()),
+        }
+    }
+}
+
+impl From<ApiError> for ProblemDetails {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::InvalidParameter(msg) => {
+                warn!(error = %msg, "Rejected airport request");
+                ProblemDetails::new(400, "Invalid parameter")
+                    .with_code("INVALID_PARAMETER")
+                    .with_detail(msg)
+            }
+            ApiError::NotFound(msg) => ProblemDetails::new(404, "Airport not found")
+                .with_code("NOT_FOUND")
+                .with_detail(msg),
+            ApiError::Conflict(msg) => ProblemDetails::new(409, "Conflict")
+                .with_code("CONFLICT")
+                .with_detail(msg),
+            ApiError::Unauthorized => ProblemDetails::new(401, "Unauthorized")
+                .with_code("UNAUTHORIZED")
+                .with_detail("Missing or invalid admin token"),
+            ApiError::AdminDisabled => ProblemDetails::new(503, "Admin endpoints disabled")
+                .with_code("ADMIN_DISABLED")
+                .with_detail(format!(
+                    "Set {AIRPORTS_ADMIN_TOKEN_ENV} to enable the admin endpoints"
+                )),
+            ApiError::ServiceError(msg) => {
+                error!(error = %msg, "Airport request failed");
+                ProblemDetails::new(500, "Internal server error")
+                    .with_code("INTERNAL_ERROR")
+                    .with_detail(msg)
+            }
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        ProblemDetails::from(self).into_response()
+    }
+}
+
+/// List airports page by page
+///
+/// Responses carry an `ETag` of the loaded dataset, requests with a matching
+/// `If-None-Match` get `304 Not Modified`. GeoJSON and KML are served for
+/// `format` or a matching `Accept` header, GeoJSON keeps the paging fields
+/// as members of the `FeatureCollection`.
 #[utoipa::path(
     get,
     path = AIRPORTS_API_PATH,
+    params(
+        ListAirportsRequest,
+        ExportFormatRequest
+    ),
     responses(
-        (status = 200, description = "List of all airports", body = SearchAirportsResponse),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "One page of matching airports", content(
+            (ListAirportsResponse = "application/json"),
+            (Object = "application/geo+json"),
+            (String = "application/vnd.google-earth.kml+xml")
+        )),
+        (status = 304, description = "Airport data unchanged since the given ETag"),
+        (status = 400, description = "Invalid paging, sorting, filter or format parameters", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "airports"
 )]
-pub async fn list_airports() -> Json<SearchAirportsResponse> {
-    match AirportService::get_all_airports().await {
-        Ok(airports) => Json(SearchAirportsResponse { airports }),
-        Err(e) => {
-            error!(error = %e, "Failed to list airports");
-            Json(SearchAirportsResponse { airports: vec![] })
-        }
-    }
+#[instrument(skip_all)]
+pub async fn list_airports(
+    State(service): State<AirportService>,
+    ProblemQuery(params): ProblemQuery<ListAirportsRequest>,
+    ProblemQuery(format): ProblemQuery<ExportFormatRequest>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = export::negotiate(format.format, &headers);
+    Ok(export::respond(format, service.list(&params)?))
 }
 
 /// Search airports by name or code
@@ -30,51 +130,429 @@ pub async fn list_airports() -> Json<SearchAirportsResponse> {
     get,
     path = AIRPORTS_SEARCH_API_PATH,
     params(
-        SearchAirportsRequest
+        SearchAirportsRequest,
+        ExportFormatRequest
     ),
     responses(
-        (status = 200, description = "List of matching airports", body = SearchAirportsResponse),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "List of matching airports", content(
+            (SearchAirportsResponse = "application/json"),
+            (Object = "application/geo+json"),
+            (String = "application/vnd.google-earth.kml+xml")
+        )),
+        (status = 400, description = "Unknown format", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "airports"
 )]
-#[instrument]
+#[instrument(skip(service, format, headers))]
 pub async fn search_airports(
-    Query(params): Query<SearchAirportsRequest>,
-) -> Json<SearchAirportsResponse> {
+    State(service): State<AirportService>,
+    ProblemQuery(params): ProblemQuery<SearchAirportsRequest>,
+    ProblemQuery(format): ProblemQuery<ExportFormatRequest>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = export::negotiate(format.format, &headers);
     info!(
         code = params.code.as_deref().unwrap_or("none"),
+        q = params.q.as_deref().unwrap_or("none"),
         name = params.name.as_deref().unwrap_or("none"),
         "Searching for airports"
     );
 
-    match AirportService::search(params.code, params.name).await {
-        Ok(airports) => Json(SearchAirportsResponse { airports }),
-        Err(e) => {
-            error!(error = %e, "Failed to search airports");
-            Json(SearchAirportsResponse { airports: vec![] })
-        }
-    }
+    let response = SearchAirportsResponse {
+        airports: service.search(&params),
+    };
+    Ok(export::respond(format, response))
+}
+
+/// Suggest airports for partially typed input
+#[utoipa::path(
+    get,
+    path = AIRPORTS_AUTOCOMPLETE_API_PATH,
+    params(
+        AutocompleteRequest
+    ),
+    responses(
+        (status = 200, description = "Suggestions ordered by relevance", body = AutocompleteResponse)
+    ),
+    tag = "airports"
+)]
+#[instrument(skip(service))]
+pub async fn autocomplete_airports(
+    State(service): State<AirportService>,
+    ProblemQuery(params): ProblemQuery<AutocompleteRequest>,
+) -> Json<AutocompleteResponse> {
+    Json(AutocompleteResponse {
+        suggestions: service.autocomplete(&params.q, params.limit),
+    })
+}
+
+/// Find airports around a position
+#[utoipa::path(
+    get,
+    path = AIRPORTS_NEARBY_API_PATH,
+    params(
+        NearbyAirportsRequest,
+        ExportFormatRequest
+    ),
+    responses(
+        (status = 200, description = "Airports within the radius, nearest first", content(
+            (NearbyAirportsResponse = "application/json"),
+            (Object = "application/geo+json"),
+            (String = "application/vnd.google-earth.kml+xml")
+        )),
+        (status = 400, description = "Invalid coordinates, radius, limit or format", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    tag = "airports"
+)]
+#[instrument(skip_all)]
+pub async fn nearby_airports(
+    State(service): State<AirportService>,
+    ProblemQuery(params): ProblemQuery<NearbyAirportsRequest>,
+    ProblemQuery(format): ProblemQuery<ExportFormatRequest>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = export::negotiate(format.format, &headers);
+    let airports = service.nearby(&params)?;
+    Ok(export::respond(format, NearbyAirportsResponse { airports }))
+}
+
+/// Runways of an airport
+///
+/// Only OurAirports data has runways, for other data the list is empty.
+#[utoipa::path(
+    get,
+    path = AIRPORTS_RUNWAYS_API_PATH,
+    params(
+        ("code" = String, Path, description = "IATA, ICAO or OurAirports ident of the airport")
+    ),
+    responses(
+        (status = 200, description = "Runways with headings, lengths and surfaces", body = RunwaysResponse),
+        (status = 404, description = "No airport with this code", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    tag = "airports"
+)]
+#[instrument(skip(service))]
+pub async fn airport_runways(
+    State(service): State<AirportService>,
+    ProblemPath(code): ProblemPath<String>,
+) -> Result<Json<RunwaysResponse>, ApiError> {
+    Ok(Json(service.runways(&code)?))
+}
+
+/// Radio frequencies of an airport
+///
+/// Only OurAirports data has frequencies, for other data the list is empty.
+#[utoipa::path(
+    get,
+    path = AIRPORTS_FREQUENCIES_API_PATH,
+    params(
+        ("code" = String, Path, description = "IATA, ICAO or OurAirports ident of the airport")
+    ),
+    responses(
+        (status = 200, description = "Frequencies by service", body = FrequenciesResponse),
+        (status = 404, description = "No airport with this code", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    tag = "airports"
+)]
+#[instrument(skip(service))]
+pub async fn airport_frequencies(
+    State(service): State<AirportService>,
+    ProblemPath(code): ProblemPath<String>,
+) -> Result<Json<FrequenciesResponse>, ApiError> {
+    Ok(Json(service.frequencies(&code)?))
+}
+
+/// Get an airport by code
+#[utoipa::path(
+    get,
+    path = AIRPORT_API_PATH,
+    params(
+        ("code" = String, Path, description = "IATA, ICAO or OurAirports ident of the airport")
+    ),
+    responses(
+        (status = 200, description = "The airport", body = AirportResponse),
+        (status = 404, description = "No airport with this code", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    tag = "airports"
+)]
+#[instrument(skip(service))]
+pub async fn get_airport(
+    State(service): State<AirportService>,
+    ProblemPath(code): ProblemPath<String>,
+) -> Result<Json<AirportResponse>, ApiError> {
+    Ok(Json(service.airport(&code)?))
+}
+
+/// Add an airport to the overlay
+///
+/// Added airports get ids from 2^30 on and are served until deleted.
+/// Requires `Authorization: Bearer <token>` with the token in
+/// `AIRPORTS_ADMIN_TOKEN`, disabled while it is unset.
+#[utoipa::path(
+    post,
+    path = AIRPORTS_API_PATH,
+    request_body = AirportRequest,
+    responses(
+        (status = 201, description = "Airport added", body = AirportResponse),
+        (status = 400, description = "Empty, reserved or invalid code, empty name or invalid position", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 401, description = "Missing or invalid admin token", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 409, description = "A code is already used by another airport, or no ids are left", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 422, description = "Body without name, code or position", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Overlay cannot be saved", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 503, description = "No admin token configured", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    tag = "airports"
+)]
+#[instrument(skip_all)]
+pub async fn create_airport(
+    State(service): State<AirportService>,
+    ProblemJson(request): ProblemJson<AirportRequest>,
+) -> Result<(StatusCode, Json<AirportResponse>), ApiError> {
+    let airport = blocking(move || service.create_airport(request)).await?;
+    Ok((StatusCode::CREATED, Json(airport)))
+}
+
+/// Replace the data of an airport
+///
+/// The airport keeps its id. Corrections of loaded airports live in the
+/// overlay and survive reloads. Requires the admin token like adding.
+#[utoipa::path(
+    put,
+    path = AIRPORT_API_PATH,
+    params(
+        ("code" = String, Path, description = "IATA, ICAO or OurAirports ident of the airport")
+    ),
+    request_body = AirportRequest,
+    responses(
+        (status = 200, description = "Airport updated", body = AirportResponse),
+        (status = 400, description = "Empty, reserved or invalid code, empty name or invalid position", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 401, description = "Missing or invalid admin token", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 404, description = "No airport with this code", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 409, description = "A code is already used by another airport", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 422, description = "Body without name, code or position", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Overlay cannot be saved", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 503, description = "No admin token configured", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    tag = "airports"
+)]
+#[instrument(skip(service, request))]
+pub async fn update_airport(
+    State(service): State<AirportService>,
+    ProblemPath(code): ProblemPath<String>,
+    ProblemJson(request): ProblemJson<AirportRequest>,
+) -> Result<Json<AirportResponse>, ApiError> {
+    let airport = blocking(move || service.update_airport(&code, request)).await?;
+    Ok(Json(airport))
+}
+
+/// Delete an airport
+///
+/// Loaded airports are hidden, added ones are removed from the overlay.
+/// Requires the admin token like adding.
+#[utoipa::path(
+    delete,
+    path = AIRPORT_API_PATH,
+    params(
+        ("code" = String, Path, description = "IATA, ICAO or OurAirports ident of the airport")
+    ),
+    responses(
+        (status = 204, description = "Airport no longer served"),
+        (status = 401, description = "Missing or invalid admin token", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 404, description = "No airport with this code", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Overlay cannot be saved", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 503, description = "No admin token configured", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    tag = "airports"
+)]
+#[instrument(skip(service))]
+pub async fn delete_airport(
+    State(service): State<AirportService>,
+    ProblemPath(code): ProblemPath<String>,
+) -> Result<StatusCode, ApiError> {
+    blocking(move || service.delete_airport(&code)).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Local changes on top of the airport data
+#[utoipa::path(
+    get,
+    path = AIRPORTS_OVERLAY_API_PATH,
+    responses(
+        (status = 200, description = "Added and replaced airports and hidden ids", body = AirportOverlayResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 503, description = "No admin token configured", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    tag = "airports"
+)]
+#[instrument(skip_all)]
+pub async fn airport_overlay(
+    State(service): State<AirportService>,
+) -> Json<AirportOverlayResponse> {
+    Json(service.overlay())
+}
+
+/// Drop all local changes
+#[utoipa::path(
+    delete,
+    path = AIRPORTS_OVERLAY_API_PATH,
+    responses(
+        (status = 204, description = "The loaded airport data is served as is"),
+        (status = 401, description = "Missing or invalid admin token", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Overlay cannot be saved", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 503, description = "No admin token configured", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    tag = "airports"
+)]
+#[instrument(skip_all)]
+pub async fn clear_airport_overlay(
+    State(service): State<AirportService>,
+) -> Result<StatusCode, ApiError> {
+    blocking(move || service.clear_overlay()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Parsing and indexing takes a while, keep it off the async workers
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T, AirportError> + Send + 'static,
+) -> Result<T, ApiError> {
+    Ok(tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| ApiError::ServiceError(e.to_string()))??)
+}
+
+/// Reload the airport data from its source
+///
+/// Invalid lines are skipped and listed. Requires `Authorization: Bearer
+/// <token>` with the token in `AIRPORTS_ADMIN_TOKEN`, disabled while it is
+/// unset.
+#[utoipa::path(
+    post,
+    path = AIRPORTS_RELOAD_API_PATH,
+    responses(
+        (status = 200, description = "New data is served, skipped lines are listed", body = ReloadAirportsResponse),
+        (status = 401, description = "Missing or invalid admin token", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 422, description = "No valid airport in the data, the previous data is still served", body = ReloadAirportsResponse),
+        (status = 500, description = "Data source cannot be read", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 503, description = "No admin token configured", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    security(
+        ("admin_token" = [])
+    ),
+    tag = "airports"
+)]
+#[instrument(skip_all)]
+pub async fn reload_airports(
+    State(service): State<AirportService>,
+) -> Result<(StatusCode, Json<ReloadAirportsResponse>), ApiError> {
+    let report = blocking(move || service.reload()).await?;
+
+    let status = if report.reloaded {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    Ok((status, Json(report)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::extract::Query;
+    use serde::de::DeserializeOwned;
+    use sky_tracer::protocol::airports::ExportFormat;
+    use std::sync::OnceLock;
+
+    fn state() -> State<AirportService> {
+        static SERVICE: OnceLock<AirportService> = OnceLock::new();
+        State(
+            SERVICE
+                .get_or_init(|| AirportService::embedded().unwrap())
+                .clone(),
+        )
+    }
+
+    async fn error_body(error: ApiError) -> (StatusCode, ProblemDetails) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn json() -> ProblemQuery<ExportFormatRequest> {
+        ProblemQuery(ExportFormatRequest::default())
+    }
+
+    async fn body<T: DeserializeOwned>(response: Response) -> T {
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn search(params: SearchAirportsRequest) -> SearchAirportsResponse {
+        let response =
+            search_airports(state(), ProblemQuery(params), json(), HeaderMap::new()).await;
+        body(response.unwrap()).await
+    }
 
     #[tokio::test]
     async fn test_list_airports() {
-        let response = list_airports().await;
-        assert!(!response.airports.is_empty());
+        let params = ListAirportsRequest {
+            limit: Some(10),
+            ..Default::default()
+        };
+        let response: ListAirportsResponse = body(
+            list_airports(state(), ProblemQuery(params), json(), HeaderMap::new())
+                .await
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.airports.len(), 10);
+        assert!(response.total > 7000);
+        assert_eq!(response.next_offset, Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_list_airports_rejects_invalid_bbox() {
+        let params = ListAirportsRequest {
+            bbox: Some("10,60,20,50".to_string()),
+            ..Default::default()
+        };
+        let error = list_airports(state(), ProblemQuery(params), json(), HeaderMap::new())
+            .await
+            .unwrap_err();
+        let (status, body) = error_body(error).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code.as_deref(), Some("INVALID_PARAMETER"));
+        assert!(body.message().contains("bbox"));
     }
 
     #[tokio::test]
     async fn test_search_airports_by_code() {
         let params = SearchAirportsRequest {
-            name: None,
             code: Some("FRA".to_string()),
+            ..Default::default()
         };
-        let response = search_airports(Query(params)).await;
+        let response = search(params).await;
         assert!(!response.airports.is_empty());
     }
 
@@ -82,9 +560,9 @@ mod tests {
     async fn test_search_airports_by_name() {
         let params = SearchAirportsRequest {
             name: Some("Frankfurt".to_string()),
-            code: None,
+            ..Default::default()
         };
-        let response = search_airports(Query(params)).await;
+        let response = search(params).await;
         assert!(!response.airports.is_empty());
         assert!(response
             .airports
@@ -95,10 +573,107 @@ mod tests {
     #[tokio::test]
     async fn test_search_airports_no_results() {
         let params = SearchAirportsRequest {
-            name: None,
             code: Some("NONEXISTENT".to_string()),
+            ..Default::default()
         };
-        let response = search_airports(Query(params)).await;
+        let response = search(params).await;
         assert!(response.airports.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_search_airports_by_text_with_limit() {
+        let params = SearchAirportsRequest {
+            q: Some("lisbon".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let response = search(params).await;
+        assert_eq!(response.airports.len(), 1);
+        assert_eq!(response.airports[0].code, "LIS");
+    }
+
+    #[tokio::test]
+    async fn test_search_airports_as_geojson() {
+        let params = SearchAirportsRequest {
+            code: Some("FRA".to_string()),
+            ..Default::default()
+        };
+        let format = ProblemQuery(ExportFormatRequest {
+            format: Some(ExportFormat::GeoJson),
+        });
+        let response = search_airports(state(), ProblemQuery(params), format, HeaderMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers()["content-type"],
+            ExportFormat::GeoJson.content_type()
+        );
+        let collection: geojson::FeatureCollection = body(response).await;
+        assert_eq!(
+            collection.features[0].properties.as_ref().unwrap()["code"],
+            "FRA"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_autocomplete_airports() {
+        let params = AutocompleteRequest {
+            q: "munich".to_string(),
+            limit: None,
+        };
+        let response = autocomplete_airports(state(), ProblemQuery(params)).await;
+        assert_eq!(response.suggestions[0].code, "MUC");
+    }
+
+    #[tokio::test]
+    async fn test_airport_runways() {
+        let response = airport_runways(state(), ProblemPath("eddf".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.airport, "FRA");
+
+        let error = airport_frequencies(state(), ProblemPath("NONEXISTENT".to_string()))
+            .await
+            .unwrap_err();
+        let (status, body) = error_body(error).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.code.as_deref(), Some("NOT_FOUND"));
+    }
+
+    #[tokio::test]
+    async fn test_nearby_airports() {
+        let params = NearbyAirportsRequest {
+            lat: 50.033,
+            lon: 8.57,
+            radius_km: Some(25.0),
+            limit: Some(1),
+        };
+        let response: NearbyAirportsResponse = body(
+            nearby_airports(state(), ProblemQuery(params), json(), HeaderMap::new())
+                .await
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.airports.len(), 1);
+        assert_eq!(response.airports[0].airport.code, "FRA");
+    }
+
+    #[tokio::test]
+    async fn test_nearby_airports_rejects_invalid_latitude() {
+        let params = NearbyAirportsRequest {
+            lat: 123.0,
+            lon: 8.57,
+            radius_km: None,
+            limit: None,
+        };
+        let error = nearby_airports(state(), ProblemQuery(params), json(), HeaderMap::new())
+            .await
+            .unwrap_err();
+        let (status, body) = error_body(error).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code.as_deref(), Some("INVALID_PARAMETER"));
+    }
 }
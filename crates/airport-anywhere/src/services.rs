@@ -1,135 +1,1040 @@
-use crate::models::AirportsService;
-use sky_tracer::model::airport::AirportError;
-use sky_tracer::protocol::airports::AirportResponse;
-use tracing::{info, instrument};
+use crate::models::{AirportFilter, AirportSource, AirportsService, BoundingBox, Overlay};
+use arc_swap::ArcSwap;
+use sky_tracer::model::airport::{Airport, AirportError};
+use sky_tracer::model::{IataCode, IcaoCode};
+use sky_tracer::protocol::airports::{
+    AirportOverlayResponse, AirportRequest, AirportResponse, AirportSuggestion,
+    FrequenciesResponse, FrequencyResponse, LineErrorResponse, ListAirportsRequest,
+    ListAirportsResponse, NearbyAirportResponse, NearbyAirportsRequest, Position,
+    ReloadAirportsResponse, RunwayResponse, RunwaysResponse, SearchAirportsRequest,
+    DEFAULT_AUTOCOMPLETE_LIMIT, DEFAULT_LIST_LIMIT, DEFAULT_NEARBY_LIMIT, DEFAULT_NEARBY_RADIUS_KM,
+    DEFAULT_SEARCH_LIMIT, MAX_AUTOCOMPLETE_LIMIT, MAX_LIST_LIMIT, MAX_NEARBY_LIMIT,
+    MAX_NEARBY_RADIUS_KM, MAX_REPORTED_LINE_ERRORS, MAX_SEARCH_LIMIT,
+};
+use sky_tracer::protocol::health::DependencyHealth;
+use sky_tracer::protocol::{
+    AIRPORTS_AUTOCOMPLETE_API_PATH, AIRPORTS_NEARBY_API_PATH, AIRPORTS_OVERLAY_API_PATH,
+    AIRPORTS_RELOAD_API_PATH, AIRPORTS_SEARCH_API_PATH,
+};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tracing::{info, instrument, warn};
 
 /// Airport service for business logic operations
-pub struct AirportService;
+///
+/// Cheap to clone, all clones share the same loaded dataset. A
+/// [reload](Self::reload) swaps the dataset atomically, requests in flight
+/// finish on the one they started with. Airports can be added, replaced or
+/// hidden through an [`Overlay`] on top of the loaded data, which survives
+/// reloads and is persisted when an [overlay file](Self::with_overlay_file)
+/// is set.
+#[derive(Debug, Clone)]
+pub struct AirportService {
+    airports: Arc<ArcSwap<AirportsService>>,
+    source: Option<AirportSource>,
+    overlay_path: Option<PathBuf>,
+    layers: Arc<Mutex<Layers>>,
+}
+
+/// What the served dataset is built from
+///
+/// Writers hold the lock while building and swapping in a new dataset, so
+/// concurrent reloads and overlay changes cannot lose each other's update.
+#[derive(Debug)]
+struct Layers {
+    base: Arc<AirportsService>,
+    overlay: Overlay,
+}
 
 impl AirportService {
+    /// Service over a fixed dataset that cannot be reloaded
+    pub fn new(airports: AirportsService) -> Self {
+        let base = Arc::new(airports);
+        Self {
+            airports: Arc::new(ArcSwap::new(base.clone())),
+            source: None,
+            overlay_path: None,
+            layers: Arc::new(Mutex::new(Layers {
+                base,
+                overlay: Overlay::default(),
+            })),
+        }
+    }
+
+    /// Service backed by the airport data compiled into the binary
+    pub fn embedded() -> Result<Self, AirportError> {
+        Self::from_source(AirportSource::Embedded)
+    }
+
+    /// Service loading and reloading from `source`.
+    ///
+    /// Invalid lines are skipped. When the source cannot be read or holds
+    /// no valid airport at all the embedded data is served until a reload
+    /// succeeds.
+    pub fn from_source(source: AirportSource) -> Result<Self, AirportError> {
+        let airports = match source.load() {
+            Ok(report) if !report.airports.is_empty() => {
+                for error in &report.errors {
+                    warn!(source = %source, line = error.line, error = %error.message, "Skipped invalid airport line");
+                }
+                AirportsService::from_airports(report.airports)
+                    .with_facilities(report.runways, report.frequencies)
+            }
+            Ok(report) => {
+                warn!(source = %source, invalid_lines = report.errors.len(), "No valid airports, using embedded data");
+                AirportsService::embedded()?
+            }
+            Err(e) => {
+                warn!(source = %source, error = %e, "Cannot read airport data, using embedded data");
+                AirportsService::embedded()?
+            }
+        };
+
+        Ok(Self {
+            source: Some(source),
+            ..Self::new(airports)
+        })
+    }
+
+    /// Applies the overlay stored in `path` and keeps it there on changes.
+    ///
+    /// A missing file starts an empty overlay, an unreadable one is an error
+    /// so local changes are never silently dropped.
+    pub fn with_overlay_file(mut self, path: PathBuf) -> Result<Self, AirportError> {
+        let overlay = Overlay::load(&path)?;
+        {
+            let mut layers = self.lock_layers();
+            layers.overlay = overlay;
+            self.publish(&layers);
+        }
+        info!(path = %path.display(), airports = self.airports.load().len(), "Applied airport overlay");
+        self.overlay_path = Some(path);
+        Ok(self)
+    }
+
+    /// Where reloads read from, `None` for a fixed dataset
+    pub fn source(&self) -> Option<&AirportSource> {
+        self.source.as_ref()
+    }
+
+    /// File the overlay is persisted to, `None` keeps it in memory only
+    pub fn overlay_path(&self) -> Option<&Path> {
+        self.overlay_path.as_deref()
+    }
+
+    /// The currently served dataset
+    pub fn airports(&self) -> Arc<AirportsService> {
+        self.airports.load_full()
+    }
+
+    /// Readiness of the served dataset, down while it holds no airport
+    pub fn data_health(&self) -> DependencyHealth {
+        match self.airports.load().len() {
+            0 => DependencyHealth::down("airport-data", Duration::ZERO, "No airports loaded"),
+            _ => DependencyHealth::up("airport-data", Duration::ZERO),
+        }
+    }
+
+    fn lock_layers(&self) -> MutexGuard<'_, Layers> {
+        self.layers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Serves the base dataset with the overlay applied
+    fn publish(&self, layers: &Layers) {
+        let served = if layers.overlay.is_empty() {
+            layers.base.clone()
+        } else {
+            Arc::new(layers.base.with_overlay(&layers.overlay))
+        };
+        self.airports.store(served);
+    }
+
+    /// Re-reads the source and swaps in the new dataset.
+    ///
+    /// Invalid lines are skipped and reported. If no valid airport is left
+    /// the served dataset is kept and the report says `reloaded: false`.
+    /// The overlay is applied to the new data.
+    #[instrument(skip(self))]
+    pub fn reload(&self) -> Result<ReloadAirportsResponse, AirportError> {
+        let source = self.source.as_ref().ok_or_else(|| {
+            AirportError::LoadError("no airport data source to reload from".into())
+        })?;
+        let mut layers = self.lock_layers();
+
+        let report = source.load()?;
+        let previous = self.airports.load().version();
+        let reloaded = !report.airports.is_empty();
+        if reloaded {
+            layers.base = Arc::new(
+                AirportsService::from_airports(report.airports)
+                    .with_facilities(report.runways, report.frequencies),
+            );
+            self.publish(&layers);
+        }
+        let current = self.airports.load();
+
+        info!(
+            source = %source,
+            reloaded,
+            airports = current.len(),
+            invalid_lines = report.errors.len(),
+            "Reloaded airport data"
+        );
+        Ok(ReloadAirportsResponse {
+            reloaded,
+            source: source.to_string(),
+            airports: current.len(),
+            version: format!("{:016x}", current.version()),
+            changed: current.version() != previous,
+            invalid_lines: report.errors.len(),
+            errors: report
+                .errors
+                .into_iter()
+                .take(MAX_REPORTED_LINE_ERRORS)
+                .map(|error| LineErrorResponse {
+                    file: error.file,
+                    line: error.line,
+                    error: error.message,
+                })
+                .collect(),
+        })
+    }
+
+    /// Changes the overlay, persists it and serves the result.
+    ///
+    /// `change` sees the base and the served dataset of the moment. When it
+    /// fails or the overlay cannot be saved nothing changes.
+    fn change_overlay<T>(
+        &self,
+        change: impl FnOnce(&AirportsService, &AirportsService, &mut Overlay) -> Result<T, AirportError>,
+    ) -> Result<T, AirportError> {
+        let mut layers = self.lock_layers();
+        let mut overlay = layers.overlay.clone();
+        let result = change(&layers.base, &self.airports.load(), &mut overlay)?;
+
+        if let Some(path) = &self.overlay_path {
+            overlay.save(path)?;
+        }
+        layers.overlay = overlay;
+        self.publish(&layers);
+        Ok(result)
+    }
+
+    /// The airport with the given primary, IATA or ICAO code
+    #[instrument(skip(self))]
+    pub fn airport(&self, code: &str) -> Result<AirportResponse, AirportError> {
+        self.airports
+            .load()
+            .find_exact(code)
+            .map(|airport| AirportResponse::from(airport.as_ref()))
+            .ok_or_else(|| AirportError::NotFound(code.to_string()))
+    }
+
+    /// Adds an airport to the overlay under a new id
+    #[instrument(skip(self))]
+    pub fn create_airport(&self, request: AirportRequest) -> Result<AirportResponse, AirportError> {
+        let request = validate(request)?;
+        let airport = self.change_overlay(|_, served, overlay| {
+            let id = overlay.next_id().ok_or(AirportError::NoIdsLeft)?;
+            let airport = request.into_airport(id);
+            check_codes_unused(served, &airport)?;
+            overlay.upsert(airport.clone());
+            Ok(airport)
+        })?;
+
+        info!(id = airport.id, code = %airport.code, "Added airport");
+        Ok(AirportResponse::from(&airport))
+    }
+
+    /// Replaces the airport with the given code, keeping its id
+    #[instrument(skip(self))]
+    pub fn update_airport(
+        &self,
+        code: &str,
+        request: AirportRequest,
+    ) -> Result<AirportResponse, AirportError> {
+        let request = validate(request)?;
+        let airport = self.change_overlay(|_, served, overlay| {
+            let existing = served
+                .find_exact(code)
+                .ok_or_else(|| AirportError::NotFound(code.to_string()))?;
+            let airport = request.into_airport(existing.id);
+            check_codes_unused(served, &airport)?;
+            overlay.upsert(airport.clone());
+            Ok(airport)
+        })?;
+
+        info!(id = airport.id, code = %airport.code, "Updated airport");
+        Ok(AirportResponse::from(&airport))
+    }
+
+    /// Takes the airport with the given code out of the served data.
+    ///
+    /// Airports of the loaded data are hidden, added ones are dropped.
+    #[instrument(skip(self))]
+    pub fn delete_airport(&self, code: &str) -> Result<(), AirportError> {
+        let id = self.change_overlay(|base, served, overlay| {
+            let existing = served
+                .find_exact(code)
+                .ok_or_else(|| AirportError::NotFound(code.to_string()))?;
+            overlay.remove(existing.id, base.find_by_id(existing.id).is_some());
+            Ok(existing.id)
+        })?;
+
+        info!(id, code = %code, "Deleted airport");
+        Ok(())
+    }
+
+    /// Current local changes
+    pub fn overlay(&self) -> AirportOverlayResponse {
+        let layers = self.lock_layers();
+        AirportOverlayResponse {
+            airports: layers
+                .overlay
+                .airports()
+                .map(AirportResponse::from)
+                .collect(),
+            hidden: layers.overlay.hidden().collect(),
+        }
+    }
+
+    /// Drops all local changes, serving the loaded data as is
+    #[instrument(skip(self))]
+    pub fn clear_overlay(&self) -> Result<(), AirportError> {
+        self.change_overlay(|_, _, overlay| {
+            *overlay = Overlay::default();
+            Ok(())
+        })?;
+        info!("Cleared airport overlay");
+        Ok(())
+    }
+
     /// Get all airports
-    #[instrument]
-    pub async fn get_all_airports() -> Result<Vec<AirportResponse>, AirportError> {
-        let service = AirportsService::instance()?;
-        let airports: Vec<AirportResponse> = service
+    #[instrument(skip(self))]
+    pub fn get_all_airports(&self) -> Vec<AirportResponse> {
+        let airports: Vec<AirportResponse> = self
+            .airports
+            .load()
             .all()
             .map(|airport| AirportResponse::from(airport.as_ref()))
             .collect();
 
         info!(count = airports.len(), "Retrieved all airports");
-        Ok(airports)
+        airports
     }
 
-    /// Search airports by code (IATA or ICAO)
-    #[instrument]
-    pub async fn search_by_code(code: &str) -> Result<Vec<AirportResponse>, AirportError> {
-        let service = AirportsService::instance()?;
+    /// One page of the filtered and sorted airport list
+    #[instrument(skip(self))]
+    pub fn list(
+        &self,
+        request: &ListAirportsRequest,
+    ) -> Result<ListAirportsResponse, AirportError> {
+        // One snapshot, so the cursor version matches the listed data
+        let dataset = self.airports.load();
+        let version = dataset.version();
+        let offset = match (&request.cursor, request.offset) {
+            (Some(_), Some(_)) => {
+                return Err(AirportError::invalid_value(
+                    "cursor",
+                    "cannot be combined with offset",
+                ))
+            }
+            (Some(cursor), None) => decode_cursor(cursor, version)?,
+            (None, offset) => offset.unwrap_or_default(),
+        };
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_LIST_LIMIT)
+            .min(MAX_LIST_LIMIT);
+
+        let mut filter = AirportFilter::default();
+        if let Some(country) = &request.country {
+            filter = filter.with_country(country);
+        }
+        if let Some(bbox) = &request.bbox {
+            filter = filter.with_bbox(bbox.parse::<BoundingBox>()?);
+        }
+        if let Some(airport_type) = request.airport_type {
+            filter = filter.with_airport_type(airport_type);
+        }
+
+        let matches = dataset.list(
+            &filter,
+            request.sort.unwrap_or_default(),
+            request.order.unwrap_or_default(),
+        );
+        let total = matches.len();
+        let airports: Vec<AirportResponse> = matches
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|airport| AirportResponse::from(airport.as_ref()))
+            .collect();
+        let next_offset =
+            Some(offset + airports.len()).filter(|&next| !airports.is_empty() && next < total);
+
+        info!(total, offset, count = airports.len(), "Listed airports");
+        Ok(ListAirportsResponse {
+            airports,
+            total,
+            offset,
+            limit,
+            next_offset,
+            next_cursor: next_offset.map(|next| encode_cursor(version, next)),
+        })
+    }
+
+    /// Runways of the airport with the given code
+    #[instrument(skip(self))]
+    pub fn runways(&self, code: &str) -> Result<RunwaysResponse, AirportError> {
+        let dataset = self.airports.load();
+        let airport = dataset
+            .find_exact(code)
+            .ok_or_else(|| AirportError::NotFound(code.to_string()))?;
+
+        Ok(RunwaysResponse {
+            airport: airport.code.clone(),
+            runways: dataset
+                .runways(airport.id)
+                .iter()
+                .map(RunwayResponse::from)
+                .collect(),
+        })
+    }
 
-        match service.find_by_code(code) {
+    /// Radio frequencies of the airport with the given code
+    #[instrument(skip(self))]
+    pub fn frequencies(&self, code: &str) -> Result<FrequenciesResponse, AirportError> {
+        let dataset = self.airports.load();
+        let airport = dataset
+            .find_exact(code)
+            .ok_or_else(|| AirportError::NotFound(code.to_string()))?;
+
+        Ok(FrequenciesResponse {
+            airport: airport.code.clone(),
+            frequencies: dataset
+                .frequencies(airport.id)
+                .iter()
+                .map(FrequencyResponse::from)
+                .collect(),
+        })
+    }
+
+    /// Search airports by code (IATA or ICAO)
+    #[instrument(skip(self))]
+    pub fn search_by_code(&self, code: &str) -> Vec<AirportResponse> {
+        match self.airports.load().find_by_code(code) {
             Ok(airport) => {
                 info!(code = %code, "Found airport by code");
-                Ok(vec![AirportResponse::from(airport.as_ref())])
+                vec![AirportResponse::from(airport.as_ref())]
             }
-            Err(AirportError::NotFound(_)) => {
+            Err(_) => {
                 info!(code = %code, "No airport found with code");
-                Ok(vec![])
-            }
-            Err(e) => {
-                tracing::error!(code = %code, error = %e, "Error searching airport by code");
-                Err(e)
+                vec![]
             }
         }
     }
 
-    /// Search airports by name (partial match)
-    #[instrument]
-    pub async fn search_by_name(name_query: &str) -> Result<Vec<AirportResponse>, AirportError> {
-        let service = AirportsService::instance()?;
-        let airports: Vec<AirportResponse> = service
-            .search_by_name(name_query)
+    /// Ranked, typo-tolerant search over codes, name, city and country
+    #[instrument(skip(self))]
+    pub fn search_text(&self, query: &str, limit: Option<usize>) -> Vec<AirportResponse> {
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT).min(MAX_SEARCH_LIMIT);
+        let airports: Vec<AirportResponse> = self
+            .airports
+            .load()
+            .search(query, limit)
             .into_iter()
-            .map(|airport| AirportResponse::from(airport.as_ref()))
+            .map(|hit| AirportResponse::from(hit.airport.as_ref()))
+            .collect();
+
+        info!(query = %query, count = airports.len(), "Searched airports");
+        airports
+    }
+
+    /// Suggestions for a partially typed airport, e.g. in a flight form
+    #[instrument(skip(self))]
+    pub fn autocomplete(&self, query: &str, limit: Option<usize>) -> Vec<AirportSuggestion> {
+        let limit = limit
+            .unwrap_or(DEFAULT_AUTOCOMPLETE_LIMIT)
+            .min(MAX_AUTOCOMPLETE_LIMIT);
+        self.airports
+            .load()
+            .search(query, limit)
+            .into_iter()
+            .map(|hit| AirportSuggestion::from(hit.airport.as_ref()))
+            .collect()
+    }
+
+    /// Airports around a position, nearest first
+    #[instrument(skip(self))]
+    pub fn nearby(
+        &self,
+        request: &NearbyAirportsRequest,
+    ) -> Result<Vec<NearbyAirportResponse>, AirportError> {
+        if !(-90.0..=90.0).contains(&request.lat) {
+            return Err(AirportError::invalid_value("lat", request.lat.to_string()));
+        }
+        if !(-180.0..=180.0).contains(&request.lon) {
+            return Err(AirportError::invalid_value("lon", request.lon.to_string()));
+        }
+        let radius_km = request.radius_km.unwrap_or(DEFAULT_NEARBY_RADIUS_KM);
+        if !(radius_km > 0.0 && radius_km <= MAX_NEARBY_RADIUS_KM) {
+            return Err(AirportError::invalid_value(
+                "radius_km",
+                radius_km.to_string(),
+            ));
+        }
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_NEARBY_LIMIT)
+            .min(MAX_NEARBY_LIMIT);
+
+        let airports: Vec<NearbyAirportResponse> = self
+            .airports
+            .load()
+            .nearby((request.lat, request.lon), radius_km, limit)
+            .into_iter()
+            .map(|(airport, distance_km)| NearbyAirportResponse {
+                airport: AirportResponse::from(airport.as_ref()),
+                distance_km,
+            })
             .collect();
 
-        info!(query = %name_query, count = airports.len(), "Searched airports by name");
+        info!(count = airports.len(), "Found nearby airports");
         Ok(airports)
     }
 
-    /// Combined search - try code first, then name
-    #[instrument]
-    pub async fn search(
-        code: Option<String>,
-        name: Option<String>,
-    ) -> Result<Vec<AirportResponse>, AirportError> {
-        if let Some(code) = code {
-            Self::search_by_code(&code).await
-        } else if let Some(name_query) = name {
-            Self::search_by_name(&name_query).await
+    /// Combined search - code first, then free text, then name
+    #[instrument(skip(self))]
+    pub fn search(&self, request: &SearchAirportsRequest) -> Vec<AirportResponse> {
+        if let Some(code) = &request.code {
+            self.search_by_code(code)
+        } else if let Some(query) = request.q.as_ref().or(request.name.as_ref()) {
+            self.search_text(query, request.limit)
         } else {
-            Self::get_all_airports().await
+            let mut airports = self.get_all_airports();
+            if let Some(limit) = request.limit {
+                airports.truncate(limit);
+            }
+            airports
         }
     }
 }
 
+/// Trims the request and checks the required fields
+fn validate(mut request: AirportRequest) -> Result<AirportRequest, AirportError> {
+    request.name = request.name.trim().to_string();
+    request.code = request.code.trim().to_uppercase();
+
+    if request.name.is_empty() {
+        return Err(AirportError::missing_field("name"));
+    }
+    if request.code.is_empty() {
+        return Err(AirportError::missing_field("code"));
+    }
+    if is_reserved_code(&request.code) {
+        return Err(AirportError::invalid_value("code", request.code));
+    }
+    let Position {
+        latitude,
+        longitude,
+    } = request.position;
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(AirportError::invalid_value(
+            "latitude",
+            latitude.to_string(),
+        ));
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(AirportError::invalid_value(
+            "longitude",
+            longitude.to_string(),
+        ));
+    }
+    Ok(request)
+}
+
+/// Routes below `/api/v1/airports/` that would shadow an airport code
+const RESERVED_PATHS: [&str; 5] = [
+    AIRPORTS_AUTOCOMPLETE_API_PATH,
+    AIRPORTS_NEARBY_API_PATH,
+    AIRPORTS_OVERLAY_API_PATH,
+    AIRPORTS_RELOAD_API_PATH,
+    AIRPORTS_SEARCH_API_PATH,
+];
+
+fn is_reserved_code(code: &str) -> bool {
+    RESERVED_PATHS
+        .iter()
+        .filter_map(|path| path.rsplit('/').next())
+        .any(|segment| segment.eq_ignore_ascii_case(code))
+}
+
+/// Codes of `airport` must not find a different airport
+fn check_codes_unused(served: &AirportsService, airport: &Airport) -> Result<(), AirportError> {
+    let codes = [
+        Some(airport.code.as_str()),
+        airport.iata.as_ref().map(IataCode::as_str),
+        airport.icao.as_ref().map(IcaoCode::as_str),
+    ];
+    for code in codes.into_iter().flatten() {
+        if served
+            .find_exact(code)
+            .is_some_and(|other| other.id != airport.id)
+        {
+            return Err(AirportError::AlreadyExists(code.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Cursors pin the dataset version, so a page of a reloaded dataset is not
+/// silently continued with offsets into different contents
+fn encode_cursor(version: u64, offset: usize) -> String {
+    format!("{version:016x}{offset:x}")
+}
+
+fn decode_cursor(cursor: &str, version: u64) -> Result<usize, AirportError> {
+    let invalid = || AirportError::invalid_value("cursor", cursor);
+    let (cursor_version, offset) = cursor
+        .split_at_checked(16)
+        .filter(|(_, offset)| !offset.is_empty())
+        .ok_or_else(invalid)?;
+    let cursor_version = u64::from_str_radix(cursor_version, 16).map_err(|_| invalid())?;
+    let offset = usize::from_str_radix(offset, 16).map_err(|_| invalid())?;
+
+    if cursor_version != version {
+        return Err(AirportError::invalid_value(
+            "cursor",
+            "airport data changed, restart from the first page",
+        ));
+    }
+    Ok(offset)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::{LINE, OTHER_LINE};
+    use sky_tracer::protocol::airports::{AirportSortField, SortOrder};
+    use std::sync::OnceLock;
+
+    /// Parse the embedded dataset once for all tests
+    fn service() -> AirportService {
+        static SERVICE: OnceLock<AirportService> = OnceLock::new();
+        SERVICE
+            .get_or_init(|| AirportService::embedded().unwrap())
+            .clone()
+    }
 
-    #[tokio::test]
-    async fn test_get_all_airports() {
-        let result = AirportService::get_all_airports().await;
-        assert!(result.is_ok());
-        let airports = result.unwrap();
+    #[test]
+    fn test_get_all_airports() {
+        let airports = service().get_all_airports();
         assert!(!airports.is_empty());
     }
 
-    #[tokio::test]
-    async fn test_search_by_code() {
-        let result = AirportService::search_by_code("FRA").await;
-        assert!(result.is_ok());
-        let airports = result.unwrap();
+    #[test]
+    fn test_search_by_code() {
+        let airports = service().search_by_code("FRA");
         assert!(!airports.is_empty());
         assert!(airports.iter().any(|a| a.code.contains("FRA")));
     }
 
-    #[tokio::test]
-    async fn test_search_by_name() {
-        let result = AirportService::search_by_name("Frankfurt").await;
-        assert!(result.is_ok());
-        let airports = result.unwrap();
+    #[test]
+    fn test_search_text() {
+        let airports = service().search_text("Frankfurt", None);
         assert!(!airports.is_empty());
         assert!(airports.iter().any(|a| a.name.contains("Frankfurt")));
     }
 
-    #[tokio::test]
-    async fn test_search_nonexistent_code() {
-        let result = AirportService::search_by_code("NONEXISTENT").await;
-        assert!(result.is_ok());
-        let airports = result.unwrap();
+    #[test]
+    fn test_search_text_ranking() {
+        let service = service();
+        let first = |query: &str| service.search_text(query, Some(1)).remove(0).code;
+
+        // Exact code before everything else
+        assert_eq!(first("LIS"), "LIS");
+        assert_eq!(first("lppt"), "LIS");
+        // City names, typos and accents
+        assert_eq!(first("Lisbon"), "LIS");
+        assert_eq!(first("Frankfrt am Main"), "FRA");
+        assert_eq!(first("Zürich"), "ZRH");
+        assert_eq!(first("zurich airport"), "ZRH");
+
+        assert_eq!(service.search_text("airport", Some(3)).len(), 3);
+        assert!(service.search_text("xqzvw", None).is_empty());
+    }
+
+    #[test]
+    fn test_autocomplete() {
+        let suggestions = service().autocomplete("frankf", None);
+
+        assert!(!suggestions.is_empty());
+        assert!(suggestions.len() <= DEFAULT_AUTOCOMPLETE_LIMIT);
+        assert!(suggestions.iter().any(|s| s.code == "FRA"));
+        assert_eq!(
+            service().autocomplete("a", Some(500)).len(),
+            MAX_AUTOCOMPLETE_LIMIT
+        );
+    }
+
+    #[test]
+    fn test_search_nonexistent_code() {
+        let airports = service().search_by_code("NONEXISTENT");
         assert!(airports.is_empty());
     }
 
-    #[tokio::test]
-    async fn test_combined_search_with_code() {
-        let result = AirportService::search(Some("FRA".to_string()), None).await;
-        assert!(result.is_ok());
-        let airports = result.unwrap();
+    #[test]
+    fn test_combined_search_with_code() {
+        let airports = service().search(&SearchAirportsRequest {
+            code: Some("FRA".to_string()),
+            ..Default::default()
+        });
         assert!(!airports.is_empty());
     }
 
-    #[tokio::test]
-    async fn test_combined_search_with_name() {
-        let result = AirportService::search(None, Some("Frankfurt".to_string())).await;
-        assert!(result.is_ok());
-        let airports = result.unwrap();
+    #[test]
+    fn test_combined_search_with_name() {
+        let airports = service().search(&SearchAirportsRequest {
+            name: Some("Frankfurt".to_string()),
+            ..Default::default()
+        });
         assert!(!airports.is_empty());
     }
 
-    #[tokio::test]
-    async fn test_combined_search_all() {
-        let result = AirportService::search(None, None).await;
-        assert!(result.is_ok());
-        let airports = result.unwrap();
+    #[test]
+    fn test_combined_search_all() {
+        let airports = service().search(&SearchAirportsRequest::default());
         assert!(!airports.is_empty());
     }
+
+    #[test]
+    fn test_nearby_defaults_and_validation() {
+        let request = |lat: f64, lon: f64, radius_km: Option<f64>| NearbyAirportsRequest {
+            lat,
+            lon,
+            radius_km,
+            limit: None,
+        };
+
+        let airports = service().nearby(&request(50.033, 8.57, None)).unwrap();
+        assert_eq!(airports[0].airport.code, "FRA");
+        assert!(airports.len() <= DEFAULT_NEARBY_LIMIT);
+        assert!(airports
+            .iter()
+            .all(|a| a.distance_km <= DEFAULT_NEARBY_RADIUS_KM));
+
+        for invalid in [
+            request(91.0, 0.0, None),
+            request(0.0, -181.0, None),
+            request(0.0, 0.0, Some(0.0)),
+            request(0.0, 0.0, Some(f64::NAN)),
+        ] {
+            assert!(matches!(
+                service().nearby(&invalid),
+                Err(AirportError::InvalidValue { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_list_pages() {
+        let service = service();
+        let request = ListAirportsRequest {
+            country: Some("germany".to_string()),
+            limit: Some(20),
+            ..Default::default()
+        };
+
+        let first = service.list(&request).unwrap();
+        assert_eq!(first.airports.len(), 20);
+        assert!(first.total > 20);
+        assert!(first.airports.iter().all(|a| a.country == "Germany"));
+        assert!(first
+            .airports
+            .windows(2)
+            .all(|pair| pair[0].id < pair[1].id));
+        assert_eq!(first.next_offset, Some(20));
+
+        // Walking the cursors visits every match exactly once
+        let mut seen = first.airports.len();
+        let mut cursor = first.next_cursor;
+        while let Some(next) = cursor {
+            let page = service
+                .list(&ListAirportsRequest {
+                    cursor: Some(next),
+                    ..request.clone()
+                })
+                .unwrap();
+            seen += page.airports.len();
+            cursor = page.next_cursor;
+        }
+        assert_eq!(seen, first.total);
+
+        let past_end = service
+            .list(&ListAirportsRequest {
+                offset: Some(first.total),
+                ..request.clone()
+            })
+            .unwrap();
+        assert!(past_end.airports.is_empty());
+        assert_eq!(past_end.next_offset, None);
+
+        let count_only = service
+            .list(&ListAirportsRequest {
+                limit: Some(0),
+                ..request
+            })
+            .unwrap();
+        assert!(count_only.airports.is_empty());
+        assert_eq!(count_only.total, first.total);
+        assert_eq!(count_only.next_cursor, None);
+    }
+
+    #[test]
+    fn test_list_sort_and_limits() {
+        let service = service();
+        let highest = service
+            .list(&ListAirportsRequest {
+                sort: Some(AirportSortField::Elevation),
+                order: Some(SortOrder::Desc),
+                limit: Some(5000),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(highest.limit, MAX_LIST_LIMIT);
+        assert_eq!(highest.airports.len(), MAX_LIST_LIMIT);
+        assert!(highest
+            .airports
+            .windows(2)
+            .all(|pair| pair[0].elevation_ft >= pair[1].elevation_ft));
+    }
+
+    #[test]
+    fn test_list_rejects_invalid_parameters() {
+        let service = service();
+        let invalid = [
+            ListAirportsRequest {
+                bbox: Some("1,2,3".to_string()),
+                ..Default::default()
+            },
+            ListAirportsRequest {
+                cursor: Some("not a cursor".to_string()),
+                ..Default::default()
+            },
+            ListAirportsRequest {
+                // Cursor of another dataset version
+                cursor: Some(encode_cursor(service.airports().version() ^ 1, 10)),
+                ..Default::default()
+            },
+            ListAirportsRequest {
+                cursor: Some(encode_cursor(service.airports().version(), 10)),
+                offset: Some(10),
+                ..Default::default()
+            },
+        ];
+
+        for request in invalid {
+            assert!(
+                matches!(
+                    service.list(&request),
+                    Err(AirportError::InvalidValue { .. })
+                ),
+                "{request:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reload_swaps_dataset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("airports.dat");
+        std::fs::write(&path, LINE).unwrap();
+        let service = AirportService::from_source(AirportSource::File(path.clone())).unwrap();
+        let clone = service.clone();
+        assert_eq!(service.airports().len(), 1);
+
+        std::fs::write(&path, format!("{LINE}\nbroken line\n{OTHER_LINE}")).unwrap();
+        let report = service.reload().unwrap();
+
+        assert!(report.reloaded && report.changed);
+        assert_eq!(report.airports, 2);
+        assert_eq!(report.invalid_lines, 1);
+        assert_eq!(report.errors[0].line, 2);
+        // All clones serve the new data
+        assert_eq!(clone.search_by_code("OTH").len(), 1);
+
+        // Reloading the same data changes nothing
+        let report = service.reload().unwrap();
+        assert!(report.reloaded && !report.changed);
+    }
+
+    #[test]
+    fn test_reload_keeps_data_without_valid_airports() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("airports.dat");
+        std::fs::write(&path, LINE).unwrap();
+        let service = AirportService::from_source(AirportSource::File(path.clone())).unwrap();
+        let version = service.airports().version();
+
+        std::fs::write(&path, "garbage\nmore garbage").unwrap();
+        let report = service.reload().unwrap();
+        assert!(!report.reloaded && !report.changed);
+        assert_eq!(report.invalid_lines, 2);
+        assert_eq!(service.airports().version(), version);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(service.reload(), Err(AirportError::IoError(_))));
+        assert_eq!(service.airports().len(), 1);
+    }
+
+    #[test]
+    fn test_unreadable_source_falls_back_to_embedded() {
+        let source = AirportSource::File("/nonexistent/airports.dat".into());
+        let service = AirportService::from_source(source.clone()).unwrap();
+
+        assert!(service.airports().len() > 7000);
+        assert_eq!(service.source(), Some(&source));
+        // A fixed dataset has nothing to reload from
+        let fixed = AirportService::new(AirportsService::default());
+        assert!(fixed.reload().is_err());
+    }
+
+    #[test]
+    fn test_runways_and_frequencies() {
+        use crate::models::ourairports::tests::{AIRPORTS, FREQUENCIES, RUNWAYS};
+        use crate::models::ourairports::{FREQUENCIES_FILE, RUNWAYS_FILE};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("airports.csv");
+        std::fs::write(&path, AIRPORTS).unwrap();
+        std::fs::write(dir.path().join(RUNWAYS_FILE), RUNWAYS).unwrap();
+        std::fs::write(dir.path().join(FREQUENCIES_FILE), FREQUENCIES).unwrap();
+        let ourairports = AirportService::from_source(AirportSource::File(path)).unwrap();
+
+        let runways = ourairports.runways("fra").unwrap();
+        assert_eq!(runways.airport, "FRA");
+        assert_eq!(runways.runways.len(), 2);
+        assert_eq!(runways.runways[0].designation, "07C/25C");
+        assert_eq!(runways.runways[0].low_end.heading_deg, Some(69.9));
+
+        let frequencies = ourairports.frequencies("EDDF").unwrap();
+        assert_eq!(frequencies.frequencies[0].frequency_type, "TWR");
+
+        assert!(matches!(
+            ourairports.runways("NONEXISTENT"),
+            Err(AirportError::NotFound(_))
+        ));
+        // OpenFlights data knows airports but no runways
+        assert!(service().runways("FRA").unwrap().runways.is_empty());
+    }
+
+    #[test]
+    fn test_clones_share_dataset() {
+        let service = service();
+        let clone = service.clone();
+        assert!(Arc::ptr_eq(&service.airports, &clone.airports));
+    }
+
+    fn airport_request(name: &str, code: &str) -> AirportRequest {
+        AirportRequest {
+            name: name.to_string(),
+            code: code.to_string(),
+            position: Position {
+                latitude: 47.0,
+                longitude: 8.0,
+            },
+            iata: None,
+            icao: None,
+            city: String::new(),
+            country: "Switzerland".to_string(),
+            elevation_ft: None,
+            timezone: None,
+            airport_type: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_overlay_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let overlay_path = dir.path().join("overlay.json");
+        let service = AirportService::new(AirportsService::from_csv_str(LINE).unwrap())
+            .with_overlay_file(overlay_path.clone())
+            .unwrap();
+        let existing = service.get_all_airports().remove(0);
+
+        let added = service
+            .create_airport(airport_request(" Private Strip ", "xps"))
+            .unwrap();
+        assert_eq!(added.code, "XPS");
+        assert_eq!(added.name, "Private Strip");
+        assert_eq!(service.airport("xps").unwrap(), added);
+        assert_eq!(service.search_text("private", None)[0].code, "XPS");
+
+        // Codes stay unique
+        assert!(matches!(
+            service.create_airport(airport_request("Other", &existing.code)),
+            Err(AirportError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            service.create_airport(airport_request(" ", "ABC")),
+            Err(AirportError::MissingField(_))
+        ));
+        // Codes of static routes could not be looked up
+        for code in ["overlay", "Search", "NEARBY", "reload"] {
+            assert!(matches!(
+                service.create_airport(airport_request("Route", code)),
+                Err(AirportError::InvalidValue { .. })
+            ));
+        }
+
+        // Loaded airports can be corrected and hidden
+        let mut correction = airport_request("Corrected", &existing.code);
+        correction.position.latitude = 1.5;
+        let updated = service.update_airport(&existing.code, correction).unwrap();
+        assert_eq!(updated.id, existing.id);
+        assert_eq!(
+            service.airport(&existing.code).unwrap().position.latitude,
+            1.5
+        );
+
+        service.delete_airport(&existing.code).unwrap();
+        assert!(service.airport(&existing.code).is_err());
+        let overlay = service.overlay();
+        assert_eq!(overlay.hidden, [existing.id]);
+        assert_eq!(overlay.airports, std::slice::from_ref(&added));
+
+        // Persisted changes are applied by a new service
+        let restarted = AirportService::new(AirportsService::from_csv_str(LINE).unwrap())
+            .with_overlay_file(overlay_path)
+            .unwrap();
+        assert_eq!(restarted.get_all_airports(), [added]);
+
+        restarted.clear_overlay().unwrap();
+        assert_eq!(restarted.get_all_airports(), [existing]);
+    }
+
+    #[test]
+    fn test_overlay_survives_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("airports.dat");
+        std::fs::write(&path, LINE).unwrap();
+        let service = AirportService::from_source(AirportSource::File(path.clone())).unwrap();
+        service
+            .create_airport(airport_request("Private Strip", "XPS"))
+            .unwrap();
+
+        std::fs::write(&path, format!("{LINE}\n{OTHER_LINE}")).unwrap();
+        let report = service.reload().unwrap();
+
+        assert_eq!(report.airports, 3);
+        assert_eq!(service.search_by_code("XPS").len(), 1);
+        assert_eq!(service.search_by_code("OTH").len(), 1);
+        // Without an overlay file changes are kept in memory
+        assert!(service.overlay_path().is_none());
+    }
 }
@@ -11,17 +11,22 @@ pub fn airport_card(props: &AirportCardProps) -> Html {
     html! {
         <div class="airport-card">
             <h3 class="airport-name">{&props.airport.name}</h3>
+            <div class="airport-location">
+                {[props.airport.city.as_str(), props.airport.country.as_str()]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ")}
+            </div>
             <div class="airport-codes">
-                {if !props.airport.code.is_empty() {
-                    format!("IATA: {}", props.airport.code)
-                } else {
-                    "No IATA".to_string()
+                {match &props.airport.iata {
+                    Some(iata) => format!("IATA: {}", iata),
+                    None => "No IATA".to_string(),
                 }}
                 {" / "}
-                {if !props.airport.code.is_empty() {
-                    format!("ICAO: {}", props.airport.code)
-                } else {
-                    "No ICAO".to_string()
+                {match &props.airport.icao {
+                    Some(icao) => format!("ICAO: {}", icao),
+                    None => "No ICAO".to_string(),
                 }}
             </div>
             <div class="airport-position">
@@ -29,7 +34,13 @@ pub fn airport_card(props: &AirportCardProps) -> Html {
                 {format!("{:.6}°N, {:.6}°E",
                     props.airport.position.latitude,
                     props.airport.position.longitude)}
+                {props.airport.elevation_ft.map(|ft| format!(", {} ft", ft)).unwrap_or_default()}
             </div>
+            {if let Some(timezone) = &props.airport.timezone {
+                html! { <div class="airport-timezone">{format!("Time zone: {}", timezone)}</div> }
+            } else {
+                html! {}
+            }}
         </div>
     }
 }
@@ -1,2 +1,61 @@
 pub mod components;
 pub mod pages;
+
+use crate::services::AirportService;
+use axum::{
+    extract::{Query, State},
+    response::Html,
+};
+use pages::{Home, HomeProps};
+use serde::Deserialize;
+use sky_tracer::protocol::airports::AirportResponse;
+use tracing::{info, instrument};
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    q: Option<String>,
+}
+
+#[instrument(skip(params, service))]
+pub async fn render_page(
+    Query(params): Query<SearchParams>,
+    State(service): State<AirportService>,
+) -> Html<String> {
+    info!(?params, "Rendering page with search parameters");
+
+    let airports: Vec<AirportResponse> = if let Some(query) = &params.q {
+        info!(?query, "Searching for airports");
+        service.search_text(query, None)
+    } else {
+        info!("Listing all airports");
+        service.get_all_airports()
+    };
+
+    info!(airports_found = airports.len(), "Found airports");
+
+    let renderer = yew::ServerRenderer::<Home>::with_props(move || HomeProps {
+        airports,
+        query: params.q,
+    });
+
+    let html = renderer.render().await;
+
+    Html(format!(
+        r#"<!DOCTYPE html>
+        <html>
+            <head>
+                <meta charset="utf-8" />
+                <meta name="viewport" content="width=device-width, initial-scale=1.0">
+                <title>Airport Anywhere</title>
+                <style>
+                    {}
+                </style>
+            </head>
+            <body>
+                {}
+            </body>
+        </html>"#,
+        include_str!("../../assets/styles.css"),
+        html
+    ))
+}
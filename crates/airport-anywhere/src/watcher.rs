@@ -0,0 +1,112 @@
+use crate::services::AirportService;
+use notify_debouncer_mini::notify::{self, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Quiet period after the last change before the data is reloaded
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Reloads the airport data whenever its files change, stops when dropped
+pub struct DataWatcher {
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+/// Watches the data files of `service`, `None` for sources that cannot change.
+///
+/// The parent directory is watched rather than the file itself, so files
+/// replaced by a rename, as editors and deployment tools do, are picked up.
+pub fn watch(service: AirportService) -> Result<Option<DataWatcher>, notify::Error> {
+    let Some(path) = service.source().and_then(|source| source.path()) else {
+        return Ok(None);
+    };
+    let path = path.to_path_buf();
+    let file_names: Vec<_> = service
+        .source()
+        .map(|source| source.watched_paths())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|path| path.file_name().map(ToOwned::to_owned))
+        .collect();
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => ".".into(),
+    };
+
+    let mut debouncer = new_debouncer(DEBOUNCE, move |events: DebounceEventResult| {
+        let events = match events {
+            Ok(events) => events,
+            Err(e) => {
+                warn!(error = %e, "Airport data watch failed");
+                return;
+            }
+        };
+        if !events.iter().any(|event| {
+            event
+                .path
+                .file_name()
+                .is_some_and(|name| file_names.iter().any(|watched| watched == name))
+        }) {
+            return;
+        }
+
+        match service.reload() {
+            Ok(report) if report.reloaded => info!(
+                airports = report.airports,
+                changed = report.changed,
+                invalid_lines = report.invalid_lines,
+                "Airport data file changed, reloaded"
+            ),
+            Ok(report) => warn!(
+                invalid_lines = report.invalid_lines,
+                "Airport data file changed but holds no valid airport, keeping current data"
+            ),
+            Err(e) => error!(error = %e, "Airport data file changed but cannot be reloaded"),
+        }
+    })?;
+    debouncer
+        .watcher()
+        .watch(&directory, RecursiveMode::NonRecursive)?;
+
+    info!(path = %path.display(), "Watching airport data file");
+    Ok(Some(DataWatcher {
+        _debouncer: debouncer,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{LINE, OTHER_LINE};
+    use crate::models::AirportSource;
+    use std::time::Instant;
+
+    #[test]
+    fn test_embedded_source_is_not_watched() {
+        let service = AirportService::embedded().unwrap();
+        assert!(watch(service).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_replaced_file_is_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("airports.dat");
+        std::fs::write(&path, LINE).unwrap();
+        let service = AirportService::from_source(AirportSource::File(path.clone())).unwrap();
+        let _watcher = watch(service.clone()).unwrap().unwrap();
+
+        // Replace the file the way deployments do, by renaming over it
+        let staged = dir.path().join("airports.dat.new");
+        std::fs::write(&staged, format!("{LINE}\n{OTHER_LINE}")).unwrap();
+        std::fs::rename(&staged, &path).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while service.airports().len() != 2 {
+            assert!(
+                Instant::now() < deadline,
+                "data file change was not picked up"
+            );
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::services::AirportService;
+use sky_tracer::protocol::airports::AirportSuggestion;
+use tracing::warn;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// Typed characters before suggestions are fetched
+const MIN_QUERY_LENGTH: usize = 2;
+
+#[derive(Properties, PartialEq)]
+pub struct AirportInputProps {
+    pub id: AttrValue,
+    pub node_ref: NodeRef,
+    #[prop_or_default]
+    pub placeholder: AttrValue,
+}
+
+/// Airport code input with suggestions from the airport service
+#[function_component(AirportInput)]
+pub fn airport_input(props: &AirportInputProps) -> Html {
+    let suggestions = use_state(Vec::<AirportSuggestion>::new);
+
+    let oninput = {
+        let suggestions = suggestions.clone();
+        Callback::from(move |e: InputEvent| {
+            let query = e.target_unchecked_into::<HtmlInputElement>().value();
+            let suggestions = suggestions.clone();
+            if query.trim().chars().count() < MIN_QUERY_LENGTH {
+                suggestions.set(Vec::new());
+                return;
+            }
+
+            spawn_local(async move {
+                match AirportService::autocomplete(&query).await {
+                    Ok(found) => suggestions.set(found),
+                    Err(err) => warn!("Airport suggestions unavailable: {}", err),
+                }
+            });
+        })
+    };
+
+    let list_id = format!("{}-suggestions", props.id);
+
+    html! {
+        <>
+            <input
+                type="text"
+                id={props.id.clone()}
+                ref={props.node_ref.clone()}
+                required=true
+                placeholder={props.placeholder.clone()}
+                list={list_id.clone()}
+                autocomplete="off"
+                {oninput}
+            />
+            <datalist id={list_id}>
+                { for suggestions.iter().map(|suggestion| html! {
+                    <option
+                        key={suggestion.code.clone()}
+                        value={suggestion.code.clone()}
+                        label={suggestion.label.clone()}
+                    />
+                }) }
+            </datalist>
+        </>
+    }
+}
@@ -1,4 +1,6 @@
+use crate::components::AirportInput;
 use crate::services::FlightService;
+use sky_tracer::model::{AircraftRegistration, IataCode};
 use sky_tracer::protocol::flights::CreateFlightRequest;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlInputElement;
@@ -73,6 +75,21 @@ pub fn flight_form() -> Html {
                 .value();
             let arrival_time = arrival_time_ref.cast::<HtmlInputElement>().unwrap().value();
 
+            let aircraft_number = match aircraft.parse::<AircraftRegistration>() {
+                Ok(aircraft_number) => aircraft_number,
+                Err(err) => {
+                    status.set(Some(err.to_string()));
+                    return;
+                }
+            };
+            let (departure, arrival) = match (departure.parse::<IataCode>(), arrival.parse()) {
+                (Ok(departure), Ok(arrival)) => (departure, arrival),
+                (Err(err), _) | (_, Err(err)) => {
+                    status.set(Some(err.to_string()));
+                    return;
+                }
+            };
+
             // Parse times
             let parsed_departure_time = match chrono::DateTime::parse_from_rfc3339(&departure_time)
             {
@@ -96,11 +113,13 @@ pub fn flight_form() -> Html {
             };
 
             let request = CreateFlightRequest {
-                aircraft_number: aircraft,
+                aircraft_number,
                 departure,
                 arrival,
                 departure_time: parsed_departure_time,
                 arrival_time: parsed_arrival_time,
+                airline: None,
+                flight_number: None,
             };
 
             spawn_local(async move {
@@ -130,21 +149,17 @@ pub fn flight_form() -> Html {
                 </div>
                 <div class="form-group">
                     <label for="departure">{"Departure Airport"}</label>
-                    <input
-                        type="text"
+                    <AirportInput
                         id="departure"
-                        ref={departure_ref}
-                        required=true
+                        node_ref={departure_ref}
                         placeholder="FRA"
                     />
                 </div>
                 <div class="form-group">
                     <label for="arrival">{"Arrival Airport"}</label>
-                    <input
-                        type="text"
+                    <AirportInput
                         id="arrival"
-                        ref={arrival_ref}
-                        required=true
+                        node_ref={arrival_ref}
                         placeholder="LIS"
                     />
                 </div>
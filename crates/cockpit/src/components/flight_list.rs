@@ -101,11 +101,11 @@ pub fn flight_list() -> Html {
                             let progress_class = get_progress_class(progress);
 
                             html! {
-                                <tr key={flight.flight_number.clone()}>
-                                    <td>{&flight.flight_number}</td>
-                                    <td>{&flight.aircraft_number}</td>
-                                    <td>{&flight.departure}</td>
-                                    <td>{&flight.arrival}</td>
+                                <tr key={flight.flight_number.as_str()}>
+                                    <td>{flight.flight_number.as_str()}</td>
+                                    <td>{flight.aircraft_number.as_str()}</td>
+                                    <td>{flight.departure.as_str()}</td>
+                                    <td>{flight.arrival.as_str()}</td>
                                     <td>{flight.departure_time.format("%Y-%m-%d %H:%M").to_string()}</td>
                                     <td class="progress-cell">
                                         <div class="progress-bar-container">
@@ -1,9 +1,11 @@
+mod airport_input;
 mod flight_form;
 mod flight_list;
 mod flight_map;
 mod flight_tracker;
 mod status_panel;
 
+pub use airport_input::AirportInput;
 pub use flight_form::FlightForm;
 pub use flight_list::FlightList;
 pub use flight_map::FlightMap;
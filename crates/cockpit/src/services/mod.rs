@@ -1,33 +1,32 @@
 use crate::models::{Airport, Flight};
-use gloo_net::http::Request;
-use sky_tracer::protocol::flights::{CreateFlightRequest, FlightPositionResponse, FlightResponse};
-use sky_tracer::protocol::{AIRPORTS_SEARCH_API_PATH, FLIGHTS_API_PATH};
+use sky_tracer::client::{AirportsClient, FlightsClient};
+use sky_tracer::model::IataCode;
+use sky_tracer::protocol::airports::{AirportSuggestion, AutocompleteRequest};
+use sky_tracer::protocol::flights::{CreateFlightRequest, FlightResponse, ListFlightsRequest};
 use std::collections::HashMap;
 use tracing::{error, info, warn};
 
+/// The APIs are served from the origin of the page
+fn base_url() -> String {
+    gloo_utils::window().location().origin().unwrap_or_default()
+}
+
 pub struct FlightService;
 
 impl FlightService {
+    fn client() -> FlightsClient {
+        FlightsClient::new(base_url())
+    }
+
     /// Create a new flight
     pub async fn create_flight(request: CreateFlightRequest) -> Result<(), String> {
-        match Request::post(FLIGHTS_API_PATH)
-            .json(&request)
-            .map_err(|e| format!("Failed to serialize request: {}", e))?
-            .send()
-            .await
-        {
-            Ok(response) => {
-                if response.ok() {
-                    info!("Flight created successfully");
-                    Ok(())
-                } else {
-                    let error_msg = format!("Server error: {}", response.status());
-                    error!("{}", error_msg);
-                    Err(error_msg)
-                }
+        match Self::client().create(&request).await {
+            Ok(_) => {
+                info!("Flight created successfully");
+                Ok(())
             }
             Err(e) => {
-                let error_msg = format!("Network error: {}", e);
+                let error_msg = format!("Failed to create flight: {}", e);
                 error!("{}", error_msg);
                 Err(error_msg)
             }
@@ -36,28 +35,13 @@ impl FlightService {
 
     /// Get all flights
     pub async fn get_flights() -> Result<Vec<FlightResponse>, String> {
-        match Request::get(FLIGHTS_API_PATH).send().await {
-            Ok(response) => {
-                if response.ok() {
-                    match response.json::<Vec<FlightResponse>>().await {
-                        Ok(flights) => {
-                            info!("Retrieved {} flights", flights.len());
-                            Ok(flights)
-                        }
-                        Err(e) => {
-                            let error_msg = format!("Failed to parse flights: {}", e);
-                            error!("{}", error_msg);
-                            Err(error_msg)
-                        }
-                    }
-                } else {
-                    let error_msg = format!("Server error: {}", response.status());
-                    error!("{}", error_msg);
-                    Err(error_msg)
-                }
+        match Self::client().list(&ListFlightsRequest::default()).await {
+            Ok(flights) => {
+                info!("Retrieved {} flights", flights.len());
+                Ok(flights)
             }
             Err(e) => {
-                let error_msg = format!("Network error: {}", e);
+                let error_msg = format!("Failed to fetch flights: {}", e);
                 error!("{}", error_msg);
                 Err(error_msg)
             }
@@ -66,28 +50,10 @@ impl FlightService {
 
     /// Get flight position
     pub async fn get_flight_position(flight_number: &str) -> Result<Option<(f64, f64)>, String> {
-        let position_url = format!("{}/{}/position", FLIGHTS_API_PATH, flight_number);
-
-        match Request::get(&position_url).send().await {
-            Ok(response) => {
-                if response.ok() {
-                    match response.json::<FlightPositionResponse>().await {
-                        Ok(position) => Ok(Some((position.latitude, position.longitude))),
-                        Err(e) => {
-                            warn!("Failed to parse position for {}: {}", flight_number, e);
-                            Ok(None)
-                        }
-                    }
-                } else {
-                    warn!("No position available for flight {}", flight_number);
-                    Ok(None)
-                }
-            }
+        match Self::client().position(flight_number).await {
+            Ok(position) => Ok(Some((position.latitude, position.longitude))),
             Err(e) => {
-                warn!(
-                    "Network error getting position for {}: {}",
-                    flight_number, e
-                );
+                warn!("No position available for flight {}: {}", flight_number, e);
                 Ok(None)
             }
         }
@@ -97,45 +63,42 @@ impl FlightService {
 pub struct AirportService;
 
 impl AirportService {
+    fn client() -> AirportsClient {
+        AirportsClient::new(base_url())
+    }
+
     /// Search for airport by code
     pub async fn search_by_code(code: &str) -> Result<Option<Airport>, String> {
-        let search_url = format!("{}?code={}", AIRPORTS_SEARCH_API_PATH, code);
-
-        match Request::get(&search_url).send().await {
-            Ok(response) => {
-                if response.ok() {
-                    match response
-                        .json::<sky_tracer::protocol::airports::SearchAirportsResponse>()
-                        .await
-                    {
-                        Ok(search_response) => {
-                            if let Some(airport) = search_response.airports.first() {
-                                Ok(Some(Airport::new(
-                                    airport.code.clone(),
-                                    (airport.position.latitude, airport.position.longitude),
-                                )))
-                            } else {
-                                Ok(None)
-                            }
-                        }
-                        Err(e) => {
-                            let error_msg = format!("Failed to parse airport search: {}", e);
-                            error!("{}", error_msg);
-                            Err(error_msg)
-                        }
-                    }
-                } else {
-                    warn!("Airport not found: {}", code);
-                    Ok(None)
-                }
+        match Self::client().airport(code).await {
+            Ok(Some(airport)) => Ok(Some(Airport::new(
+                airport.code,
+                (airport.position.latitude, airport.position.longitude),
+            ))),
+            Ok(None) => Ok(None),
+            Err(e) if e.status().is_some() => {
+                warn!("Airport not found: {}", code);
+                Ok(None)
             }
             Err(e) => {
-                let error_msg = format!("Network error searching for airport {}: {}", code, e);
+                let error_msg = format!("Error searching for airport {}: {}", code, e);
                 error!("{}", error_msg);
                 Err(error_msg)
             }
         }
     }
+
+    /// Suggestions for a partially typed airport
+    pub async fn autocomplete(query: &str) -> Result<Vec<AirportSuggestion>, String> {
+        let request = AutocompleteRequest {
+            q: query.to_string(),
+            limit: None,
+        };
+        Self::client()
+            .autocomplete(&request)
+            .await
+            .map(|autocomplete| autocomplete.suggestions)
+            .map_err(|e| format!("Failed to fetch airport suggestions: {}", e))
+    }
 }
 
 pub struct DataService;
@@ -147,14 +110,14 @@ impl DataService {
         let mut airport_map = HashMap::new();
 
         // Collect unique airport codes
-        let airport_codes: std::collections::HashSet<String> = flight_responses
+        let airport_codes: std::collections::HashSet<IataCode> = flight_responses
             .iter()
             .flat_map(|f| vec![f.departure.clone(), f.arrival.clone()])
             .collect();
 
         // Fetch all airport data
         for code in airport_codes {
-            if let Ok(Some(airport)) = AirportService::search_by_code(&code).await {
+            if let Ok(Some(airport)) = AirportService::search_by_code(code.as_str()).await {
                 airport_map.insert(code, airport);
             }
         }
@@ -167,7 +130,7 @@ impl DataService {
                 airport_map.get(&flight_response.arrival),
             ) {
                 let mut flight = Flight::new(
-                    flight_response.flight_number.clone(),
+                    flight_response.flight_number.to_string(),
                     departure.clone(),
                     arrival.clone(),
                     None,
@@ -175,7 +138,7 @@ impl DataService {
 
                 // Try to get position
                 if let Ok(Some(position)) =
-                    FlightService::get_flight_position(&flight_response.flight_number).await
+                    FlightService::get_flight_position(flight_response.flight_number.as_str()).await
                 {
                     flight.position = Some(position);
                 }
@@ -196,10 +159,10 @@ impl DataService {
 
     /// Check API connection status
     pub async fn check_connection() -> bool {
-        match Request::get(FLIGHTS_API_PATH).send().await {
-            Ok(response) => response.ok(),
-            Err(_) => false,
-        }
+        FlightService::client()
+            .list(&ListFlightsRequest::default())
+            .await
+            .is_ok()
     }
 }
 
@@ -207,15 +170,18 @@ impl DataService {
 mod tests {
     use super::*;
     use chrono::Utc;
+    use sky_tracer::protocol::{AIRPORTS_SEARCH_API_PATH, FLIGHTS_API_PATH};
 
     #[test]
     fn test_create_flight_request() {
         let request = CreateFlightRequest {
-            aircraft_number: "LH-A320".to_string(),
-            departure: "FRA".to_string(),
-            arrival: "LIS".to_string(),
+            aircraft_number: "LH-A320".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "LIS".parse().unwrap(),
             departure_time: Utc::now(),
             arrival_time: Some(Utc::now() + chrono::Duration::hours(2)),
+            airline: None,
+            flight_number: None,
         };
 
         assert_eq!(request.aircraft_number, "LH-A320");
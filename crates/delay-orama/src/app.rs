@@ -1,19 +1,63 @@
 use crate::{routes, services::DelayService};
-use axum::{routing::get, Router};
-use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
+use axum::{middleware, routing::get, Router};
+use sky_tracer::clock::follow_caller_time;
+use sky_tracer::protocol::health::health_routes;
+use sky_tracer::protocol::metrics::{metrics_routes, observe};
+use sky_tracer::protocol::problem::problem_instance;
 use tower_http::cors::{Any, CorsLayer};
 
 pub fn app(delay_service: DelayService) -> Router {
-    Router::new()
+    let app = Router::new()
         .route("/", get(routes::render_home_page))
         .route("/{airport_code}", get(routes::render_airport_delays))
-        .layer(OtelInResponseLayer)
-        .layer(OtelAxumLayer::default())
+        .layer(middleware::from_fn_with_state(
+            delay_service.clock().clone(),
+            follow_caller_time,
+        ))
+        .layer(middleware::from_fn(problem_instance))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
                 .allow_headers(Any),
-        )
-        .with_state(delay_service)
+        );
+    let probes = health_routes("delay-orama", |service: DelayService| async move {
+        service.readiness().await
+    })
+    .merge(metrics_routes(|_: DelayService| async {}));
+    observe("delay-orama", app, probes).with_state(delay_service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+    };
+    use sky_tracer::client::{AirportsClient, BabelClient};
+    use sky_tracer::protocol::problem::{ProblemDetails, PROBLEM_JSON};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_invalid_airport_code_is_a_problem() {
+        let app = app(DelayService::new(
+            BabelClient::new("http://127.0.0.1:9"),
+            AirportsClient::new("http://127.0.0.1:9"),
+        ));
+
+        let response = app
+            .oneshot(Request::get("/%FF").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()[CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code.as_deref(), Some("INVALID_PARAMETER"));
+        assert_eq!(problem.instance.as_deref(), Some("/%FF"));
+    }
 }
@@ -1,8 +1,5 @@
 pub mod app;
-pub mod client;
 pub mod routes;
 pub mod services;
 pub mod ui;
 pub mod utils;
-
-pub use client::create_client;
@@ -1,10 +1,13 @@
-use delay_orama::{app::app, create_client, services::DelayService};
+use delay_orama::{app::app, services::DelayService};
+use sky_tracer::client::{AirportsClient, BabelClient, ClientConfig};
 use std::env;
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
     let _guard = init_tracing_opentelemetry::tracing_subscriber_ext::init_subscribers()?;
+    let clock = sky_tracer::clock::init_from_env()?;
+    info!(clock = ?clock, "Configured clock");
 
     // Get service configuration
     let service_port = env::var("PORT")
@@ -14,18 +17,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
 
     let service_name = env::var("SERVICE_NAME").unwrap_or_else(|_| "delay-orama".to_string());
 
-    let tower_babel_base_url = env::var("TOWER_BABEL_BASE_URL")
-        .unwrap_or_else(|_| "http://tower-of-babel:3003".to_string());
+    let babel = BabelClient::with_config(ClientConfig::from_env(
+        "TOWER_BABEL_BASE_URL",
+        "http://tower-of-babel:3003",
+    ));
 
-    let airport_service_base_url = env::var("AIRPORT_SERVICE_BASE_URL")
-        .unwrap_or_else(|_| "http://airport-anywhere:3000".to_string());
+    let airports = AirportsClient::with_config(ClientConfig::from_env(
+        "AIRPORT_SERVICE_BASE_URL",
+        "http://airport-anywhere:3000",
+    ));
 
     info!("Starting {} service on port {}", service_name, service_port);
-    info!(tower_babel_base_url = %tower_babel_base_url, "Configured Tower of Babel base URL");
-    info!(airport_service_base_url = %airport_service_base_url, "Configured Airport Service base URL");
+    info!(tower_babel_base_url = %babel.base_url(), "Configured Tower of Babel base URL");
+    info!(airport_service_base_url = %airports.base_url(), "Configured Airport Service base URL");
 
-    let client = create_client();
-    let delay_service = DelayService::new(client, tower_babel_base_url, airport_service_base_url);
+    let delay_service = DelayService::new(babel, airports);
     let app = app(delay_service);
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", service_port)).await?;
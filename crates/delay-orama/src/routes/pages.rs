@@ -2,10 +2,8 @@ use crate::{
     services::DelayService,
     ui::pages::{Home, HomeProps},
 };
-use axum::{
-    extract::{Path, State},
-    response::Html,
-};
+use axum::{extract::State, response::Html};
+use sky_tracer::protocol::problem::ProblemPath;
 use tracing::{info, instrument};
 
 #[instrument]
@@ -45,13 +43,14 @@ pub async fn render_home_page() -> Html<String> {
 
 #[instrument(skip(service), fields(airport_code = %airport_code))]
 pub async fn render_airport_delays(
-    Path(airport_code): Path<String>,
+    ProblemPath(airport_code): ProblemPath<String>,
     State(service): State<DelayService>,
 ) -> Html<String> {
     info!("Rendering delays page for airport: {}", airport_code);
 
     let (flights_with_positions, airport_position, error_message) =
         service.get_airport_delays_with_errors(&airport_code).await;
+    let now = service.now();
 
     // Clone airport_code before moving it into the closure
     let airport_code_for_title = airport_code.clone();
@@ -62,6 +61,7 @@ pub async fn render_airport_delays(
         airport_position,
         airport_code: Some(airport_code_for_props),
         error_message,
+        now,
     });
 
     let html = renderer.render().await;
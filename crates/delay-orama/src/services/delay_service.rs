@@ -1,18 +1,15 @@
-use reqwest_middleware::ClientWithMiddleware;
-use sky_tracer::protocol::{
-    airports::SearchAirportsResponse,
-    flights::{FlightPositionResponse, FlightResponse},
-    AIRPORTS_SEARCH_API_PATH, BABEL_API_PATH,
-};
+use chrono::{DateTime, Utc};
+use sky_tracer::client::{AirportsClient, BabelClient, ClientError, NO_FUTURE_FLIGHTS};
+use sky_tracer::clock::SharedClock;
+use sky_tracer::protocol::flights::{FlightPositionResponse, FlightResponse};
+use sky_tracer::protocol::health::{check_dependency, DependencyHealth};
 use thiserror::Error;
 use tracing::{error, info, instrument, warn};
 
 #[derive(Error, Debug)]
 pub enum DelayServiceError {
     #[error("HTTP request failed: {0}")]
-    HttpError(#[from] reqwest_middleware::Error),
-    #[error("Failed to parse response: {0}")]
-    ParseError(#[from] reqwest::Error),
+    HttpError(#[from] ClientError),
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
     #[error("Airport not found: {0}")]
@@ -21,24 +18,47 @@ pub enum DelayServiceError {
 
 #[derive(Clone)]
 pub struct DelayService {
-    client: ClientWithMiddleware,
-    tower_babel_base_url: String,
-    airport_service_base_url: String,
+    babel: BabelClient,
+    airports: AirportsClient,
+    clock: SharedClock,
 }
 
 impl DelayService {
-    pub fn new(
-        client: ClientWithMiddleware,
-        tower_babel_base_url: String,
-        airport_service_base_url: String,
-    ) -> Self {
+    pub fn new(babel: BabelClient, airports: AirportsClient) -> Self {
         Self {
-            client,
-            tower_babel_base_url,
-            airport_service_base_url,
+            babel,
+            airports,
+            clock: SharedClock::default(),
         }
     }
 
+    /// Measure delays against the given clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Clock of the service
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Health of tower of babel and the airport service, checked concurrently
+    pub async fn readiness(&self) -> Vec<DependencyHealth> {
+        let (babel, airports) = tokio::join!(
+            check_dependency("tower-of-babel", self.babel.live()),
+            check_dependency("airport-anywhere", self.airports.live())
+        );
+        vec![
+            babel.with_circuit(self.babel.circuit_state()),
+            airports.with_circuit(self.airports.circuit_state()),
+        ]
+    }
+
     #[instrument(skip(self), fields(airport_code = %airport_code))]
     pub async fn get_airport_position(
         &self,
@@ -46,23 +66,8 @@ impl DelayService {
     ) -> Result<Option<(f64, f64)>, DelayServiceError> {
         info!("Fetching position for airport: {}", airport_code);
 
-        // Use the protocol constant for the airport search API path
-        let url = format!(
-            "{}{}",
-            self.airport_service_base_url, AIRPORTS_SEARCH_API_PATH
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .query(&[("code", airport_code)])
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            let search_response = response.json::<SearchAirportsResponse>().await?;
-
-            if let Some(airport) = search_response.airports.first() {
+        match self.airports.airport(airport_code).await {
+            Ok(Some(airport)) => {
                 info!(
                     airport_code = %airport_code,
                     airport_name = %airport.name,
@@ -74,16 +79,19 @@ impl DelayService {
                     airport.position.latitude,
                     airport.position.longitude,
                 )))
-            } else {
+            }
+            Ok(None) => {
                 warn!("Airport not found: {}", airport_code);
                 Ok(None)
             }
-        } else {
-            error!(
-                "Failed to fetch airport position: HTTP {}",
-                response.status()
-            );
-            Err(DelayServiceError::AirportNotFound(airport_code.to_string()))
+            Err(e) if e.status().is_some_and(|status| status.is_client_error()) => {
+                warn!("Airport lookup rejected: {}", e);
+                Err(DelayServiceError::AirportNotFound(airport_code.to_string()))
+            }
+            Err(e) => {
+                error!("Failed to fetch airport position: {}", e);
+                Err(e.into())
+            }
         }
     }
 
@@ -94,25 +102,24 @@ impl DelayService {
     ) -> Result<Vec<FlightResponse>, DelayServiceError> {
         info!("Fetching flights for airport: {}", airport_code);
 
-        // Use the protocol constant for the babel API path
-        let url = format!(
-            "{}{}/{}",
-            self.tower_babel_base_url, BABEL_API_PATH, airport_code
-        );
-
-        let response = self.client.get(&url).send().await?;
-
-        if response.status().is_success() {
-            let flights = response.json::<Vec<FlightResponse>>().await?;
-            info!(
-                "Retrieved {} flights for airport {}",
-                flights.len(),
-                airport_code
-            );
-            Ok(flights)
-        } else {
-            error!("Failed to fetch flights: HTTP {}", response.status());
-            Ok(Vec::new())
+        match self.babel.flights_by_airport(airport_code).await {
+            Ok(flights) => {
+                info!(
+                    "Retrieved {} flights for airport {}",
+                    flights.len(),
+                    airport_code
+                );
+                Ok(flights)
+            }
+            // An airport without (future) flights is not an error of the page
+            Err(e) if e.is_not_found() || e.code() == Some(NO_FUTURE_FLIGHTS) => {
+                info!("No flights for airport {}: {}", airport_code, e);
+                Ok(Vec::new())
+            }
+            Err(e) => {
+                error!("Failed to fetch flights: {}", e);
+                Err(e.into())
+            }
         }
     }
 
@@ -123,21 +130,19 @@ impl DelayService {
     ) -> Result<Option<FlightPositionResponse>, DelayServiceError> {
         info!("Fetching position for flight: {}", flight_number);
 
-        // Use the protocol constant for the position API path
-        let url = format!(
-            "{}{}/{}/position",
-            self.tower_babel_base_url, BABEL_API_PATH, flight_number
-        );
-
-        let response = self.client.get(&url).send().await?;
-
-        if response.status().is_success() {
-            let position = response.json::<FlightPositionResponse>().await?;
-            info!("Retrieved position for flight {}", flight_number);
-            Ok(Some(position))
-        } else {
-            info!("No position data available for flight {}", flight_number);
-            Ok(None)
+        match self.babel.flight_position(flight_number).await {
+            Ok(position) => {
+                info!("Retrieved position for flight {}", flight_number);
+                Ok(Some(position))
+            }
+            Err(e @ ClientError::Api { .. }) => {
+                info!(
+                    "No position data available for flight {}: {}",
+                    flight_number, e
+                );
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -151,7 +156,9 @@ impl DelayService {
         let mut flights_with_positions = Vec::new();
 
         for flight in flights {
-            let position = self.get_flight_position(&flight.flight_number).await?;
+            let position = self
+                .get_flight_position(flight.flight_number.as_str())
+                .await?;
             flights_with_positions.push((flight, position));
         }
 
@@ -183,8 +190,8 @@ impl DelayService {
         let flights = match flights_result {
             Ok(flights) => flights,
             Err(e) => {
-                error!("Failed to fetch flights: {:?}", e);
-                error_message = Some(format!("Failed to fetch flight information: {:?}", e));
+                error!("Failed to fetch flights: {}", e);
+                error_message = Some(format!("Failed to fetch flight information: {}", e));
                 Vec::new()
             }
         };
@@ -1,5 +1,5 @@
 use crate::utils::calculate_distance;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sky_tracer::protocol::flights::{FlightPositionResponse, FlightResponse};
 use yew::prelude::*;
 
@@ -7,6 +7,7 @@ use yew::prelude::*;
 pub struct DelayTableProps {
     pub flights: Vec<(FlightResponse, Option<FlightPositionResponse>)>,
     pub airport_position: Option<(f64, f64)>,
+    pub now: DateTime<Utc>,
 }
 
 #[function_component(DelayTable)]
@@ -28,13 +29,13 @@ pub fn delay_table(props: &DelayTableProps) -> Html {
                 </thead>
                 <tbody>
                     {props.flights.iter().map(|(flight, position)| {
-                        let status = calculate_delay_status(flight);
+                        let status = calculate_delay_status(flight, props.now);
                         let distance = calculate_flight_distance(position, props.airport_position);
                         html! {
                             <tr>
-                                <td>{&flight.flight_number}</td>
-                                <td>{&flight.departure}</td>
-                                <td>{&flight.arrival}</td>
+                                <td>{flight.flight_number.as_str()}</td>
+                                <td>{flight.departure.as_str()}</td>
+                                <td>{flight.arrival.as_str()}</td>
                                 <td>{flight.departure_time.format("%H:%M").to_string()}</td>
                                 <td>
                                     <span class={status.1}>{status.0}</span>
@@ -72,8 +73,7 @@ fn format_distance(distance: Option<f64>) -> String {
         .unwrap_or_else(|| "N/A".to_string())
 }
 
-fn calculate_delay_status(flight: &FlightResponse) -> (String, &'static str) {
-    let now = Utc::now();
+fn calculate_delay_status(flight: &FlightResponse, now: DateTime<Utc>) -> (String, &'static str) {
     if now > flight.departure_time {
         (
             format!(
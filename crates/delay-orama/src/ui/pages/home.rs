@@ -1,4 +1,5 @@
 use crate::ui::components::{DelayTable, ErrorMessage};
+use chrono::{DateTime, Utc};
 use sky_tracer::protocol::flights::{FlightPositionResponse, FlightResponse};
 use yew::prelude::*;
 
@@ -8,6 +9,8 @@ pub struct HomeProps {
     pub airport_position: Option<(f64, f64)>,
     pub airport_code: Option<String>,
     pub error_message: Option<String>,
+    /// Time delays are measured at
+    pub now: DateTime<Utc>,
 }
 
 #[function_component(Home)]
@@ -44,6 +47,7 @@ pub fn home(props: &HomeProps) -> Html {
                             <DelayTable
                                 flights={props.flights.clone()}
                                 airport_position={props.airport_position}
+                                now={props.now}
                             />
                         }
                     }
@@ -0,0 +1,237 @@
+//! Registry of airlines operating flights, keyed by IATA and ICAO designators.
+//!
+//! Flight numbers consist of the airline's IATA designator followed by one to
+//! four digits and an optional operational suffix letter, e.g. `LH400` or
+//! `TP1234A`.
+
+use sky_tracer::model::FlightNumber;
+use sky_tracer::protocol::flights::AirlineResponse;
+
+/// Airline used for flights created without airline or flight number
+pub const DEFAULT_AIRLINE: &str = "LH";
+
+/// Highest numeric part of a flight number
+pub const MAX_FLIGHT_SEQUENCE: u32 = 9999;
+
+/// Built-in airlines as (IATA, ICAO, name)
+const AIRLINES: &[(&str, &str, &str)] = &[
+    ("AA", "AAL", "American Airlines"),
+    ("AC", "ACA", "Air Canada"),
+    ("AF", "AFR", "Air France"),
+    ("AY", "FIN", "Finnair"),
+    ("AZ", "ITY", "ITA Airways"),
+    ("BA", "BAW", "British Airways"),
+    ("CX", "CPA", "Cathay Pacific"),
+    ("DE", "CFG", "Condor"),
+    ("DL", "DAL", "Delta Air Lines"),
+    ("EK", "UAE", "Emirates"),
+    ("EW", "EWG", "Eurowings"),
+    ("FR", "RYR", "Ryanair"),
+    ("IB", "IBE", "Iberia"),
+    ("JL", "JAL", "Japan Airlines"),
+    ("KL", "KLM", "KLM Royal Dutch Airlines"),
+    ("LH", "DLH", "Lufthansa"),
+    ("LX", "SWR", "Swiss International Air Lines"),
+    ("NH", "ANA", "All Nippon Airways"),
+    ("OS", "AUA", "Austrian Airlines"),
+    ("QF", "QFA", "Qantas"),
+    ("QR", "QTR", "Qatar Airways"),
+    ("SK", "SAS", "Scandinavian Airlines"),
+    ("SQ", "SIA", "Singapore Airlines"),
+    ("TK", "THY", "Turkish Airlines"),
+    ("TP", "TAP", "TAP Air Portugal"),
+    ("U2", "EZY", "easyJet"),
+    ("UA", "UAL", "United Airlines"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Airline {
+    pub iata: String,
+    pub icao: String,
+    pub name: String,
+}
+
+impl Airline {
+    /// Flight number for the given sequence, e.g. `LH400`
+    pub fn flight_number(&self, sequence: u32) -> Result<FlightNumber, AirlineError> {
+        let number = format!("{}{}", self.iata, sequence);
+        number
+            .parse()
+            .map_err(|_| AirlineError::InvalidFlightNumber(number))
+    }
+
+    /// Numeric part of one of this airline's flight numbers without suffix
+    pub fn sequence_of(&self, flight_number: &str) -> Option<u32> {
+        let digits = flight_number.strip_prefix(&self.iata)?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+}
+
+impl From<&Airline> for AirlineResponse {
+    fn from(airline: &Airline) -> Self {
+        Self {
+            iata: airline.iata.clone(),
+            icao: airline.icao.clone(),
+            name: airline.name.clone(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum AirlineError {
+    #[error("Unknown airline: {0}")]
+    UnknownAirline(String),
+    #[error("Invalid flight number: {0}")]
+    InvalidFlightNumber(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct AirlineRegistry {
+    airlines: Vec<Airline>,
+}
+
+impl Default for AirlineRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl AirlineRegistry {
+    /// Registry with the built-in airline list
+    pub fn builtin() -> Self {
+        Self::new(
+            AIRLINES
+                .iter()
+                .map(|(iata, icao, name)| Airline {
+                    iata: iata.to_string(),
+                    icao: icao.to_string(),
+                    name: name.to_string(),
+                })
+                .collect(),
+        )
+    }
+
+    pub fn new(airlines: Vec<Airline>) -> Self {
+        Self { airlines }
+    }
+
+    pub fn list(&self) -> &[Airline] {
+        &self.airlines
+    }
+
+    /// Look up an airline by IATA or ICAO designator, ignoring case
+    pub fn find(&self, designator: &str) -> Option<&Airline> {
+        let designator = designator.trim();
+        self.airlines.iter().find(|airline| {
+            airline.iata.eq_ignore_ascii_case(designator)
+                || airline.icao.eq_ignore_ascii_case(designator)
+        })
+    }
+
+    /// Look up an airline, failing for unknown designators
+    pub fn resolve(&self, designator: &str) -> Result<&Airline, AirlineError> {
+        self.find(designator)
+            .ok_or_else(|| AirlineError::UnknownAirline(designator.trim().to_string()))
+    }
+
+    /// Validate a flight number and normalize it to its IATA form.
+    ///
+    /// Accepts IATA (`LH400`) and ICAO (`DLH400`) prefixes, any case and
+    /// embedded spaces (`lh 400`).
+    pub fn parse_flight_number(
+        &self,
+        value: &str,
+    ) -> Result<(&Airline, FlightNumber), AirlineError> {
+        let flight_number: FlightNumber = value
+            .parse()
+            .map_err(|_| AirlineError::InvalidFlightNumber(value.to_string()))?;
+        self.normalize_flight_number(&flight_number)
+    }
+
+    /// The operating airline of `flight_number` and the number with its IATA
+    /// designator, e.g. `LH400` for `DLH400`
+    pub fn normalize_flight_number(
+        &self,
+        flight_number: &FlightNumber,
+    ) -> Result<(&Airline, FlightNumber), AirlineError> {
+        let designator = flight_number.designator();
+        let airline = self
+            .airlines
+            .iter()
+            .find(|airline| match designator.len() {
+                3 => airline.icao == designator,
+                _ => airline.iata == designator,
+            })
+            .ok_or_else(|| AirlineError::UnknownAirline(designator.to_string()))?;
+
+        let mut normalized = format!("{}{}", airline.iata, flight_number.number());
+        normalized.extend(flight_number.suffix());
+        match normalized.parse() {
+            Ok(normalized) => Ok((airline, normalized)),
+            Err(_) => Err(AirlineError::InvalidFlightNumber(normalized)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_by_iata_and_icao() {
+        let registry = AirlineRegistry::builtin();
+
+        assert_eq!(registry.find("LH").unwrap().name, "Lufthansa");
+        assert_eq!(registry.find("dlh").unwrap().iata, "LH");
+        assert!(registry.find("XX").is_none());
+        assert!(registry.find(DEFAULT_AIRLINE).is_some());
+    }
+
+    #[test]
+    fn test_parse_flight_number_normalizes_to_iata() {
+        let registry = AirlineRegistry::builtin();
+
+        for (input, expected) in [
+            ("LH400", "LH400"),
+            ("lh 400", "LH400"),
+            ("DLH400", "LH400"),
+            ("LH0400", "LH400"),
+            ("TP1234A", "TP1234A"),
+            ("U21", "U21"),
+        ] {
+            let (_, number) = registry.parse_flight_number(input).unwrap();
+            assert_eq!(number, expected, "parsing {}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_flight_number_rejects_invalid_numbers() {
+        let registry = AirlineRegistry::builtin();
+
+        for input in ["LH", "LH12345", "LH0", "LH12AB", "LH-12"] {
+            assert_eq!(
+                registry.parse_flight_number(input),
+                Err(AirlineError::InvalidFlightNumber(input.to_string())),
+                "parsing {}",
+                input
+            );
+        }
+        assert!(matches!(
+            registry.parse_flight_number("XX123"),
+            Err(AirlineError::UnknownAirline(_))
+        ));
+    }
+
+    #[test]
+    fn test_sequence_of() {
+        let airline = AirlineRegistry::builtin().find("LH").unwrap().clone();
+
+        assert_eq!(airline.sequence_of("LH400"), Some(400));
+        assert_eq!(airline.sequence_of("LH400A"), None);
+        assert_eq!(airline.sequence_of("TP400"), None);
+        assert_eq!(airline.flight_number(7).unwrap(), "LH7");
+    }
+}
@@ -1,22 +1,31 @@
 use crate::{
     openapi,
-    routes::{create_flight, get_flight_position, list_flights},
+    routes::{
+        create_flight, delete_flight, get_flight, get_flight_position, list_airlines, list_flights,
+        patch_flight, update_flight,
+    },
     services::FlightService,
     ui::pages::{Home, HomeProps},
 };
 use axum::{
     extract::{Query, State},
+    middleware,
     response::Html,
     routing::{get, post},
     Router,
 };
-use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use sky_tracer::clock::follow_caller_time;
 use sky_tracer::protocol::flights::FlightResponse;
-use sky_tracer::protocol::{FLIGHTS_API_PATH, FLIGHTS_POSITION_API_PATH};
+use sky_tracer::protocol::health::health_routes;
+use sky_tracer::protocol::metrics::{metrics_routes, observe};
+use sky_tracer::protocol::problem::problem_instance;
+use sky_tracer::protocol::{
+    AIRLINES_API_PATH, FLIGHTS_API_PATH, FLIGHTS_DETAIL_API_PATH, FLIGHTS_POSITION_API_PATH,
+};
 use tower_http::cors::{Any, CorsLayer};
-use tracing::{info, instrument};
+use tracing::{error, info, instrument};
 
 #[derive(Debug, Deserialize)]
 struct PageParams {
@@ -39,20 +48,14 @@ async fn render_page(
 
     let flights = flight_service
         .list_flights(params.departure, params.arrival, date)
-        .await;
+        .await
+        .unwrap_or_else(|e| {
+            error!(error = %e, "Failed to list flights");
+            Vec::new()
+        });
 
     let renderer = yew::ServerRenderer::<Home>::with_props(move || HomeProps {
-        flights: flights
-            .into_iter()
-            .map(|f| FlightResponse {
-                flight_number: f.flight_number,
-                aircraft_number: f.aircraft_number,
-                departure: f.departure,
-                arrival: f.arrival,
-                departure_time: f.departure_time,
-                arrival_time: f.arrival_time,
-            })
-            .collect(),
+        flights: flights.into_iter().map(FlightResponse::from).collect(),
     });
 
     let html = renderer.render().await;
@@ -77,12 +80,23 @@ async fn render_page(
     ))
 }
 
+/// Application with volatile in-memory flight storage
 pub fn app() -> Router {
-    let flight_service = FlightService::new();
+    app_with_service(FlightService::new())
+}
 
+pub fn app_with_service(flight_service: FlightService) -> Router {
     let api_router = Router::new()
         .route(FLIGHTS_API_PATH, post(create_flight).get(list_flights))
+        .route(
+            FLIGHTS_DETAIL_API_PATH,
+            get(get_flight)
+                .put(update_flight)
+                .patch(patch_flight)
+                .delete(delete_flight),
+        )
         .route(FLIGHTS_POSITION_API_PATH, get(get_flight_position))
+        .route(AIRLINES_API_PATH, get(list_airlines))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
@@ -90,11 +104,20 @@ pub fn app() -> Router {
                 .allow_headers(Any),
         );
 
-    Router::new()
+    let app = Router::new()
         .route("/", get(render_page))
         .merge(openapi::routes())
         .merge(api_router)
-        .layer(OtelInResponseLayer)
-        .layer(OtelAxumLayer::default())
-        .with_state(flight_service)
+        .layer(middleware::from_fn_with_state(
+            flight_service.clock().clone(),
+            follow_caller_time,
+        ))
+        .layer(middleware::from_fn(problem_instance));
+    let probes = health_routes("flight-controller", |service: FlightService| async move {
+        service.readiness().await
+    })
+    .merge(metrics_routes(|service: FlightService| async move {
+        service.observe_metrics().await
+    }));
+    observe("flight-controller", app, probes).with_state(flight_service)
 }
@@ -1,8 +1,11 @@
+pub mod airlines;
 pub mod app;
 pub mod models;
 pub mod openapi;
 pub mod routes;
 pub mod services;
+pub mod storage;
+pub mod validation;
 
 #[cfg(feature = "ssr")]
 pub mod ui;
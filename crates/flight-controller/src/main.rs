@@ -1,10 +1,15 @@
-use flight_controller::app::app;
+use flight_controller::app::app_with_service;
+use flight_controller::services::{FlightService, HttpAirportDirectory};
+use flight_controller::storage;
 use sky_tracer::protocol::FLIGHTS_API_PATH;
+use std::sync::Arc;
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
     let _guard = init_tracing_opentelemetry::tracing_subscriber_ext::init_subscribers()?;
+    let clock = sky_tracer::clock::init_from_env()?;
+    info!(clock = ?clock, "Configured clock");
 
     // Get service configuration
     let service_port = std::env::var("PORT")
@@ -18,7 +23,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
     info!("Starting {} service on port {}", service_name, service_port);
     info!("Flight API available at: {}", FLIGHTS_API_PATH);
 
-    let app = app();
+    let airports_service_url = std::env::var("AIRPORTS_SERVICE_BASE_URL")
+        .unwrap_or_else(|_| "http://localhost:3000".to_string());
+    info!("Validating airports against: {}", airports_service_url);
+
+    let store = storage::from_env()?;
+    let flight_service = FlightService::with_store(store)
+        .with_airport_directory(Arc::new(HttpAirportDirectory::new(airports_service_url)));
+    let app = app_with_service(flight_service);
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", service_port)).await?;
     info!("Server running on http://localhost:{}", service_port);
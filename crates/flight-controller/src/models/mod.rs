@@ -1,29 +1,34 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sky_tracer::model::{AircraftRegistration, FlightNumber, FlightStatus, IataCode};
+use sky_tracer::protocol::flights::FlightResponse;
 
 /// Internal flight model for the flight controller service
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Flight {
     /// Unique flight number (e.g., "LH1234")
-    pub flight_number: String,
+    pub flight_number: FlightNumber,
     /// Aircraft registration number
-    pub aircraft_number: String,
+    pub aircraft_number: AircraftRegistration,
     /// Departure airport code
-    pub departure: String,
+    pub departure: IataCode,
     /// Arrival airport code
-    pub arrival: String,
+    pub arrival: IataCode,
     /// Scheduled departure time
     pub departure_time: DateTime<Utc>,
     /// Scheduled arrival time
     pub arrival_time: Option<DateTime<Utc>>,
+    /// Operational status
+    #[serde(default)]
+    pub status: FlightStatus,
 }
 
 impl Flight {
     pub fn new(
-        flight_number: String,
-        aircraft_number: String,
-        departure: String,
-        arrival: String,
+        flight_number: FlightNumber,
+        aircraft_number: AircraftRegistration,
+        departure: IataCode,
+        arrival: IataCode,
         departure_time: DateTime<Utc>,
         arrival_time: Option<DateTime<Utc>>,
     ) -> Self {
@@ -34,6 +39,7 @@ impl Flight {
             arrival,
             departure_time,
             arrival_time,
+            status: FlightStatus::Scheduled,
         }
     }
 
@@ -51,8 +57,9 @@ impl Flight {
         date: Option<DateTime<Utc>>,
     ) -> bool {
         let matches_departure =
-            departure.is_none_or(|dep| self.departure.eq_ignore_ascii_case(dep));
-        let matches_arrival = arrival.is_none_or(|arr| self.arrival.eq_ignore_ascii_case(arr));
+            departure.is_none_or(|dep| self.departure.as_str().eq_ignore_ascii_case(dep));
+        let matches_arrival =
+            arrival.is_none_or(|arr| self.arrival.as_str().eq_ignore_ascii_case(arr));
         let matches_date =
             date.is_none_or(|date| self.departure_time.date_naive() == date.date_naive());
 
@@ -60,6 +67,20 @@ impl Flight {
     }
 }
 
+impl From<Flight> for FlightResponse {
+    fn from(flight: Flight) -> Self {
+        Self {
+            flight_number: flight.flight_number,
+            aircraft_number: flight.aircraft_number,
+            departure: flight.departure,
+            arrival: flight.arrival,
+            departure_time: flight.departure_time,
+            arrival_time: flight.arrival_time,
+            status: flight.status,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,10 +91,10 @@ mod tests {
         let arrival_time = Some(departure_time + chrono::Duration::hours(2));
 
         let flight = Flight::new(
-            "LH1234".to_string(),
-            "D-ABCD".to_string(),
-            "FRA".to_string(),
-            "LIS".to_string(),
+            "LH1234".parse().unwrap(),
+            "D-ABCD".parse().unwrap(),
+            "FRA".parse().unwrap(),
+            "LIS".parse().unwrap(),
             departure_time,
             arrival_time,
         );
@@ -84,23 +105,24 @@ mod tests {
         assert_eq!(flight.arrival, "LIS");
         assert_eq!(flight.departure_time, departure_time);
         assert_eq!(flight.arrival_time, arrival_time);
+        assert_eq!(flight.status, FlightStatus::Scheduled);
     }
 
     #[test]
     fn test_get_arrival_time_with_explicit_time() {
         let departure_time = Utc::now();
-        let arrival_time = Some(departure_time + chrono::Duration::hours(3));
+        let arrival_time = departure_time + chrono::Duration::hours(3);
 
         let flight = Flight::new(
-            "LH1234".to_string(),
-            "D-ABCD".to_string(),
-            "FRA".to_string(),
-            "LIS".to_string(),
+            "LH1234".parse().unwrap(),
+            "D-ABCD".parse().unwrap(),
+            "FRA".parse().unwrap(),
+            "LIS".parse().unwrap(),
             departure_time,
-            arrival_time,
+            Some(arrival_time),
         );
 
-        assert_eq!(flight.get_arrival_time(), arrival_time.unwrap());
+        assert_eq!(flight.get_arrival_time(), arrival_time);
     }
 
     #[test]
@@ -109,10 +131,10 @@ mod tests {
         let expected_arrival = departure_time + chrono::Duration::hours(2);
 
         let flight = Flight::new(
-            "LH1234".to_string(),
-            "D-ABCD".to_string(),
-            "FRA".to_string(),
-            "LIS".to_string(),
+            "LH1234".parse().unwrap(),
+            "D-ABCD".parse().unwrap(),
+            "FRA".parse().unwrap(),
+            "LIS".parse().unwrap(),
             departure_time,
             None,
         );
@@ -125,10 +147,10 @@ mod tests {
         let departure_time = Utc::now();
 
         let flight = Flight::new(
-            "LH1234".to_string(),
-            "D-ABCD".to_string(),
-            "FRA".to_string(),
-            "LIS".to_string(),
+            "LH1234".parse().unwrap(),
+            "D-ABCD".parse().unwrap(),
+            "FRA".parse().unwrap(),
+            "LIS".parse().unwrap(),
             departure_time,
             None,
         );
@@ -9,13 +9,24 @@ use utoipa_swagger_ui::SwaggerUi;
     paths(
         routes::create_flight,
         routes::list_flights,
+        routes::get_flight,
+        routes::update_flight,
+        routes::patch_flight,
+        routes::delete_flight,
         routes::get_flight_position,
+        routes::list_airlines,
     ),
     components(
         schemas(
             flights::CreateFlightRequest,
+            flights::UpdateFlightRequest,
+            flights::PatchFlightRequest,
             flights::FlightResponse,
+            sky_tracer::model::FlightStatus,
             flights::FlightPositionResponse,
+            flights::AirlineResponse,
+            sky_tracer::protocol::problem::ProblemDetails,
+            flights::FieldError,
             flights::ListFlightsRequest
         )
     ),
@@ -1,36 +1,90 @@
-use crate::services::FlightService;
+use crate::services::{FlightService, FlightServiceError};
+use crate::validation::{
+    validate_create, validate_patch, validate_update, CreateFlightInput, PatchFlightInput,
+    UpdateFlightInput,
+};
 use axum::{
-    extract::{Path, Query, State},
+    extract::State,
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
 use chrono::{DateTime, Utc};
 use sky_tracer::protocol::flights::{
-    CreateFlightRequest, FlightPositionResponse, FlightResponse, ListFlightsRequest,
+    AirlineResponse, CreateFlightRequest, FieldError, FlightPositionResponse, FlightResponse,
+    ListFlightsRequest, PatchFlightRequest, UpdateFlightRequest,
+};
+use sky_tracer::protocol::problem::{
+    ProblemDetails, ProblemJson, ProblemPath, ProblemQuery, PROBLEM_JSON,
+};
+use sky_tracer::protocol::{
+    AIRLINES_API_PATH, FLIGHTS_API_PATH, FLIGHTS_DETAIL_API_PATH, FLIGHTS_POSITION_API_PATH,
 };
-use sky_tracer::protocol::{FLIGHTS_API_PATH, FLIGHTS_POSITION_API_PATH};
 use tracing::{debug, error, info, instrument, warn};
 
 // Custom error type for our API
 #[derive(Debug)]
 pub enum ApiError {
     FlightCreationError(String),
+    Validation(Vec<FieldError>),
     NotFound,
+    Conflict(String),
     ParseError(String),
+    Unavailable(String),
     ServiceError(String),
 }
 
+impl From<FlightServiceError> for ApiError {
+    fn from(error: FlightServiceError) -> Self {
+        match error {
+            FlightServiceError::NotFound(_) => ApiError::NotFound,
+            FlightServiceError::Validation(errors) => ApiError::Validation(errors),
+            FlightServiceError::Airline(_) | FlightServiceError::AirlineMismatch { .. } => {
+                ApiError::FlightCreationError(error.to_string())
+            }
+            FlightServiceError::InvalidTransition { .. }
+            | FlightServiceError::ScheduleLocked(_)
+            | FlightServiceError::AlreadyExists(_)
+            | FlightServiceError::FlightNumbersExhausted(_) => {
+                ApiError::Conflict(error.to_string())
+            }
+            FlightServiceError::AirportLookup(e) => ApiError::Unavailable(e.to_string()),
+            FlightServiceError::Storage(e) => ApiError::ServiceError(e.to_string()),
+        }
+    }
+}
+
+impl From<ApiError> for ProblemDetails {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::FlightCreationError(msg) => ProblemDetails::new(400, "Invalid flight")
+                .with_code("INVALID_FLIGHT")
+                .with_detail(msg),
+            ApiError::Validation(fields) => ProblemDetails::new(400, "Flight validation failed")
+                .with_code("VALIDATION_FAILED")
+                .with_fields(fields),
+            ApiError::NotFound => {
+                ProblemDetails::new(404, "Flight not found").with_code("NOT_FOUND")
+            }
+            ApiError::Conflict(msg) => ProblemDetails::new(409, "Conflict")
+                .with_code("CONFLICT")
+                .with_detail(msg),
+            ApiError::ParseError(msg) => ProblemDetails::new(400, "Invalid parameter")
+                .with_code("INVALID_PARAMETER")
+                .with_detail(msg),
+            ApiError::Unavailable(msg) => ProblemDetails::new(503, "Dependency unavailable")
+                .with_code("DEPENDENCY_UNAVAILABLE")
+                .with_detail(msg),
+            ApiError::ServiceError(msg) => ProblemDetails::new(500, "Internal server error")
+                .with_code("INTERNAL_ERROR")
+                .with_detail(msg),
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, message) = match self {
-            ApiError::FlightCreationError(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::NotFound => (StatusCode::NOT_FOUND, "Flight not found".to_string()),
-            ApiError::ParseError(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::ServiceError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-        };
-
-        (status, Json(serde_json::json!({ "error": message }))).into_response()
+        ProblemDetails::from(self).into_response()
     }
 }
 
@@ -41,8 +95,10 @@ impl IntoResponse for ApiError {
     request_body = CreateFlightRequest,
     responses(
         (status = 201, description = "Flight created successfully", body = FlightResponse),
-        (status = 400, description = "Invalid flight data provided"),
-        (status = 500, description = "Internal server error")
+        (status = 400, description = "Invalid flight data, unknown airline or malformed flight number", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 409, description = "Flight number already in use", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 503, description = "Airport service unavailable", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "flights"
 )]
@@ -53,9 +109,10 @@ impl IntoResponse for ApiError {
 ))]
 pub async fn create_flight(
     State(flight_service): State<FlightService>,
-    Json(request): Json<CreateFlightRequest>,
+    ProblemJson(request): ProblemJson<CreateFlightInput>,
 ) -> Result<(StatusCode, Json<FlightResponse>), ApiError> {
     debug!("Creating new flight");
+    let request = validate_create(request).map_err(ApiError::Validation)?;
 
     match flight_service.create_flight(request).await {
         Ok(flight) => {
@@ -64,20 +121,11 @@ pub async fn create_flight(
                 "Flight created successfully"
             );
 
-            let response = FlightResponse {
-                flight_number: flight.flight_number,
-                aircraft_number: flight.aircraft_number,
-                departure: flight.departure,
-                arrival: flight.arrival,
-                departure_time: flight.departure_time,
-                arrival_time: flight.arrival_time,
-            };
-
-            Ok((StatusCode::CREATED, Json(response)))
+            Ok((StatusCode::CREATED, Json(flight.into())))
         }
         Err(e) => {
             error!(error = %e, "Failed to create flight");
-            Err(ApiError::FlightCreationError(e))
+            Err(e.into())
         }
     }
 }
@@ -91,8 +139,8 @@ pub async fn create_flight(
     ),
     responses(
         (status = 200, description = "List of flights", body = Vec<FlightResponse>),
-        (status = 400, description = "Invalid query parameters"),
-        (status = 500, description = "Internal server error")
+        (status = 400, description = "Invalid query parameters", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "flights"
 )]
@@ -103,7 +151,7 @@ pub async fn create_flight(
 ))]
 pub async fn list_flights(
     State(flight_service): State<FlightService>,
-    Query(params): Query<ListFlightsRequest>,
+    ProblemQuery(params): ProblemQuery<ListFlightsRequest>,
 ) -> Result<Json<Vec<FlightResponse>>, ApiError> {
     debug!("Listing flights with filters");
 
@@ -124,36 +172,188 @@ pub async fn list_flights(
 
     let flights = flight_service
         .list_flights(params.departure, params.arrival, date)
-        .await;
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to list flights");
+            ApiError::ServiceError(format!("Failed to list flights: {}", e))
+        })?;
 
     info!(
         flights_count = flights.len(),
         "Retrieved flights matching criteria"
     );
 
-    let response: Vec<FlightResponse> = flights
-        .into_iter()
-        .map(|flight| FlightResponse {
-            flight_number: flight.flight_number,
-            aircraft_number: flight.aircraft_number,
-            departure: flight.departure,
-            arrival: flight.arrival,
-            departure_time: flight.departure_time,
-            arrival_time: flight.arrival_time,
-        })
-        .collect();
+    let response: Vec<FlightResponse> = flights.into_iter().map(FlightResponse::from).collect();
 
     Ok(Json(response))
 }
 
+/// List airlines flights can be created for
+#[utoipa::path(
+    get,
+    path = AIRLINES_API_PATH,
+    responses(
+        (status = 200, description = "Registered airlines", body = Vec<AirlineResponse>)
+    ),
+    tag = "flights"
+)]
+#[instrument(skip(flight_service))]
+pub async fn list_airlines(
+    State(flight_service): State<FlightService>,
+) -> Json<Vec<AirlineResponse>> {
+    let airlines: Vec<AirlineResponse> = flight_service
+        .airlines()
+        .list()
+        .iter()
+        .map(AirlineResponse::from)
+        .collect();
+    debug!(airlines_count = airlines.len(), "Listing airlines");
+
+    Json(airlines)
+}
+
+/// Get a single flight
+#[utoipa::path(
+    get,
+    path = FLIGHTS_DETAIL_API_PATH,
+    responses(
+        (status = 200, description = "Flight found", body = FlightResponse),
+        (status = 404, description = "Flight not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    params(
+        ("flight_number" = String, Path, description = "Flight number")
+    ),
+    tag = "flights"
+)]
+#[instrument(skip(flight_service), fields(flight_number = %flight_number))]
+pub async fn get_flight(
+    State(flight_service): State<FlightService>,
+    ProblemPath(flight_number): ProblemPath<String>,
+) -> Result<Json<FlightResponse>, ApiError> {
+    debug!("Fetching flight {}", flight_number);
+
+    match flight_service.get_flight(&flight_number).await {
+        Ok(Some(flight)) => Ok(Json(flight.into())),
+        Ok(None) => {
+            warn!("Flight not found: {}", flight_number);
+            Err(ApiError::NotFound)
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to load flight");
+            Err(ApiError::ServiceError(format!(
+                "Failed to load flight: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Replace the schedule of a flight
+#[utoipa::path(
+    put,
+    path = FLIGHTS_DETAIL_API_PATH,
+    request_body = UpdateFlightRequest,
+    responses(
+        (status = 200, description = "Flight updated", body = FlightResponse),
+        (status = 400, description = "Invalid flight data", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 404, description = "Flight not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 409, description = "Flight can no longer be rescheduled", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    params(
+        ("flight_number" = String, Path, description = "Flight number")
+    ),
+    tag = "flights"
+)]
+#[instrument(skip(flight_service, request), fields(flight_number = %flight_number))]
+pub async fn update_flight(
+    State(flight_service): State<FlightService>,
+    ProblemPath(flight_number): ProblemPath<String>,
+    ProblemJson(request): ProblemJson<UpdateFlightInput>,
+) -> Result<Json<FlightResponse>, ApiError> {
+    debug!("Updating flight {}", flight_number);
+    let request = validate_update(request).map_err(ApiError::Validation)?;
+
+    let flight = flight_service
+        .update_flight(&flight_number, request)
+        .await
+        .inspect_err(|e| warn!(error = %e, "Failed to update flight"))?;
+
+    Ok(Json(flight.into()))
+}
+
+/// Partially update a flight, e.g. to change its status
+#[utoipa::path(
+    patch,
+    path = FLIGHTS_DETAIL_API_PATH,
+    request_body = PatchFlightRequest,
+    responses(
+        (status = 200, description = "Flight updated", body = FlightResponse),
+        (status = 400, description = "Invalid flight data", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 404, description = "Flight not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 409, description = "Status transition or schedule change not allowed", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    params(
+        ("flight_number" = String, Path, description = "Flight number")
+    ),
+    tag = "flights"
+)]
+#[instrument(skip(flight_service, request), fields(flight_number = %flight_number, status = ?request.status))]
+pub async fn patch_flight(
+    State(flight_service): State<FlightService>,
+    ProblemPath(flight_number): ProblemPath<String>,
+    ProblemJson(request): ProblemJson<PatchFlightInput>,
+) -> Result<Json<FlightResponse>, ApiError> {
+    debug!("Patching flight {}", flight_number);
+    let request = validate_patch(request).map_err(ApiError::Validation)?;
+
+    let flight = flight_service
+        .patch_flight(&flight_number, request)
+        .await
+        .inspect_err(|e| warn!(error = %e, "Failed to patch flight"))?;
+
+    Ok(Json(flight.into()))
+}
+
+/// Delete a flight
+#[utoipa::path(
+    delete,
+    path = FLIGHTS_DETAIL_API_PATH,
+    responses(
+        (status = 204, description = "Flight deleted"),
+        (status = 404, description = "Flight not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
+    ),
+    params(
+        ("flight_number" = String, Path, description = "Flight number")
+    ),
+    tag = "flights"
+)]
+#[instrument(skip(flight_service), fields(flight_number = %flight_number))]
+pub async fn delete_flight(
+    State(flight_service): State<FlightService>,
+    ProblemPath(flight_number): ProblemPath<String>,
+) -> Result<StatusCode, ApiError> {
+    debug!("Deleting flight {}", flight_number);
+
+    flight_service
+        .delete_flight(&flight_number)
+        .await
+        .inspect_err(|e| warn!(error = %e, "Failed to delete flight"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Get flight position
 #[utoipa::path(
     get,
     path = FLIGHTS_POSITION_API_PATH,
     responses(
         (status = 200, description = "Flight position retrieved", body = FlightPositionResponse),
-        (status = 404, description = "Flight not found"),
-        (status = 500, description = "Internal server error")
+        (status = 404, description = "Flight not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     params(
         ("flight_number" = String, Path, description = "Flight number")
@@ -163,12 +363,12 @@ pub async fn list_flights(
 #[instrument(skip(flight_service), fields(flight_number = %flight_number))]
 pub async fn get_flight_position(
     State(flight_service): State<FlightService>,
-    Path(flight_number): Path<String>,
+    ProblemPath(flight_number): ProblemPath<String>,
 ) -> Result<Json<FlightPositionResponse>, ApiError> {
     debug!("Fetching flight position for flight {}", flight_number);
 
     let flight = match flight_service.get_flight(&flight_number).await {
-        Some(f) => {
+        Ok(Some(f)) => {
             debug!(
                 departure = %f.departure,
                 arrival = %f.arrival,
@@ -177,27 +377,41 @@ pub async fn get_flight_position(
             );
             f
         }
-        None => {
+        Ok(None) => {
             warn!("Flight not found: {}", flight_number);
             return Err(ApiError::NotFound);
         }
+        Err(e) => {
+            error!(error = %e, "Failed to load flight");
+            return Err(ApiError::ServiceError(format!(
+                "Failed to load flight: {}",
+                e
+            )));
+        }
     };
 
     match flight_service.calculate_flight_position(&flight).await {
-        Ok((latitude, longitude, timestamp)) => {
+        Ok(position) => {
             info!(
                 flight_number = %flight_number,
-                latitude = latitude,
-                longitude = longitude,
-                timestamp = %timestamp,
+                latitude = position.latitude,
+                longitude = position.longitude,
+                altitude = position.altitude,
+                phase = ?position.phase,
+                timestamp = %position.timestamp,
                 "Successfully retrieved flight position"
             );
 
             Ok(Json(FlightPositionResponse {
-                flight_number: flight.flight_number,
-                latitude,
-                longitude,
-                timestamp,
+                flight_number: flight.flight_number.to_string(),
+                latitude: position.latitude,
+                longitude: position.longitude,
+                altitude: position.altitude,
+                heading: position.heading,
+                ground_speed: position.ground_speed,
+                vertical_rate: position.vertical_rate,
+                phase: position.phase,
+                timestamp: position.timestamp,
             }))
         }
         Err(e) => {
@@ -222,15 +436,17 @@ mod tests {
     #[tokio::test]
     async fn test_create_flight_route() {
         let flight_service = FlightService::new();
-        let request = CreateFlightRequest {
+        let request = CreateFlightInput {
             aircraft_number: "D-ABCD".to_string(),
             departure: "FRA".to_string(),
             arrival: "LIS".to_string(),
             departure_time: Utc::now(),
             arrival_time: None,
+            airline: None,
+            flight_number: None,
         };
 
-        let result = create_flight(State(flight_service), Json(request)).await;
+        let result = create_flight(State(flight_service), ProblemJson(request)).await;
         assert!(result.is_ok());
 
         let (status, response) = result.unwrap();
@@ -247,7 +463,7 @@ mod tests {
             date: None,
         };
 
-        let result = list_flights(State(flight_service), Query(params)).await;
+        let result = list_flights(State(flight_service), ProblemQuery(params)).await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
@@ -258,6 +474,7 @@ mod tests {
     fn test_api_path_constants() {
         // Verify we're using the correct API paths
         assert_eq!(FLIGHTS_API_PATH, "/api/v1/flights");
+        assert_eq!(FLIGHTS_DETAIL_API_PATH, "/api/v1/flights/{flight_number}");
         assert_eq!(
             FLIGHTS_POSITION_API_PATH,
             "/api/v1/flights/{flight_number}/position"
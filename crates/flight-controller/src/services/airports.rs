@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use sky_tracer::client::{AirportsClient, ClientConfig};
+use sky_tracer::model::Airport;
+use sky_tracer::protocol::airports::SearchAirportsRequest;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, instrument, warn};
+
+/// How long a found airport is remembered
+const FOUND_TTL: Duration = Duration::from_secs(60 * 60);
+/// How long an unknown code is remembered, kept short so new airports show up
+const MISSING_TTL: Duration = Duration::from_secs(60);
+/// Upper bound for a single lookup, flight writes wait for it. Lookups are
+/// not retried unless `AIRPORTS_SERVICE_MAX_RETRIES` asks for it, so each of
+/// the two lookups of a write takes at most this long.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(thiserror::Error, Debug)]
+pub enum AirportLookupError {
+    #[error("Airport service unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// Source of truth for which airport codes exist
+#[async_trait]
+pub trait AirportDirectory: Send + Sync {
+    /// Whether an airport with the given IATA or ICAO code exists
+    async fn contains(&self, code: &str) -> Result<bool, AirportLookupError>;
+}
+
+/// Fixed set of airport codes, e.g. for tests and offline setups
+#[derive(Debug, Clone, Default)]
+pub struct StaticAirportDirectory {
+    codes: HashSet<String>,
+}
+
+impl StaticAirportDirectory {
+    pub fn new<I, S>(codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            codes: codes
+                .into_iter()
+                .map(|code| code.as_ref().to_uppercase())
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl AirportDirectory for StaticAirportDirectory {
+    async fn contains(&self, code: &str) -> Result<bool, AirportLookupError> {
+        Ok(self.codes.contains(&code.trim().to_uppercase()))
+    }
+}
+
+struct CacheEntry {
+    found: bool,
+    expires: Instant,
+}
+
+/// Airport lookup against airport-anywhere with a local cache
+pub struct HttpAirportDirectory {
+    client: AirportsClient,
+    cache: RwLock<HashMap<String, CacheEntry>>,
+}
+
+impl HttpAirportDirectory {
+    pub fn new(base_url: String) -> Self {
+        let config = ClientConfig::new(base_url)
+            .with_timeout(LOOKUP_TIMEOUT)
+            .without_retry()
+            .with_env_overrides("AIRPORTS_SERVICE");
+
+        Self {
+            client: AirportsClient::with_config(config),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn fetch(&self, code: &str) -> Result<bool, AirportLookupError> {
+        debug!(url = %self.client.base_url(), code = %code, "Looking up airport");
+
+        let request = SearchAirportsRequest {
+            code: Some(code.to_string()),
+            ..Default::default()
+        };
+        let search = self
+            .client
+            .search(&request)
+            .await
+            .map_err(|e| AirportLookupError::Unavailable(e.to_string()))?;
+
+        // The search also matches code prefixes, so insist on an exact match
+        Ok(search
+            .airports
+            .into_iter()
+            .map(Airport::from)
+            .any(|airport| airport.has_code(code)))
+    }
+}
+
+#[async_trait]
+impl AirportDirectory for HttpAirportDirectory {
+    #[instrument(skip(self))]
+    async fn contains(&self, code: &str) -> Result<bool, AirportLookupError> {
+        let code = code.trim().to_uppercase();
+
+        if let Some(entry) = self.cache.read().await.get(&code) {
+            if entry.expires > Instant::now() {
+                debug!(found = entry.found, "Airport lookup served from cache");
+                return Ok(entry.found);
+            }
+        }
+
+        let found = self.fetch(&code).await.inspect_err(|e| {
+            warn!(error = %e, "Airport lookup failed");
+        })?;
+        let ttl = if found { FOUND_TTL } else { MISSING_TTL };
+        self.cache.write().await.insert(
+            code,
+            CacheEntry {
+                found,
+                expires: Instant::now() + ttl,
+            },
+        );
+
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_static_directory_ignores_case() {
+        let directory = StaticAirportDirectory::new(["FRA", "lis"]);
+
+        assert!(directory.contains("fra").await.unwrap());
+        assert!(directory.contains("LIS").await.unwrap());
+        assert!(!directory.contains("XXX").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_airport_service_is_reported() {
+        // Nothing listens on the discard port
+        let directory = HttpAirportDirectory::new("http://127.0.0.1:9".to_string());
+
+        let result = directory.contains("FRA").await;
+        assert!(matches!(result, Err(AirportLookupError::Unavailable(_))));
+        // Failures are not cached as missing airports
+        assert!(directory.cache.read().await.is_empty());
+    }
+}
@@ -1,104 +1,260 @@
+use crate::airlines::{
+    Airline, AirlineError, AirlineRegistry, DEFAULT_AIRLINE, MAX_FLIGHT_SEQUENCE,
+};
 use crate::models::Flight;
-use async_trait::async_trait;
+use crate::storage::{FlightStore, InMemoryFlightStore, StorageError};
+use crate::validation::validate_schedule;
 use chrono::{DateTime, Utc};
-use http::Extensions;
-use opentelemetry::global;
-use reqwest::{Request, Response};
-use reqwest_middleware::{
-    ClientBuilder, ClientWithMiddleware, Middleware, Next, Result as MiddlewareResult,
+use sky_tracer::client::{ClientConfig, SatellitesClient};
+use sky_tracer::clock::SharedClock;
+use sky_tracer::model::Position;
+use sky_tracer::model::{FlightNumber, FlightStatus, IataCode};
+use sky_tracer::protocol::flights::{
+    CreateFlightRequest, FieldError, PatchFlightRequest, UpdateFlightRequest,
 };
-use sky_tracer::protocol::flights::CreateFlightRequest;
-use sky_tracer::protocol::satellite::{CalculatePositionRequest, CalculatePositionResponse};
-use sky_tracer::protocol::SATELLITES_POSITION_API_PATH;
+use sky_tracer::protocol::health::{check_dependency, DependencyHealth};
+use sky_tracer::protocol::metrics::Metrics;
+use sky_tracer::protocol::satellite::CalculatePositionRequest;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Mutex;
 use tracing::{debug, error, info, instrument, warn};
-use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
+mod airports;
 
-impl opentelemetry::propagation::Injector for HeaderInjector<'_> {
-    fn set(&mut self, key: &str, value: String) {
-        if let Ok(name) = reqwest::header::HeaderName::from_bytes(key.as_bytes()) {
-            if let Ok(val) = reqwest::header::HeaderValue::from_str(&value) {
-                self.0.insert(name, val);
-            }
-        }
-    }
-}
+pub use airports::{
+    AirportDirectory, AirportLookupError, HttpAirportDirectory, StaticAirportDirectory,
+};
 
-struct OtelMiddleware;
+#[derive(thiserror::Error, Debug)]
+pub enum FlightServiceError {
+    #[error("Flight not found: {0}")]
+    NotFound(String),
+    #[error("Invalid status transition from {from} to {to}")]
+    InvalidTransition {
+        from: FlightStatus,
+        to: FlightStatus,
+    },
+    #[error("Flight schedule can not be changed in status {0}")]
+    ScheduleLocked(FlightStatus),
+    #[error(transparent)]
+    Airline(#[from] AirlineError),
+    #[error("Flight number {flight_number} does not belong to airline {airline}")]
+    AirlineMismatch {
+        flight_number: String,
+        airline: String,
+    },
+    #[error("Flight already exists: {0}")]
+    AlreadyExists(String),
+    #[error("No flight numbers left for airline {0}")]
+    FlightNumbersExhausted(String),
+    #[error("Invalid flight: {}", describe_field_errors(.0))]
+    Validation(Vec<FieldError>),
+    #[error(transparent)]
+    AirportLookup(#[from] AirportLookupError),
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+}
 
-#[async_trait]
-impl Middleware for OtelMiddleware {
-    async fn handle(
-        &self,
-        mut req: Request,
-        extensions: &mut Extensions,
-        next: Next<'_>,
-    ) -> MiddlewareResult<Response> {
-        let cx = tracing::Span::current().context();
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(&cx, &mut HeaderInjector(req.headers_mut()));
-        });
-        next.run(req, extensions).await
-    }
+fn describe_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct FlightService {
-    flights: Arc<RwLock<HashMap<String, Flight>>>,
-    http_client: ClientWithMiddleware,
+    store: Arc<dyn FlightStore>,
+    airlines: Arc<AirlineRegistry>,
+    /// Airport codes are only checked when a directory is configured
+    airports: Option<Arc<dyn AirportDirectory>>,
+    /// Orbital beacon calculating flight positions
+    satellites: SatellitesClient,
+    clock: SharedClock,
+    /// Serializes read-modify-write sequences against the store and keeps the
+    /// last allocated flight number sequence per airline
+    write_lock: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl Default for FlightService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FlightService {
+    /// Create a flight service with volatile in-memory storage
     pub fn new() -> Self {
-        let http_client = ClientBuilder::new(reqwest::Client::new())
-            .with(OtelMiddleware)
-            .build();
+        Self::with_store(Arc::new(InMemoryFlightStore::new()))
+    }
+
+    /// Create a flight service backed by the given storage
+    pub fn with_store(store: Arc<dyn FlightStore>) -> Self {
+        let satellites = SatellitesClient::with_config(ClientConfig::from_env(
+            "ORBITAL_BEACON_BASE_URL",
+            "http://orbital-beacon:3002",
+        ));
 
         Self {
-            flights: Arc::new(RwLock::new(HashMap::new())),
-            http_client,
+            store,
+            airlines: Arc::new(AirlineRegistry::builtin()),
+            airports: None,
+            satellites,
+            clock: SharedClock::default(),
+            write_lock: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    /// Create a new flight
+    /// Check departure and arrival airports against the given directory
+    pub fn with_airport_directory(mut self, airports: Arc<dyn AirportDirectory>) -> Self {
+        self.airports = Some(airports);
+        self
+    }
+
+    /// Calculate flight positions with the given orbital beacon client
+    pub fn with_satellites_client(mut self, satellites: SatellitesClient) -> Self {
+        self.satellites = satellites;
+        self
+    }
+
+    /// Place flights at the time of the given clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Clock of the service
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Health of the orbital beacon flight positions are calculated by
+    pub async fn readiness(&self) -> Vec<DependencyHealth> {
+        vec![check_dependency("orbital-beacon", self.satellites.live())
+            .await
+            .with_circuit(self.satellites.circuit_state())]
+    }
+
+    /// Refresh the number of scheduled and airborne flights for the metrics
+    /// scrape
+    pub async fn observe_metrics(&self) {
+        let flights = match self.store.list().await {
+            Ok(flights) => flights,
+            Err(e) => {
+                warn!(error = %e, "Cannot count flights for metrics");
+                return;
+            }
+        };
+        let count = |statuses: &[FlightStatus]| {
+            flights
+                .iter()
+                .filter(|flight| statuses.contains(&flight.status))
+                .count() as u64
+        };
+
+        let gauges = FlightGauges::global();
+        gauges.scheduled.store(
+            count(&[FlightStatus::Scheduled, FlightStatus::Boarding]),
+            Ordering::Relaxed,
+        );
+        gauges.airborne.store(
+            count(&[FlightStatus::Departed, FlightStatus::EnRoute]),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Airlines flights can be created for
+    pub fn airlines(&self) -> &AirlineRegistry {
+        &self.airlines
+    }
+
+    /// Create a new flight.
+    ///
+    /// An explicit flight number is taken as is and rejected if already in
+    /// use. Otherwise the next free number of the requested (or default)
+    /// airline is allocated.
     #[instrument(skip(self), fields(
         aircraft = %request.aircraft_number,
         departure = %request.departure,
         arrival = %request.arrival
     ))]
-    pub async fn create_flight(&self, request: CreateFlightRequest) -> Result<Flight, String> {
-        let flight_number = self.generate_flight_number(&request.departure).await;
-
-        let flight = Flight::new(
-            flight_number.clone(),
-            request.aircraft_number,
-            request.departure,
-            request.arrival,
-            request.departure_time,
-            request.arrival_time,
-        );
+    pub async fn create_flight(
+        &self,
+        request: CreateFlightRequest,
+    ) -> Result<Flight, FlightServiceError> {
+        let airline = request
+            .airline
+            .as_deref()
+            .map(|designator| self.airlines.resolve(designator))
+            .transpose()?;
+
+        let schedule = |flight_number| {
+            Flight::new(
+                flight_number,
+                request.aircraft_number.clone(),
+                request.departure.clone(),
+                request.arrival.clone(),
+                request.departure_time,
+                request.arrival_time,
+            )
+        };
 
-        let mut flights = self.flights.write().await;
-        flights.insert(flight_number.clone(), flight.clone());
+        // Airport lookups may be slow, keep them out of the write lock
+        let airport_errors = self
+            .check_airports(Some(&request.departure), Some(&request.arrival))
+            .await?;
 
-        info!(
-            flight_number = %flight_number,
-            total_flights = flights.len(),
-            "Flight created successfully"
-        );
+        let mut sequences = self.write_lock.lock().await;
+        let flight = match &request.flight_number {
+            Some(flight_number) => {
+                let (owner, flight_number) =
+                    self.airlines.normalize_flight_number(flight_number)?;
+                if let Some(airline) = airline.filter(|airline| *airline != owner) {
+                    return Err(FlightServiceError::AirlineMismatch {
+                        flight_number: flight_number.to_string(),
+                        airline: airline.iata.clone(),
+                    });
+                }
+
+                let flight = schedule(flight_number);
+                validate(&flight, &airport_errors)?;
+                self.store
+                    .insert(flight.clone())
+                    .await
+                    .map_err(|e| match e {
+                        StorageError::AlreadyExists(number) => {
+                            warn!(flight_number = %number, "Flight number already in use");
+                            FlightServiceError::AlreadyExists(number)
+                        }
+                        e => e.into(),
+                    })?;
+                flight
+            }
+            None => {
+                let airline = match airline {
+                    Some(airline) => airline,
+                    None => self.airlines.resolve(DEFAULT_AIRLINE)?,
+                };
+                let sequence = self.next_sequence(&sequences, airline).await?;
+                let flight = schedule(airline.flight_number(sequence)?);
+                validate(&flight, &airport_errors)?;
+                self.allocate_flight(&mut sequences, airline, sequence, flight)
+                    .await?
+            }
+        };
+
+        info!(flight_number = %flight.flight_number, "Flight created successfully");
 
         Ok(flight)
     }
 
     /// Get a specific flight by flight number
     #[instrument(skip(self))]
-    pub async fn get_flight(&self, flight_number: &str) -> Option<Flight> {
-        let flights = self.flights.read().await;
-        let flight = flights.get(flight_number).cloned();
+    pub async fn get_flight(&self, flight_number: &str) -> Result<Option<Flight>, StorageError> {
+        let flight = self.store.get(&flight_key(flight_number)).await?;
 
         if flight.is_some() {
             debug!("Flight found: {}", flight_number);
@@ -106,7 +262,7 @@ impl FlightService {
             debug!("Flight not found: {}", flight_number);
         }
 
-        flight
+        Ok(flight)
     }
 
     /// List all flights with optional filters
@@ -120,44 +276,189 @@ impl FlightService {
         departure: Option<String>,
         arrival: Option<String>,
         date: Option<DateTime<Utc>>,
-    ) -> Vec<Flight> {
-        let flights = self.flights.read().await;
+    ) -> Result<Vec<Flight>, StorageError> {
+        let flights = self.store.list().await?;
+        let total_flights = flights.len();
 
         let filtered_flights: Vec<Flight> = flights
-            .values()
+            .into_iter()
             .filter(|flight| flight.matches_filters(departure.as_deref(), arrival.as_deref(), date))
-            .cloned()
             .collect();
 
         info!(
-            total_flights = flights.len(),
+            total_flights = total_flights,
             filtered_flights = filtered_flights.len(),
             "Listed flights with filters"
         );
 
-        filtered_flights
+        Ok(filtered_flights)
     }
 
-    /// Calculate flight position using orbital beacon service
+    /// Replace the schedule of a flight
+    #[instrument(skip(self, request))]
+    pub async fn update_flight(
+        &self,
+        flight_number: &str,
+        request: UpdateFlightRequest,
+    ) -> Result<Flight, FlightServiceError> {
+        let airport_errors = self
+            .check_airports(Some(&request.departure), Some(&request.arrival))
+            .await?;
+
+        let _guard = self.write_lock.lock().await;
+        let mut flight = self.load_flight(flight_number).await?;
+
+        if !flight.status.allows_schedule_changes() {
+            warn!(status = %flight.status, "Rejected schedule change");
+            return Err(FlightServiceError::ScheduleLocked(flight.status));
+        }
+
+        flight.aircraft_number = request.aircraft_number;
+        flight.departure = request.departure;
+        flight.arrival = request.arrival;
+        flight.departure_time = request.departure_time;
+        flight.arrival_time = request.arrival_time;
+        validate(&flight, &airport_errors)?;
+
+        self.store.update(flight.clone()).await?;
+        info!(flight_number = %flight_number, "Flight updated successfully");
+
+        Ok(flight)
+    }
+
+    /// Partially update a flight, including status changes
+    #[instrument(skip(self, request))]
+    pub async fn patch_flight(
+        &self,
+        flight_number: &str,
+        request: PatchFlightRequest,
+    ) -> Result<Flight, FlightServiceError> {
+        // Only airports the patch changes are looked up
+        let airport_errors = self
+            .check_airports(request.departure.as_ref(), request.arrival.as_ref())
+            .await?;
+
+        let _guard = self.write_lock.lock().await;
+        let mut flight = self.load_flight(flight_number).await?;
+
+        let changes_schedule = request.aircraft_number.is_some()
+            || request.departure.is_some()
+            || request.arrival.is_some()
+            || request.departure_time.is_some()
+            || request.arrival_time.is_some();
+        if changes_schedule && !flight.status.allows_schedule_changes() {
+            warn!(status = %flight.status, "Rejected schedule change");
+            return Err(FlightServiceError::ScheduleLocked(flight.status));
+        }
+
+        if let Some(status) = request.status {
+            if !flight.status.can_transition_to(status) {
+                warn!(from = %flight.status, to = %status, "Rejected status transition");
+                return Err(FlightServiceError::InvalidTransition {
+                    from: flight.status,
+                    to: status,
+                });
+            }
+            flight.status = status;
+        }
+
+        if let Some(aircraft_number) = request.aircraft_number {
+            flight.aircraft_number = aircraft_number;
+        }
+        if let Some(departure) = request.departure {
+            flight.departure = departure;
+        }
+        if let Some(arrival) = request.arrival {
+            flight.arrival = arrival;
+        }
+        if let Some(departure_time) = request.departure_time {
+            flight.departure_time = departure_time;
+        }
+        if let Some(arrival_time) = request.arrival_time {
+            flight.arrival_time = Some(arrival_time);
+        }
+        if changes_schedule {
+            validate(&flight, &airport_errors)?;
+        }
+
+        self.store.update(flight.clone()).await?;
+        info!(
+            flight_number = %flight_number,
+            status = %flight.status,
+            "Flight patched successfully"
+        );
+
+        Ok(flight)
+    }
+
+    /// Delete a flight
     #[instrument(skip(self))]
-    pub async fn calculate_flight_position(
+    pub async fn delete_flight(&self, flight_number: &str) -> Result<(), FlightServiceError> {
+        let _guard = self.write_lock.lock().await;
+
+        if self.store.delete(&flight_key(flight_number)).await? {
+            info!(flight_number = %flight_number, "Flight deleted successfully");
+            Ok(())
+        } else {
+            debug!("Flight not found: {}", flight_number);
+            Err(FlightServiceError::NotFound(flight_number.to_string()))
+        }
+    }
+
+    /// Look up the given airports in the directory, if one is configured.
+    ///
+    /// Runs before the write lock is taken so a slow airport service does not
+    /// hold up unrelated flight writes.
+    async fn check_airports(
         &self,
-        flight: &Flight,
-    ) -> Result<(f64, f64, DateTime<Utc>), String> {
-        // Use the correct environment variable name matching the compose file
-        let orbital_beacon_url = std::env::var("ORBITAL_BEACON_BASE_URL")
-            .unwrap_or_else(|_| "http://orbital-beacon:3002".to_string());
+        departure: Option<&IataCode>,
+        arrival: Option<&IataCode>,
+    ) -> Result<Vec<FieldError>, FlightServiceError> {
+        let mut errors = Vec::new();
+        let Some(airports) = &self.airports else {
+            return Ok(errors);
+        };
 
-        debug!(url = %orbital_beacon_url, "Using orbital beacon URL");
+        for (field, code) in [("departure", departure), ("arrival", arrival)] {
+            let Some(code) = code else {
+                continue;
+            };
+            // An arrival equal to the departure is rejected by the schedule check
+            if field == "arrival" && departure == Some(code) {
+                continue;
+            }
+            if !airports.contains(code.as_str()).await? {
+                errors.push(FieldError::new(
+                    field,
+                    "UNKNOWN_AIRPORT",
+                    format!("Unknown airport {}", code),
+                ));
+            }
+        }
+
+        Ok(errors)
+    }
+
+    async fn load_flight(&self, flight_number: &str) -> Result<Flight, FlightServiceError> {
+        self.store
+            .get(&flight_key(flight_number))
+            .await?
+            .ok_or_else(|| FlightServiceError::NotFound(flight_number.to_string()))
+    }
+
+    /// Calculate flight position using orbital beacon service
+    #[instrument(skip(self))]
+    pub async fn calculate_flight_position(&self, flight: &Flight) -> Result<Position, String> {
+        debug!(url = %self.satellites.base_url(), "Using orbital beacon URL");
 
         let arrival_time = flight.get_arrival_time();
 
         let position_request = CalculatePositionRequest {
-            departure: flight.departure.clone(),
-            arrival: flight.arrival.clone(),
+            departure: flight.departure.to_string(),
+            arrival: flight.arrival.to_string(),
             departure_time: flight.departure_time,
             arrival_time,
-            current_time: Some(Utc::now()),
+            current_time: Some(self.clock.now()),
         };
 
         debug!(
@@ -169,89 +470,165 @@ impl FlightService {
             "Sending position calculation request"
         );
 
-        // Serialize JSON manually for reqwest_middleware compatibility
-        let json_body = match serde_json::to_string(&position_request) {
-            Ok(body) => body,
+        let position_data = match self.satellites.calculate_position(&position_request).await {
+            Ok(position_data) => position_data,
+            Err(e) if e.is_unavailable() => {
+                error!(error = %e, "Failed to connect to orbital beacon");
+                return Err(format!("Failed to connect to orbital beacon: {}", e));
+            }
             Err(e) => {
-                error!(error = %e, "Failed to serialize position request");
-                return Err(format!("Failed to serialize request: {}", e));
+                error!(error = %e, "Orbital beacon returned error");
+                return Err(format!("Orbital beacon error: {}", e));
             }
         };
 
-        // Use the orbital beacon position API path constant
-        let full_url = format!("{}{}", orbital_beacon_url, SATELLITES_POSITION_API_PATH);
+        info!(
+            positions_count = position_data.positions.len(),
+            "Received position data from orbital beacon"
+        );
 
-        info!(url = %full_url, "Sending position request to orbital beacon");
+        if let Some(position) = position_data.positions.into_iter().next() {
+            info!(
+                flight_number = %flight.flight_number,
+                latitude = position.latitude,
+                longitude = position.longitude,
+                altitude = position.altitude,
+                phase = ?position.phase,
+                satellite_id = %position.satellite_id,
+                "Successfully calculated flight position"
+            );
+            Ok(position)
+        } else {
+            warn!("No position data available from orbital beacon");
+            Err("No position data available".to_string())
+        }
+    }
 
-        match self
-            .http_client
-            .post(&full_url)
-            .header("Content-Type", "application/json")
-            .body(json_body)
-            .send()
-            .await
-        {
-            Ok(response) => {
-                let status = response.status();
-                info!(status = %status, "Received response from orbital beacon");
-
-                if response.status().is_success() {
-                    match response.json::<CalculatePositionResponse>().await {
-                        Ok(position_data) => {
-                            info!(
-                                positions_count = position_data.positions.len(),
-                                "Received position data from orbital beacon"
-                            );
-
-                            if let Some(position) = position_data.positions.first() {
-                                info!(
-                                    flight_number = %flight.flight_number,
-                                    latitude = position.latitude,
-                                    longitude = position.longitude,
-                                    altitude = position.altitude,
-                                    satellite_id = %position.satellite_id,
-                                    "Successfully calculated flight position"
-                                );
-
-                                Ok((position.latitude, position.longitude, position.timestamp))
-                            } else {
-                                warn!("No position data available from orbital beacon");
-                                Err("No position data available".to_string())
-                            }
-                        }
-                        Err(e) => {
-                            error!(error = %e, "Failed to parse position response");
-                            Err(format!("Failed to parse position response: {}", e))
-                        }
-                    }
-                } else {
-                    let error_text = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "Unknown error".to_string());
-                    error!(
-                        status = %status,
-                        error = %error_text,
-                        "Orbital beacon returned error"
-                    );
-                    Err(format!("Orbital beacon error ({}): {}", status, error_text))
+    /// Sequence following the last one allocated for `airline`
+    async fn next_sequence(
+        &self,
+        sequences: &HashMap<String, u32>,
+        airline: &Airline,
+    ) -> Result<u32, FlightServiceError> {
+        let sequence = match sequences.get(&airline.iata) {
+            Some(sequence) => *sequence,
+            None => self.highest_sequence(airline).await?,
+        } + 1;
+
+        if sequence > MAX_FLIGHT_SEQUENCE {
+            error!(airline = %airline.iata, "Flight numbers exhausted");
+            return Err(FlightServiceError::FlightNumbersExhausted(
+                airline.iata.clone(),
+            ));
+        }
+        Ok(sequence)
+    }
+
+    /// Store `flight` under the flight number of `sequence` or the next free
+    /// one of `airline`.
+    ///
+    /// Numbers are claimed by inserting; one taken concurrently by another
+    /// instance sharing the store makes the insert fail and the next number is
+    /// tried.
+    async fn allocate_flight(
+        &self,
+        sequences: &mut HashMap<String, u32>,
+        airline: &Airline,
+        mut sequence: u32,
+        mut flight: Flight,
+    ) -> Result<Flight, FlightServiceError> {
+        loop {
+            flight.flight_number = airline.flight_number(sequence)?;
+            match self.store.insert(flight.clone()).await {
+                Ok(()) => {
+                    sequences.insert(airline.iata.clone(), sequence);
+                    return Ok(flight);
+                }
+                Err(StorageError::AlreadyExists(number)) => {
+                    debug!(flight_number = %number, "Flight number taken, trying next");
+                }
+                Err(e) => {
+                    error!(error = %e, "Failed to store flight");
+                    return Err(e.into());
                 }
             }
-            Err(e) => {
-                error!(error = %e, url = %full_url, "Failed to connect to orbital beacon");
-                Err(format!("Failed to connect to orbital beacon: {}", e))
+
+            sequence += 1;
+            if sequence > MAX_FLIGHT_SEQUENCE {
+                error!(airline = %airline.iata, "Flight numbers exhausted");
+                return Err(FlightServiceError::FlightNumbersExhausted(
+                    airline.iata.clone(),
+                ));
             }
         }
     }
 
-    /// Generate a unique flight number based on departure airport
-    async fn generate_flight_number(&self, departure: &str) -> String {
-        let flights = self.flights.read().await;
-        let count = flights.len() as u32;
-        format!("{}{:04}", departure.to_uppercase(), count + 1)
+    /// Highest sequence of `airline` already in the store
+    async fn highest_sequence(&self, airline: &Airline) -> Result<u32, StorageError> {
+        let flights = self.store.list().await?;
+        Ok(flights
+            .iter()
+            .filter_map(|flight| airline.sequence_of(flight.flight_number.as_str()))
+            .max()
+            .unwrap_or(0))
+    }
+}
+
+/// Flight counts of the process, reported by gauges registered once
+struct FlightGauges {
+    scheduled: Arc<AtomicU64>,
+    airborne: Arc<AtomicU64>,
+}
+
+impl FlightGauges {
+    fn global() -> &'static FlightGauges {
+        static GAUGES: OnceLock<FlightGauges> = OnceLock::new();
+        GAUGES.get_or_init(|| {
+            let metrics = Metrics::global();
+            FlightGauges {
+                scheduled: metrics.service_gauge(
+                    "flight-controller",
+                    "sky_tracer.flights.scheduled",
+                    "{flight}",
+                    "Flights scheduled or boarding",
+                ),
+                airborne: metrics.service_gauge(
+                    "flight-controller",
+                    "sky_tracer.flights.airborne",
+                    "{flight}",
+                    "Flights departed or en route",
+                ),
+            }
+        })
     }
 }
 
+/// Check the schedule together with the airport errors found before the
+/// write lock was taken
+fn validate(flight: &Flight, airport_errors: &[FieldError]) -> Result<(), FlightServiceError> {
+    let mut errors = validate_schedule(flight);
+    for error in airport_errors {
+        if !errors.iter().any(|e| e.field == error.field) {
+            errors.push(error.clone());
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        warn!(errors = %describe_field_errors(&errors), "Rejected invalid flight");
+        Err(FlightServiceError::Validation(errors))
+    }
+}
+
+/// Key the flight with `flight_number` from a request is stored under.
+/// Numbers that do not parse are kept as given and match no flight.
+fn flight_key(flight_number: &str) -> String {
+    flight_number
+        .parse::<FlightNumber>()
+        .map_or_else(|_| flight_number.to_string(), String::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,11 +637,13 @@ mod tests {
     async fn test_create_flight() {
         let service = FlightService::new();
         let request = CreateFlightRequest {
-            aircraft_number: "D-ABCD".to_string(),
-            departure: "FRA".to_string(),
-            arrival: "LIS".to_string(),
+            aircraft_number: "D-ABCD".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "LIS".parse().unwrap(),
             departure_time: Utc::now(),
             arrival_time: None,
+            airline: None,
+            flight_number: None,
         };
 
         let result = service.create_flight(request).await;
@@ -274,28 +653,46 @@ mod tests {
         assert_eq!(flight.aircraft_number, "D-ABCD");
         assert_eq!(flight.departure, "FRA");
         assert_eq!(flight.arrival, "LIS");
-        assert_eq!(flight.flight_number, "FRA0001");
+        assert_eq!(flight.flight_number, "LH1");
     }
 
     #[tokio::test]
     async fn test_get_flight() {
         let service = FlightService::new();
         let request = CreateFlightRequest {
-            aircraft_number: "D-ABCD".to_string(),
-            departure: "FRA".to_string(),
-            arrival: "LIS".to_string(),
+            aircraft_number: "D-ABCD".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "LIS".parse().unwrap(),
             departure_time: Utc::now(),
             arrival_time: None,
+            airline: None,
+            flight_number: None,
         };
 
         let created_flight = service.create_flight(request).await.unwrap();
-        let retrieved_flight = service.get_flight(&created_flight.flight_number).await;
+        let retrieved_flight = service
+            .get_flight(created_flight.flight_number.as_str())
+            .await
+            .unwrap();
 
         assert!(retrieved_flight.is_some());
         assert_eq!(
             retrieved_flight.unwrap().flight_number,
             created_flight.flight_number
         );
+
+        // Flight numbers from paths are normalized like stored ones
+        let spelled = format!(
+            "{} 0{}",
+            created_flight.flight_number.designator().to_lowercase(),
+            created_flight.flight_number.number()
+        );
+        let retrieved_flight = service.get_flight(&spelled).await.unwrap();
+        assert_eq!(
+            retrieved_flight.map(|flight| flight.flight_number),
+            Some(created_flight.flight_number.clone())
+        );
+        assert!(service.get_flight("not a flight").await.unwrap().is_none());
     }
 
     #[tokio::test]
@@ -304,43 +701,378 @@ mod tests {
 
         // Create test flights
         let request1 = CreateFlightRequest {
-            aircraft_number: "D-ABCD".to_string(),
-            departure: "FRA".to_string(),
-            arrival: "LIS".to_string(),
+            aircraft_number: "D-ABCD".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "LIS".parse().unwrap(),
             departure_time: Utc::now(),
             arrival_time: None,
+            airline: None,
+            flight_number: None,
         };
 
         let request2 = CreateFlightRequest {
-            aircraft_number: "D-EFGH".to_string(),
-            departure: "CDG".to_string(),
-            arrival: "MAD".to_string(),
+            aircraft_number: "D-EFGH".parse().unwrap(),
+            departure: "CDG".parse().unwrap(),
+            arrival: "MAD".parse().unwrap(),
             departure_time: Utc::now(),
             arrival_time: None,
+            airline: None,
+            flight_number: None,
         };
 
         service.create_flight(request1).await.unwrap();
         service.create_flight(request2).await.unwrap();
 
         // Test no filters
-        let all_flights = service.list_flights(None, None, None).await;
+        let all_flights = service.list_flights(None, None, None).await.unwrap();
         assert_eq!(all_flights.len(), 2);
 
         // Test departure filter
         let fra_flights = service
             .list_flights(Some("FRA".to_string()), None, None)
-            .await;
+            .await
+            .unwrap();
         assert_eq!(fra_flights.len(), 1);
         assert_eq!(fra_flights[0].departure, "FRA");
 
         // Test arrival filter
         let lis_flights = service
             .list_flights(None, Some("LIS".to_string()), None)
-            .await;
+            .await
+            .unwrap();
         assert_eq!(lis_flights.len(), 1);
         assert_eq!(lis_flights[0].arrival, "LIS");
     }
 
+    #[tokio::test]
+    async fn test_flights_persist_across_service_restarts() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let request = CreateFlightRequest {
+            aircraft_number: "D-ABCD".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "LIS".parse().unwrap(),
+            departure_time: Utc::now(),
+            arrival_time: None,
+            airline: None,
+            flight_number: None,
+        };
+
+        let service = FlightService::with_store(Arc::new(
+            crate::storage::SqliteFlightStore::open(file.path()).unwrap(),
+        ));
+        let created_flight = service.create_flight(request.clone()).await.unwrap();
+        drop(service);
+
+        let service = FlightService::with_store(Arc::new(
+            crate::storage::SqliteFlightStore::open(file.path()).unwrap(),
+        ));
+        let retrieved_flight = service
+            .get_flight(created_flight.flight_number.as_str())
+            .await
+            .unwrap();
+        assert_eq!(retrieved_flight, Some(created_flight));
+
+        // Flight numbers continue where the previous instance stopped
+        let next_flight = service.create_flight(request).await.unwrap();
+        assert_eq!(next_flight.flight_number, "LH2");
+    }
+
+    fn lisbon_request() -> CreateFlightRequest {
+        CreateFlightRequest {
+            aircraft_number: "D-ABCD".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "LIS".parse().unwrap(),
+            departure_time: Utc::now(),
+            arrival_time: None,
+            airline: None,
+            flight_number: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_patch_flight_status_lifecycle() {
+        let service = FlightService::new();
+        let flight = service.create_flight(lisbon_request()).await.unwrap();
+        assert_eq!(flight.status, FlightStatus::Scheduled);
+
+        for status in [
+            FlightStatus::Boarding,
+            FlightStatus::Departed,
+            FlightStatus::EnRoute,
+            FlightStatus::Landed,
+        ] {
+            let patch = PatchFlightRequest {
+                status: Some(status),
+                ..Default::default()
+            };
+            let patched = service
+                .patch_flight(flight.flight_number.as_str(), patch)
+                .await
+                .unwrap();
+            assert_eq!(patched.status, status);
+        }
+
+        let patch = PatchFlightRequest {
+            status: Some(FlightStatus::Cancelled),
+            ..Default::default()
+        };
+        let result = service
+            .patch_flight(flight.flight_number.as_str(), patch)
+            .await;
+        assert!(matches!(
+            result,
+            Err(FlightServiceError::InvalidTransition {
+                from: FlightStatus::Landed,
+                to: FlightStatus::Cancelled
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_flight_rejected_after_departure() {
+        let service = FlightService::new();
+        let flight = service.create_flight(lisbon_request()).await.unwrap();
+
+        let update = UpdateFlightRequest {
+            aircraft_number: "D-EFGH".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "OPO".parse().unwrap(),
+            departure_time: flight.departure_time,
+            arrival_time: None,
+        };
+        let updated = service
+            .update_flight(flight.flight_number.as_str(), update.clone())
+            .await
+            .unwrap();
+        assert_eq!(updated.arrival, "OPO");
+        assert_eq!(updated.aircraft_number, "D-EFGH");
+
+        for status in [FlightStatus::Boarding, FlightStatus::Departed] {
+            let patch = PatchFlightRequest {
+                status: Some(status),
+                ..Default::default()
+            };
+            service
+                .patch_flight(flight.flight_number.as_str(), patch)
+                .await
+                .unwrap();
+        }
+
+        let result = service
+            .update_flight(flight.flight_number.as_str(), update)
+            .await;
+        assert!(matches!(
+            result,
+            Err(FlightServiceError::ScheduleLocked(FlightStatus::Departed))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_flight_numbers_stay_unique_after_delete() {
+        let service = FlightService::new();
+        let first = service.create_flight(lisbon_request()).await.unwrap();
+        let second = service.create_flight(lisbon_request()).await.unwrap();
+
+        service
+            .delete_flight(first.flight_number.as_str())
+            .await
+            .unwrap();
+        assert!(matches!(
+            service.delete_flight(first.flight_number.as_str()).await,
+            Err(FlightServiceError::NotFound(_))
+        ));
+
+        // The next number must not collide with the remaining flight
+        let third = service.create_flight(lisbon_request()).await.unwrap();
+        assert_ne!(third.flight_number, second.flight_number);
+        assert_eq!(
+            service.list_flights(None, None, None).await.unwrap().len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flight_numbers_are_allocated_per_airline() {
+        let service = FlightService::new();
+        let tap = CreateFlightRequest {
+            airline: Some("TAP".to_string()),
+            ..lisbon_request()
+        };
+
+        let first = service.create_flight(tap.clone()).await.unwrap();
+        let second = service.create_flight(tap).await.unwrap();
+        let default = service.create_flight(lisbon_request()).await.unwrap();
+
+        assert_eq!(first.flight_number, "TP1");
+        assert_eq!(second.flight_number, "TP2");
+        assert_eq!(default.flight_number, "LH1");
+    }
+
+    #[tokio::test]
+    async fn test_explicit_flight_number() {
+        let service = FlightService::new();
+        let request = CreateFlightRequest {
+            flight_number: Some("dlh 400".parse().unwrap()),
+            ..lisbon_request()
+        };
+
+        let flight = service.create_flight(request.clone()).await.unwrap();
+        assert_eq!(flight.flight_number, "LH400");
+
+        let result = service.create_flight(request).await;
+        assert!(matches!(
+            result,
+            Err(FlightServiceError::AlreadyExists(number)) if number == "LH400"
+        ));
+
+        let mismatch = CreateFlightRequest {
+            airline: Some("TP".to_string()),
+            flight_number: Some("LH401".parse().unwrap()),
+            ..lisbon_request()
+        };
+        assert!(matches!(
+            service.create_flight(mismatch).await,
+            Err(FlightServiceError::AirlineMismatch { .. })
+        ));
+
+        let unknown = CreateFlightRequest {
+            airline: Some("XX".to_string()),
+            ..lisbon_request()
+        };
+        assert!(matches!(
+            service.create_flight(unknown).await,
+            Err(FlightServiceError::Airline(AirlineError::UnknownAirline(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_allocation_skips_numbers_taken_elsewhere() {
+        let store = Arc::new(InMemoryFlightStore::new());
+        let service = FlightService::with_store(store.clone());
+        service.create_flight(lisbon_request()).await.unwrap();
+
+        // Another instance sharing the store claims the next number
+        let other = FlightService::with_store(store);
+        let explicit = CreateFlightRequest {
+            flight_number: Some("LH2".parse().unwrap()),
+            ..lisbon_request()
+        };
+        other.create_flight(explicit).await.unwrap();
+
+        let flight = service.create_flight(lisbon_request()).await.unwrap();
+        assert_eq!(flight.flight_number, "LH3");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_creation_allocates_unique_numbers() {
+        let service = FlightService::new();
+
+        let handles: Vec<_> = (0..20)
+            .map(|_| {
+                let service = service.clone();
+                tokio::spawn(async move { service.create_flight(lisbon_request()).await })
+            })
+            .collect();
+
+        let mut numbers = std::collections::HashSet::new();
+        for handle in handles {
+            let flight = handle.await.unwrap().unwrap();
+            assert!(numbers.insert(flight.flight_number));
+        }
+        assert_eq!(numbers.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_updates_are_validated() {
+        let service = FlightService::new()
+            .with_airport_directory(Arc::new(StaticAirportDirectory::new(["FRA", "LIS"])));
+        let flight = service.create_flight(lisbon_request()).await.unwrap();
+
+        let patch = PatchFlightRequest {
+            arrival: Some("OPO".parse().unwrap()),
+            arrival_time: Some(flight.departure_time),
+            ..Default::default()
+        };
+        let result = service
+            .patch_flight(flight.flight_number.as_str(), patch)
+            .await;
+        let Err(FlightServiceError::Validation(errors)) = result else {
+            panic!("expected validation error, got {:?}", result);
+        };
+        let codes: Vec<_> = errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, vec!["ZERO_DURATION", "UNKNOWN_AIRPORT"]);
+
+        // The stored flight is unchanged
+        let stored = service
+            .get_flight(flight.flight_number.as_str())
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(flight));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_airport_service_rejects_creation() {
+        let service = FlightService::new().with_airport_directory(Arc::new(
+            HttpAirportDirectory::new("http://127.0.0.1:9".to_string()),
+        ));
+
+        let result = service.create_flight(lisbon_request()).await;
+        assert!(matches!(result, Err(FlightServiceError::AirportLookup(_))));
+        assert!(service
+            .list_flights(None, None, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Directory that never answers for OPO
+    struct StalledAirportDirectory;
+
+    #[async_trait::async_trait]
+    impl AirportDirectory for StalledAirportDirectory {
+        async fn contains(&self, code: &str) -> Result<bool, AirportLookupError> {
+            if code == "OPO" {
+                std::future::pending::<()>().await;
+            }
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_airport_lookup_does_not_block_other_writes() {
+        let service =
+            FlightService::new().with_airport_directory(Arc::new(StalledAirportDirectory));
+        let flight = service.create_flight(lisbon_request()).await.unwrap();
+
+        let stalled = tokio::spawn({
+            let service = service.clone();
+            async move {
+                let request = CreateFlightRequest {
+                    arrival: "OPO".parse().unwrap(),
+                    ..lisbon_request()
+                };
+                service.create_flight(request).await
+            }
+        });
+        tokio::task::yield_now().await;
+
+        // A status change does not touch the route and needs no lookup
+        let patch = PatchFlightRequest {
+            status: Some(FlightStatus::Boarding),
+            ..Default::default()
+        };
+        let patched = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            service.patch_flight(flight.flight_number.as_str(), patch),
+        )
+        .await
+        .expect("patch waited for the stalled airport lookup")
+        .unwrap();
+        assert_eq!(patched.status, FlightStatus::Boarding);
+
+        stalled.abort();
+    }
+
     #[test]
     fn test_api_path_constants() {
         // Verify we're using the correct API paths
@@ -0,0 +1,61 @@
+use super::{FlightStore, StorageError};
+use crate::models::Flight;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Volatile flight storage, lost on restart
+#[derive(Clone, Default)]
+pub struct InMemoryFlightStore {
+    flights: Arc<RwLock<HashMap<String, Flight>>>,
+}
+
+impl InMemoryFlightStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl FlightStore for InMemoryFlightStore {
+    async fn insert(&self, flight: Flight) -> Result<(), StorageError> {
+        let mut flights = self.flights.write().await;
+        if flights.contains_key(flight.flight_number.as_str()) {
+            return Err(StorageError::AlreadyExists(flight.flight_number.into()));
+        }
+        flights.insert(flight.flight_number.to_string(), flight);
+        Ok(())
+    }
+
+    async fn update(&self, flight: Flight) -> Result<(), StorageError> {
+        let mut flights = self.flights.write().await;
+        match flights.get_mut(flight.flight_number.as_str()) {
+            Some(existing) => {
+                *existing = flight;
+                Ok(())
+            }
+            None => Err(StorageError::NotFound(flight.flight_number.into())),
+        }
+    }
+
+    async fn delete(&self, flight_number: &str) -> Result<bool, StorageError> {
+        let mut flights = self.flights.write().await;
+        Ok(flights.remove(flight_number).is_some())
+    }
+
+    async fn get(&self, flight_number: &str) -> Result<Option<Flight>, StorageError> {
+        let flights = self.flights.read().await;
+        Ok(flights.get(flight_number).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Flight>, StorageError> {
+        let flights = self.flights.read().await;
+        Ok(flights.values().cloned().collect())
+    }
+
+    async fn count(&self) -> Result<usize, StorageError> {
+        let flights = self.flights.read().await;
+        Ok(flights.len())
+    }
+}
@@ -0,0 +1,79 @@
+//! Flight persistence backends.
+//!
+//! The backend is selected with `FLIGHT_STORAGE` (`memory` or `sqlite`, default
+//! `memory`). The SQLite database file is configured with `FLIGHT_DATABASE_PATH`.
+
+mod memory;
+mod sqlite;
+
+pub use memory::InMemoryFlightStore;
+pub use sqlite::SqliteFlightStore;
+
+use crate::models::Flight;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::info;
+
+/// Environment variable selecting the storage backend
+pub const FLIGHT_STORAGE_ENV: &str = "FLIGHT_STORAGE";
+/// Environment variable with the SQLite database path
+pub const FLIGHT_DATABASE_PATH_ENV: &str = "FLIGHT_DATABASE_PATH";
+/// Database path used when `FLIGHT_DATABASE_PATH` is not set
+pub const DEFAULT_DATABASE_PATH: &str = "flights.db";
+
+#[derive(thiserror::Error, Debug)]
+pub enum StorageError {
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Flight already exists: {0}")]
+    AlreadyExists(String),
+    #[error("Flight not found: {0}")]
+    NotFound(String),
+    #[error("Invalid stored data: {0}")]
+    InvalidData(String),
+    #[error("Storage task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+    #[error("Unknown storage backend: {0}")]
+    UnknownBackend(String),
+}
+
+/// Storage for flights, keyed by flight number
+#[async_trait]
+pub trait FlightStore: Send + Sync {
+    /// Store a new flight, failing if the flight number is taken
+    async fn insert(&self, flight: Flight) -> Result<(), StorageError>;
+
+    /// Replace an existing flight with the same flight number
+    async fn update(&self, flight: Flight) -> Result<(), StorageError>;
+
+    /// Remove a flight, returning whether it existed
+    async fn delete(&self, flight_number: &str) -> Result<bool, StorageError>;
+
+    /// Get a flight by its flight number
+    async fn get(&self, flight_number: &str) -> Result<Option<Flight>, StorageError>;
+
+    /// List all stored flights
+    async fn list(&self) -> Result<Vec<Flight>, StorageError>;
+
+    /// Number of stored flights
+    async fn count(&self) -> Result<usize, StorageError>;
+}
+
+/// Create the storage backend configured in the environment
+pub fn from_env() -> Result<Arc<dyn FlightStore>, StorageError> {
+    let backend = std::env::var(FLIGHT_STORAGE_ENV).unwrap_or_else(|_| "memory".to_string());
+
+    match backend.to_lowercase().as_str() {
+        "memory" => {
+            info!("Using in-memory flight storage");
+            Ok(Arc::new(InMemoryFlightStore::new()))
+        }
+        "sqlite" => {
+            let path = std::env::var(FLIGHT_DATABASE_PATH_ENV)
+                .unwrap_or_else(|_| DEFAULT_DATABASE_PATH.to_string());
+            info!(path = %path, "Using SQLite flight storage");
+            Ok(Arc::new(SqliteFlightStore::open(path)?))
+        }
+        other => Err(StorageError::UnknownBackend(other.to_string())),
+    }
+}
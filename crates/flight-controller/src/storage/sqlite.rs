@@ -0,0 +1,500 @@
+use super::{FlightStore, StorageError};
+use crate::models::Flight;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension, Row};
+use sky_tracer::model::{AircraftRegistration, FlightNumber, IataCode, IdentifierError};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+/// Schema migrations, applied in order. The index of the last applied
+/// migration plus one is tracked in SQLite's `user_version` pragma.
+/// Append new migrations to the end, never edit existing ones.
+const MIGRATIONS: &[Migration] = &[
+    Migration::Sql(
+        "CREATE TABLE flights (
+        flight_number TEXT PRIMARY KEY NOT NULL,
+        aircraft_number TEXT NOT NULL,
+        departure TEXT NOT NULL,
+        arrival TEXT NOT NULL,
+        departure_time TEXT NOT NULL,
+        arrival_time TEXT
+    );
+    CREATE INDEX idx_flights_departure ON flights (departure);
+    CREATE INDEX idx_flights_arrival ON flights (arrival);",
+    ),
+    Migration::Sql("ALTER TABLE flights ADD COLUMN status TEXT NOT NULL DEFAULT 'Scheduled';"),
+    Migration::Sql(
+        "CREATE TABLE rejected_flights (
+        flight_number TEXT NOT NULL,
+        aircraft_number TEXT NOT NULL,
+        departure TEXT NOT NULL,
+        arrival TEXT NOT NULL,
+        departure_time TEXT NOT NULL,
+        arrival_time TEXT,
+        status TEXT NOT NULL,
+        reason TEXT NOT NULL
+    );",
+    ),
+    Migration::Data(normalize_identifiers),
+];
+
+/// Step from one schema version to the next
+enum Migration {
+    /// Statements changing the schema
+    Sql(&'static str),
+    /// Rewrite of stored rows that SQL cannot express
+    Data(fn(&Connection) -> Result<(), StorageError>),
+}
+
+impl Migration {
+    fn apply(&self, connection: &Connection) -> Result<(), StorageError> {
+        match self {
+            Self::Sql(statements) => Ok(connection.execute_batch(statements)?),
+            Self::Data(rewrite) => rewrite(connection),
+        }
+    }
+}
+
+const FLIGHT_COLUMNS: &str =
+    "flight_number, aircraft_number, departure, arrival, departure_time, arrival_time, status";
+
+/// Flight storage in an embedded SQLite database file
+#[derive(Clone)]
+pub struct SqliteFlightStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteFlightStore {
+    /// Open (or create) the database at `path` and apply pending migrations
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let connection = Connection::open(path)?;
+        Self::from_connection(connection)
+    }
+
+    /// Open a private in-memory database, mainly useful for tests
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(mut connection: Connection) -> Result<Self, StorageError> {
+        migrate(&mut connection)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Run a blocking database operation off the async runtime
+    async fn with_connection<T, F>(&self, operation: F) -> Result<T, StorageError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, StorageError> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().unwrap_or_else(|e| e.into_inner());
+            operation(&connection)
+        })
+        .await?
+    }
+}
+
+/// Apply all migrations newer than the database's schema version
+fn migrate(connection: &mut Connection) -> Result<(), StorageError> {
+    let version: usize =
+        connection.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))? as usize;
+
+    if version > MIGRATIONS.len() {
+        return Err(StorageError::InvalidData(format!(
+            "database schema version {} is newer than supported version {}",
+            version,
+            MIGRATIONS.len()
+        )));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let transaction = connection.transaction()?;
+        migration.apply(&transaction)?;
+        transaction.pragma_update(None, "user_version", (index + 1) as i64)?;
+        transaction.commit()?;
+        info!(version = index + 1, "Applied flight storage migration");
+    }
+
+    debug!(
+        version = MIGRATIONS.len(),
+        "Flight storage schema up to date"
+    );
+    Ok(())
+}
+
+fn flight_from_row(row: &Row<'_>) -> rusqlite::Result<Flight> {
+    Ok(Flight {
+        flight_number: identifier_column(row, 0)?,
+        aircraft_number: identifier_column(row, 1)?,
+        departure: identifier_column(row, 2)?,
+        arrival: identifier_column(row, 3)?,
+        departure_time: row.get::<_, DateTime<Utc>>(4)?,
+        arrival_time: row.get::<_, Option<DateTime<Utc>>>(5)?,
+        status: row.get::<_, String>(6)?.parse().map_err(|e: String| {
+            rusqlite::Error::FromSqlConversionFailure(6, Type::Text, e.into())
+        })?,
+    })
+}
+
+/// Read a text column holding a validated identifier
+fn identifier_column<T>(row: &Row<'_>, index: usize) -> rusqlite::Result<T>
+where
+    T: FromStr<Err = IdentifierError>,
+{
+    row.get::<_, String>(index)?
+        .parse()
+        .map_err(|e: IdentifierError| {
+            rusqlite::Error::FromSqlConversionFailure(index, Type::Text, e.into())
+        })
+}
+
+/// Rewrite identifiers stored before they were validated into their
+/// normalized form, so lookups by the normalized flight number find them.
+/// Rows that do not parse, or collide with another row once normalized,
+/// are moved to `rejected_flights`.
+fn normalize_identifiers(connection: &Connection) -> Result<(), StorageError> {
+    let rows = connection
+        .prepare("SELECT rowid, flight_number, aircraft_number, departure, arrival FROM flights")?
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                [
+                    row.get::<_, String>(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ],
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (rowid, identifiers) in rows {
+        let reason = match normalized_identifiers(&identifiers) {
+            Ok(normalized) => match connection.execute(
+                "UPDATE flights SET flight_number = ?2, aircraft_number = ?3, departure = ?4,
+                    arrival = ?5
+                 WHERE rowid = ?1",
+                params![
+                    rowid,
+                    normalized[0],
+                    normalized[1],
+                    normalized[2],
+                    normalized[3]
+                ],
+            ) {
+                Ok(_) => continue,
+                Err(e) => match insert_error(e, normalized[0].clone()) {
+                    StorageError::AlreadyExists(_) => {
+                        format!("Duplicate of flight {}", normalized[0])
+                    }
+                    other => return Err(other),
+                },
+            },
+            Err(e) => e.to_string(),
+        };
+
+        warn!(flight_number = %identifiers[0], reason = %reason, "Rejected stored flight");
+        connection.execute(
+            &format!(
+                "INSERT INTO rejected_flights ({FLIGHT_COLUMNS}, reason)
+                 SELECT {FLIGHT_COLUMNS}, ?2 FROM flights WHERE rowid = ?1"
+            ),
+            params![rowid, reason],
+        )?;
+        connection.execute("DELETE FROM flights WHERE rowid = ?1", params![rowid])?;
+    }
+    Ok(())
+}
+
+/// Flight number, aircraft registration, departure and arrival as the
+/// identifier types normalize them
+fn normalized_identifiers(
+    [flight_number, aircraft_number, departure, arrival]: &[String; 4],
+) -> Result<[String; 4], IdentifierError> {
+    Ok([
+        flight_number.parse::<FlightNumber>()?.into(),
+        aircraft_number.parse::<AircraftRegistration>()?.into(),
+        departure.parse::<IataCode>()?.into(),
+        arrival.parse::<IataCode>()?.into(),
+    ])
+}
+
+/// Map unique key violations to [`StorageError::AlreadyExists`]
+fn insert_error(error: rusqlite::Error, flight_number: String) -> StorageError {
+    match error {
+        rusqlite::Error::SqliteFailure(ref failure, _)
+            if failure.code == ErrorCode::ConstraintViolation =>
+        {
+            StorageError::AlreadyExists(flight_number)
+        }
+        other => other.into(),
+    }
+}
+
+#[async_trait]
+impl FlightStore for SqliteFlightStore {
+    async fn insert(&self, flight: Flight) -> Result<(), StorageError> {
+        self.with_connection(move |connection| {
+            connection
+                .execute(
+                    &format!(
+                        "INSERT INTO flights ({FLIGHT_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+                    ),
+                    params![
+                        flight.flight_number.as_str(),
+                        flight.aircraft_number.as_str(),
+                        flight.departure.as_str(),
+                        flight.arrival.as_str(),
+                        flight.departure_time,
+                        flight.arrival_time,
+                        flight.status.as_str(),
+                    ],
+                )
+                .map_err(|e| insert_error(e, flight.flight_number.to_string()))?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn update(&self, flight: Flight) -> Result<(), StorageError> {
+        self.with_connection(move |connection| {
+            let updated = connection.execute(
+                "UPDATE flights SET aircraft_number = ?2, departure = ?3, arrival = ?4,
+                    departure_time = ?5, arrival_time = ?6, status = ?7
+                 WHERE flight_number = ?1",
+                params![
+                    flight.flight_number.as_str(),
+                    flight.aircraft_number.as_str(),
+                    flight.departure.as_str(),
+                    flight.arrival.as_str(),
+                    flight.departure_time,
+                    flight.arrival_time,
+                    flight.status.as_str(),
+                ],
+            )?;
+            if updated == 0 {
+                return Err(StorageError::NotFound(flight.flight_number.into()));
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, flight_number: &str) -> Result<bool, StorageError> {
+        let flight_number = flight_number.to_string();
+        self.with_connection(move |connection| {
+            let deleted = connection.execute(
+                "DELETE FROM flights WHERE flight_number = ?1",
+                params![flight_number],
+            )?;
+            Ok(deleted > 0)
+        })
+        .await
+    }
+
+    async fn get(&self, flight_number: &str) -> Result<Option<Flight>, StorageError> {
+        let flight_number = flight_number.to_string();
+        self.with_connection(move |connection| {
+            let flight = connection
+                .query_row(
+                    &format!("SELECT {FLIGHT_COLUMNS} FROM flights WHERE flight_number = ?1"),
+                    params![flight_number],
+                    flight_from_row,
+                )
+                .optional()?;
+            Ok(flight)
+        })
+        .await
+    }
+
+    async fn list(&self) -> Result<Vec<Flight>, StorageError> {
+        self.with_connection(|connection| {
+            let mut statement = connection.prepare(&format!(
+                "SELECT {FLIGHT_COLUMNS} FROM flights ORDER BY departure_time, flight_number"
+            ))?;
+            let flights = statement
+                .query_map([], flight_from_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(flights)
+        })
+        .await
+    }
+
+    async fn count(&self) -> Result<usize, StorageError> {
+        self.with_connection(|connection| {
+            let count: i64 =
+                connection.query_row("SELECT COUNT(*) FROM flights", [], |row| row.get(0))?;
+            Ok(count as usize)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use sky_tracer::model::FlightStatus;
+    use tempfile::NamedTempFile;
+
+    fn flight(flight_number: &str, departure: &str) -> Flight {
+        let departure_time = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+        Flight::new(
+            flight_number.parse().unwrap(),
+            "D-ABCD".parse().unwrap(),
+            departure.parse().unwrap(),
+            "LIS".parse().unwrap(),
+            departure_time,
+            Some(departure_time + Duration::hours(3)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_get() {
+        let store = SqliteFlightStore::open_in_memory().unwrap();
+        let expected = flight("LH1", "FRA");
+
+        store.insert(expected.clone()).await.unwrap();
+
+        let found = store.get("LH1").await.unwrap();
+        assert_eq!(
+            found.as_ref().map(|f| &f.flight_number),
+            Some(&expected.flight_number)
+        );
+        assert_eq!(found.unwrap().arrival_time, expected.arrival_time);
+        assert!(store.get("LH9999").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_flight_number_is_rejected() {
+        let store = SqliteFlightStore::open_in_memory().unwrap();
+        store.insert(flight("LH1", "FRA")).await.unwrap();
+
+        let result = store.insert(flight("LH1", "FRA")).await;
+        assert!(matches!(result, Err(StorageError::AlreadyExists(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete() {
+        let store = SqliteFlightStore::open_in_memory().unwrap();
+        let mut stored = flight("LH1", "FRA");
+        store.insert(stored.clone()).await.unwrap();
+
+        stored.status = FlightStatus::Boarding;
+        stored.arrival = "OPO".parse().unwrap();
+        store.update(stored.clone()).await.unwrap();
+
+        let found = store.get("LH1").await.unwrap().unwrap();
+        assert_eq!(found.status, FlightStatus::Boarding);
+        assert_eq!(found.arrival, "OPO");
+
+        assert!(store.delete("LH1").await.unwrap());
+        assert!(!store.delete("LH1").await.unwrap());
+        assert!(matches!(
+            store.update(stored).await,
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_existing_rows_are_migrated() {
+        let file = NamedTempFile::new().unwrap();
+        {
+            // Database created before the status column existed, holding
+            // identifiers stored before they were validated
+            let connection = Connection::open(file.path()).unwrap();
+            MIGRATIONS[0].apply(&connection).unwrap();
+            connection.pragma_update(None, "user_version", 1).unwrap();
+            connection
+                .execute_batch(
+                    "INSERT INTO flights (flight_number, aircraft_number, departure, arrival, departure_time)
+                     VALUES ('FRA0001', 'd-abcd', 'fra', 'LIS', '2025-06-01T10:00:00Z'),
+                            ('FRA0002', 'D-ABCD', 'Frankfurt', 'LIS', '2025-06-01T11:00:00Z'),
+                            ('CDG0005', 'F-GKXA', 'CDG', 'LIS', '2025-06-01T12:00:00Z'),
+                            ('CDG5', 'F-GKXA', 'CDG', 'OPO', '2025-06-01T12:00:00Z');",
+                )
+                .unwrap();
+        }
+
+        let store = SqliteFlightStore::open(file.path()).unwrap();
+        let flights = store.list().await.unwrap();
+        let numbers: Vec<_> = flights.iter().map(|f| f.flight_number.as_str()).collect();
+        assert_eq!(numbers, ["FRA1", "CDG5"]);
+
+        let mut flight = store.get("FRA1").await.unwrap().unwrap();
+        assert_eq!(flight.status, FlightStatus::Scheduled);
+        assert_eq!(flight.aircraft_number, "D-ABCD");
+        assert_eq!(flight.departure, "FRA");
+
+        flight.status = FlightStatus::Boarding;
+        store.update(flight).await.unwrap();
+        assert_eq!(
+            store.get("FRA1").await.unwrap().unwrap().status,
+            FlightStatus::Boarding
+        );
+
+        let connection = Connection::open(file.path()).unwrap();
+        let rejected: Vec<String> = connection
+            .prepare("SELECT flight_number FROM rejected_flights ORDER BY flight_number")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rejected, ["CDG0005", "FRA0002"]);
+    }
+
+    #[tokio::test]
+    async fn test_flights_survive_reopen() {
+        let file = NamedTempFile::new().unwrap();
+
+        {
+            let store = SqliteFlightStore::open(file.path()).unwrap();
+            store.insert(flight("LH1", "FRA")).await.unwrap();
+            store.insert(flight("AF2", "CDG")).await.unwrap();
+        }
+
+        let store = SqliteFlightStore::open(file.path()).unwrap();
+        assert_eq!(store.count().await.unwrap(), 2);
+
+        let flights = store.list().await.unwrap();
+        let numbers: Vec<_> = flights.iter().map(|f| f.flight_number.as_str()).collect();
+        assert_eq!(numbers, vec!["AF2", "LH1"]);
+    }
+
+    #[test]
+    fn test_migrations_are_idempotent() {
+        let file = NamedTempFile::new().unwrap();
+
+        SqliteFlightStore::open(file.path()).unwrap();
+        SqliteFlightStore::open(file.path()).unwrap();
+
+        let connection = Connection::open(file.path()).unwrap();
+        let version: i64 = connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let file = NamedTempFile::new().unwrap();
+        {
+            let connection = Connection::open(file.path()).unwrap();
+            connection
+                .pragma_update(None, "user_version", MIGRATIONS.len() as i64 + 1)
+                .unwrap();
+        }
+
+        let result = SqliteFlightStore::open(file.path());
+        assert!(matches!(result, Err(StorageError::InvalidData(_))));
+    }
+}
@@ -1,8 +1,8 @@
 use crate::app::app;
+use axum::http::StatusCode;
 use axum_test::TestServer;
-use reqwest::StatusCode;
 use serde_json::json;
-use sky_tracer::protocol::FLIGHTS_API_PATH;
+use sky_tracer::protocol::{FLIGHTS_API_PATH, FLIGHTS_DETAIL_API_PATH};
 
 #[tokio::test]
 async fn it_should_response_on_root_with_html_page() {
@@ -76,19 +76,20 @@ async fn it_should_create_flight() {
     let app = app();
     let server = TestServer::new(app).unwrap();
     let create_request = json!({
-        "aircraft_number": "123",
+        "aircraft_number": "D-AIBL",
         "arrival": "fra",
         "arrival_time": "2025-04-23T16:37:46.810397+00:00",
         "departure": "lis",
         "departure_time": "2025-04-23T16:28:46.810395Z"
     });
     let expected_response = json!({
-        "aircraft_number": "123",
-        "arrival": "fra",
+        "aircraft_number": "D-AIBL",
+        "arrival": "FRA",
         "arrival_time": "2025-04-23T16:37:46.810397Z",
-        "departure": "lis",
+        "departure": "LIS",
         "departure_time": "2025-04-23T16:28:46.810395Z",
-        "flight_number": "LIS0001"
+        "flight_number": "LH1",
+        "status": "Scheduled"
     });
     // When
     let response1 = server.post(FLIGHTS_API_PATH).json(&create_request).await;
@@ -105,29 +106,31 @@ async fn it_response_with_list_of_flights_with_multiple_entries() {
     let app = app();
     let server = TestServer::new(app).unwrap();
     let create_request = json!({
-        "aircraft_number": "123",
-        "arrival": "fra",
+        "aircraft_number": "D-AIBL",
+        "arrival": "FRA",
         "arrival_time": "2025-04-23T16:37:46.810397+00:00",
-        "departure": "lis",
+        "departure": "LIS",
         "departure_time": "2025-04-23T16:28:46.810395Z"
     });
 
     let expected_response = json!([
         {
-            "aircraft_number": "123",
-            "arrival": "fra",
+            "aircraft_number": "D-AIBL",
+            "arrival": "FRA",
             "arrival_time": "2025-04-23T16:37:46.810397Z",
-            "departure": "lis",
+            "departure": "LIS",
             "departure_time": "2025-04-23T16:28:46.810395Z",
-            "flight_number": "LIS0001"
+            "flight_number": "LH1",
+            "status": "Scheduled"
         },
         {
-            "aircraft_number": "123",
-            "arrival": "fra",
+            "aircraft_number": "D-AIBL",
+            "arrival": "FRA",
             "arrival_time": "2025-04-23T16:37:46.810397Z",
-            "departure": "lis",
+            "departure": "LIS",
             "departure_time": "2025-04-23T16:28:46.810395Z",
-            "flight_number": "LIS0002"
+            "flight_number": "LH2",
+            "status": "Scheduled"
         }
     ]);
 
@@ -159,6 +162,14 @@ async fn it_should_return_error_when_aircraft_number_is_missing() {
     let response = server.post(FLIGHTS_API_PATH).json(&create_request).await;
     // Then
     response.assert_status_unprocessable_entity();
+    response.assert_header("content-type", "application/problem+json");
+    let problem: serde_json::Value = response.json();
+    assert_eq!(problem["code"], "INVALID_BODY");
+    assert_eq!(problem["instance"], FLIGHTS_API_PATH);
+    assert!(problem["detail"]
+        .as_str()
+        .unwrap()
+        .contains("aircraft_number"));
 }
 
 #[tokio::test]
@@ -210,6 +221,253 @@ async fn it_should_return_404_for_nonexistent_flight_position() {
     response.assert_status_not_found();
 }
 
+#[tokio::test]
+async fn it_should_create_flight_with_explicit_number_once() {
+    // Given
+    let app = app();
+    let server = TestServer::new(app).unwrap();
+    let create_request = json!({
+        "aircraft_number": "CS-TUA",
+        "arrival": "FRA",
+        "departure": "LIS",
+        "departure_time": "2025-04-23T16:28:46Z",
+        "flight_number": "TAP 572"
+    });
+
+    // When
+    let response1 = server.post(FLIGHTS_API_PATH).json(&create_request).await;
+    let response2 = server.post(FLIGHTS_API_PATH).json(&create_request).await;
+
+    // Then
+    response1.assert_status(StatusCode::CREATED);
+    let flight: serde_json::Value = response1.json();
+    assert_eq!(flight["flight_number"], "TP572");
+    response2.assert_status(StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn it_should_reject_unknown_airline() {
+    // Given
+    let app = app();
+    let server = TestServer::new(app).unwrap();
+    let create_request = json!({
+        "aircraft_number": "D-ABCD",
+        "arrival": "LIS",
+        "departure": "FRA",
+        "departure_time": "2025-04-23T16:28:46Z",
+        "airline": "XX"
+    });
+
+    // When
+    let response = server.post(FLIGHTS_API_PATH).json(&create_request).await;
+
+    // Then
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn it_should_list_airlines() {
+    use sky_tracer::protocol::AIRLINES_API_PATH;
+
+    // Given
+    let app = app();
+    let server = TestServer::new(app).unwrap();
+
+    // When
+    let response = server.get(AIRLINES_API_PATH).await;
+
+    // Then
+    response.assert_status_ok();
+    let airlines: Vec<serde_json::Value> = response.json();
+    assert!(airlines
+        .iter()
+        .any(|a| a["iata"] == "LH" && a["icao"] == "DLH"));
+}
+
+#[tokio::test]
+async fn it_should_reject_unknown_airports_with_field_errors() {
+    use crate::app::app_with_service;
+    use crate::services::{FlightService, StaticAirportDirectory};
+    use std::sync::Arc;
+
+    // Given
+    let service = FlightService::new()
+        .with_airport_directory(Arc::new(StaticAirportDirectory::new(["FRA", "LIS"])));
+    let server = TestServer::new(app_with_service(service)).unwrap();
+    let create_request = json!({
+        "aircraft_number": "D-ABCD",
+        "arrival": "XYZ",
+        "departure": "fra",
+        "departure_time": "2025-04-23T16:28:46Z"
+    });
+
+    // When
+    let response = server.post(FLIGHTS_API_PATH).json(&create_request).await;
+
+    // Then
+    response.assert_status_bad_request();
+    response.assert_header("content-type", "application/problem+json");
+    response.assert_json(&json!({
+        "type": "urn:sky-tracer:problem:validation-failed",
+        "title": "Flight validation failed",
+        "status": 400,
+        "instance": "/api/v1/flights",
+        "code": "VALIDATION_FAILED",
+        "fields": [{
+            "field": "arrival",
+            "code": "UNKNOWN_AIRPORT",
+            "message": "Unknown airport XYZ"
+        }]
+    }));
+    server.get(FLIGHTS_API_PATH).await.assert_json(&json!([]));
+}
+
+#[tokio::test]
+async fn it_should_reject_malformed_airport_codes_with_field_errors() {
+    // Given
+    let server = TestServer::new(app()).unwrap();
+    let create_request = json!({
+        "aircraft_number": "D-ABCD",
+        "arrival": "LI5BON",
+        "departure": "fra",
+        "departure_time": "2025-04-23T16:28:46Z"
+    });
+
+    // When
+    let response = server.post(FLIGHTS_API_PATH).json(&create_request).await;
+
+    // Then
+    response.assert_status_bad_request();
+    response.assert_header("content-type", "application/problem+json");
+    response.assert_json(&json!({
+        "type": "urn:sky-tracer:problem:validation-failed",
+        "title": "Flight validation failed",
+        "status": 400,
+        "instance": "/api/v1/flights",
+        "code": "VALIDATION_FAILED",
+        "fields": [{
+            "field": "arrival",
+            "code": "INVALID_FORMAT",
+            "message": "Invalid IATA code \"LI5BON\", expected three letters or digits, e.g. FRA"
+        }]
+    }));
+}
+
+#[tokio::test]
+async fn it_should_reject_inverted_time_window() {
+    // Given
+    let app = app();
+    let server = TestServer::new(app).unwrap();
+    let create_request = json!({
+        "aircraft_number": "D-ABCD",
+        "arrival": "LIS",
+        "arrival_time": "2025-04-23T15:00:00Z",
+        "departure": "LIS",
+        "departure_time": "2025-04-23T16:28:46Z"
+    });
+
+    // When
+    let response = server.post(FLIGHTS_API_PATH).json(&create_request).await;
+
+    // Then
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "VALIDATION_FAILED");
+    let fields: Vec<(&str, &str)> = body["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["field"].as_str().unwrap(), f["code"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("arrival", "SAME_AS_DEPARTURE"),
+            ("arrival_time", "BEFORE_DEPARTURE")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn it_should_manage_flight_lifecycle() {
+    // Given
+    let app = app();
+    let server = TestServer::new(app).unwrap();
+    let create_request = json!({
+        "aircraft_number": "D-ABCD",
+        "arrival": "LIS",
+        "departure": "FRA",
+        "departure_time": "2025-04-23T16:28:46Z"
+    });
+    let create_response = server.post(FLIGHTS_API_PATH).json(&create_request).await;
+    create_response.assert_status(StatusCode::CREATED);
+    let flight_url = FLIGHTS_DETAIL_API_PATH.replace("{flight_number}", "LH1");
+
+    // When - Reschedule while still scheduled
+    let update_response = server
+        .put(&flight_url)
+        .json(&json!({
+            "aircraft_number": "D-EFGH",
+            "arrival": "OPO",
+            "departure": "FRA",
+            "departure_time": "2025-04-23T18:00:00Z"
+        }))
+        .await;
+    // Then
+    update_response.assert_status_ok();
+    let flight: serde_json::Value = server.get(&flight_url).await.json();
+    assert_eq!(flight["arrival"], "OPO");
+    assert_eq!(flight["aircraft_number"], "D-EFGH");
+    assert_eq!(flight["status"], "Scheduled");
+
+    // When - Board and depart
+    for status in ["Boarding", "Departed"] {
+        let response = server
+            .patch(&flight_url)
+            .json(&json!({ "status": status }))
+            .await;
+        response.assert_status_ok();
+        let flight: serde_json::Value = response.json();
+        assert_eq!(flight["status"], status);
+    }
+
+    // Then - A departed flight can neither be cancelled nor rescheduled
+    let cancel_response = server
+        .patch(&flight_url)
+        .json(&json!({ "status": "Cancelled" }))
+        .await;
+    cancel_response.assert_status(StatusCode::CONFLICT);
+    let reschedule_response = server
+        .patch(&flight_url)
+        .json(&json!({ "arrival": "LIS" }))
+        .await;
+    reschedule_response.assert_status(StatusCode::CONFLICT);
+
+    // When - Delete the flight
+    let delete_response = server.delete(&flight_url).await;
+    // Then
+    delete_response.assert_status(StatusCode::NO_CONTENT);
+    server.get(&flight_url).await.assert_status_not_found();
+    server.delete(&flight_url).await.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn it_should_return_404_when_updating_nonexistent_flight() {
+    // Given
+    let app = app();
+    let server = TestServer::new(app).unwrap();
+    let flight_url = FLIGHTS_DETAIL_API_PATH.replace("{flight_number}", "NONEXISTENT");
+
+    // When
+    let response = server
+        .patch(&flight_url)
+        .json(&json!({ "status": "Cancelled" }))
+        .await;
+
+    // Then
+    response.assert_status_not_found();
+}
+
 #[test]
 fn test_api_path_constants() {
     // Verify the constants are correct
@@ -220,3 +478,63 @@ fn test_api_path_constants() {
         "/api/v1/flights/{flight_number}/position"
     );
 }
+
+#[tokio::test]
+async fn it_should_not_be_ready_without_orbital_beacon() {
+    use crate::app::app_with_service;
+    use crate::services::FlightService;
+    use sky_tracer::client::SatellitesClient;
+    use sky_tracer::protocol::health::{HEALTH_LIVE_PATH, HEALTH_READY_PATH};
+
+    // Given - nothing listens on the discard port
+    let service =
+        FlightService::new().with_satellites_client(SatellitesClient::new("http://127.0.0.1:9"));
+    let server = TestServer::new(app_with_service(service)).unwrap();
+
+    // When
+    let live = server.get(HEALTH_LIVE_PATH).await;
+    let ready = server.get(HEALTH_READY_PATH).await;
+
+    // Then
+    live.assert_status_ok();
+    ready.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let report: serde_json::Value = ready.json();
+    assert_eq!(report["service"], "flight-controller");
+    assert_eq!(report["status"], "down");
+    assert_eq!(report["dependencies"][0]["name"], "orbital-beacon");
+    assert_eq!(report["dependencies"][0]["status"], "down");
+}
+
+#[tokio::test]
+async fn it_should_expose_flight_gauges_and_request_metrics() {
+    use sky_tracer::protocol::metrics::METRICS_PATH;
+
+    // Given - one flight scheduled and one boarded and departed
+    let server = TestServer::new(app()).unwrap();
+    let create_request = json!({
+        "aircraft_number": "D-AIBL",
+        "arrival": "FRA",
+        "arrival_time": "2025-04-23T16:37:46.810397+00:00",
+        "departure": "LIS",
+        "departure_time": "2025-04-23T16:28:46.810395Z"
+    });
+    server.post(FLIGHTS_API_PATH).json(&create_request).await;
+    server.post(FLIGHTS_API_PATH).json(&create_request).await;
+    for status in ["Boarding", "Departed"] {
+        server
+            .patch("/api/v1/flights/LH2")
+            .json(&json!({ "status": status }))
+            .await
+            .assert_status_ok();
+    }
+
+    // When
+    let response = server.get(METRICS_PATH).await;
+
+    // Then
+    response.assert_status_ok();
+    let text = response.text();
+    assert!(text.contains(r#"sky_tracer_flights_scheduled{service_name="flight-controller"} 1"#));
+    assert!(text.contains(r#"sky_tracer_flights_airborne{service_name="flight-controller"} 1"#));
+    assert!(text.contains(&format!(r#"http_route="{FLIGHTS_DETAIL_API_PATH}""#)));
+}
@@ -17,17 +17,19 @@ pub fn flight_table(props: &FlightTableProps) -> Html {
                     <th>{"From"}</th>
                     <th>{"To"}</th>
                     <th>{"Departure Time"}</th>
+                    <th>{"Status"}</th>
                 </tr>
             </thead>
             <tbody>
                 {props.flights.iter().map(|flight| {
                     html! {
                         <tr>
-                            <td>{&flight.flight_number}</td>
-                            <td>{&flight.aircraft_number}</td>
-                            <td>{&flight.departure}</td>
-                            <td>{&flight.arrival}</td>
+                            <td>{flight.flight_number.as_str()}</td>
+                            <td>{flight.aircraft_number.as_str()}</td>
+                            <td>{flight.departure.as_str()}</td>
+                            <td>{flight.arrival.as_str()}</td>
                             <td>{flight.departure_time.format("%Y-%m-%d %H:%M").to_string()}</td>
+                            <td>{flight.status.to_string()}</td>
                         </tr>
                     }
                 }).collect::<Html>()}
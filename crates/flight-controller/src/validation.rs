@@ -0,0 +1,298 @@
+//! Checks applied to flight requests and schedules before they are stored.
+
+use crate::models::Flight;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sky_tracer::model::{FlightStatus, IdentifierError};
+use sky_tracer::protocol::flights::{
+    CreateFlightRequest, FieldError, PatchFlightRequest, UpdateFlightRequest,
+};
+use std::str::FromStr;
+
+/// Body of a create request as sent, identifiers not yet validated
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateFlightInput {
+    pub aircraft_number: String,
+    pub departure: String,
+    pub arrival: String,
+    pub departure_time: DateTime<Utc>,
+    pub arrival_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub airline: Option<String>,
+    #[serde(default)]
+    pub flight_number: Option<String>,
+}
+
+/// Body of an update request as sent, identifiers not yet validated
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateFlightInput {
+    pub aircraft_number: String,
+    pub departure: String,
+    pub arrival: String,
+    pub departure_time: DateTime<Utc>,
+    pub arrival_time: Option<DateTime<Utc>>,
+}
+
+/// Body of a patch request as sent, identifiers not yet validated
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PatchFlightInput {
+    pub aircraft_number: Option<String>,
+    pub departure: Option<String>,
+    pub arrival: Option<String>,
+    pub departure_time: Option<DateTime<Utc>>,
+    pub arrival_time: Option<DateTime<Utc>>,
+    pub status: Option<FlightStatus>,
+}
+
+/// Field errors collected while reading the identifiers of a request
+#[derive(Default)]
+struct Identifiers(Vec<FieldError>);
+
+impl Identifiers {
+    fn required<T>(&mut self, field: &str, value: &str, label: &str) -> Option<T>
+    where
+        T: FromStr<Err = IdentifierError>,
+    {
+        if value.trim().is_empty() {
+            self.0.push(FieldError::new(
+                field,
+                "REQUIRED",
+                format!("{label} must not be empty"),
+            ));
+            return None;
+        }
+        self.parse(field, value)
+    }
+
+    fn optional<T>(&mut self, field: &str, value: Option<&str>) -> Option<Option<T>>
+    where
+        T: FromStr<Err = IdentifierError>,
+    {
+        match value {
+            Some(value) => self.parse(field, value).map(Some),
+            None => Some(None),
+        }
+    }
+
+    fn parse<T>(&mut self, field: &str, value: &str) -> Option<T>
+    where
+        T: FromStr<Err = IdentifierError>,
+    {
+        value
+            .parse()
+            .inspect_err(|e: &IdentifierError| {
+                self.0
+                    .push(FieldError::new(field, "INVALID_FORMAT", e.to_string()))
+            })
+            .ok()
+    }
+}
+
+/// Validate the identifiers of a create request
+pub fn validate_create(input: CreateFlightInput) -> Result<CreateFlightRequest, Vec<FieldError>> {
+    let mut identifiers = Identifiers::default();
+    let aircraft_number =
+        identifiers.required("aircraft_number", &input.aircraft_number, "Aircraft number");
+    let departure = identifiers.required("departure", &input.departure, "Airport code");
+    let arrival = identifiers.required("arrival", &input.arrival, "Airport code");
+    let flight_number = identifiers.optional("flight_number", input.flight_number.as_deref());
+
+    let (Some(aircraft_number), Some(departure), Some(arrival), Some(flight_number)) =
+        (aircraft_number, departure, arrival, flight_number)
+    else {
+        return Err(identifiers.0);
+    };
+    Ok(CreateFlightRequest {
+        aircraft_number,
+        departure,
+        arrival,
+        departure_time: input.departure_time,
+        arrival_time: input.arrival_time,
+        airline: input.airline,
+        flight_number,
+    })
+}
+
+/// Validate the identifiers of an update request
+pub fn validate_update(input: UpdateFlightInput) -> Result<UpdateFlightRequest, Vec<FieldError>> {
+    let mut identifiers = Identifiers::default();
+    let aircraft_number =
+        identifiers.required("aircraft_number", &input.aircraft_number, "Aircraft number");
+    let departure = identifiers.required("departure", &input.departure, "Airport code");
+    let arrival = identifiers.required("arrival", &input.arrival, "Airport code");
+
+    let (Some(aircraft_number), Some(departure), Some(arrival)) =
+        (aircraft_number, departure, arrival)
+    else {
+        return Err(identifiers.0);
+    };
+    Ok(UpdateFlightRequest {
+        aircraft_number,
+        departure,
+        arrival,
+        departure_time: input.departure_time,
+        arrival_time: input.arrival_time,
+    })
+}
+
+/// Validate the identifiers given in a patch request
+pub fn validate_patch(input: PatchFlightInput) -> Result<PatchFlightRequest, Vec<FieldError>> {
+    let mut identifiers = Identifiers::default();
+    let aircraft_number = identifiers.optional("aircraft_number", input.aircraft_number.as_deref());
+    let departure = identifiers.optional("departure", input.departure.as_deref());
+    let arrival = identifiers.optional("arrival", input.arrival.as_deref());
+
+    let (Some(aircraft_number), Some(departure), Some(arrival)) =
+        (aircraft_number, departure, arrival)
+    else {
+        return Err(identifiers.0);
+    };
+    Ok(PatchFlightRequest {
+        aircraft_number,
+        departure,
+        arrival,
+        departure_time: input.departure_time,
+        arrival_time: input.arrival_time,
+        status: input.status,
+    })
+}
+
+/// Check a flight's schedule for problems that need no external lookup.
+///
+/// The identifiers are valid by type, so only their combination and the
+/// times are left to check.
+pub fn validate_schedule(flight: &Flight) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if flight.departure == flight.arrival {
+        errors.push(FieldError::new(
+            "arrival",
+            "SAME_AS_DEPARTURE",
+            format!(
+                "Arrival airport must differ from departure airport {}",
+                flight.departure
+            ),
+        ));
+    }
+
+    if let Some(arrival_time) = flight.arrival_time {
+        if arrival_time == flight.departure_time {
+            errors.push(FieldError::new(
+                "arrival_time",
+                "ZERO_DURATION",
+                "Arrival time must be after departure time",
+            ));
+        } else if arrival_time < flight.departure_time {
+            errors.push(FieldError::new(
+                "arrival_time",
+                "BEFORE_DEPARTURE",
+                format!(
+                    "Arrival time {} is before departure time {}",
+                    arrival_time.to_rfc3339(),
+                    flight.departure_time.to_rfc3339()
+                ),
+            ));
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn flight(departure: &str, arrival: &str, duration: Option<Duration>) -> Flight {
+        let departure_time = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+        Flight::new(
+            "LH1".parse().unwrap(),
+            "D-ABCD".parse().unwrap(),
+            departure.parse().unwrap(),
+            arrival.parse().unwrap(),
+            departure_time,
+            duration.map(|d| departure_time + d),
+        )
+    }
+
+    fn codes(errors: &[FieldError]) -> Vec<(&str, &str)> {
+        errors
+            .iter()
+            .map(|e| (e.field.as_str(), e.code.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_schedule() {
+        assert!(validate_schedule(&flight("FRA", "LIS", Some(Duration::hours(3)))).is_empty());
+        assert!(validate_schedule(&flight("FRA", "LIS", None)).is_empty());
+    }
+
+    #[test]
+    fn test_time_window_must_be_positive() {
+        let zero = validate_schedule(&flight("FRA", "LIS", Some(Duration::zero())));
+        assert_eq!(codes(&zero), vec![("arrival_time", "ZERO_DURATION")]);
+
+        let inverted = validate_schedule(&flight("FRA", "LIS", Some(Duration::minutes(-5))));
+        assert_eq!(codes(&inverted), vec![("arrival_time", "BEFORE_DEPARTURE")]);
+    }
+
+    #[test]
+    fn test_airports_must_differ() {
+        let errors = validate_schedule(&flight("FRA", "fra", None));
+        assert_eq!(codes(&errors), vec![("arrival", "SAME_AS_DEPARTURE")]);
+    }
+
+    fn create_input(aircraft_number: &str, departure: &str, arrival: &str) -> CreateFlightInput {
+        CreateFlightInput {
+            aircraft_number: aircraft_number.to_string(),
+            departure: departure.to_string(),
+            arrival: arrival.to_string(),
+            departure_time: Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap(),
+            arrival_time: None,
+            airline: None,
+            flight_number: None,
+        }
+    }
+
+    #[test]
+    fn test_identifiers_are_validated() {
+        let request = validate_create(create_input("d-abcd", " fra", "lis")).unwrap();
+        assert_eq!(request.aircraft_number, "D-ABCD");
+        assert_eq!(request.departure, "FRA");
+
+        let errors = validate_create(CreateFlightInput {
+            flight_number: Some("400".to_string()),
+            ..create_input(" ", "Frankfurt", "LIS")
+        })
+        .unwrap_err();
+        assert_eq!(
+            codes(&errors),
+            vec![
+                ("aircraft_number", "REQUIRED"),
+                ("departure", "INVALID_FORMAT"),
+                ("flight_number", "INVALID_FORMAT"),
+            ]
+        );
+
+        let errors = validate_patch(PatchFlightInput {
+            arrival: Some("LI".to_string()),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert_eq!(codes(&errors), vec![("arrival", "INVALID_FORMAT")]);
+        assert!(validate_patch(PatchFlightInput::default()).is_ok());
+    }
+
+    #[test]
+    fn test_all_problems_are_reported() {
+        let errors = validate_schedule(&flight("LIS", "LIS", Some(Duration::hours(-1))));
+        assert_eq!(
+            codes(&errors),
+            vec![
+                ("arrival", "SAME_AS_DEPARTURE"),
+                ("arrival_time", "BEFORE_DEPARTURE"),
+            ]
+        );
+    }
+}
@@ -12,18 +12,34 @@ pub fn lat_to_y(lat: f64) -> f64 {
     PAD + (90.0 - lat) / 180.0 * (SVG_HEIGHT - 2.0 * PAD)
 }
 
-/// Quadratic bezier arc path between two geo-points.
-/// The control point is lifted above the midpoint proportional to distance.
-pub fn arc_path(dep_lon: f64, dep_lat: f64, arr_lon: f64, arr_lat: f64) -> String {
-    let x1 = lon_to_x(dep_lon);
-    let y1 = lat_to_y(dep_lat);
-    let x2 = lon_to_x(arr_lon);
-    let y2 = lat_to_y(arr_lat);
-    let mx = (x1 + x2) / 2.0;
-    let my = (y1 + y2) / 2.0;
-    let dist = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
-    let cpy = my - dist * 0.3;
-    format!("M {x1:.1} {y1:.1} Q {mx:.1} {cpy:.1} {x2:.1} {y2:.1}")
+/// Number of segments a great-circle route is sampled into
+pub const ROUTE_SEGMENTS: usize = 64;
+
+/// SVG path following the great circle between two geo-points.
+/// The route is split into separate subpaths where it crosses the antimeridian,
+/// so it never streaks across the whole map.
+pub fn great_circle_path(dep_lon: f64, dep_lat: f64, arr_lon: f64, arr_lat: f64) -> String {
+    let points =
+        sky_tracer::geo::great_circle_path((dep_lat, dep_lon), (arr_lat, arr_lon), ROUTE_SEGMENTS);
+
+    let mut d = String::new();
+    let mut previous_lon: Option<f64> = None;
+    for (lat, lon) in points {
+        let command = match previous_lon {
+            Some(prev) if (lon - prev).abs() <= 180.0 => "L",
+            _ => "M",
+        };
+        if !d.is_empty() {
+            d.push(' ');
+        }
+        d.push_str(&format!(
+            "{command} {:.1} {:.1}",
+            lon_to_x(lon),
+            lat_to_y(lat)
+        ));
+        previous_lon = Some(lon);
+    }
+    d
 }
 
 #[cfg(test)]
@@ -61,20 +77,27 @@ mod tests {
     }
 
     #[test]
-    fn arc_path_format() {
-        let path = arc_path(0.0, 0.0, 10.0, 10.0);
+    fn great_circle_path_format() {
+        let path = great_circle_path(0.0, 0.0, 10.0, 10.0);
         assert!(path.starts_with("M "));
-        assert!(path.contains(" Q "));
+        assert!(path.contains(" L "));
+        assert_eq!(path.matches('M').count(), 1);
     }
 
     #[test]
-    fn arc_path_same_point_has_zero_dist() {
-        let path = arc_path(8.0, 50.0, 8.0, 50.0);
-        // control point y == midpoint y when dist == 0
+    fn great_circle_path_bulges_poleward() {
+        // FRA -> JFK: the midpoint of the route lies north of both airports
+        let path = great_circle_path(8.57, 50.03, -73.78, 40.64);
         let parts: Vec<&str> = path.split_whitespace().collect();
-        // "M x1 y1 Q cpx cpy x2 y2"  →  indices: 0=M 1=x1 2=y1 3=Q 4=cpx 5=cpy 6=x2 7=y2
-        let cpy: f64 = parts[5].parse().unwrap();
-        let y1: f64 = parts[2].parse().unwrap();
-        assert!((cpy - y1).abs() < 0.01);
+        let mid = parts.len() / 2 / 3 * 3;
+        let mid_y: f64 = parts[mid + 2].parse().unwrap();
+        assert!(mid_y < lat_to_y(50.03));
+    }
+
+    #[test]
+    fn great_circle_path_splits_at_antimeridian() {
+        // SFO -> HND crosses the date line and must not span the whole map
+        let path = great_circle_path(-122.375, 37.62, 139.78, 35.55);
+        assert_eq!(path.matches('M').count(), 2);
     }
 }
@@ -1,5 +1,5 @@
 use crate::continents::continent_svg_elements;
-use crate::geo::{arc_path, lat_to_y, lon_to_x, PAD, SVG_HEIGHT, SVG_WIDTH};
+use crate::geo::{great_circle_path, lat_to_y, lon_to_x, PAD, SVG_HEIGHT, SVG_WIDTH};
 use crate::types::{AirportPin, RouteArc};
 
 pub fn render(airports: Vec<AirportPin>, routes: Vec<RouteArc>, title: Option<String>) -> String {
@@ -63,7 +63,7 @@ fn render_routes(routes: &[RouteArc]) -> String {
     routes
         .iter()
         .map(|r| {
-            let d = arc_path(r.dep_lon, r.dep_lat, r.arr_lon, r.arr_lat);
+            let d = great_circle_path(r.dep_lon, r.dep_lat, r.arr_lon, r.arr_lat);
             format!(
                 "<path d=\"{d}\" fill=\"none\" stroke=\"#3b82f6\" stroke-width=\"1.2\" stroke-dasharray=\"4 3\" opacity=\"0.8\"/>"
             )
@@ -14,11 +14,11 @@ pub fn flight_with_delay(props: &FlightWithDelayProps) -> Html {
     html! {
         <div class="flight-item">
             <div class="flight-info">
-                <h3>{&flight.flight_number}</h3>
+                <h3>{flight.flight_number.as_str()}</h3>
                 <p class="route">
-                    {&flight.departure}
+                    {flight.departure.as_str()}
                     {" → "}
-                    {&flight.arrival}
+                    {flight.arrival.as_str()}
                 </p>
                 <p class="time">
                     {"Departure: "}
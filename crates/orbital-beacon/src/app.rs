@@ -1,7 +1,10 @@
 use crate::{openapi, routes, services::SatelliteService};
-use axum::Router;
 use axum::routing::{get, post, put};
-use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
+use axum::{Router, middleware};
+use sky_tracer::clock::follow_caller_time;
+use sky_tracer::protocol::health::health_routes;
+use sky_tracer::protocol::metrics::{metrics_routes, observe};
+use sky_tracer::protocol::problem::problem_instance;
 use sky_tracer::protocol::{
     SATELLITES_API_PATH, SATELLITES_POSITION_API_PATH, SATELLITES_STATUS_API_PATH,
 };
@@ -24,7 +27,7 @@ pub fn app(satellite_service: SatelliteService) -> Router {
         );
 
     // Main application routes - these will be at / after prefix stripping
-    Router::new()
+    let app = Router::new()
         .route("/", get(routes::render_home)) // Home page at root after prefix strip
         .route(
             "/launch",
@@ -35,13 +38,60 @@ pub fn app(satellite_service: SatelliteService) -> Router {
         .route("/flight_position", get(routes::render_flight_position))
         .merge(openapi::routes()) // Clean openapi routes
         .merge(api_routes) // API routes are merged at their full paths
+        .layer(middleware::from_fn_with_state(
+            satellite_service.clock().clone(),
+            follow_caller_time,
+        ))
+        .layer(middleware::from_fn(problem_instance))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
                 .allow_headers(Any),
-        )
-        .layer(OtelInResponseLayer::default())
-        .layer(OtelAxumLayer::default())
-        .with_state(satellite_service)
+        );
+    let probes = health_routes("orbital-beacon", |service: SatelliteService| async move {
+        service.readiness().await
+    })
+    .merge(metrics_routes(|service: SatelliteService| async move {
+        service.observe_metrics().await
+    }));
+    observe("orbital-beacon", app, probes).with_state(satellite_service)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode, header::CONTENT_TYPE},
+    };
+    use sky_tracer::protocol::problem::{PROBLEM_JSON, ProblemDetails};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_invalid_satellite_id_is_a_problem() {
+        let app = app(SatelliteService::new("http://127.0.0.1:9".to_string()));
+
+        let response = app
+            .oneshot(
+                Request::put("/api/v1/satellites/sputnik/status")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"status": "Active"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()[CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code.as_deref(), Some("INVALID_PARAMETER"));
+        assert_eq!(
+            problem.instance.as_deref(),
+            Some("/api/v1/satellites/sputnik/status")
+        );
+    }
 }
@@ -1,6 +1,7 @@
 pub mod app;
 pub mod models;
 pub mod openapi;
+pub mod repository;
 pub mod routes;
 pub mod services;
 pub mod utils;
@@ -1,10 +1,17 @@
-use orbital_beacon::{app::app, services::SatelliteService, utils::get_path_prefix};
+use orbital_beacon::{
+    app::app,
+    repository::{self, SATELLITE_SEED_PATH_ENV, SatelliteSeed},
+    services::SatelliteService,
+    utils::get_path_prefix,
+};
 use std::env;
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
     let _guard = init_tracing_opentelemetry::tracing_subscriber_ext::init_subscribers()?;
+    let clock = sky_tracer::clock::init_from_env()?;
+    info!(clock = ?clock, "Configured clock");
 
     // Get service configuration
     let service_port = std::env::var("PORT")
@@ -22,7 +29,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
     info!("Starting {} service on port {}", service_name, service_port);
     info!(airport_service_url = %airport_service_url, "Configured airport service");
 
-    let satellite_service = SatelliteService::new(airport_service_url);
+    let satellite_repository = repository::from_env()?;
+    if let Ok(seed_path) = env::var(SATELLITE_SEED_PATH_ENV) {
+        info!(seed_path = %seed_path, "Loading satellite seed");
+        let seed = SatelliteSeed::from_file(&seed_path)?;
+        repository::apply_seed(satellite_repository.as_ref(), &seed).await?;
+    }
+
+    let satellite_service =
+        SatelliteService::with_repository(airport_service_url, satellite_repository)
+            .with_path_prefix(get_path_prefix());
     let app = app(satellite_service);
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", service_port)).await?;
@@ -42,7 +42,7 @@ impl FlightPositionRequest {
 
     /// Get progress as a value between 0.0 and 1.0
     pub fn calculate_progress(&self) -> f64 {
-        let now = self.current_time.unwrap_or_else(Utc::now);
+        let now = self.current_time.unwrap_or_else(sky_tracer::clock::now);
         let total_duration = self.arrival_time - self.departure_time;
         let elapsed = now - self.departure_time;
 
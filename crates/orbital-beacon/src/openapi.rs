@@ -1,6 +1,7 @@
 use crate::routes::api;
 use axum::{Router, response::Redirect, routing::get};
 use sky_tracer::model::SatelliteStatus;
+use sky_tracer::protocol::problem::ProblemDetails;
 use sky_tracer::protocol::satellite::{
     CalculatePositionRequest, CalculatePositionResponse, CreateSatelliteRequest, SatelliteResponse,
     UpdateSatelliteStatusRequest,
@@ -23,7 +24,8 @@ use utoipa_swagger_ui::SwaggerUi;
             SatelliteResponse,
             CalculatePositionRequest,
             CalculatePositionResponse,
-            SatelliteStatus
+            SatelliteStatus,
+            ProblemDetails
         )
     ),
     tags(
@@ -0,0 +1,154 @@
+use super::{RepositoryError, SatelliteRepository};
+use async_trait::async_trait;
+use sky_tracer::model::Satellite;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Satellite storage persisted as a JSON array in a single file.
+///
+/// All satellites are kept in memory; every change rewrites the file through a
+/// temporary sibling file and an atomic rename, so a crash never leaves a
+/// truncated registry behind.
+#[derive(Clone)]
+pub struct FileSatelliteRepository {
+    path: PathBuf,
+    satellites: Arc<RwLock<HashMap<Uuid, Satellite>>>,
+}
+
+impl FileSatelliteRepository {
+    /// Open the registry at `path`, starting empty if the file does not exist yet
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
+        let path = path.as_ref().to_path_buf();
+
+        let satellites: Vec<Satellite> = match std::fs::read_to_string(&path) {
+            Ok(content) if content.trim().is_empty() => Vec::new(),
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        info!(
+            path = %path.display(),
+            satellites = satellites.len(),
+            "Loaded satellite registry"
+        );
+
+        Ok(Self {
+            path,
+            satellites: Arc::new(RwLock::new(
+                satellites.into_iter().map(|s| (s.id, s)).collect(),
+            )),
+        })
+    }
+
+    async fn persist(&self, satellites: &HashMap<Uuid, Satellite>) -> Result<(), RepositoryError> {
+        let mut sorted: Vec<&Satellite> = satellites.values().collect();
+        sorted.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        let content = serde_json::to_string_pretty(&sorted)?;
+
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        tokio::fs::write(&temp_path, content).await?;
+        tokio::fs::rename(&temp_path, &self.path).await?;
+
+        debug!(
+            path = %self.path.display(),
+            satellites = sorted.len(),
+            "Persisted satellite registry"
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SatelliteRepository for FileSatelliteRepository {
+    async fn save(&self, satellite: Satellite) -> Result<(), RepositoryError> {
+        let mut satellites = self.satellites.write().await;
+        let previous = satellites.insert(satellite.id, satellite.clone());
+
+        if let Err(e) = self.persist(&satellites).await {
+            // Keep memory and file consistent when the write fails
+            match previous {
+                Some(previous) => satellites.insert(previous.id, previous),
+                None => satellites.remove(&satellite.id),
+            };
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<Satellite>, RepositoryError> {
+        let satellites = self.satellites.read().await;
+        Ok(satellites.get(&id).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Satellite>, RepositoryError> {
+        let satellites = self.satellites.read().await;
+        Ok(satellites.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sky_tracer::model::SatelliteStatus;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_satellites_survive_reopen() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("satellites.json");
+
+        let mut satellite = Satellite::new("Sentinel".to_string());
+        {
+            let repository = FileSatelliteRepository::open(&path).unwrap();
+            repository.save(satellite.clone()).await.unwrap();
+
+            satellite.status = SatelliteStatus::Active;
+            repository.save(satellite.clone()).await.unwrap();
+        }
+
+        let repository = FileSatelliteRepository::open(&path).unwrap();
+        let satellites = repository.list().await.unwrap();
+        assert_eq!(satellites.len(), 1);
+
+        let stored = repository.get(satellite.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "Sentinel");
+        assert_eq!(stored.status, SatelliteStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_starts_empty() {
+        let dir = TempDir::new().unwrap();
+        let repository = FileSatelliteRepository::open(dir.path().join("missing.json")).unwrap();
+
+        assert!(repository.list().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("satellites.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let result = FileSatelliteRepository::open(&path);
+        assert!(matches!(result, Err(RepositoryError::Serialization(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failed_write_leaves_memory_unchanged() {
+        let dir = TempDir::new().unwrap();
+        // The registry path is inside a directory that does not exist
+        let repository =
+            FileSatelliteRepository::open(dir.path().join("missing").join("satellites.json"))
+                .unwrap();
+
+        let result = repository.save(Satellite::new("Lost".to_string())).await;
+        assert!(matches!(result, Err(RepositoryError::Io(_))));
+        assert!(repository.list().await.unwrap().is_empty());
+    }
+}
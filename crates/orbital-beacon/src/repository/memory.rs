@@ -0,0 +1,38 @@
+use super::{RepositoryError, SatelliteRepository};
+use async_trait::async_trait;
+use sky_tracer::model::Satellite;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Volatile satellite storage, lost on restart
+#[derive(Clone, Default)]
+pub struct InMemorySatelliteRepository {
+    satellites: Arc<RwLock<HashMap<Uuid, Satellite>>>,
+}
+
+impl InMemorySatelliteRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SatelliteRepository for InMemorySatelliteRepository {
+    async fn save(&self, satellite: Satellite) -> Result<(), RepositoryError> {
+        let mut satellites = self.satellites.write().await;
+        satellites.insert(satellite.id, satellite);
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<Satellite>, RepositoryError> {
+        let satellites = self.satellites.read().await;
+        Ok(satellites.get(&id).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Satellite>, RepositoryError> {
+        let satellites = self.satellites.read().await;
+        Ok(satellites.values().cloned().collect())
+    }
+}
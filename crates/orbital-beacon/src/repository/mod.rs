@@ -0,0 +1,69 @@
+//! Satellite persistence backends and startup seeding.
+//!
+//! The backend is selected with `SATELLITE_STORAGE` (`memory` or `file`, default
+//! `memory`). The file backend keeps the registry as JSON at `SATELLITE_DATA_PATH`.
+
+mod file;
+mod memory;
+mod seed;
+
+pub use file::FileSatelliteRepository;
+pub use memory::InMemorySatelliteRepository;
+pub use seed::{SatelliteSeed, SeedSatellite, apply_seed};
+
+use async_trait::async_trait;
+use sky_tracer::model::Satellite;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+/// Environment variable selecting the repository backend
+pub const SATELLITE_STORAGE_ENV: &str = "SATELLITE_STORAGE";
+/// Environment variable with the path of the satellite registry file
+pub const SATELLITE_DATA_PATH_ENV: &str = "SATELLITE_DATA_PATH";
+/// Environment variable with the path of the seed file applied on startup
+pub const SATELLITE_SEED_PATH_ENV: &str = "SATELLITE_SEED_PATH";
+/// Registry path used when `SATELLITE_DATA_PATH` is not set
+pub const DEFAULT_DATA_PATH: &str = "satellites.json";
+
+#[derive(thiserror::Error, Debug)]
+pub enum RepositoryError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid satellite data: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Unknown storage backend: {0}")]
+    UnknownBackend(String),
+}
+
+/// Storage for satellites, keyed by id
+#[async_trait]
+pub trait SatelliteRepository: Send + Sync {
+    /// Insert a satellite or replace the one with the same id
+    async fn save(&self, satellite: Satellite) -> Result<(), RepositoryError>;
+
+    /// Get a satellite by id
+    async fn get(&self, id: Uuid) -> Result<Option<Satellite>, RepositoryError>;
+
+    /// List all satellites
+    async fn list(&self) -> Result<Vec<Satellite>, RepositoryError>;
+}
+
+/// Create the repository backend configured in the environment
+pub fn from_env() -> Result<Arc<dyn SatelliteRepository>, RepositoryError> {
+    let backend = std::env::var(SATELLITE_STORAGE_ENV).unwrap_or_else(|_| "memory".to_string());
+
+    match backend.to_lowercase().as_str() {
+        "memory" => {
+            info!("Using in-memory satellite repository");
+            Ok(Arc::new(InMemorySatelliteRepository::new()))
+        }
+        "file" => {
+            let path = std::env::var(SATELLITE_DATA_PATH_ENV)
+                .unwrap_or_else(|_| DEFAULT_DATA_PATH.to_string());
+            info!(path = %path, "Using file satellite repository");
+            Ok(Arc::new(FileSatelliteRepository::open(path)?))
+        }
+        other => Err(RepositoryError::UnknownBackend(other.to_string())),
+    }
+}
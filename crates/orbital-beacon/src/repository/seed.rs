@@ -0,0 +1,150 @@
+use super::{RepositoryError, SatelliteRepository};
+use serde::Deserialize;
+use sky_tracer::model::{Satellite, SatelliteStatus};
+use std::path::Path;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Constellation loaded on startup, e.g.
+///
+/// ```json
+/// { "satellites": [{ "name": "Beacon-1" }, { "name": "Beacon-2", "status": "Maintenance" }] }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SatelliteSeed {
+    pub satellites: Vec<SeedSatellite>,
+}
+
+/// Seed entry for a single satellite
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SeedSatellite {
+    /// Fixed id; generated when omitted
+    pub id: Option<Uuid>,
+    pub name: String,
+    /// Initial status, seeded satellites are active unless stated otherwise
+    #[serde(default = "default_seed_status")]
+    pub status: SatelliteStatus,
+}
+
+fn default_seed_status() -> SatelliteStatus {
+    SatelliteStatus::Active
+}
+
+impl SatelliteSeed {
+    /// Read a seed file in JSON format
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// Add all seed satellites missing from the repository.
+///
+/// Satellites are matched by id when the seed entry has one and by name
+/// otherwise. Existing satellites are left untouched, so status changes made at
+/// runtime survive restarts. Returns the number of satellites added.
+pub async fn apply_seed(
+    repository: &dyn SatelliteRepository,
+    seed: &SatelliteSeed,
+) -> Result<usize, RepositoryError> {
+    let existing = repository.list().await?;
+    let mut added = 0;
+
+    for entry in &seed.satellites {
+        let present = existing.iter().any(|s| match entry.id {
+            Some(id) => s.id == id,
+            None => s.name == entry.name,
+        });
+        if present {
+            debug!(name = %entry.name, "Seed satellite already registered");
+            continue;
+        }
+
+        let mut satellite = Satellite::new(entry.name.clone());
+        if let Some(id) = entry.id {
+            satellite.id = id;
+        }
+        satellite.status = entry.status;
+
+        repository.save(satellite).await?;
+        added += 1;
+    }
+
+    info!(
+        seeded = added,
+        configured = seed.satellites.len(),
+        "Applied satellite seed"
+    );
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::InMemorySatelliteRepository;
+
+    fn seed() -> SatelliteSeed {
+        serde_json::from_str(
+            r#"{
+                "satellites": [
+                    { "id": "7f3c9a52-1a54-4b8e-9d4f-3c2b1a0e9f11", "name": "Beacon-1" },
+                    { "name": "Beacon-2" },
+                    { "name": "Beacon-3", "status": "Maintenance" }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_seed_satellites_are_active_by_default() {
+        let repository = InMemorySatelliteRepository::new();
+
+        let added = apply_seed(&repository, &seed()).await.unwrap();
+        assert_eq!(added, 3);
+
+        let satellites = repository.list().await.unwrap();
+        let active = satellites.iter().filter(|s| s.is_active()).count();
+        assert_eq!(active, 2);
+
+        let id = Uuid::parse_str("7f3c9a52-1a54-4b8e-9d4f-3c2b1a0e9f11").unwrap();
+        assert_eq!(repository.get(id).await.unwrap().unwrap().name, "Beacon-1");
+    }
+
+    #[tokio::test]
+    async fn test_seed_is_idempotent_and_keeps_runtime_changes() {
+        let repository = InMemorySatelliteRepository::new();
+        apply_seed(&repository, &seed()).await.unwrap();
+
+        let mut beacon = repository
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|s| s.name == "Beacon-2")
+            .unwrap();
+        beacon.status = SatelliteStatus::Inactive;
+        repository.save(beacon.clone()).await.unwrap();
+
+        let added = apply_seed(&repository, &seed()).await.unwrap();
+        assert_eq!(added, 0);
+        assert_eq!(repository.list().await.unwrap().len(), 3);
+        assert_eq!(
+            repository.get(beacon.id).await.unwrap().unwrap().status,
+            SatelliteStatus::Inactive
+        );
+    }
+
+    #[test]
+    fn test_bundled_seed_file_parses() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/satellites.json");
+        let seed = SatelliteSeed::from_file(path).unwrap();
+
+        assert!(!seed.satellites.is_empty());
+        assert!(
+            seed.satellites
+                .iter()
+                .all(|s| s.status == SatelliteStatus::Active)
+        );
+    }
+}
@@ -1,11 +1,7 @@
 use crate::models::FlightPositionRequest;
 use crate::services::{SatelliteService, SatelliteServiceError};
-use axum::{
-    Json,
-    extract::{Path, State},
-    http::StatusCode,
-};
-use serde_json::json;
+use axum::{Json, extract::State, http::StatusCode};
+use sky_tracer::protocol::problem::{PROBLEM_JSON, ProblemDetails, ProblemJson, ProblemPath};
 use sky_tracer::protocol::satellite::{
     CalculatePositionRequest, CalculatePositionResponse, CreateSatelliteRequest, SatelliteResponse,
     UpdateSatelliteStatusRequest,
@@ -24,15 +20,15 @@ use uuid::Uuid;
     responses(
         (status = 201, description = "Satellite created successfully", body = SatelliteResponse),
         (status = 400, description = "Invalid satellite data"),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "satellites"
 )]
 #[instrument(skip(service), fields(satellite_name = %request.name))]
 pub async fn create_satellite(
     State(service): State<SatelliteService>,
-    Json(request): Json<CreateSatelliteRequest>,
-) -> Result<(StatusCode, Json<SatelliteResponse>), (StatusCode, Json<serde_json::Value>)> {
+    ProblemJson(request): ProblemJson<CreateSatelliteRequest>,
+) -> Result<(StatusCode, Json<SatelliteResponse>), ProblemDetails> {
     info!("Creating new satellite via API");
 
     match service.create_satellite(request.name).await {
@@ -53,10 +49,7 @@ pub async fn create_satellite(
         }
         Err(e) => {
             error!(error = %e, "Failed to create satellite");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": format!("Failed to create satellite: {}", e) })),
-            ))
+            Err(e.into())
         }
     }
 }
@@ -68,8 +61,8 @@ pub async fn create_satellite(
     request_body = UpdateSatelliteStatusRequest,
     responses(
         (status = 200, description = "Satellite status updated", body = SatelliteResponse),
-        (status = 404, description = "Satellite not found"),
-        (status = 500, description = "Internal server error")
+        (status = 404, description = "Satellite not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     params(
         ("id" = Uuid, Path, description = "Satellite ID")
@@ -79,9 +72,9 @@ pub async fn create_satellite(
 #[instrument(skip(service), fields(satellite_id = %id, new_status = ?request.status))]
 pub async fn update_satellite_status(
     State(service): State<SatelliteService>,
-    Path(id): Path<Uuid>,
-    Json(request): Json<UpdateSatelliteStatusRequest>,
-) -> Result<Json<SatelliteResponse>, (StatusCode, Json<serde_json::Value>)> {
+    ProblemPath(id): ProblemPath<Uuid>,
+    ProblemJson(request): ProblemJson<UpdateSatelliteStatusRequest>,
+) -> Result<Json<SatelliteResponse>, ProblemDetails> {
     info!("Updating satellite status via API");
 
     match service.update_satellite_status(id, request.status).await {
@@ -100,19 +93,13 @@ pub async fn update_satellite_status(
             };
             Ok(Json(response))
         }
-        Err(SatelliteServiceError::InvalidSatelliteId(_)) => {
+        Err(e @ SatelliteServiceError::InvalidSatelliteId(_)) => {
             warn!(satellite_id = %id, "Satellite not found for status update");
-            Err((
-                StatusCode::NOT_FOUND,
-                Json(json!({ "error": format!("Satellite not found: {}", id) })),
-            ))
+            Err(e.into())
         }
         Err(e) => {
             error!(satellite_id = %id, error = %e, "Failed to update satellite status");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": format!("Failed to update satellite status: {}", e) })),
-            ))
+            Err(e.into())
         }
     }
 }
@@ -123,17 +110,20 @@ pub async fn update_satellite_status(
     path = SATELLITES_API_PATH,
     responses(
         (status = 200, description = "List of satellites", body = Vec<SatelliteResponse>),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "satellites"
 )]
 #[instrument(skip(service))]
 pub async fn list_satellites(
     State(service): State<SatelliteService>,
-) -> Json<Vec<SatelliteResponse>> {
+) -> Result<Json<Vec<SatelliteResponse>>, ProblemDetails> {
     info!("Listing all satellites via API");
 
-    let satellites = service.list_satellites().await;
+    let satellites = service.list_satellites().await.map_err(|e| {
+        error!(error = %e, "Failed to list satellites");
+        ProblemDetails::from(e)
+    })?;
 
     let response: Vec<SatelliteResponse> = satellites
         .into_iter()
@@ -148,7 +138,7 @@ pub async fn list_satellites(
         satellites_count = response.len(),
         "Retrieved satellite list via API"
     );
-    Json(response)
+    Ok(Json(response))
 }
 
 /// Calculate flight position
@@ -159,8 +149,10 @@ pub async fn list_satellites(
     responses(
         (status = 200, description = "Flight positions calculated", body = CalculatePositionResponse),
         (status = 400, description = "Invalid request data"),
-        (status = 404, description = "Airport or satellites not found"),
-        (status = 500, description = "Internal server error")
+        (status = 404, description = "Airport or satellites not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 502, description = "Airport service failed", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 504, description = "Airport service timed out", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "satellites"
 )]
@@ -173,8 +165,8 @@ pub async fn list_satellites(
 ))]
 pub async fn calculate_position(
     State(service): State<SatelliteService>,
-    Json(request): Json<CalculatePositionRequest>,
-) -> Result<Json<CalculatePositionResponse>, (StatusCode, Json<serde_json::Value>)> {
+    ProblemJson(request): ProblemJson<CalculatePositionRequest>,
+) -> Result<Json<CalculatePositionResponse>, ProblemDetails> {
     info!("Processing position calculation request via API");
 
     let flight_request = FlightPositionRequest::new(
@@ -214,33 +206,21 @@ pub async fn calculate_position(
 
             Ok(Json(response))
         }
-        Err(SatelliteServiceError::NoActiveSatellites) => {
+        Err(e @ SatelliteServiceError::NoActiveSatellites) => {
             warn!("No active satellites available for tracking");
-            Err((
-                StatusCode::NOT_FOUND,
-                Json(json!({ "error": "No active satellites available for tracking" })),
-            ))
+            Err(e.into())
         }
-        Err(SatelliteServiceError::AirportNotFound(code)) => {
-            warn!(airport_code = %code, "Airport not found");
-            Err((
-                StatusCode::NOT_FOUND,
-                Json(json!({ "error": format!("Airport not found: {}", code) })),
-            ))
+        Err(e @ SatelliteServiceError::AirportNotFound(_)) => {
+            warn!(error = %e, "Airport not found");
+            Err(e.into())
         }
-        Err(SatelliteServiceError::AirportFetchError(e)) => {
+        Err(e @ SatelliteServiceError::AirportFetchError(_)) => {
             error!(error = %e, "Failed to fetch airport data");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": format!("Failed to fetch airport data: {}", e) })),
-            ))
+            Err(e.into())
         }
         Err(e) => {
             error!(error = %e, "Failed to calculate flight position");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": format!("Failed to calculate position: {}", e) })),
-            ))
+            Err(e.into())
         }
     }
 }
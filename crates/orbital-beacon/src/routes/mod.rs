@@ -6,12 +6,12 @@ use crate::ui::pages::{
     FlightPosition, FlightPositionProps, Home, HomeProps, Launch, LaunchProps, UpdateStatus,
     UpdateStatusProps,
 };
-use crate::utils::get_path_prefix;
-use axum::extract::{Path, Query, State};
+use axum::extract::{Query, State};
 use axum::response::{Html, Redirect};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use sky_tracer::model::SatelliteStatus;
+use sky_tracer::protocol::problem::ProblemPath;
 use sky_tracer::protocol::satellite::SatelliteResponse;
 use std::collections::HashMap;
 use tracing::{error, info, instrument, warn};
@@ -67,7 +67,10 @@ fn render_html(title: &str, body: String) -> Html<String> {
 pub async fn render_home(State(service): State<SatelliteService>) -> Html<String> {
     info!("Rendering home page");
 
-    let satellites = service.list_satellites().await;
+    let satellites = service.list_satellites().await.unwrap_or_else(|e| {
+        error!(error = %e, "Failed to list satellites");
+        Vec::new()
+    });
     let satellites = satellites
         .into_iter()
         .map(|s| SatelliteResponse {
@@ -77,22 +80,30 @@ pub async fn render_home(State(service): State<SatelliteService>) -> Html<String
         })
         .collect();
 
-    let renderer = ServerRenderer::<Home>::with_props(move || HomeProps { satellites });
+    let path_prefix = service.path_prefix().to_string();
+    let renderer = ServerRenderer::<Home>::with_props(move || HomeProps {
+        satellites,
+        path_prefix,
+    });
 
     let body = renderer.render().await;
     render_html("Orbital Beacon", body)
 }
 
 #[axum::debug_handler]
-#[instrument(skip(_service))]
+#[instrument(skip(service))]
 pub async fn render_launch(
-    State(_service): State<SatelliteService>,
+    State(service): State<SatelliteService>,
     Query(params): Query<HashMap<String, String>>,
 ) -> Html<String> {
     info!("Rendering launch page");
     let flash_message = params.get("message").cloned();
+    let path_prefix = service.path_prefix().to_string();
 
-    let renderer = ServerRenderer::<Launch>::with_props(move || LaunchProps { flash_message });
+    let renderer = ServerRenderer::<Launch>::with_props(move || LaunchProps {
+        flash_message,
+        path_prefix,
+    });
 
     let body = renderer.render().await;
     render_html("Launch Satellite", body)
@@ -106,7 +117,7 @@ pub async fn handle_launch(
 ) -> Redirect {
     info!(satellite_name = %form.name, "Handling satellite launch request");
 
-    let path_prefix = get_path_prefix();
+    let path_prefix = service.path_prefix();
 
     match service.create_satellite(form.name.clone()).await {
         Ok(satellite) => {
@@ -123,16 +134,19 @@ pub async fn handle_launch(
 }
 
 #[axum::debug_handler]
-#[instrument(skip(_service))]
+#[instrument(skip(service))]
 pub async fn render_update_status(
-    State(_service): State<SatelliteService>,
+    State(service): State<SatelliteService>,
     Query(params): Query<HashMap<String, String>>,
 ) -> Html<String> {
     info!("Rendering update status page");
     let flash_message = params.get("message").cloned();
+    let path_prefix = service.path_prefix().to_string();
 
-    let renderer =
-        ServerRenderer::<UpdateStatus>::with_props(move || UpdateStatusProps { flash_message });
+    let renderer = ServerRenderer::<UpdateStatus>::with_props(move || UpdateStatusProps {
+        flash_message,
+        path_prefix,
+    });
 
     let body = renderer.render().await;
     render_html("Update Satellite Status", body)
@@ -142,12 +156,12 @@ pub async fn render_update_status(
 #[instrument(skip(service))]
 pub async fn handle_update_status(
     State(service): State<SatelliteService>,
-    Path(id): Path<Uuid>,
+    ProblemPath(id): ProblemPath<Uuid>,
     axum::extract::Form(form): axum::extract::Form<UpdateStatusForm>,
 ) -> Redirect {
     info!(satellite_id = %id, new_status = %form.status, "Handling satellite status update");
 
-    let path_prefix = get_path_prefix();
+    let path_prefix = service.path_prefix();
 
     let status = match form.status.as_str() {
         "Active" => SatelliteStatus::Active,
@@ -322,9 +336,11 @@ pub async fn render_flight_position(
         (None, None)
     };
 
+    let path_prefix = service.path_prefix().to_string();
     let renderer = ServerRenderer::<FlightPosition>::with_props(move || FlightPositionProps {
         position_data,
         error_message,
+        path_prefix,
     });
 
     let body = renderer.render().await;
@@ -1,18 +1,14 @@
 use crate::models::{FlightPositionRequest, PositionCalculation};
-use async_trait::async_trait;
-use http::Extensions;
-use opentelemetry::global;
-use reqwest::{Request, Response};
-use reqwest_middleware::{
-    ClientBuilder, ClientWithMiddleware, Middleware, Next, Result as MiddlewareResult,
-};
-use sky_tracer::model::{Position, Satellite, SatelliteStatus};
-use sky_tracer::protocol::AIRPORTS_SEARCH_API_PATH;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use crate::repository::{InMemorySatelliteRepository, RepositoryError, SatelliteRepository};
+use sky_tracer::client::{AirportsClient, ClientConfig, ClientError};
+use sky_tracer::clock::SharedClock;
+use sky_tracer::model::{FlightProfile, Position, Satellite, SatelliteStatus};
+use sky_tracer::protocol::health::{DependencyHealth, check_dependency};
+use sky_tracer::protocol::metrics::Metrics;
+use sky_tracer::protocol::problem::ProblemDetails;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use tracing::{debug, error, info, instrument, warn};
-use tracing_opentelemetry::OpenTelemetrySpanExt;
 use uuid::Uuid;
 
 #[derive(thiserror::Error, Debug)]
@@ -20,60 +16,127 @@ pub enum SatelliteServiceError {
     #[error("No active satellites available")]
     NoActiveSatellites,
     #[error("Failed to fetch airport data: {0}")]
-    AirportFetchError(#[from] reqwest_middleware::Error),
+    AirportFetchError(#[from] ClientError),
     #[error("Airport not found: {0}")]
     AirportNotFound(String),
     #[error("Invalid satellite ID: {0}")]
     InvalidSatelliteId(String),
+    #[error("Satellite repository error: {0}")]
+    Repository(#[from] RepositoryError),
 }
 
-struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
-
-impl opentelemetry::propagation::Injector for HeaderInjector<'_> {
-    fn set(&mut self, key: &str, value: String) {
-        if let Ok(name) = reqwest::header::HeaderName::from_bytes(key.as_bytes()) {
-            if let Ok(val) = reqwest::header::HeaderValue::from_str(&value) {
-                self.0.insert(name, val);
+impl From<SatelliteServiceError> for ProblemDetails {
+    fn from(error: SatelliteServiceError) -> Self {
+        match error {
+            SatelliteServiceError::NoActiveSatellites => {
+                ProblemDetails::new(404, "No active satellites")
+                    .with_code("NO_ACTIVE_SATELLITES")
+                    .with_detail("No active satellites available for tracking")
+            }
+            SatelliteServiceError::AirportNotFound(code) => {
+                ProblemDetails::new(404, "Airport not found")
+                    .with_code("NOT_FOUND")
+                    .with_detail(format!("Airport not found: {}", code))
+            }
+            SatelliteServiceError::InvalidSatelliteId(id) => {
+                ProblemDetails::new(404, "Satellite not found")
+                    .with_code("NOT_FOUND")
+                    .with_detail(format!("Satellite not found: {}", id))
+            }
+            SatelliteServiceError::AirportFetchError(error) => error.to_problem(),
+            error @ SatelliteServiceError::Repository(_) => {
+                ProblemDetails::new(500, "Internal server error")
+                    .with_code("INTERNAL_ERROR")
+                    .with_detail(error.to_string())
             }
         }
     }
 }
 
-struct OtelMiddleware;
-
-#[async_trait]
-impl Middleware for OtelMiddleware {
-    async fn handle(
-        &self,
-        mut req: Request,
-        extensions: &mut Extensions,
-        next: Next<'_>,
-    ) -> MiddlewareResult<Response> {
-        let cx = tracing::Span::current().context();
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(&cx, &mut HeaderInjector(req.headers_mut()));
-        });
-        next.run(req, extensions).await
-    }
-}
-
 #[derive(Clone)]
 pub struct SatelliteService {
-    satellites: Arc<RwLock<HashMap<Uuid, Satellite>>>,
-    airport_service_url: String,
-    http_client: ClientWithMiddleware,
+    repository: Arc<dyn SatelliteRepository>,
+    airports: AirportsClient,
+    clock: SharedClock,
+    path_prefix: String,
 }
 
 impl SatelliteService {
+    /// Create a satellite service with a volatile in-memory repository
     pub fn new(airport_service_url: String) -> Self {
-        let http_client = ClientBuilder::new(reqwest::Client::new())
-            .with(OtelMiddleware)
-            .build();
+        Self::with_repository(
+            airport_service_url,
+            Arc::new(InMemorySatelliteRepository::new()),
+        )
+    }
 
+    /// Create a satellite service backed by the given repository
+    pub fn with_repository(
+        airport_service_url: String,
+        repository: Arc<dyn SatelliteRepository>,
+    ) -> Self {
         Self {
-            satellites: Arc::new(RwLock::new(HashMap::new())),
-            airport_service_url,
-            http_client,
+            repository,
+            airports: AirportsClient::with_config(
+                ClientConfig::new(airport_service_url).with_env_overrides("AIRPORTS_SERVICE"),
+            ),
+            clock: SharedClock::default(),
+            path_prefix: String::new(),
+        }
+    }
+
+    /// Serve the pages below `path_prefix`, e.g. `/satellites` behind a
+    /// proxy stripping it
+    pub fn with_path_prefix(mut self, path_prefix: impl Into<String>) -> Self {
+        self.path_prefix = path_prefix.into();
+        self
+    }
+
+    /// Prefix the links of the pages start with
+    pub fn path_prefix(&self) -> &str {
+        &self.path_prefix
+    }
+
+    /// Position flights requested without a time at the time of the given
+    /// clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Clock of the service
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Health of the airport service the positions are calculated with
+    pub async fn readiness(&self) -> Vec<DependencyHealth> {
+        vec![
+            check_dependency("airport-anywhere", self.airports.live())
+                .await
+                .with_circuit(self.airports.circuit_state()),
+        ]
+    }
+
+    /// Refresh the number of active satellites for the metrics scrape
+    pub async fn observe_metrics(&self) {
+        // Registered once per process, the gauge reports the last count
+        static ACTIVE: OnceLock<Arc<AtomicU64>> = OnceLock::new();
+        match self.repository.list().await {
+            Ok(satellites) => {
+                let active = satellites.iter().filter(|s| s.is_active()).count();
+                ACTIVE
+                    .get_or_init(|| {
+                        Metrics::global().service_gauge(
+                            "orbital-beacon",
+                            "sky_tracer.satellites.active",
+                            "{satellite}",
+                            "Satellites currently calculating positions",
+                        )
+                    })
+                    .store(active as u64, Ordering::Relaxed);
+            }
+            Err(e) => warn!(error = %e, "Cannot count satellites for metrics"),
         }
     }
 
@@ -82,8 +145,7 @@ impl SatelliteService {
     pub async fn create_satellite(&self, name: String) -> Result<Satellite, SatelliteServiceError> {
         info!(name = %name, "Creating new satellite");
         let satellite = Satellite::new(name);
-        let mut satellites = self.satellites.write().await;
-        satellites.insert(satellite.id, satellite.clone());
+        self.repository.save(satellite.clone()).await?;
         info!(id = %satellite.id, name = %satellite.name, "Satellite created successfully");
         Ok(satellite)
     }
@@ -96,13 +158,13 @@ impl SatelliteService {
         status: SatelliteStatus,
     ) -> Result<Satellite, SatelliteServiceError> {
         info!(id = %id, status = ?status, "Updating satellite status");
-        let mut satellites = self.satellites.write().await;
 
-        match satellites.get_mut(&id) {
-            Some(satellite) => {
+        match self.repository.get(id).await? {
+            Some(mut satellite) => {
                 satellite.status = status;
+                self.repository.save(satellite.clone()).await?;
                 info!(id = %id, name = %satellite.name, status = ?status, "Satellite status updated successfully");
-                Ok(satellite.clone())
+                Ok(satellite)
             }
             None => {
                 warn!(id = %id, "Satellite not found for status update");
@@ -113,10 +175,12 @@ impl SatelliteService {
 
     /// Get a specific satellite by ID
     #[instrument(skip(self))]
-    pub async fn get_satellite(&self, id: Uuid) -> Option<Satellite> {
+    pub async fn get_satellite(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<Satellite>, SatelliteServiceError> {
         debug!(id = %id, "Retrieving satellite by ID");
-        let satellites = self.satellites.read().await;
-        let satellite = satellites.get(&id).cloned();
+        let satellite = self.repository.get(id).await?;
 
         if satellite.is_some() {
             debug!(id = %id, "Satellite found");
@@ -124,15 +188,14 @@ impl SatelliteService {
             debug!(id = %id, "Satellite not found");
         }
 
-        satellite
+        Ok(satellite)
     }
 
     /// List all satellites
     #[instrument(skip(self))]
-    pub async fn list_satellites(&self) -> Vec<Satellite> {
+    pub async fn list_satellites(&self) -> Result<Vec<Satellite>, SatelliteServiceError> {
         debug!("Listing all satellites");
-        let satellites = self.satellites.read().await;
-        let satellite_list: Vec<Satellite> = satellites.values().cloned().collect();
+        let satellite_list = self.repository.list().await?;
 
         let active_count = satellite_list.iter().filter(|s| s.is_active()).count();
         info!(
@@ -141,7 +204,7 @@ impl SatelliteService {
             "Retrieved satellite list"
         );
 
-        satellite_list
+        Ok(satellite_list)
     }
 
     /// Calculate flight position using active satellites
@@ -157,11 +220,12 @@ impl SatelliteService {
         );
 
         // Check for active satellites
-        let satellites = self.satellites.read().await;
-        let active_satellites: Vec<_> = satellites
-            .values()
+        let active_satellites: Vec<_> = self
+            .repository
+            .list()
+            .await?
+            .into_iter()
             .filter(|s| s.is_active())
-            .cloned()
             .collect();
 
         if active_satellites.is_empty() {
@@ -194,27 +258,31 @@ impl SatelliteService {
         );
 
         // Calculate positions if flight is in progress
+        let now = request.current_time.unwrap_or_else(|| self.clock.now());
+        let request = request.with_current_time(now);
         let positions = if request.is_in_progress() {
             let progress = request.calculate_progress();
-            let current_lat = departure_airport.latitude
-                + (arrival_airport.latitude - departure_airport.latitude) * progress;
-            let current_lon = departure_airport.longitude
-                + (arrival_airport.longitude - departure_airport.longitude) * progress;
-            let altitude = 10000.0; // Standard cruising altitude
+            let profile = FlightProfile::new(
+                departure_airport.position(),
+                arrival_airport.position(),
+                request.arrival_time - request.departure_time,
+            );
+            let state = profile.state_at(progress);
 
             debug!(
                 progress = progress,
-                current_lat = current_lat,
-                current_lon = current_lon,
-                altitude = altitude,
+                current_lat = state.latitude,
+                current_lon = state.longitude,
+                altitude = state.altitude,
+                ground_speed = state.ground_speed,
+                heading = state.heading,
+                phase = ?state.phase,
                 "Calculated current flight position"
             );
 
             active_satellites
                 .iter()
-                .map(|satellite| {
-                    Position::new(current_lat, current_lon, altitude as f32, satellite.id)
-                })
+                .map(|satellite| Position::from_state(&state, satellite.id, now))
                 .collect()
         } else {
             debug!("Flight not in progress, returning empty positions");
@@ -238,59 +306,40 @@ impl SatelliteService {
     async fn fetch_airport(
         &self,
         code: &str,
-    ) -> Result<Option<sky_tracer::model::Airport>, reqwest_middleware::Error> {
+    ) -> Result<Option<sky_tracer::model::Airport>, ClientError> {
         info!(code = %code, "Fetching airport information");
 
-        let url = format!(
-            "{}{}?code={}",
-            self.airport_service_url, AIRPORTS_SEARCH_API_PATH, code
-        );
+        match self.airports.airport(code).await {
+            Ok(Some(airport_response)) => {
+                let airport = sky_tracer::model::Airport::from(airport_response);
 
-        debug!(url = %url, "Making request to airport service");
+                info!(
+                    code = %code,
+                    name = %airport.name,
+                    lat = airport.latitude,
+                    lon = airport.longitude,
+                    "Successfully retrieved airport information"
+                );
 
-        let response = self.http_client.get(&url).send().await?;
-
-        if response.status().is_success() {
-            match response
-                .json::<sky_tracer::protocol::airports::SearchAirportsResponse>()
-                .await
-            {
-                Ok(search_response) => {
-                    if let Some(airport_response) = search_response.airports.first() {
-                        let airport = sky_tracer::model::Airport {
-                            id: airport_response.id,
-                            latitude: airport_response.position.latitude,
-                            longitude: airport_response.position.longitude,
-                            name: airport_response.name.clone(),
-                            code: airport_response.code.clone(),
-                        };
-
-                        info!(
-                            code = %code,
-                            name = %airport.name,
-                            lat = airport.latitude,
-                            lon = airport.longitude,
-                            "Successfully retrieved airport information"
-                        );
-
-                        Ok(Some(airport))
-                    } else {
-                        warn!(code = %code, "Airport not found in search results");
-                        Ok(None)
-                    }
-                }
-                Err(e) => {
-                    error!(code = %code, error = %e, "Failed to parse airport search response");
-                    Ok(None)
-                }
+                Ok(Some(airport))
             }
-        } else {
-            warn!(
-                code = %code,
-                status = %response.status(),
-                "Airport service returned non-success status"
-            );
-            Ok(None)
+            Ok(None) => {
+                warn!(code = %code, "Airport not found in search results");
+                Ok(None)
+            }
+            Err(e @ ClientError::Decode { .. }) => {
+                error!(code = %code, error = %e, "Failed to parse airport search response");
+                Ok(None)
+            }
+            Err(ClientError::Api { status, .. }) => {
+                warn!(
+                    code = %code,
+                    status = %status,
+                    "Airport service returned non-success status"
+                );
+                Ok(None)
+            }
+            Err(e) => Err(e),
         }
     }
 }
@@ -349,7 +398,7 @@ mod tests {
         let service = SatelliteService::new("http://localhost:3000".to_string());
 
         // Initially empty
-        let satellites = service.list_satellites().await;
+        let satellites = service.list_satellites().await.unwrap();
         assert!(satellites.is_empty());
 
         // Add some satellites
@@ -362,7 +411,7 @@ mod tests {
             .await
             .unwrap();
 
-        let satellites = service.list_satellites().await;
+        let satellites = service.list_satellites().await.unwrap();
         assert_eq!(satellites.len(), 2);
     }
 
@@ -1,8 +1,13 @@
 use yew::prelude::*;
 
+#[derive(Properties, PartialEq)]
+pub struct LaunchSatelliteProps {
+    pub path_prefix: String,
+}
+
 #[function_component(LaunchSatellite)]
-pub fn launch_satellite() -> Html {
-    let path_prefix = crate::utils::get_path_prefix();
+pub fn launch_satellite(props: &LaunchSatelliteProps) -> Html {
+    let path_prefix = &props.path_prefix;
 
     html! {
         <div class="launch-satellite">
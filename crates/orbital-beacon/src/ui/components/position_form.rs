@@ -7,11 +7,16 @@ fn calculate_default_times(current_time: DateTime<Utc>) -> (DateTime<Utc>, DateT
     (departure_time, arrival_time)
 }
 
+#[derive(Properties, PartialEq)]
+pub struct PositionFormProps {
+    pub path_prefix: String,
+}
+
 #[function_component(PositionForm)]
-pub fn position_form() -> Html {
-    let now = Utc::now();
+pub fn position_form(props: &PositionFormProps) -> Html {
+    let now = sky_tracer::clock::now();
     let (departure_time, arrival_time) = calculate_default_times(now);
-    let path_prefix = crate::utils::get_path_prefix();
+    let path_prefix = &props.path_prefix;
 
     html! {
         <div class="position-form">
@@ -4,11 +4,12 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq)]
 pub struct SatelliteListProps {
     pub satellites: Vec<SatelliteResponse>,
+    pub path_prefix: String,
 }
 
 #[function_component(SatelliteList)]
 pub fn satellite_list(props: &SatelliteListProps) -> Html {
-    let path_prefix = crate::utils::get_path_prefix();
+    let path_prefix = &props.path_prefix;
 
     html! {
         <div class="satellite-list">
@@ -32,7 +33,7 @@ pub fn satellite_list(props: &SatelliteListProps) -> Html {
                                         {&satellite.name}
                                     </div>
                                     <div class="control-panel">
-                                        <form action={format!("{}/update_status/{}", path_prefix.clone(), satellite.id)} method="POST">
+                                        <form action={format!("{}/update_status/{}", path_prefix, satellite.id)} method="POST">
                                             <select name="status" class="status-select">
                                                 <option value="Active">{"Active"}</option>
                                                 <option value="Inactive">{"Inactive"}</option>
@@ -6,21 +6,22 @@ use yew::prelude::*;
 pub struct FlightPositionProps {
     pub position_data: Option<CalculatePositionResponse>,
     pub error_message: Option<String>,
+    pub path_prefix: String,
 }
 
 #[function_component(FlightPosition)]
 pub fn flight_position(props: &FlightPositionProps) -> Html {
-    let path_prefix = crate::utils::get_path_prefix();
+    let path_prefix = &props.path_prefix;
 
     html! {
         <div class="container">
             <header>
                 <h1>{"Flight Position"}</h1>
-                <a href={path_prefix} class="back-link">{"Back to Home"}</a>
+                <a href={path_prefix.clone()} class="back-link">{"Back to Home"}</a>
             </header>
 
             <main>
-                <PositionForm />
+                <PositionForm path_prefix={path_prefix.clone()} />
 
                 {
                     if let Some(error) = &props.error_message {
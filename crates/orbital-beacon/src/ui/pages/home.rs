@@ -5,11 +5,13 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq)]
 pub struct HomeProps {
     pub satellites: Vec<SatelliteResponse>,
+    /// Prefix of the links, e.g. `/satellites` behind the gateway
+    pub path_prefix: String,
 }
 
 #[function_component(Home)]
 pub fn home(props: &HomeProps) -> Html {
-    let path_prefix = crate::utils::get_path_prefix();
+    let path_prefix = &props.path_prefix;
 
     html! {
         <div class="container">
@@ -29,7 +31,10 @@ pub fn home(props: &HomeProps) -> Html {
             <main>
                 <div class="grid">
                     <div class="panel">
-                        <SatelliteList satellites={props.satellites.clone()} />
+                        <SatelliteList
+                            satellites={props.satellites.clone()}
+                            path_prefix={path_prefix.clone()}
+                        />
                     </div>
                 </div>
             </main>
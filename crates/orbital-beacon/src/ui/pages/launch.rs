@@ -4,11 +4,12 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq)]
 pub struct LaunchProps {
     pub flash_message: Option<String>,
+    pub path_prefix: String,
 }
 
 #[function_component(Launch)]
 pub fn launch(props: &LaunchProps) -> Html {
-    let path_prefix = crate::utils::get_path_prefix();
+    let path_prefix = props.path_prefix.clone();
 
     html! {
         <div class="container">
@@ -27,7 +28,7 @@ pub fn launch(props: &LaunchProps) -> Html {
             }}
 
             <main>
-                <LaunchSatellite />
+                <LaunchSatellite path_prefix={path_prefix.clone()} />
                 <a href={path_prefix} class="back-link">{"Back to Home"}</a>
             </main>
         </div>
@@ -3,11 +3,12 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq)]
 pub struct UpdateStatusProps {
     pub flash_message: Option<String>,
+    pub path_prefix: String,
 }
 
 #[function_component(UpdateStatus)]
 pub fn update_status(props: &UpdateStatusProps) -> Html {
-    let path_prefix = crate::utils::get_path_prefix();
+    let path_prefix = props.path_prefix.clone();
 
     html! {
         <div class="container">
@@ -1,8 +1,65 @@
-use crate::{openapi, routes};
-use axum::Router;
+use crate::{
+    openapi, routes,
+    services::{NexusState, health},
+};
+use axum::{Router, middleware};
+use sky_tracer::clock::follow_caller_time;
+use sky_tracer::protocol::health::health_routes;
+use sky_tracer::protocol::metrics::{metrics_routes, observe};
+use sky_tracer::protocol::problem::problem_instance;
 
-pub fn app() -> Router {
-    Router::new()
+pub fn app(state: NexusState) -> Router {
+    let app = Router::new()
         .merge(openapi::routes())
-        .merge(routes::create_router())
+        .merge(routes::create_router(&state))
+        .layer(middleware::from_fn_with_state(
+            state.clock.clone(),
+            follow_caller_time,
+        ));
+    let probes = health_routes("sky-nexus", |state: NexusState| async move {
+        health::readiness(&state.upstreams).await
+    })
+    .merge(metrics_routes(|_: NexusState| async {}));
+    observe("sky-nexus", app, probes)
+        .with_state(state)
+        .layer(middleware::from_fn(problem_instance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Upstreams;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode, header::CONTENT_TYPE},
+    };
+    use sky_tracer::clock::SharedClock;
+    use sky_tracer::protocol::NEXUS_FLIGHTS_API_PATH;
+    use sky_tracer::protocol::problem::{PROBLEM_JSON, ProblemDetails};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_invalid_flight_body_is_a_problem() {
+        let response = app(NexusState {
+            upstreams: Upstreams::from_env(),
+            clock: SharedClock::default(),
+        })
+        .oneshot(
+            Request::post(NEXUS_FLIGHTS_API_PATH)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.headers()[CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code.as_deref(), Some("INVALID_BODY"));
+        assert_eq!(problem.instance.as_deref(), Some(NEXUS_FLIGHTS_API_PATH));
+    }
 }
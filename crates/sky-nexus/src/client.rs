@@ -1,43 +0,0 @@
-use async_trait::async_trait;
-use http::Extensions;
-use opentelemetry::global;
-use reqwest::{Request, Response};
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, Next, Result};
-use tracing::Span;
-use tracing_opentelemetry::OpenTelemetrySpanExt;
-
-struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
-
-impl opentelemetry::propagation::Injector for HeaderInjector<'_> {
-    fn set(&mut self, key: &str, value: String) {
-        if let Ok(name) = reqwest::header::HeaderName::from_bytes(key.as_bytes()) {
-            if let Ok(val) = reqwest::header::HeaderValue::from_str(&value) {
-                self.0.insert(name, val);
-            }
-        }
-    }
-}
-
-pub struct OtelMiddleware;
-
-#[async_trait]
-impl Middleware for OtelMiddleware {
-    async fn handle(
-        &self,
-        mut req: Request,
-        extensions: &mut Extensions,
-        next: Next<'_>,
-    ) -> Result<Response> {
-        let cx = Span::current().context();
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(&cx, &mut HeaderInjector(req.headers_mut()));
-        });
-        next.run(req, extensions).await
-    }
-}
-
-pub fn create_client() -> ClientWithMiddleware {
-    ClientBuilder::new(reqwest::Client::new())
-        .with(OtelMiddleware)
-        .build()
-}
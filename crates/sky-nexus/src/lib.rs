@@ -1,5 +1,4 @@
 pub mod app;
-pub mod client;
 pub mod mcp;
 pub mod models;
 pub mod openapi;
@@ -1,10 +1,13 @@
 use sky_nexus::app;
+use sky_nexus::services::{NexusState, Upstreams};
 use std::env;
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
     let _guard = init_tracing_opentelemetry::tracing_subscriber_ext::init_subscribers()?;
+    let clock = sky_tracer::clock::init_from_env()?;
+    info!(clock = ?clock, "Configured clock");
 
     // Get service configuration
     let service_port = env::var("PORT")
@@ -15,25 +18,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
     let service_name = env::var("SERVICE_NAME").unwrap_or_else(|_| "sky-nexus".to_string());
 
     // Get service base URLs
-    let airport_service_base_url = env::var("AIRPORT_SERVICE_BASE_URL")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string());
-
-    let flight_service_base_url =
-        env::var("FLIGHT_SERVICE_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string());
-
-    let satellite_service_base_url = env::var("SATELLITE_SERVICE_BASE_URL")
-        .unwrap_or_else(|_| "http://localhost:3002".to_string());
-
-    let babel_service_base_url =
-        env::var("BABEL_SERVICE_BASE_URL").unwrap_or_else(|_| "http://localhost:3003".to_string());
+    let upstreams = Upstreams::from_env();
 
     info!("Starting {} service on port {}", service_name, service_port);
-    info!(airport_service_base_url = %airport_service_base_url, "Configured Airport Service base URL");
-    info!(flight_service_base_url = %flight_service_base_url, "Configured Flight Service base URL");
-    info!(satellite_service_base_url = %satellite_service_base_url, "Configured Satellite Service base URL");
-    info!(babel_service_base_url = %babel_service_base_url, "Configured Babel Service base URL");
+    info!(airport_service_base_url = %upstreams.airports.base_url(), "Configured Airport Service base URL");
+    info!(flight_service_base_url = %upstreams.flights.base_url(), "Configured Flight Service base URL");
+    info!(satellite_service_base_url = %upstreams.satellites.base_url(), "Configured Satellite Service base URL");
+    info!(babel_service_base_url = %upstreams.babel.base_url(), "Configured Babel Service base URL");
 
-    let app = app::app();
+    let app = app::app(NexusState {
+        upstreams,
+        clock: clock.clone(),
+    });
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", service_port)).await?;
     info!("Sky Nexus listening on http://localhost:{}", service_port);
@@ -1,3 +1,4 @@
+use crate::services::Upstreams;
 use crate::services::airports::fetch_airport_by_code;
 use crate::services::babel::fetch_flights_by_airport;
 use rmcp::{
@@ -37,19 +38,15 @@ pub struct DelayInvestigationParams {
 #[derive(Clone, Debug)]
 pub struct SkyNexusPrompts {
     pub prompt_router: PromptRouter<Self>,
-}
-
-impl Default for SkyNexusPrompts {
-    fn default() -> Self {
-        Self::new()
-    }
+    upstreams: Upstreams,
 }
 
 #[prompt_router]
 impl SkyNexusPrompts {
-    pub fn new() -> Self {
+    pub fn new(upstreams: Upstreams) -> Self {
         Self {
             prompt_router: Self::prompt_router(),
+            upstreams,
         }
     }
 
@@ -63,7 +60,7 @@ impl SkyNexusPrompts {
         &self,
         Parameters(p): Parameters<AirportBriefingParams>,
     ) -> GetPromptResult {
-        let context = match fetch_airport_by_code(&p.airport_code).await {
+        let context = match fetch_airport_by_code(&self.upstreams.airports, &p.airport_code).await {
             Ok(a) => format!(
                 "Airport: {} ({})\nLatitude: {}\nLongitude: {}",
                 a.name, a.code, a.latitude, a.longitude
@@ -114,8 +111,8 @@ impl SkyNexusPrompts {
             .unwrap_or_default();
 
         let (dep_ctx, arr_ctx) = tokio::join!(
-            fetch_airport_by_code(&p.departure),
-            fetch_airport_by_code(&p.arrival),
+            fetch_airport_by_code(&self.upstreams.airports, &p.departure),
+            fetch_airport_by_code(&self.upstreams.airports, &p.arrival),
         );
 
         let dep_info = dep_ctx
@@ -169,7 +166,9 @@ impl SkyNexusPrompts {
         &self,
         Parameters(p): Parameters<DelayInvestigationParams>,
     ) -> GetPromptResult {
-        let flights_context = match fetch_flights_by_airport(&p.airport_code).await {
+        let flights_context = match fetch_flights_by_airport(&self.upstreams.babel, &p.airport_code)
+            .await
+        {
             Ok(flights) if !flights.is_empty() => {
                 let summary: Vec<String> = flights
                     .iter()
@@ -7,6 +7,7 @@ use rmcp::{
     },
 };
 use serde_json::json;
+use sky_tracer::client::AirportsClient;
 use tracing::{error, info};
 
 const URI_PREFIX: &str = "airports://";
@@ -78,6 +79,7 @@ pub fn list_resource_templates() -> ListResourceTemplatesResult {
 }
 
 pub async fn read_resource(
+    airports: &AirportsClient,
     params: ReadResourceRequestParams,
 ) -> Result<ReadResourceResult, McpError> {
     let code = parse_code(&params.uri).ok_or_else(|| {
@@ -92,7 +94,7 @@ pub async fn read_resource(
 
     info!(code = %code, uri = %params.uri, "Reading airport resource");
 
-    let airport = fetch_airport_by_code(code).await.map_err(|e| {
+    let airport = fetch_airport_by_code(airports, code).await.map_err(|e| {
         error!(code = %code, error = %e, "Failed to fetch airport resource");
         McpError::internal_error(
             "Airport not found",
@@ -103,9 +105,16 @@ pub async fn read_resource(
     let body = serde_json::to_string_pretty(&json!({
         "id": airport.id,
         "code": airport.code,
+        "iata": airport.iata,
+        "icao": airport.icao,
         "name": airport.name,
+        "city": airport.city,
+        "country": airport.country,
         "latitude": airport.latitude,
         "longitude": airport.longitude,
+        "elevation_ft": airport.elevation_ft,
+        "timezone": airport.timezone,
+        "airport_type": airport.airport_type,
     }))
     .unwrap_or_default();
 
@@ -1,21 +1,23 @@
 use crate::mcp::prompts::SkyNexusPrompts;
 use crate::mcp::resources;
+use crate::services::{NexusState, Upstreams};
 
-use crate::mcp::tools::{AirportTools, BabelTools, DateTimeTools, FlightTools, MapTools, SatelliteTools};
+use crate::mcp::tools::{
+    AirportTools, BabelTools, DateTimeTools, FlightTools, MapTools, SatelliteTools,
+};
 use rmcp::{
     RoleServer, ServerHandler,
     handler::server::prompt::PromptContext,
     model::{
-        CallToolResult, GetPromptRequestParams, GetPromptResult, Implementation,
-        ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListToolsResult,
-        PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult, ServerCapabilities,
-        ServerInfo,
+        CallToolResult, GetPromptRequestParams, GetPromptResult, Implementation, ListPromptsResult,
+        ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, PaginatedRequestParams,
+        ReadResourceRequestParams, ReadResourceResult, ServerCapabilities, ServerInfo,
     },
     service::RequestContext,
 };
 
 /// Single MCP server that aggregates all Sky Nexus tool categories.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct SkyNexusTools {
     airports: AirportTools,
     flights: FlightTools,
@@ -24,18 +26,21 @@ pub struct SkyNexusTools {
     babel: BabelTools,
     map: MapTools,
     prompts: SkyNexusPrompts,
+    upstreams: Upstreams,
 }
 
 impl SkyNexusTools {
-    pub fn new() -> Self {
+    pub fn new(state: NexusState) -> Self {
+        let NexusState { upstreams, clock } = state;
         Self {
-            airports: AirportTools::new(),
-            flights: FlightTools::new(),
-            satellites: SatelliteTools::new(),
-            datetime: DateTimeTools::new(),
-            babel: BabelTools::new(),
-            map: MapTools::new(),
-            prompts: SkyNexusPrompts::new(),
+            airports: AirportTools::new(upstreams.clone()),
+            flights: FlightTools::new(upstreams.clone()),
+            satellites: SatelliteTools::new(upstreams.clone()),
+            datetime: DateTimeTools::new(clock),
+            babel: BabelTools::new(upstreams.clone()),
+            map: MapTools::new(upstreams.clone()),
+            prompts: SkyNexusPrompts::new(upstreams.clone()),
+            upstreams,
         }
     }
 }
@@ -104,10 +109,17 @@ impl ServerHandler for SkyNexusTools {
         context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
         match request.name.as_ref() {
-            "list_airports" | "get_airport" => self.airports.call_tool(request, context).await,
-            "list_flights" | "get_flight" | "create_flight" | "search_flights_by_route" => {
-                self.flights.call_tool(request, context).await
+            "list_airports" | "get_airport" | "find_nearest_airports" => {
+                self.airports.call_tool(request, context).await
             }
+            "list_flights"
+            | "get_flight"
+            | "create_flight"
+            | "update_flight"
+            | "update_flight_status"
+            | "cancel_flight"
+            | "delete_flight"
+            | "search_flights_by_route" => self.flights.call_tool(request, context).await,
             "list_satellites"
             | "create_satellite"
             | "update_satellite_status"
@@ -148,7 +160,7 @@ impl ServerHandler for SkyNexusTools {
         request: ReadResourceRequestParams,
         _context: RequestContext<RoleServer>,
     ) -> Result<ReadResourceResult, rmcp::ErrorData> {
-        resources::read_resource(request).await
+        resources::read_resource(&self.upstreams.airports, request).await
     }
 
     async fn list_prompts(
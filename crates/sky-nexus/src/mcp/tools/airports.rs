@@ -1,4 +1,7 @@
-use crate::services::airports::{fetch_airport_by_code, fetch_airports};
+use crate::services::Upstreams;
+use crate::services::airports::{
+    AirportServiceError, fetch_airport_by_code, fetch_airports, fetch_nearby_airports,
+};
 use rmcp::{
     ErrorData as McpError, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
@@ -6,6 +9,8 @@ use rmcp::{
     schemars, tool, tool_handler, tool_router,
 };
 use serde_json::json;
+use sky_tracer::model::{IataCode, IcaoCode};
+use sky_tracer::protocol::airports::NearbyAirportsRequest;
 use tracing::{error, info};
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -14,16 +19,30 @@ pub struct GetAirportRequest {
     pub code: String,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct FindNearestAirportsRequest {
+    #[schemars(description = "Latitude in decimal degrees")]
+    pub latitude: f64,
+    #[schemars(description = "Longitude in decimal degrees")]
+    pub longitude: f64,
+    #[schemars(description = "Search radius in kilometers (optional, defaults to 100)")]
+    pub radius_km: Option<f64>,
+    #[schemars(description = "Maximum number of airports (optional, defaults to 10)")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
 pub struct AirportTools {
     tool_router: ToolRouter<Self>,
+    upstreams: Upstreams,
 }
 
 #[tool_router]
 impl AirportTools {
-    pub fn new() -> Self {
+    pub fn new(upstreams: Upstreams) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            upstreams,
         }
     }
 
@@ -31,13 +50,15 @@ impl AirportTools {
     pub async fn list_airports(&self) -> Result<CallToolResult, McpError> {
         info!("Listing airports");
 
-        let airports = fetch_airports().await.map_err(|e| {
-            error!("Failed to fetch airports: {}", e);
-            McpError::internal_error(
-                "Failed to fetch airports",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
+        let airports = fetch_airports(&self.upstreams.airports)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch airports: {}", e);
+                McpError::internal_error(
+                    "Failed to fetch airports",
+                    Some(json!({"error": e.to_string()})),
+                )
+            })?;
 
         let mut result = String::new();
         for airport in &airports {
@@ -60,21 +81,91 @@ impl AirportTools {
     ) -> Result<CallToolResult, McpError> {
         info!("Getting airport by code: {}", code);
 
-        let airport = fetch_airport_by_code(&code).await.map_err(|e| {
-            error!("Failed to fetch airport {}: {}", code, e);
-            McpError::internal_error(
-                "Failed to fetch airport",
-                Some(json!({"error": e.to_string(), "code": code})),
-            )
-        })?;
+        let airport = fetch_airport_by_code(&self.upstreams.airports, &code)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch airport {}: {}", code, e);
+                McpError::internal_error(
+                    "Failed to fetch airport",
+                    Some(json!({"error": e.to_string(), "code": code})),
+                )
+            })?;
 
+        let optional = |value: Option<&str>| value.unwrap_or("-").to_string();
         let result = format!(
-            "Airport: {}\nCode: {}\nLatitude: {}\nLongitude: {}\nID: {}\n",
-            airport.name, airport.code, airport.latitude, airport.longitude, airport.id
+            "Airport: {}\nCode: {}\nIATA: {}\nICAO: {}\nCity: {}\nCountry: {}\nLatitude: {}\nLongitude: {}\nElevation: {}\nTime zone: {}\nType: {}\nID: {}\n",
+            airport.name,
+            airport.code,
+            optional(airport.iata.as_ref().map(IataCode::as_str)),
+            optional(airport.icao.as_ref().map(IcaoCode::as_str)),
+            airport.city,
+            airport.country,
+            airport.latitude,
+            airport.longitude,
+            airport
+                .elevation_ft
+                .map(|ft| format!("{} ft", ft))
+                .unwrap_or_else(|| "-".to_string()),
+            optional(airport.timezone.as_deref()),
+            airport.airport_type,
+            airport.id
         );
 
         Ok(CallToolResult::success(vec![Content::text(result)]))
     }
+
+    #[tool(
+        description = "Find the airports nearest to a position, e.g. diversion options for a flight"
+    )]
+    pub async fn find_nearest_airports(
+        &self,
+        Parameters(FindNearestAirportsRequest {
+            latitude,
+            longitude,
+            radius_km,
+            limit,
+        }): Parameters<FindNearestAirportsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Finding airports near {}, {}", latitude, longitude);
+
+        let request = NearbyAirportsRequest {
+            lat: latitude,
+            lon: longitude,
+            radius_km,
+            limit,
+        };
+        let airports = fetch_nearby_airports(&self.upstreams.airports, &request)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch nearby airports: {}", e);
+                match e {
+                    AirportServiceError::InvalidRequest(message) => {
+                        McpError::invalid_params(message, None)
+                    }
+                    e => McpError::internal_error(
+                        "Failed to fetch nearby airports",
+                        Some(json!({"error": e.to_string()})),
+                    ),
+                }
+            })?;
+
+        let mut result = String::new();
+        for nearby in &airports {
+            result.push_str(&format!(
+                "{} ({}) - {:.1} km - Lat: {}, Lon: {}\n",
+                nearby.airport.name,
+                nearby.airport.code,
+                nearby.distance_km,
+                nearby.airport.position.latitude,
+                nearby.airport.position.longitude
+            ));
+        }
+        if result.is_empty() {
+            result = "No airports found within the search radius.".to_string();
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
 }
 
 #[tool_handler]
@@ -85,7 +176,8 @@ impl ServerHandler for AirportTools {
             .with_instructions(
                 "Airport tools for Sky Nexus:\n\
                 - list_airports: List all airports with their codes and coordinates\n\
-                - get_airport: Get detailed information about a specific airport by code"
+                - get_airport: Get detailed information about a specific airport by code\n\
+                - find_nearest_airports: Find airports around a position, nearest first"
                     .to_string(),
             )
     }
@@ -1,3 +1,4 @@
+use crate::services::Upstreams;
 use crate::services::babel::{BabelServiceError, fetch_flight_position, fetch_flights_by_airport};
 use rmcp::{
     ErrorData as McpError, ServerHandler,
@@ -21,16 +22,18 @@ pub struct GetFlightPositionRequest {
     pub flight_number: String,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct BabelTools {
     tool_router: ToolRouter<Self>,
+    upstreams: Upstreams,
 }
 
 #[tool_router]
 impl BabelTools {
-    pub fn new() -> Self {
+    pub fn new(upstreams: Upstreams) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            upstreams,
         }
     }
 
@@ -41,7 +44,7 @@ impl BabelTools {
     ) -> Result<CallToolResult, McpError> {
         info!("Getting flights for airport: {}", req.airport_code);
 
-        match fetch_flights_by_airport(&req.airport_code).await {
+        match fetch_flights_by_airport(&self.upstreams.babel, &req.airport_code).await {
             Ok(flights) => {
                 if flights.is_empty() {
                     let result = format!(
@@ -125,7 +128,7 @@ impl BabelTools {
     ) -> Result<CallToolResult, McpError> {
         info!("Getting position for flight: {}", req.flight_number);
 
-        match fetch_flight_position(&req.flight_number).await {
+        match fetch_flight_position(&self.upstreams.babel, &req.flight_number).await {
             Ok(position) => {
                 let result = format!(
                     "Flight Position for {}:\n\
@@ -133,12 +136,22 @@ impl BabelTools {
                      Current Location:\n\
                      - Latitude: {:.6}\n\
                      - Longitude: {:.6}\n\
+                     - Altitude: {:.0} m\n\
+                     - Heading: {:.0}°\n\
+                     - Ground Speed: {:.0} km/h\n\
+                     - Vertical Rate: {:.1} m/s\n\
+                     - Phase: {:?}\n\
                      - Last Updated: {}\n\
                      \n\
                      Status: Position tracked by Tower of Babel",
                     position.flight_number,
                     position.latitude,
                     position.longitude,
+                    position.altitude,
+                    position.heading,
+                    position.ground_speed,
+                    position.vertical_rate,
+                    position.phase,
                     position.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
                 );
 
@@ -179,7 +192,8 @@ impl BabelTools {
 
         // Try to fetch flights if the pattern looks like an airport code
         if req.pattern.len() == 3 || req.pattern.len() == 4 {
-            match fetch_flights_by_airport(&req.pattern.to_uppercase()).await {
+            match fetch_flights_by_airport(&self.upstreams.babel, &req.pattern.to_uppercase()).await
+            {
                 Ok(flights) => {
                     if flights.is_empty() {
                         let result = format!(
@@ -260,7 +274,7 @@ impl ServerHandler for BabelTools {
                 - Real-time flight positions with GPS coordinates\n\
                 - Flight status and tracking information\n\
                 \n\
-                Note: Flight position data includes latitude, longitude and timestamp only.\n\
+                Note: Flight position data includes coordinates, altitude, heading, ground speed, vertical rate and flight phase.\n\
                 Additional flight details (aircraft, route, schedule) are available via airport search.\n\
                 \n\
                 Useful for flight tracking, airport operations, and passenger information."
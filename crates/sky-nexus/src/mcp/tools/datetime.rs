@@ -12,6 +12,7 @@ use rmcp::{
 };
 use serde::Deserialize;
 use serde_json::json;
+use sky_tracer::clock::SharedClock;
 use tracing::{error, info};
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -34,16 +35,18 @@ pub struct TimezoneDifferenceRequest {
     pub to_timezone: String,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct DateTimeTools {
     tool_router: ToolRouter<Self>,
+    clock: SharedClock,
 }
 
 #[tool_router]
 impl DateTimeTools {
-    pub fn new() -> Self {
+    pub fn new(clock: SharedClock) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            clock,
         }
     }
 
@@ -62,7 +65,7 @@ impl DateTimeTools {
             format: req.format,
         };
 
-        match get_current_datetime(query).await {
+        match get_current_datetime(&self.clock, query).await {
             Ok(response) => {
                 let result = format!(
                     "Current date and time: {}\n\
@@ -102,7 +105,7 @@ impl DateTimeTools {
     pub async fn get_aviation_times(&self) -> Result<CallToolResult, McpError> {
         info!("Getting current time in aviation-relevant timezones");
 
-        match get_aviation_times().await {
+        match get_aviation_times(&self.clock).await {
             Ok(response) => {
                 let mut result = String::from("Current time in major aviation hubs:\n\n");
 
@@ -159,7 +162,7 @@ impl DateTimeTools {
         })?;
 
         // Get current UTC time
-        let now: DateTime<Utc> = Utc::now();
+        let now: DateTime<Utc> = self.clock.now();
 
         // Convert to local times
         let from_local = now.with_timezone(&from_tz);
@@ -243,7 +246,7 @@ impl DateTimeTools {
             to_timezone: req.to_timezone,
         };
 
-        match compare_timezones(comparison_request).await {
+        match compare_timezones(&self.clock, comparison_request).await {
             Ok(response) => {
                 let result = format!(
                     "Timezone Comparison:\n\
@@ -1,4 +1,8 @@
-use crate::services::flights::{create_flight, fetch_flight_by_number, fetch_flights};
+use crate::services::Upstreams;
+use crate::services::flights::{
+    FlightServiceError, create_flight, delete_flight, fetch_flight_by_number, fetch_flights,
+    patch_flight, update_flight,
+};
 use rmcp::{
     ErrorData as McpError, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
@@ -8,7 +12,8 @@ use rmcp::{
 use serde::Deserialize;
 use serde_json::json;
 use sky_tracer::model::flight::Flight;
-use sky_tracer::protocol::flights::CreateFlightRequest;
+use sky_tracer::model::{FlightStatus, IdentifierError};
+use sky_tracer::protocol::flights::{CreateFlightRequest, PatchFlightRequest, UpdateFlightRequest};
 use tracing::{error, info};
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -29,6 +34,38 @@ pub struct CreateFlightToolRequest {
     pub departure_time: String,
     #[schemars(description = "Arrival time (RFC3339, optional)")]
     pub arrival_time: Option<String>,
+    #[schemars(description = "Airline IATA or ICAO designator, e.g. LH or DLH (optional)")]
+    pub airline: Option<String>,
+    #[schemars(
+        description = "Explicit flight number, e.g. LH400 (optional, allocated if omitted)"
+    )]
+    pub flight_number: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpdateFlightToolRequest {
+    #[schemars(description = "Flight number of the flight to reschedule")]
+    pub flight_number: String,
+    #[schemars(description = "Aircraft registration number")]
+    pub aircraft_number: String,
+    #[schemars(description = "Departure airport code")]
+    pub departure: String,
+    #[schemars(description = "Arrival airport code")]
+    pub arrival: String,
+    #[schemars(description = "Departure time (RFC3339)")]
+    pub departure_time: String,
+    #[schemars(description = "Arrival time (RFC3339, optional)")]
+    pub arrival_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpdateFlightStatusRequest {
+    #[schemars(description = "Flight number")]
+    pub flight_number: String,
+    #[schemars(
+        description = "New status: Scheduled, Boarding, Departed, EnRoute, Landed, Cancelled or Diverted"
+    )]
+    pub status: String,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -41,16 +78,76 @@ pub struct ListFlightsToolRequest {
     pub date: Option<String>,
 }
 
-#[derive(Clone, Debug, Default)]
+fn parse_time(field: &str, value: &str) -> Result<chrono::DateTime<chrono::Utc>, McpError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .map_err(|e| {
+            McpError::invalid_params(
+                format!("Invalid {} format", field),
+                Some(json!({"error": e.to_string()})),
+            )
+        })
+}
+
+fn parse_identifier<T>(field: &str, value: &str) -> Result<T, McpError>
+where
+    T: std::str::FromStr<Err = IdentifierError>,
+{
+    value.parse().map_err(|e: IdentifierError| {
+        McpError::invalid_params(
+            format!("Invalid {}", field),
+            Some(json!({"error": e.to_string()})),
+        )
+    })
+}
+
+/// Rejected changes are the caller's fault, everything else is ours
+fn flight_change_error(message: &str, flight_number: &str, e: FlightServiceError) -> McpError {
+    error!("{} {}: {}", message, flight_number, e);
+    let data = Some(json!({"error": e.to_string(), "flight_number": flight_number}));
+    match e {
+        FlightServiceError::NotFound(_)
+        | FlightServiceError::Conflict(_)
+        | FlightServiceError::InvalidRequest(_) => {
+            McpError::invalid_params(message.to_string(), data)
+        }
+        _ => McpError::internal_error(message.to_string(), data),
+    }
+}
+
+fn format_flight(flight: &Flight) -> String {
+    format!(
+        "Flight Number: {}\n\
+         Aircraft: {}\n\
+         Route: {} → {}\n\
+         Departure: {}\n\
+         Arrival: {}\n\
+         Status: {}",
+        flight.flight_number,
+        flight.aircraft_number,
+        flight.departure,
+        flight.arrival,
+        flight.departure_time.format("%Y-%m-%d %H:%M UTC"),
+        flight
+            .arrival_time
+            .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| "TBD".to_string()),
+        flight.status
+    )
+}
+
+#[derive(Clone, Debug)]
 pub struct FlightTools {
     tool_router: ToolRouter<Self>,
+    upstreams: Upstreams,
 }
 
 #[tool_router]
 impl FlightTools {
-    pub fn new() -> Self {
+    pub fn new(upstreams: Upstreams) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            upstreams,
         }
     }
 
@@ -64,7 +161,7 @@ impl FlightTools {
             req.departure, req.arrival, req.date
         );
 
-        let flights = fetch_flights().await.map_err(|e| {
+        let flights = fetch_flights(&self.upstreams.flights).await.map_err(|e| {
             error!("Failed to fetch flights: {}", e);
             McpError::internal_error(
                 "Failed to fetch flights",
@@ -79,13 +176,17 @@ impl FlightTools {
                 let matches_departure = req.departure.as_ref().is_none_or(|dep| {
                     flight
                         .departure
+                        .as_str()
                         .to_lowercase()
                         .contains(&dep.to_lowercase())
                 });
-                let matches_arrival = req
-                    .arrival
-                    .as_ref()
-                    .is_none_or(|arr| flight.arrival.to_lowercase().contains(&arr.to_lowercase()));
+                let matches_arrival = req.arrival.as_ref().is_none_or(|arr| {
+                    flight
+                        .arrival
+                        .as_str()
+                        .to_lowercase()
+                        .contains(&arr.to_lowercase())
+                });
                 let matches_date = req.date.as_ref().is_none_or(|date| {
                     flight.departure_time.format("%Y-%m-%d").to_string() == *date
                 });
@@ -105,7 +206,8 @@ impl FlightTools {
                      Aircraft: {}\n\
                      Route: {} → {}\n\
                      Departure: {}\n\
-                     Arrival: {}\n\n",
+                     Arrival: {}\n\
+                     Status: {}\n\n",
                     flight.flight_number,
                     flight.aircraft_number,
                     flight.departure,
@@ -114,7 +216,8 @@ impl FlightTools {
                     flight
                         .arrival_time
                         .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
-                        .unwrap_or_else(|| "TBD".to_string())
+                        .unwrap_or_else(|| "TBD".to_string()),
+                    flight.status
                 ));
             }
         }
@@ -129,32 +232,17 @@ impl FlightTools {
     ) -> Result<CallToolResult, McpError> {
         info!("Getting flight details for: {}", flight_number);
 
-        let flight = fetch_flight_by_number(&flight_number).await.map_err(|e| {
-            error!("Failed to fetch flight {}: {}", flight_number, e);
-            McpError::internal_error(
-                "Failed to fetch flight",
-                Some(json!({"error": e.to_string(), "flight_number": flight_number})),
-            )
-        })?;
+        let flight = fetch_flight_by_number(&self.upstreams.flights, &flight_number)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch flight {}: {}", flight_number, e);
+                McpError::internal_error(
+                    "Failed to fetch flight",
+                    Some(json!({"error": e.to_string(), "flight_number": flight_number})),
+                )
+            })?;
 
-        let result = format!(
-            "Flight Details:\n\
-             Flight Number: {}\n\
-             Aircraft: {}\n\
-             Route: {} → {}\n\
-             Departure Time: {}\n\
-             Arrival Time: {}\n\
-             Status: Active",
-            flight.flight_number,
-            flight.aircraft_number,
-            flight.departure,
-            flight.arrival,
-            flight.departure_time.format("%Y-%m-%d %H:%M UTC"),
-            flight
-                .arrival_time
-                .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
-                .unwrap_or_else(|| "TBD".to_string())
-        );
+        let result = format!("Flight Details:\n{}", format_flight(&flight));
 
         Ok(CallToolResult::success(vec![Content::text(result)]))
     }
@@ -166,78 +254,145 @@ impl FlightTools {
     ) -> Result<CallToolResult, McpError> {
         info!("Creating new flight: {} → {}", req.departure, req.arrival);
 
-        // Parse departure time
-        let departure_time = chrono::DateTime::parse_from_rfc3339(&req.departure_time)
-            .map_err(|e| {
-                McpError::invalid_params(
-                    "Invalid departure_time format",
-                    Some(json!({"error": e.to_string()})),
-                )
-            })?
-            .with_timezone(&chrono::Utc);
-
-        // Parse arrival time if provided
+        let departure_time = parse_time("departure_time", &req.departure_time)?;
         let arrival_time = req
             .arrival_time
-            .map(|at| {
-                chrono::DateTime::parse_from_rfc3339(&at)
-                    .map(|dt| dt.with_timezone(&chrono::Utc))
-                    .map_err(|e| {
-                        McpError::invalid_params(
-                            "Invalid arrival_time format",
-                            Some(json!({"error": e.to_string()})),
-                        )
-                    })
-            })
+            .map(|at| parse_time("arrival_time", &at))
             .transpose()?;
 
         let create_request = CreateFlightRequest {
-            aircraft_number: req.aircraft_number,
-            departure: req.departure,
-            arrival: req.arrival,
+            aircraft_number: parse_identifier("aircraft_number", &req.aircraft_number)?,
+            departure: parse_identifier("departure", &req.departure)?,
+            arrival: parse_identifier("arrival", &req.arrival)?,
             departure_time,
             arrival_time,
+            airline: req.airline,
+            flight_number: req
+                .flight_number
+                .map(|number| parse_identifier("flight_number", &number))
+                .transpose()?,
         };
+        let requested = create_request
+            .flight_number
+            .as_ref()
+            .map_or_else(|| "new flight".to_string(), ToString::to_string);
+
+        let created_flight = create_flight(&self.upstreams.flights, create_request)
+            .await
+            .map_err(|e| flight_change_error("Failed to create flight", &requested, e))?;
+
+        let result = format!(
+            "Flight created successfully!\n{}",
+            format_flight(&created_flight)
+        );
+
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
 
-        // Convert to Flight model for the create_flight service
-        let flight = Flight {
-            flight_number: format!("{}0001", create_request.departure), // Temporary, will be overwritten
-            aircraft_number: create_request.aircraft_number,
-            departure: create_request.departure,
-            arrival: create_request.arrival,
-            departure_time: create_request.departure_time,
-            arrival_time: create_request.arrival_time,
+    #[tool(description = "Reschedule a flight that has not departed yet")]
+    pub async fn update_flight(
+        &self,
+        Parameters(req): Parameters<UpdateFlightToolRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Updating flight: {}", req.flight_number);
+
+        let request = UpdateFlightRequest {
+            aircraft_number: parse_identifier("aircraft_number", &req.aircraft_number)?,
+            departure: parse_identifier("departure", &req.departure)?,
+            arrival: parse_identifier("arrival", &req.arrival)?,
+            departure_time: parse_time("departure_time", &req.departure_time)?,
+            arrival_time: req
+                .arrival_time
+                .map(|at| parse_time("arrival_time", &at))
+                .transpose()?,
         };
 
-        let created_flight = create_flight(flight).await.map_err(|e| {
-            error!("Failed to create flight: {}", e);
-            McpError::internal_error(
-                "Failed to create flight",
-                Some(json!({"error": e.to_string()})),
+        let flight = update_flight(&self.upstreams.flights, &req.flight_number, request)
+            .await
+            .map_err(|e| flight_change_error("Failed to update flight", &req.flight_number, e))?;
+
+        let result = format!("Flight updated successfully!\n{}", format_flight(&flight));
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
+
+    #[tool(
+        description = "Change the status of a flight, e.g. Boarding, Departed, EnRoute or Landed"
+    )]
+    pub async fn update_flight_status(
+        &self,
+        Parameters(req): Parameters<UpdateFlightStatusRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        info!(
+            "Updating status of flight {} to {}",
+            req.flight_number, req.status
+        );
+
+        let status: FlightStatus = req.status.parse().map_err(|e: String| {
+            McpError::invalid_params(
+                "Invalid flight status",
+                Some(json!({"error": e, "allowed": FlightStatus::ALL})),
             )
         })?;
 
+        let flight = patch_flight(
+            &self.upstreams.flights,
+            &req.flight_number,
+            PatchFlightRequest {
+                status: Some(status),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| {
+            flight_change_error("Failed to update flight status", &req.flight_number, e)
+        })?;
+
         let result = format!(
-            "Flight created successfully!\n\
-             Flight Number: {}\n\
-             Aircraft: {}\n\
-             Route: {} → {}\n\
-             Departure: {}\n\
-             Arrival: {}",
-            created_flight.flight_number,
-            created_flight.aircraft_number,
-            created_flight.departure,
-            created_flight.arrival,
-            created_flight.departure_time.format("%Y-%m-%d %H:%M UTC"),
-            created_flight
-                .arrival_time
-                .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
-                .unwrap_or_else(|| "TBD".to_string())
+            "Flight status updated successfully!\n{}",
+            format_flight(&flight)
         );
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
 
+    #[tool(description = "Cancel a flight that has not departed yet")]
+    pub async fn cancel_flight(
+        &self,
+        Parameters(GetFlightRequest { flight_number }): Parameters<GetFlightRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Cancelling flight: {}", flight_number);
+
+        let flight = patch_flight(
+            &self.upstreams.flights,
+            &flight_number,
+            PatchFlightRequest {
+                status: Some(FlightStatus::Cancelled),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| flight_change_error("Failed to cancel flight", &flight_number, e))?;
+
+        let result = format!("Flight cancelled.\n{}", format_flight(&flight));
         Ok(CallToolResult::success(vec![Content::text(result)]))
     }
 
+    #[tool(description = "Delete a flight permanently")]
+    pub async fn delete_flight(
+        &self,
+        Parameters(GetFlightRequest { flight_number }): Parameters<GetFlightRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("Deleting flight: {}", flight_number);
+
+        delete_flight(&self.upstreams.flights, &flight_number)
+            .await
+            .map_err(|e| flight_change_error("Failed to delete flight", &flight_number, e))?;
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Flight {} deleted.",
+            flight_number
+        ))]))
+    }
+
     #[tool(description = "Search flights by route")]
     pub async fn search_flights_by_route(
         &self,
@@ -262,9 +417,191 @@ impl ServerHandler for FlightTools {
                 "Flight tools for Sky Nexus:\n\
                 - list_flights: List all flights with optional filters (departure, arrival, date)\n\
                 - get_flight: Get detailed information about a specific flight by flight number\n\
-                - create_flight: Create a new flight with aircraft, route, and schedule details\n\
+                - create_flight: Create a new flight with aircraft, route, schedule and optional airline or flight number\n\
+                - update_flight: Reschedule a flight that has not departed yet\n\
+                - update_flight_status: Move a flight through its lifecycle (Boarding, Departed, EnRoute, Landed, ...)\n\
+                - cancel_flight: Cancel a flight that has not departed yet\n\
+                - delete_flight: Delete a flight permanently\n\
                 - search_flights_by_route: Search flights by departure and arrival airports"
                     .to_string(),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Json, Router, extract::Path, http::StatusCode, response::IntoResponse, routing::put,
+    };
+    use rmcp::model::ErrorCode;
+    use sky_tracer::client::{AirportsClient, BabelClient, FlightsClient, SatellitesClient};
+    use sky_tracer::protocol::FLIGHTS_DETAIL_API_PATH;
+    use sky_tracer::protocol::flights::FlightResponse;
+    use sky_tracer::protocol::problem::ProblemDetails;
+
+    fn flight(flight_number: &str, status: FlightStatus) -> FlightResponse {
+        FlightResponse {
+            flight_number: flight_number.parse().unwrap(),
+            aircraft_number: "D-AIBL".parse().unwrap(),
+            departure: "FRA".parse().unwrap(),
+            arrival: "LIS".parse().unwrap(),
+            departure_time: "2025-06-01T08:00:00Z".parse().unwrap(),
+            arrival_time: Some("2025-06-01T11:00:00Z".parse().unwrap()),
+            status,
+        }
+    }
+
+    /// Flight controller knowing LH400, rejecting changes of LH401 and
+    /// failing on LH500
+    async fn tools() -> FlightTools {
+        let upstream = Router::new().route(
+            FLIGHTS_DETAIL_API_PATH,
+            put(
+                |Path(number): Path<String>, Json(request): Json<UpdateFlightRequest>| async move {
+                    match number.as_str() {
+                        "LH400" => Json(FlightResponse {
+                            departure_time: request.departure_time,
+                            arrival_time: request.arrival_time,
+                            ..flight("LH400", FlightStatus::Scheduled)
+                        })
+                        .into_response(),
+                        "LH401" => ProblemDetails::new(400, "Invalid flight")
+                            .with_code("VALIDATION_ERROR")
+                            .into_response(),
+                        _ => StatusCode::NOT_FOUND.into_response(),
+                    }
+                },
+            )
+            .patch(
+                |Path(number): Path<String>, Json(request): Json<PatchFlightRequest>| async move {
+                    match number.as_str() {
+                        "LH400" => Json(flight("LH400", request.status.unwrap())).into_response(),
+                        "LH401" => ProblemDetails::new(409, "Invalid status transition")
+                            .with_code("INVALID_TRANSITION")
+                            .into_response(),
+                        "LH500" => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+                        _ => StatusCode::NOT_FOUND.into_response(),
+                    }
+                },
+            )
+            .delete(|Path(number): Path<String>| async move {
+                match number.as_str() {
+                    "LH400" => StatusCode::NO_CONTENT,
+                    _ => StatusCode::NOT_FOUND,
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+        FlightTools::new(Upstreams {
+            airports: AirportsClient::new(url.clone()),
+            flights: FlightsClient::new(url.clone()),
+            satellites: SatellitesClient::new(url.clone()),
+            babel: BabelClient::new(url),
+        })
+    }
+
+    fn text(result: CallToolResult) -> String {
+        result.content[0].as_text().unwrap().text.clone()
+    }
+
+    fn flight_number(flight_number: &str) -> Parameters<GetFlightRequest> {
+        Parameters(GetFlightRequest {
+            flight_number: flight_number.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_update_flight_reschedules() {
+        let tools = tools().await;
+        let request = |flight_number: &str| {
+            Parameters(UpdateFlightToolRequest {
+                flight_number: flight_number.to_string(),
+                aircraft_number: "D-AIBL".to_string(),
+                departure: "FRA".to_string(),
+                arrival: "LIS".to_string(),
+                departure_time: "2025-06-02T09:30:00Z".to_string(),
+                arrival_time: Some("2025-06-02T12:30:00Z".to_string()),
+            })
+        };
+
+        let result = text(tools.update_flight(request("LH400")).await.unwrap());
+        assert!(
+            result.contains("Departure: 2025-06-02 09:30 UTC"),
+            "{result}"
+        );
+
+        let error = tools.update_flight(request("LH401")).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+        assert!(error.data.unwrap().to_string().contains("Invalid flight"));
+
+        let error = tools.update_flight(request("LH404")).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+
+        let mut invalid = request("LH400");
+        invalid.0.departure_time = "tomorrow".to_string();
+        let error = tools.update_flight(invalid).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_status_changes_and_cancellation() {
+        let tools = tools().await;
+        let status = |flight_number: &str, status: &str| {
+            Parameters(UpdateFlightStatusRequest {
+                flight_number: flight_number.to_string(),
+                status: status.to_string(),
+            })
+        };
+
+        let result = tools
+            .update_flight_status(status("LH400", "Boarding"))
+            .await
+            .unwrap();
+        assert!(text(result).contains("Status: Boarding"));
+
+        let error = tools
+            .update_flight_status(status("LH400", "Teleported"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+
+        // Rejected transitions are the caller's fault, upstream failures are not
+        let error = tools
+            .update_flight_status(status("LH401", "Landed"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+        let error = tools
+            .update_flight_status(status("LH500", "Landed"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+
+        let result = tools.cancel_flight(flight_number("LH400")).await.unwrap();
+        assert!(text(result).contains("Status: Cancelled"));
+        let error = tools
+            .cancel_flight(flight_number("LH401"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_delete_flight() {
+        let tools = tools().await;
+
+        let result = tools.delete_flight(flight_number("LH400")).await.unwrap();
+        assert_eq!(text(result), "Flight LH400 deleted.");
+
+        let error = tools
+            .delete_flight(flight_number("LH404"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+        assert!(error.data.unwrap().to_string().contains("LH404"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::services::Upstreams;
 use crate::services::airports::fetch_airports;
 use crate::services::babel::{fetch_flight_position, fetch_flights_by_airport};
 use crate::services::flights::fetch_flights;
@@ -24,16 +25,18 @@ pub struct FlightMapRequest {
     pub title: Option<String>,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct MapTools {
     tool_router: ToolRouter<Self>,
+    upstreams: Upstreams,
 }
 
 #[tool_router]
 impl MapTools {
-    pub fn new() -> Self {
+    pub fn new(upstreams: Upstreams) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            upstreams,
         }
     }
 
@@ -51,18 +54,22 @@ impl MapTools {
         info!("Generating flight map (focus: {:?})", req.airport_code);
 
         // ── Fetch airports ────────────────────────────────────────────────
-        let all_airports = fetch_airports().await.map_err(|e| {
-            McpError::internal_error(
-                "Failed to fetch airports",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
+        let all_airports = fetch_airports(&self.upstreams.airports)
+            .await
+            .map_err(|e| {
+                McpError::internal_error(
+                    "Failed to fetch airports",
+                    Some(json!({"error": e.to_string()})),
+                )
+            })?;
 
         // ── Fetch flights ─────────────────────────────────────────────────
         let flights = if let Some(ref code) = req.airport_code {
-            fetch_flights_by_airport(code).await.unwrap_or_default()
+            fetch_flights_by_airport(&self.upstreams.babel, code)
+                .await
+                .unwrap_or_default()
         } else {
-            fetch_flights()
+            fetch_flights(&self.upstreams.flights)
                 .await
                 .map_err(|e| {
                     McpError::internal_error(
@@ -71,14 +78,7 @@ impl MapTools {
                     )
                 })?
                 .into_iter()
-                .map(|f| sky_tracer::protocol::flights::FlightResponse {
-                    flight_number: f.flight_number,
-                    aircraft_number: f.aircraft_number,
-                    departure: f.departure,
-                    arrival: f.arrival,
-                    departure_time: f.departure_time,
-                    arrival_time: f.arrival_time,
-                })
+                .map(sky_tracer::protocol::flights::FlightResponse::from)
                 .collect()
         };
 
@@ -89,8 +89,8 @@ impl MapTools {
         // ── Only include airports that appear in at least one route ────────
         let mut used_codes: HashSet<String> = HashSet::new();
         for f in &flights {
-            used_codes.insert(f.departure.clone());
-            used_codes.insert(f.arrival.clone());
+            used_codes.insert(f.departure.to_string());
+            used_codes.insert(f.arrival.to_string());
         }
 
         let pins: Vec<AirportPin> = used_codes
@@ -107,14 +107,14 @@ impl MapTools {
         // ── Build route arcs with optional live positions ─────────────────
         let mut arcs: Vec<RouteArc> = Vec::new();
         for flight in &flights {
-            let dep = match airport_map.get(&flight.departure) {
+            let dep = match airport_map.get(flight.departure.as_str()) {
                 Some(a) => a,
                 None => {
                     warn!("Unknown departure airport: {}", flight.departure);
                     continue;
                 }
             };
-            let arr = match airport_map.get(&flight.arrival) {
+            let arr = match airport_map.get(flight.arrival.as_str()) {
                 Some(a) => a,
                 None => {
                     warn!("Unknown arrival airport: {}", flight.arrival);
@@ -122,10 +122,12 @@ impl MapTools {
                 }
             };
 
-            let pos = fetch_flight_position(&flight.flight_number).await.ok();
+            let pos = fetch_flight_position(&self.upstreams.babel, flight.flight_number.as_str())
+                .await
+                .ok();
 
             arcs.push(RouteArc {
-                label: flight.flight_number.clone(),
+                label: flight.flight_number.to_string(),
                 dep_lat: dep.latitude,
                 dep_lon: dep.longitude,
                 arr_lat: arr.latitude,
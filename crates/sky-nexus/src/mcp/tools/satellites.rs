@@ -1,3 +1,4 @@
+use crate::services::Upstreams;
 use crate::services::satellites::{
     calculate_position, create_satellite, fetch_satellites, update_satellite_status,
 };
@@ -49,29 +50,33 @@ pub struct CalculatePositionToolRequest {
     pub current_time: Option<String>,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct SatelliteTools {
     pub tool_router: ToolRouter<Self>,
+    upstreams: Upstreams,
 }
 
 #[tool_router]
 impl SatelliteTools {
-    pub fn new() -> Self {
+    pub fn new(upstreams: Upstreams) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            upstreams,
         }
     }
 
     #[tool(description = "List all satellites")]
     pub async fn list_satellites(&self) -> Result<CallToolResult, McpError> {
         info!("Listing satellites");
-        let satellites = fetch_satellites().await.map_err(|e| {
-            error!("Failed to fetch satellites: {}", e);
-            McpError::internal_error(
-                "Failed to fetch satellites",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
+        let satellites = fetch_satellites(&self.upstreams.satellites)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch satellites: {}", e);
+                McpError::internal_error(
+                    "Failed to fetch satellites",
+                    Some(json!({"error": e.to_string()})),
+                )
+            })?;
 
         let mut result = String::new();
         for sat in &satellites {
@@ -94,13 +99,15 @@ impl SatelliteTools {
     ) -> Result<CallToolResult, McpError> {
         info!("Creating satellite: {}", name);
         let req = CreateSatelliteRequest { name };
-        let sat = create_satellite(req).await.map_err(|e| {
-            error!("Failed to create satellite: {}", e);
-            McpError::internal_error(
-                "Failed to create satellite",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
+        let sat = create_satellite(&self.upstreams.satellites, req)
+            .await
+            .map_err(|e| {
+                error!("Failed to create satellite: {}", e);
+                McpError::internal_error(
+                    "Failed to create satellite",
+                    Some(json!({"error": e.to_string()})),
+                )
+            })?;
         let result = format!(
             "Satellite created: {} (ID: {}) - Status: {:?}",
             sat.name, sat.id, sat.status
@@ -130,13 +137,15 @@ impl SatelliteTools {
         let req = UpdateSatelliteStatusRequest {
             status: status_enum,
         };
-        let sat = update_satellite_status(id, req).await.map_err(|e| {
-            error!("Failed to update satellite status: {}", e);
-            McpError::internal_error(
-                "Failed to update satellite status",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
+        let sat = update_satellite_status(&self.upstreams.satellites, id, req)
+            .await
+            .map_err(|e| {
+                error!("Failed to update satellite status: {}", e);
+                McpError::internal_error(
+                    "Failed to update satellite status",
+                    Some(json!({"error": e.to_string()})),
+                )
+            })?;
         let result = format!(
             "Satellite updated: {} (ID: {}) - Status: {:?}",
             sat.name, sat.id, sat.status
@@ -186,18 +195,26 @@ impl SatelliteTools {
                 })
                 .transpose()?,
         };
-        let resp = calculate_position(request).await.map_err(|e| {
-            error!("Failed to calculate position: {}", e);
-            McpError::internal_error(
-                "Failed to calculate position",
-                Some(json!({"error": e.to_string()})),
-            )
-        })?;
+        let resp = calculate_position(&self.upstreams.satellites, request)
+            .await
+            .map_err(|e| {
+                error!("Failed to calculate position: {}", e);
+                McpError::internal_error(
+                    "Failed to calculate position",
+                    Some(json!({"error": e.to_string()})),
+                )
+            })?;
         let mut result = String::new();
         for pos in &resp.positions {
             result.push_str(&format!(
-                "Satellite: {}\nLat: {}\nLon: {}\nAlt: {}\n\n",
-                pos.satellite_id, pos.latitude, pos.longitude, pos.altitude
+                "Satellite: {}\nLat: {}\nLon: {}\nAlt: {}\nHeading: {}\nGround Speed: {}\nPhase: {:?}\n\n",
+                pos.satellite_id,
+                pos.latitude,
+                pos.longitude,
+                pos.altitude,
+                pos.heading,
+                pos.ground_speed,
+                pos.phase
             ));
         }
         if result.is_empty() {
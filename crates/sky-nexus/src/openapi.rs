@@ -2,7 +2,8 @@ use crate::models::datetime::*;
 use axum::{Router, response::Redirect, routing::get};
 use sky_tracer::protocol::{
     airports::AirportResponse,
-    flights::{CreateFlightRequest, FlightResponse},
+    flights::{CreateFlightRequest, FieldError, FlightResponse},
+    problem::ProblemDetails,
     satellite::{
         CalculatePositionRequest, CalculatePositionResponse, CreateSatelliteRequest,
         SatelliteResponse, UpdateSatelliteStatusRequest,
@@ -44,6 +45,8 @@ use utoipa_swagger_ui::SwaggerUi;
             TimezoneComparisonRequest,
             TimezoneComparisonResponse,
             TimezoneInfo,
+            ProblemDetails,
+            FieldError,
         )
     ),
     tags(
@@ -1,14 +1,16 @@
 pub mod v1;
 
 use crate::mcp::SkyNexusTools;
+use crate::services::NexusState;
 use axum::Router;
 use rmcp::transport::streamable_http_server::{
     StreamableHttpService, session::local::LocalSessionManager,
 };
 
-pub fn create_router() -> Router {
+pub fn create_router(state: &NexusState) -> Router<NexusState> {
+    let state = state.clone();
     let mcp_service = StreamableHttpService::new(
-        || Ok(SkyNexusTools::new()),
+        move || Ok(SkyNexusTools::new(state.clone())),
         LocalSessionManager::default().into(),
         Default::default(),
     );
@@ -1,9 +1,14 @@
-use crate::services::airports::{AirportServiceError, fetch_airport_by_code, fetch_airports};
-use axum::{Json, Router, extract::Path, http::StatusCode, response::IntoResponse, routing::get};
-use sky_tracer::protocol::{NEXUS_AIRPORTS_API_PATH, airports::AirportResponse};
+use crate::services::airports::{fetch_airport_by_code, fetch_airports};
+use crate::services::{NexusState, Upstreams};
+use axum::{Json, Router, extract::State, routing::get};
+use sky_tracer::protocol::{
+    NEXUS_AIRPORTS_API_PATH,
+    airports::AirportResponse,
+    problem::{PROBLEM_JSON, ProblemDetails, ProblemPath},
+};
 use tracing::{error, info, instrument};
 
-pub fn router() -> Router {
+pub fn router() -> Router<NexusState> {
     Router::new()
         .route("/", get(list_airports))
         .route("/{code}", get(get_airport))
@@ -14,33 +19,24 @@ pub fn router() -> Router {
     path = NEXUS_AIRPORTS_API_PATH,
     responses(
         (status = 200, description = "List all airports", body = [AirportResponse]),
-        (status = 500, description = "Internal server error")
+        (status = 502, description = "Airport service failed", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 504, description = "Airport service timed out", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Airports"
 )]
-#[instrument]
-pub async fn list_airports() -> impl IntoResponse {
+#[instrument(skip(upstreams))]
+pub async fn list_airports(
+    State(upstreams): State<Upstreams>,
+) -> Result<Json<Vec<AirportResponse>>, ProblemDetails> {
     info!("Listing airports");
 
-    match fetch_airports().await {
-        Ok(airports) => {
-            let responses: Vec<AirportResponse> =
-                airports.iter().map(AirportResponse::from).collect();
-            info!("Successfully listed {} airports", responses.len());
-            (StatusCode::OK, Json(responses)).into_response()
-        }
-        Err(e) => {
-            error!("Failed to fetch airports: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": "Failed to fetch airports",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+    let airports = fetch_airports(&upstreams.airports).await.map_err(|e| {
+        error!("Failed to fetch airports: {}", e);
+        ProblemDetails::from(e)
+    })?;
+    let responses: Vec<AirportResponse> = airports.iter().map(AirportResponse::from).collect();
+    info!("Successfully listed {} airports", responses.len());
+    Ok(Json(responses))
 }
 
 #[utoipa::path(
@@ -51,37 +47,23 @@ pub async fn list_airports() -> impl IntoResponse {
     ),
     responses(
         (status = 200, description = "Get airport by code", body = AirportResponse),
-        (status = 404, description = "Airport not found")
+        (status = 404, description = "Airport not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 502, description = "Airport service failed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Airports"
 )]
-#[instrument]
-pub async fn get_airport(Path(code): Path<String>) -> impl IntoResponse {
+#[instrument(skip(upstreams))]
+pub async fn get_airport(
+    State(upstreams): State<Upstreams>,
+    ProblemPath(code): ProblemPath<String>,
+) -> Result<Json<AirportResponse>, ProblemDetails> {
     info!("Getting airport by code: {}", code);
 
-    match fetch_airport_by_code(&code).await {
-        Ok(airport) => {
-            let response = AirportResponse::from(&airport);
-            (StatusCode::OK, Json(response)).into_response()
-        }
-        Err(AirportServiceError::NotFound(_)) => (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": "Airport not found",
-                "code": code
-            })),
-        )
-            .into_response(),
-        Err(e) => {
+    let airport = fetch_airport_by_code(&upstreams.airports, &code)
+        .await
+        .map_err(|e| {
             error!("Failed to get airport {}: {}", code, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": "Failed to get airport",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+            ProblemDetails::from(e)
+        })?;
+    Ok(Json(AirportResponse::from(&airport)))
 }
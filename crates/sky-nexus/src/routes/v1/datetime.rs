@@ -1,11 +1,12 @@
 use crate::models::datetime::*;
-use crate::services::datetime::{
-    DateTimeServiceError, compare_timezones, get_aviation_times, get_current_datetime,
-};
-use axum::{Json, Router, extract::Query, http::StatusCode, response::IntoResponse, routing::get};
+use crate::services::NexusState;
+use crate::services::datetime::{compare_timezones, get_aviation_times, get_current_datetime};
+use axum::{Json, Router, extract::State, routing::get};
+use sky_tracer::clock::SharedClock;
+use sky_tracer::protocol::problem::{PROBLEM_JSON, ProblemDetails, ProblemJson, ProblemQuery};
 use tracing::{error, info, instrument};
 
-pub fn router() -> Router {
+pub fn router() -> Router<NexusState> {
     Router::new()
         .route("/current", get(get_current_datetime_route))
         .route("/aviation-times", get(get_aviation_times_route))
@@ -18,81 +19,52 @@ pub fn router() -> Router {
     params(GetDateTimeQuery),
     responses(
         (status = 200, description = "Current date and time", body = DateTimeResponse),
-        (status = 400, description = "Invalid timezone or format")
+        (status = 400, description = "Invalid timezone or format", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "DateTime"
 )]
-#[instrument]
+#[instrument(skip(clock))]
 pub async fn get_current_datetime_route(
-    Query(query): Query<GetDateTimeQuery>,
-) -> impl IntoResponse {
+    State(clock): State<SharedClock>,
+    ProblemQuery(query): ProblemQuery<GetDateTimeQuery>,
+) -> Result<Json<DateTimeResponse>, ProblemDetails> {
     info!(
         "Getting current datetime with timezone: {:?}, format: {:?}",
         query.timezone, query.format
     );
 
-    match get_current_datetime(query).await {
-        Ok(response) => {
-            info!("Successfully retrieved current datetime");
-            (StatusCode::OK, Json(response)).into_response()
-        }
-        Err(DateTimeServiceError::InvalidTimezone(msg)) => {
-            error!("Invalid timezone: {}", msg);
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "error": "Invalid timezone",
-                    "details": msg
-                })),
-            )
-                .into_response()
-        }
-        Err(e) => {
-            error!("Failed to get current datetime: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": "Failed to get current datetime",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+    let response = get_current_datetime(&clock, query).await.map_err(|e| {
+        error!("Failed to get current datetime: {}", e);
+        ProblemDetails::from(e)
+    })?;
+    info!("Successfully retrieved current datetime");
+    Ok(Json(response))
 }
 
 #[utoipa::path(
     get,
     path = "/api/v1/nexus/datetime/aviation-times",
     responses(
-        (status = 200, description = "Current time in major aviation hubs", body = AviationTimesResponse)
+        (status = 200, description = "Current time in major aviation hubs", body = AviationTimesResponse),
+        (status = 500, description = "Times cannot be computed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "DateTime"
 )]
-#[instrument]
-pub async fn get_aviation_times_route() -> impl IntoResponse {
+#[instrument(skip(clock))]
+pub async fn get_aviation_times_route(
+    State(clock): State<SharedClock>,
+) -> Result<Json<AviationTimesResponse>, ProblemDetails> {
     info!("Getting aviation times");
 
-    match get_aviation_times().await {
-        Ok(response) => {
-            info!(
-                "Successfully retrieved aviation times for {} locations",
-                response.times.len()
-            );
-            (StatusCode::OK, Json(response)).into_response()
-        }
-        Err(e) => {
-            error!("Failed to get aviation times: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": "Failed to get aviation times",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+    let response = get_aviation_times(&clock).await.map_err(|e| {
+        error!("Failed to get aviation times: {}", e);
+        ProblemDetails::from(e)
+    })?;
+    info!(
+        "Successfully retrieved aviation times for {} locations",
+        response.times.len()
+    );
+    Ok(Json(response))
 }
 
 #[utoipa::path(
@@ -101,45 +73,24 @@ pub async fn get_aviation_times_route() -> impl IntoResponse {
     request_body = TimezoneComparisonRequest,
     responses(
         (status = 200, description = "Timezone comparison result", body = TimezoneComparisonResponse),
-        (status = 400, description = "Invalid timezone")
+        (status = 400, description = "Invalid timezone", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "DateTime"
 )]
-#[instrument]
+#[instrument(skip(clock))]
 pub async fn compare_timezones_route(
-    Json(request): Json<TimezoneComparisonRequest>,
-) -> impl IntoResponse {
+    State(clock): State<SharedClock>,
+    ProblemJson(request): ProblemJson<TimezoneComparisonRequest>,
+) -> Result<Json<TimezoneComparisonResponse>, ProblemDetails> {
     info!(
         "Comparing timezones: {} vs {}",
         request.from_timezone, request.to_timezone
     );
 
-    match compare_timezones(request).await {
-        Ok(response) => {
-            info!("Successfully compared timezones");
-            (StatusCode::OK, Json(response)).into_response()
-        }
-        Err(DateTimeServiceError::InvalidTimezone(msg)) => {
-            error!("Invalid timezone in comparison: {}", msg);
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "error": "Invalid timezone",
-                    "details": msg
-                })),
-            )
-                .into_response()
-        }
-        Err(e) => {
-            error!("Failed to compare timezones: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": "Failed to compare timezones",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+    let response = compare_timezones(&clock, request).await.map_err(|e| {
+        error!("Failed to compare timezones: {}", e);
+        ProblemDetails::from(e)
+    })?;
+    info!("Successfully compared timezones");
+    Ok(Json(response))
 }
@@ -1,12 +1,11 @@
-use crate::services::flights::{
-    FlightServiceError, create_flight, fetch_flight_by_number, fetch_flights,
-};
-use axum::{Json, Router, http::StatusCode, response::IntoResponse, routing::get};
-use sky_tracer::model::flight::Flight;
+use crate::services::flights::{create_flight, fetch_flight_by_number, fetch_flights};
+use crate::services::{NexusState, Upstreams};
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
 use sky_tracer::protocol::flights::{CreateFlightRequest, FlightResponse};
+use sky_tracer::protocol::problem::{PROBLEM_JSON, ProblemDetails, ProblemJson, ProblemPath};
 use tracing::{error, info};
 
-pub fn router() -> Router {
+pub fn router() -> Router<NexusState> {
     Router::new()
         .route("/", get(list_flights).post(post_flight))
         .route("/{flight_number}", get(get_flight))
@@ -16,48 +15,28 @@ pub fn router() -> Router {
     get,
     path = "/api/v1/nexus/flights",
     responses(
-        (status = 200, description = "List all flights", body = [FlightResponse])
+        (status = 200, description = "List all flights", body = [FlightResponse]),
+        (status = 502, description = "Flight controller failed", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 504, description = "Flight controller timed out", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Flights"
 )]
-pub async fn list_flights() -> impl IntoResponse {
+pub async fn list_flights(
+    State(upstreams): State<Upstreams>,
+) -> Result<Json<Vec<FlightResponse>>, ProblemDetails> {
     info!("Listing flights");
 
-    match fetch_flights().await {
-        Ok(flights) => {
-            let responses = flights
-                .into_iter()
-                .map(|f| FlightResponse {
-                    flight_number: f.flight_number,
-                    aircraft_number: f.aircraft_number,
-                    departure: f.departure,
-                    arrival: f.arrival,
-                    departure_time: f.departure_time,
-                    arrival_time: f.arrival_time,
-                })
-                .collect::<Vec<_>>();
+    let flights = fetch_flights(&upstreams.flights).await.map_err(|e| {
+        error!("Failed to fetch flights: {}", e);
+        ProblemDetails::from(e)
+    })?;
+    let responses = flights
+        .into_iter()
+        .map(FlightResponse::from)
+        .collect::<Vec<_>>();
 
-            info!("Successfully listed {} flights", responses.len());
-            (StatusCode::OK, Json(responses)).into_response()
-        }
-        Err(e) => {
-            error!("Failed to fetch flights: {}", e);
-            let status = match e {
-                FlightServiceError::NotFound(_) => StatusCode::NOT_FOUND,
-                FlightServiceError::Network(_) => StatusCode::BAD_GATEWAY,
-                FlightServiceError::Middleware(_) => StatusCode::BAD_GATEWAY,
-                FlightServiceError::ParseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            (
-                status,
-                Json(serde_json::json!({
-                    "error": "Failed to fetch flights",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+    info!("Successfully listed {} flights", responses.len());
+    Ok(Json(responses))
 }
 
 #[utoipa::path(
@@ -65,57 +44,32 @@ pub async fn list_flights() -> impl IntoResponse {
     path = "/api/v1/nexus/flights",
     request_body = CreateFlightRequest,
     responses(
-        (status = 201, description = "Flight created", body = FlightResponse)
+        (status = 201, description = "Flight created", body = FlightResponse),
+        (status = 400, description = "Unknown airline or invalid flight number", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 409, description = "Flight number already in use", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 502, description = "Flight controller failed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Flights"
 )]
-pub async fn post_flight(Json(request): Json<CreateFlightRequest>) -> impl IntoResponse {
+pub async fn post_flight(
+    State(upstreams): State<Upstreams>,
+    ProblemJson(request): ProblemJson<CreateFlightRequest>,
+) -> Result<(StatusCode, Json<FlightResponse>), ProblemDetails> {
     info!(
         "Creating flight: {} -> {}",
         request.departure, request.arrival
     );
 
-    let flight = Flight {
-        flight_number: format!("{}0001", request.departure),
-        aircraft_number: request.aircraft_number,
-        departure: request.departure,
-        arrival: request.arrival,
-        departure_time: request.departure_time,
-        arrival_time: request.arrival_time,
-    };
-
-    match create_flight(flight).await {
-        Ok(created) => {
-            let response = FlightResponse {
-                flight_number: created.flight_number,
-                aircraft_number: created.aircraft_number,
-                departure: created.departure,
-                arrival: created.arrival,
-                departure_time: created.departure_time,
-                arrival_time: created.arrival_time,
-            };
-
-            info!("Successfully created flight: {}", response.flight_number);
-            (StatusCode::CREATED, Json(response)).into_response()
-        }
-        Err(e) => {
+    let created = create_flight(&upstreams.flights, request)
+        .await
+        .map_err(|e| {
             error!("Failed to create flight: {}", e);
-            let status = match e {
-                FlightServiceError::NotFound(_) => StatusCode::NOT_FOUND,
-                FlightServiceError::Network(_) => StatusCode::BAD_GATEWAY,
-                FlightServiceError::Middleware(_) => StatusCode::BAD_GATEWAY,
-                FlightServiceError::ParseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            (
-                status,
-                Json(serde_json::json!({
-                    "error": "Failed to create flight",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+            ProblemDetails::from(e)
+        })?;
+    let response = FlightResponse::from(created);
+
+    info!("Successfully created flight: {}", response.flight_number);
+    Ok((StatusCode::CREATED, Json(response)))
 }
 
 #[utoipa::path(
@@ -126,46 +80,22 @@ pub async fn post_flight(Json(request): Json<CreateFlightRequest>) -> impl IntoR
     ),
     responses(
         (status = 200, description = "Get flight by number", body = FlightResponse),
-        (status = 404, description = "Flight not found")
+        (status = 404, description = "Flight not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 502, description = "Flight controller failed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Flights"
 )]
 pub async fn get_flight(
-    axum::extract::Path(flight_number): axum::extract::Path<String>,
-) -> impl IntoResponse {
+    State(upstreams): State<Upstreams>,
+    ProblemPath(flight_number): ProblemPath<String>,
+) -> Result<Json<FlightResponse>, ProblemDetails> {
     info!("Getting flight: {}", flight_number);
 
-    match fetch_flight_by_number(&flight_number).await {
-        Ok(flight) => {
-            let response = FlightResponse {
-                flight_number: flight.flight_number,
-                aircraft_number: flight.aircraft_number,
-                departure: flight.departure,
-                arrival: flight.arrival,
-                departure_time: flight.departure_time,
-                arrival_time: flight.arrival_time,
-            };
-
-            (StatusCode::OK, Json(response)).into_response()
-        }
-        Err(FlightServiceError::NotFound(_)) => (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": "Flight not found",
-                "flight_number": flight_number
-            })),
-        )
-            .into_response(),
-        Err(e) => {
+    let flight = fetch_flight_by_number(&upstreams.flights, &flight_number)
+        .await
+        .map_err(|e| {
             error!("Failed to get flight {}: {}", flight_number, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({
-                    "error": "Failed to get flight",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response()
-        }
-    }
+            ProblemDetails::from(e)
+        })?;
+    Ok(Json(FlightResponse::from(flight)))
 }
@@ -3,9 +3,10 @@ pub mod datetime;
 pub mod flights;
 pub mod satellites;
 
+use crate::services::NexusState;
 use axum::Router;
 
-pub fn create_router() -> Router {
+pub fn create_router() -> Router<NexusState> {
     Router::new()
         .nest("/nexus/airports", airports::router())
         .nest("/nexus/flights", flights::router())
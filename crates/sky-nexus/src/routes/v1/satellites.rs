@@ -1,18 +1,20 @@
 use crate::services::satellites::{
     calculate_position, create_satellite, fetch_satellites, update_satellite_status,
 };
+use crate::services::{NexusState, Upstreams};
 use axum::{
     Json, Router,
-    extract::Path,
+    extract::State,
     routing::{get, post, put},
 };
+use sky_tracer::protocol::problem::{PROBLEM_JSON, ProblemDetails, ProblemJson, ProblemPath};
 use sky_tracer::protocol::satellite::{
     CalculatePositionRequest, CalculatePositionResponse, CreateSatelliteRequest, SatelliteResponse,
     UpdateSatelliteStatusRequest,
 };
 use uuid::Uuid;
 
-pub fn router() -> Router {
+pub fn router() -> Router<NexusState> {
     Router::new()
         .route("/", get(list_satellites).post(post_satellite))
         .route("/{id}/status", put(put_satellite_status))
@@ -23,13 +25,16 @@ pub fn router() -> Router {
     get,
     path = "/api/v1/nexus/satellites",
     responses(
-        (status = 200, description = "List all satellites", body = [SatelliteResponse])
+        (status = 200, description = "List all satellites", body = [SatelliteResponse]),
+        (status = 502, description = "Orbital beacon failed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Satellites"
 )]
-pub async fn list_satellites() -> Json<Vec<SatelliteResponse>> {
-    let satellites = fetch_satellites().await.unwrap_or_default();
-    Json(satellites)
+pub async fn list_satellites(
+    State(upstreams): State<Upstreams>,
+) -> Result<Json<Vec<SatelliteResponse>>, ProblemDetails> {
+    let satellites = fetch_satellites(&upstreams.satellites).await?;
+    Ok(Json(satellites))
 }
 
 #[utoipa::path(
@@ -37,13 +42,17 @@ pub async fn list_satellites() -> Json<Vec<SatelliteResponse>> {
     path = "/api/v1/nexus/satellites",
     request_body = CreateSatelliteRequest,
     responses(
-        (status = 201, description = "Satellite created", body = SatelliteResponse)
+        (status = 201, description = "Satellite created", body = SatelliteResponse),
+        (status = 502, description = "Orbital beacon failed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Satellites"
 )]
-pub async fn post_satellite(Json(req): Json<CreateSatelliteRequest>) -> Json<SatelliteResponse> {
-    let satellite = create_satellite(req).await.unwrap();
-    Json(satellite)
+pub async fn post_satellite(
+    State(upstreams): State<Upstreams>,
+    ProblemJson(req): ProblemJson<CreateSatelliteRequest>,
+) -> Result<Json<SatelliteResponse>, ProblemDetails> {
+    let satellite = create_satellite(&upstreams.satellites, req).await?;
+    Ok(Json(satellite))
 }
 
 #[utoipa::path(
@@ -54,16 +63,19 @@ pub async fn post_satellite(Json(req): Json<CreateSatelliteRequest>) -> Json<Sat
         ("id" = Uuid, Path, description = "Satellite ID")
     ),
     responses(
-        (status = 200, description = "Satellite status updated", body = SatelliteResponse)
+        (status = 200, description = "Satellite status updated", body = SatelliteResponse),
+        (status = 404, description = "Satellite not found", body = ProblemDetails, content_type = PROBLEM_JSON),
+        (status = 502, description = "Orbital beacon failed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Satellites"
 )]
 pub async fn put_satellite_status(
-    Path(id): Path<Uuid>,
-    Json(req): Json<UpdateSatelliteStatusRequest>,
-) -> Json<SatelliteResponse> {
-    let satellite = update_satellite_status(id, req).await.unwrap();
-    Json(satellite)
+    State(upstreams): State<Upstreams>,
+    ProblemPath(id): ProblemPath<Uuid>,
+    ProblemJson(req): ProblemJson<UpdateSatelliteStatusRequest>,
+) -> Result<Json<SatelliteResponse>, ProblemDetails> {
+    let satellite = update_satellite_status(&upstreams.satellites, id, req).await?;
+    Ok(Json(satellite))
 }
 
 #[utoipa::path(
@@ -71,13 +83,15 @@ pub async fn put_satellite_status(
     path = "/api/v1/nexus/satellites/position",
     request_body = CalculatePositionRequest,
     responses(
-        (status = 200, description = "Flight positions calculated", body = CalculatePositionResponse)
+        (status = 200, description = "Flight positions calculated", body = CalculatePositionResponse),
+        (status = 502, description = "Orbital beacon failed", body = ProblemDetails, content_type = PROBLEM_JSON)
     ),
     tag = "Satellites"
 )]
 pub async fn post_calculate_position(
-    Json(req): Json<CalculatePositionRequest>,
-) -> Json<CalculatePositionResponse> {
-    let resp = calculate_position(req).await.unwrap();
-    Json(resp)
+    State(upstreams): State<Upstreams>,
+    ProblemJson(req): ProblemJson<CalculatePositionRequest>,
+) -> Result<Json<CalculatePositionResponse>, ProblemDetails> {
+    let resp = calculate_position(&upstreams.satellites, req).await?;
+    Ok(Json(resp))
 }
@@ -1,97 +1,106 @@
-use crate::client::create_client;
+use axum::http::StatusCode;
+use sky_tracer::client::{AirportsClient, ClientError};
 use sky_tracer::model::airport::Airport;
-use sky_tracer::protocol::{
-    AIRPORTS_API_PATH, AIRPORTS_SEARCH_API_PATH, airports::SearchAirportsResponse,
-};
-use std::env;
+use sky_tracer::protocol::airports::{NearbyAirportResponse, NearbyAirportsRequest};
+use sky_tracer::protocol::problem::ProblemDetails;
 use thiserror::Error;
 use tracing::{error, info, instrument};
 
 #[derive(Error, Debug)]
 pub enum AirportServiceError {
-    #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
-    #[error("Middleware error: {0}")]
-    Middleware(#[from] reqwest_middleware::Error),
+    #[error("Airport service error: {0}")]
+    Client(#[from] ClientError),
     #[error("Airport not found: {0}")]
     NotFound(String),
-    #[error("Parse error: {0}")]
-    ParseError(String),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
 }
 
-fn get_airport_service_base_url() -> String {
-    env::var("AIRPORT_SERVICE_BASE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+impl From<AirportServiceError> for ProblemDetails {
+    fn from(error: AirportServiceError) -> Self {
+        match error {
+            AirportServiceError::NotFound(message) => ProblemDetails::new(404, "Airport not found")
+                .with_code("NOT_FOUND")
+                .with_detail(message),
+            AirportServiceError::InvalidRequest(message) => {
+                ProblemDetails::new(400, "Invalid request")
+                    .with_code("INVALID_PARAMETER")
+                    .with_detail(message)
+            }
+            AirportServiceError::Client(error) => error.to_problem(),
+        }
+    }
 }
 
-#[instrument]
-pub async fn fetch_airports() -> Result<Vec<Airport>, AirportServiceError> {
+#[instrument(skip(client))]
+pub async fn fetch_airports(client: &AirportsClient) -> Result<Vec<Airport>, AirportServiceError> {
     info!("Fetching all airports");
-    let client = create_client();
-    let base_url = get_airport_service_base_url();
-    let url = format!("{}{}", base_url, AIRPORTS_API_PATH);
 
-    info!(url = %url, "Making request to fetch airports");
-    let resp = client.get(&url).send().await?;
+    let airports: Vec<Airport> = client
+        .list_all()
+        .await
+        .inspect_err(|e| error!(error = %e, "Failed to fetch airports"))?
+        .into_iter()
+        .map(Airport::from)
+        .collect();
 
-    if resp.status().is_success() {
-        let search_response = resp.json::<SearchAirportsResponse>().await?;
-        let airports: Vec<Airport> = search_response
-            .airports
-            .into_iter()
-            .map(|airport_response| Airport {
-                id: airport_response.id,
-                latitude: airport_response.position.latitude,
-                longitude: airport_response.position.longitude,
-                name: airport_response.name,
-                code: airport_response.code,
-            })
-            .collect();
-
-        info!(count = airports.len(), "Successfully fetched airports");
-        Ok(airports)
-    } else {
-        error!(status = %resp.status(), "Failed to fetch airports");
-        Err(AirportServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ))
-    }
+    info!(count = airports.len(), "Successfully fetched airports");
+    Ok(airports)
 }
 
-#[instrument]
-pub async fn fetch_airport_by_code(code: &str) -> Result<Airport, AirportServiceError> {
+#[instrument(skip(client))]
+pub async fn fetch_airport_by_code(
+    client: &AirportsClient,
+    code: &str,
+) -> Result<Airport, AirportServiceError> {
     info!(code = %code, "Fetching airport by code");
-    let client = create_client();
-    let base_url = get_airport_service_base_url();
-    let url = format!("{}{}?code={}", base_url, AIRPORTS_SEARCH_API_PATH, code);
 
-    info!(url = %url, "Making request to search airport");
-    let resp = client.get(&url).send().await?;
-
-    if resp.status().is_success() {
-        let search_response = resp.json::<SearchAirportsResponse>().await?;
-
-        if let Some(airport_response) = search_response.airports.first() {
-            let airport = Airport {
-                id: airport_response.id,
-                latitude: airport_response.position.latitude,
-                longitude: airport_response.position.longitude,
-                name: airport_response.name.clone(),
-                code: airport_response.code.clone(),
-            };
+    match client.airport(code).await {
+        Ok(Some(airport_response)) => {
+            let airport = Airport::from(airport_response);
 
             info!(code = %code, name = %airport.name, "Successfully found airport");
             Ok(airport)
-        } else {
+        }
+        Ok(None) => {
             info!(code = %code, "Airport not found");
             Err(AirportServiceError::NotFound(format!(
                 "Airport with code {} not found",
                 code
             )))
         }
-    } else {
-        error!(status = %resp.status(), code = %code, "Failed to search airport");
-        Err(AirportServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ))
+        Err(e) => {
+            error!(error = %e, code = %code, "Failed to search airport");
+            Err(e.into())
+        }
+    }
+}
+
+#[instrument(skip(client))]
+pub async fn fetch_nearby_airports(
+    client: &AirportsClient,
+    request: &NearbyAirportsRequest,
+) -> Result<Vec<NearbyAirportResponse>, AirportServiceError> {
+    info!(
+        lat = request.lat,
+        lon = request.lon,
+        "Fetching nearby airports"
+    );
+
+    match client.nearby(request).await {
+        Ok(nearby) => {
+            info!(
+                count = nearby.airports.len(),
+                "Successfully fetched nearby airports"
+            );
+            Ok(nearby.airports)
+        }
+        Err(e) if e.status() == Some(StatusCode::BAD_REQUEST) => Err(
+            AirportServiceError::InvalidRequest(e.to_problem().to_string()),
+        ),
+        Err(e) => {
+            error!(error = %e, "Failed to fetch nearby airports");
+            Err(e.into())
+        }
     }
 }
@@ -1,105 +1,54 @@
-use crate::client::create_client;
-use sky_tracer::protocol::{
-    BABEL_AIRPORT_API_PATH, BABEL_POSITION_API_PATH,
-    flights::{FlightPositionResponse, FlightResponse},
-};
-use std::env;
+use sky_tracer::client::{BabelClient, ClientError, NO_FUTURE_FLIGHTS};
+use sky_tracer::protocol::flights::{FlightPositionResponse, FlightResponse};
 use thiserror::Error;
 use tracing::{error, info, instrument};
 
 #[derive(Error, Debug)]
 pub enum BabelServiceError {
-    #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
-    #[error("Middleware error: {0}")]
-    Middleware(#[from] reqwest_middleware::Error),
+    #[error("Babel service error: {0}")]
+    Client(#[from] ClientError),
     #[error("Not found: {0}")]
     NotFound(String),
     #[error("No future flights found for airport: {0}")]
     NoFutureFlights(String),
-    #[error("Parse error: {0}")]
-    ParseError(String),
 }
 
-fn get_babel_service_base_url() -> String {
-    env::var("BABEL_SERVICE_BASE_URL").unwrap_or_else(|_| "http://localhost:3003".to_string())
-}
-
-#[instrument]
+#[instrument(skip(client))]
 pub async fn fetch_flights_by_airport(
+    client: &BabelClient,
     airport_code: &str,
 ) -> Result<Vec<FlightResponse>, BabelServiceError> {
     info!("Fetching flights for airport: {}", airport_code);
-    let client = create_client();
-    let base_url = get_babel_service_base_url();
-    let url = format!(
-        "{}{}",
-        base_url,
-        BABEL_AIRPORT_API_PATH.replace("{airport_code}", airport_code)
-    );
-
-    info!(url = %url, "Making request to fetch flights by airport");
-    let resp = client.get(&url).send().await?;
-
-    match resp.status() {
-        reqwest::StatusCode::OK => {
-            let flights = resp.json::<Vec<FlightResponse>>().await.map_err(|e| {
-                error!("Failed to parse flights response: {}", e);
-                BabelServiceError::ParseError(format!("JSON parse error: {}", e))
-            })?;
 
+    match client.flights_by_airport(airport_code).await {
+        Ok(flights) => {
             info!(count = flights.len(), airport_code = %airport_code, "Successfully fetched flights");
             Ok(flights)
         }
-        reqwest::StatusCode::NOT_FOUND => {
-            let error_text = resp
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-
-            // Check if it's specifically "no future flights" vs "airport not found"
-            if error_text.contains("No future flights") || error_text.contains("NO_FUTURE_FLIGHTS")
-            {
-                Err(BabelServiceError::NoFutureFlights(airport_code.to_string()))
-            } else {
-                Err(BabelServiceError::NotFound(format!(
-                    "Airport {} not found or no data available",
-                    airport_code
-                )))
-            }
+        // Tell "no future flights" apart from "airport not found"
+        Err(e) if e.code() == Some(NO_FUTURE_FLIGHTS) => {
+            Err(BabelServiceError::NoFutureFlights(airport_code.to_string()))
         }
-        status => {
-            error!(status = %status, airport_code = %airport_code, "Failed to fetch flights");
-            Err(BabelServiceError::Network(reqwest::Error::from(
-                resp.error_for_status().unwrap_err(),
-            )))
+        Err(e) if e.is_not_found() => Err(BabelServiceError::NotFound(format!(
+            "Airport {} not found or no data available",
+            airport_code
+        ))),
+        Err(e) => {
+            error!(error = %e, airport_code = %airport_code, "Failed to fetch flights");
+            Err(e.into())
         }
     }
 }
 
-#[instrument]
+#[instrument(skip(client))]
 pub async fn fetch_flight_position(
+    client: &BabelClient,
     flight_number: &str,
 ) -> Result<FlightPositionResponse, BabelServiceError> {
     info!("Fetching position for flight: {}", flight_number);
-    let client = create_client();
-    let base_url = get_babel_service_base_url();
-    let url = format!(
-        "{}{}",
-        base_url,
-        BABEL_POSITION_API_PATH.replace("{flight_number}", flight_number)
-    );
-
-    info!(url = %url, "Making request to fetch flight position");
-    let resp = client.get(&url).send().await?;
-
-    match resp.status() {
-        reqwest::StatusCode::OK => {
-            let position = resp.json::<FlightPositionResponse>().await.map_err(|e| {
-                error!("Failed to parse flight position response: {}", e);
-                BabelServiceError::ParseError(format!("JSON parse error: {}", e))
-            })?;
 
+    match client.flight_position(flight_number).await {
+        Ok(position) => {
             info!(
                 flight_number = %flight_number,
                 latitude = position.latitude,
@@ -108,15 +57,13 @@ pub async fn fetch_flight_position(
             );
             Ok(position)
         }
-        reqwest::StatusCode::NOT_FOUND => Err(BabelServiceError::NotFound(format!(
+        Err(e) if e.is_not_found() => Err(BabelServiceError::NotFound(format!(
             "Flight {} not found or not currently in flight",
             flight_number
         ))),
-        status => {
-            error!(status = %status, flight_number = %flight_number, "Failed to fetch flight position");
-            Err(BabelServiceError::Network(reqwest::Error::from(
-                resp.error_for_status().unwrap_err(),
-            )))
+        Err(e) => {
+            error!(error = %e, flight_number = %flight_number, "Failed to fetch flight position");
+            Err(e.into())
         }
     }
 }
@@ -1,6 +1,8 @@
 use crate::models::datetime::*;
 use chrono::{DateTime, Datelike, Utc};
 use chrono_tz::Tz;
+use sky_tracer::clock::SharedClock;
+use sky_tracer::protocol::problem::ProblemDetails;
 use thiserror::Error;
 use tracing::{info, instrument, warn};
 
@@ -12,6 +14,23 @@ pub enum DateTimeServiceError {
     ParseError(String),
 }
 
+impl From<DateTimeServiceError> for ProblemDetails {
+    fn from(error: DateTimeServiceError) -> Self {
+        match error {
+            DateTimeServiceError::InvalidTimezone(message) => {
+                ProblemDetails::new(400, "Invalid timezone")
+                    .with_code("INVALID_TIMEZONE")
+                    .with_detail(message)
+            }
+            DateTimeServiceError::ParseError(message) => {
+                ProblemDetails::new(500, "Cannot compute date and time")
+                    .with_code("INTERNAL_ERROR")
+                    .with_detail(message)
+            }
+        }
+    }
+}
+
 /// Calculate UTC offset in seconds using naive datetime difference
 /// This is more robust than trying to use the offset methods
 fn calculate_utc_offset_seconds(
@@ -24,8 +43,9 @@ fn calculate_utc_offset_seconds(
     duration.num_seconds() as i32
 }
 
-#[instrument]
+#[instrument(skip(clock))]
 pub async fn get_current_datetime(
+    clock: &SharedClock,
     query: GetDateTimeQuery,
 ) -> Result<DateTimeResponse, DateTimeServiceError> {
     let config = DateTimeConfig::from(query);
@@ -36,7 +56,7 @@ pub async fn get_current_datetime(
         config.format()
     );
 
-    let now = Utc::now();
+    let now = clock.now();
     let timezone_str = config.timezone();
 
     let (formatted_time, utc_offset) = match timezone_str {
@@ -79,11 +99,13 @@ pub async fn get_current_datetime(
     })
 }
 
-#[instrument]
-pub async fn get_aviation_times() -> Result<AviationTimesResponse, DateTimeServiceError> {
+#[instrument(skip(clock))]
+pub async fn get_aviation_times(
+    clock: &SharedClock,
+) -> Result<AviationTimesResponse, DateTimeServiceError> {
     info!("Getting current time in aviation-relevant timezones");
 
-    let now = Utc::now();
+    let now = clock.now();
 
     // Common aviation timezones
     let timezone_configs = vec![
@@ -140,8 +162,9 @@ pub async fn get_aviation_times() -> Result<AviationTimesResponse, DateTimeServi
     })
 }
 
-#[instrument]
+#[instrument(skip(clock))]
 pub async fn compare_timezones(
+    clock: &SharedClock,
     request: TimezoneComparisonRequest,
 ) -> Result<TimezoneComparisonResponse, DateTimeServiceError> {
     info!(
@@ -149,7 +172,7 @@ pub async fn compare_timezones(
         request.from_timezone, request.to_timezone
     );
 
-    let now = Utc::now();
+    let now = clock.now();
 
     // Handle UTC specially
     let (from_time_formatted, from_offset_seconds, from_abbreviation) =
@@ -1,152 +1,170 @@
-use crate::client::create_client;
+use axum::http::StatusCode;
+use sky_tracer::client::{ClientError, FlightsClient};
 use sky_tracer::model::flight::Flight;
-use sky_tracer::protocol::{
-    FLIGHTS_API_PATH,
-    flights::{CreateFlightRequest, FlightResponse},
+use sky_tracer::protocol::flights::{
+    CreateFlightRequest, ListFlightsRequest, PatchFlightRequest, UpdateFlightRequest,
 };
-use std::env;
+use sky_tracer::protocol::problem::ProblemDetails;
 use thiserror::Error;
 use tracing::{debug, error, info, instrument};
 
 #[derive(Error, Debug)]
 pub enum FlightServiceError {
-    #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
-    #[error("Middleware error: {0}")]
-    Middleware(#[from] reqwest_middleware::Error),
+    #[error("Flight service error: {0}")]
+    Client(ClientError),
     #[error("Flight not found: {0}")]
     NotFound(String),
-    #[error("Parse error: {0}")]
-    ParseError(String),
+    #[error("Flight change rejected: {0}")]
+    Conflict(ProblemDetails),
+    #[error("Invalid flight request: {0}")]
+    InvalidRequest(ProblemDetails),
 }
 
-fn get_flight_service_base_url() -> String {
-    env::var("FLIGHT_SERVICE_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+impl From<FlightServiceError> for ProblemDetails {
+    fn from(error: FlightServiceError) -> Self {
+        match error {
+            FlightServiceError::NotFound(message) => ProblemDetails::new(404, "Flight not found")
+                .with_code("NOT_FOUND")
+                .with_detail(message),
+            FlightServiceError::Conflict(problem) | FlightServiceError::InvalidRequest(problem) => {
+                problem
+            }
+            FlightServiceError::Client(error) => error.to_problem(),
+        }
+    }
 }
 
-#[instrument]
-pub async fn fetch_flights() -> Result<Vec<Flight>, FlightServiceError> {
-    let client = create_client();
-    let base_url = get_flight_service_base_url();
-    let url = format!("{}{}", base_url, FLIGHTS_API_PATH);
-
-    info!("Fetching flights from: {}", url);
-
-    let resp = client.get(&url).send().await?;
-    let status = resp.status();
-
-    debug!("Response status: {}", status);
-
-    if !status.is_success() {
-        error!("Flight service returned error status: {}", status);
-        return Err(FlightServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ));
+/// Turn error answers of the flight controller into service errors
+fn flight_error(flight_number: &str, error: ClientError) -> FlightServiceError {
+    match error.status() {
+        Some(StatusCode::NOT_FOUND) => {
+            error!("Flight not found: {}", flight_number);
+            FlightServiceError::NotFound(format!("Flight {} not found", flight_number))
+        }
+        Some(status @ (StatusCode::CONFLICT | StatusCode::BAD_REQUEST)) => {
+            // The flight controller explains why the request was rejected
+            let problem = error.to_problem();
+            error!("Flight {} request rejected: {}", flight_number, problem);
+            if status == StatusCode::CONFLICT {
+                FlightServiceError::Conflict(problem)
+            } else {
+                FlightServiceError::InvalidRequest(problem)
+            }
+        }
+        _ => {
+            error!("Flight service request failed: {}", error);
+            FlightServiceError::Client(error)
+        }
     }
+}
 
-    let response_text = resp.text().await?;
-    debug!("Response body: {}", response_text);
+#[instrument(skip(client))]
+pub async fn fetch_flights(client: &FlightsClient) -> Result<Vec<Flight>, FlightServiceError> {
+    info!("Fetching flights from: {}", client.base_url());
 
-    if response_text.trim().is_empty() {
-        info!("Empty response from flight service, returning empty flight list");
-        return Ok(vec![]);
-    }
+    let flight_responses = client
+        .list(&ListFlightsRequest::default())
+        .await
+        .map_err(|e| flight_error("list", e))?;
 
-    let flight_responses: Vec<FlightResponse> =
-        serde_json::from_str(&response_text).map_err(|e| {
-            error!("Failed to parse flight response as JSON: {}", e);
-            error!("Response was: {}", response_text);
-            FlightServiceError::ParseError(format!(
-                "JSON parse error: {}, response: {}",
-                e, response_text
-            ))
-        })?;
-
-    let flights: Vec<Flight> = flight_responses
-        .into_iter()
-        .map(|fr| Flight {
-            flight_number: fr.flight_number,
-            aircraft_number: fr.aircraft_number,
-            departure: fr.departure,
-            arrival: fr.arrival,
-            departure_time: fr.departure_time,
-            arrival_time: fr.arrival_time,
-        })
-        .collect();
+    let flights: Vec<Flight> = flight_responses.into_iter().map(Flight::from).collect();
 
     info!("Successfully fetched {} flights", flights.len());
     Ok(flights)
 }
 
-#[instrument]
-pub async fn fetch_flight_by_number(flight_number: &str) -> Result<Flight, FlightServiceError> {
-    info!("Fetching flight by number: {}", flight_number);
-
-    let flights = fetch_flights().await?;
-
-    flights
-        .into_iter()
-        .find(|f| f.flight_number == flight_number)
-        .ok_or_else(|| {
-            error!("Flight not found: {}", flight_number);
-            FlightServiceError::NotFound(format!("Flight {} not found", flight_number))
-        })
+#[instrument(skip(client))]
+pub async fn fetch_flight_by_number(
+    client: &FlightsClient,
+    flight_number: &str,
+) -> Result<Flight, FlightServiceError> {
+    info!("Fetching flight {}", flight_number);
+
+    let flight = client
+        .get(flight_number)
+        .await
+        .map_err(|e| flight_error(flight_number, e))?;
+    Ok(flight.into())
 }
 
-#[instrument]
-pub async fn create_flight(flight: Flight) -> Result<Flight, FlightServiceError> {
-    let client = create_client();
-    let base_url = get_flight_service_base_url();
-    let url = format!("{}{}", base_url, FLIGHTS_API_PATH);
-
-    info!("Creating flight at: {}", url);
+#[instrument(skip(client, request))]
+pub async fn create_flight(
+    client: &FlightsClient,
+    request: CreateFlightRequest,
+) -> Result<Flight, FlightServiceError> {
+    info!("Creating flight");
+    debug!("Create request: {:?}", request);
+
+    let requested = request
+        .flight_number
+        .as_ref()
+        .map_or("new flight", |number| number.as_str());
+    let created_flight: Flight = client
+        .create(&request)
+        .await
+        .map_err(|e| flight_error(requested, e))?
+        .into();
 
-    let create_request = CreateFlightRequest {
-        aircraft_number: flight.aircraft_number,
-        departure: flight.departure,
-        arrival: flight.arrival,
-        departure_time: flight.departure_time,
-        arrival_time: flight.arrival_time,
-    };
+    info!(
+        "Successfully created flight: {}",
+        created_flight.flight_number
+    );
+    Ok(created_flight)
+}
 
-    debug!("Create request: {:?}", create_request);
+#[instrument(skip(client, request))]
+pub async fn update_flight(
+    client: &FlightsClient,
+    flight_number: &str,
+    request: UpdateFlightRequest,
+) -> Result<Flight, FlightServiceError> {
+    info!("Updating flight {}", flight_number);
+    debug!("Update request: {:?}", request);
+
+    let flight: Flight = client
+        .update(flight_number, &request)
+        .await
+        .map_err(|e| flight_error(flight_number, e))?
+        .into();
+
+    info!("Successfully updated flight: {}", flight.flight_number);
+    Ok(flight)
+}
 
-    let resp = client.post(&url).json(&create_request).send().await?;
-    let status = resp.status();
+#[instrument(skip(client, request))]
+pub async fn patch_flight(
+    client: &FlightsClient,
+    flight_number: &str,
+    request: PatchFlightRequest,
+) -> Result<Flight, FlightServiceError> {
+    info!("Patching flight {}", flight_number);
+    debug!("Patch request: {:?}", request);
+
+    let flight: Flight = client
+        .patch(flight_number, &request)
+        .await
+        .map_err(|e| flight_error(flight_number, e))?
+        .into();
 
-    debug!("Create response status: {}", status);
+    info!(
+        "Successfully patched flight: {} ({})",
+        flight.flight_number, flight.status
+    );
+    Ok(flight)
+}
 
-    if !status.is_success() {
-        error!("Flight creation failed with status: {}", status);
-        return Err(FlightServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ));
-    }
+#[instrument(skip(client))]
+pub async fn delete_flight(
+    client: &FlightsClient,
+    flight_number: &str,
+) -> Result<(), FlightServiceError> {
+    info!("Deleting flight {}", flight_number);
 
-    let response_text = resp.text().await?;
-    debug!("Create response body: {}", response_text);
-
-    let flight_response: FlightResponse = serde_json::from_str(&response_text).map_err(|e| {
-        error!("Failed to parse create flight response: {}", e);
-        error!("Response was: {}", response_text);
-        FlightServiceError::ParseError(format!(
-            "JSON parse error: {}, response: {}",
-            e, response_text
-        ))
-    })?;
-
-    let created_flight = Flight {
-        flight_number: flight_response.flight_number,
-        aircraft_number: flight_response.aircraft_number,
-        departure: flight_response.departure,
-        arrival: flight_response.arrival,
-        departure_time: flight_response.departure_time,
-        arrival_time: flight_response.arrival_time,
-    };
+    client
+        .delete(flight_number)
+        .await
+        .map_err(|e| flight_error(flight_number, e))?;
 
-    info!(
-        "Successfully created flight: {}",
-        created_flight.flight_number
-    );
-    Ok(created_flight)
+    info!("Successfully deleted flight: {}", flight_number);
+    Ok(())
 }
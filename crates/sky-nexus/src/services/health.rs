@@ -0,0 +1,24 @@
+use super::Upstreams;
+use sky_tracer::protocol::health::{DependencyHealth, check_dependency};
+
+/// Health of all services Sky Nexus aggregates, checked concurrently
+pub async fn readiness(upstreams: &Upstreams) -> Vec<DependencyHealth> {
+    let Upstreams {
+        airports,
+        flights,
+        satellites,
+        babel,
+    } = upstreams;
+    let (airports_health, flights_health, satellites_health, babel_health) = tokio::join!(
+        check_dependency("airport-anywhere", airports.live()),
+        check_dependency("flight-controller", flights.live()),
+        check_dependency("orbital-beacon", satellites.live()),
+        check_dependency("tower-of-babel", babel.live()),
+    );
+    vec![
+        airports_health.with_circuit(airports.circuit_state()),
+        flights_health.with_circuit(flights.circuit_state()),
+        satellites_health.with_circuit(satellites.circuit_state()),
+        babel_health.with_circuit(babel.circuit_state()),
+    ]
+}
@@ -2,4 +2,66 @@ pub mod airports;
 pub mod babel;
 pub mod datetime;
 pub mod flights;
+pub mod health;
 pub mod satellites;
+
+use axum::extract::FromRef;
+use sky_tracer::client::{
+    AirportsClient, BabelClient, ClientConfig, FlightsClient, SatellitesClient,
+};
+use sky_tracer::clock::SharedClock;
+use std::fmt;
+
+/// State of the routes and tools of Sky Nexus
+#[derive(Clone, Debug, FromRef)]
+pub struct NexusState {
+    pub upstreams: Upstreams,
+    /// Clock the date and time tools read
+    pub clock: SharedClock,
+}
+
+/// Clients of the services Sky Nexus aggregates
+#[derive(Clone)]
+pub struct Upstreams {
+    pub airports: AirportsClient,
+    pub flights: FlightsClient,
+    pub satellites: SatellitesClient,
+    pub babel: BabelClient,
+}
+
+impl Upstreams {
+    /// Clients for `AIRPORT_SERVICE_BASE_URL`, `FLIGHT_SERVICE_BASE_URL`,
+    /// `SATELLITE_SERVICE_BASE_URL` and `BABEL_SERVICE_BASE_URL`, defaulting
+    /// to the Docker Compose ports
+    pub fn from_env() -> Self {
+        Self {
+            airports: AirportsClient::with_config(ClientConfig::from_env(
+                "AIRPORT_SERVICE_BASE_URL",
+                "http://localhost:3000",
+            )),
+            flights: FlightsClient::with_config(ClientConfig::from_env(
+                "FLIGHT_SERVICE_BASE_URL",
+                "http://localhost:3001",
+            )),
+            satellites: SatellitesClient::with_config(ClientConfig::from_env(
+                "SATELLITE_SERVICE_BASE_URL",
+                "http://localhost:3002",
+            )),
+            babel: BabelClient::with_config(ClientConfig::from_env(
+                "BABEL_SERVICE_BASE_URL",
+                "http://localhost:3003",
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for Upstreams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Upstreams")
+            .field("airports", &self.airports.base_url())
+            .field("flights", &self.flights.base_url())
+            .field("satellites", &self.satellites.base_url())
+            .field("babel", &self.babel.base_url())
+            .finish()
+    }
+}
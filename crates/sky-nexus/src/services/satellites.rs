@@ -1,126 +1,90 @@
-use crate::client::create_client;
-use sky_tracer::protocol::{
-    SATELLITES_API_PATH, SATELLITES_POSITION_API_PATH, SATELLITES_STATUS_API_PATH,
-    satellite::{
-        CalculatePositionRequest, CalculatePositionResponse, CreateSatelliteRequest,
-        SatelliteResponse, UpdateSatelliteStatusRequest,
-    },
+use sky_tracer::client::{ClientError, SatellitesClient};
+use sky_tracer::protocol::problem::ProblemDetails;
+use sky_tracer::protocol::satellite::{
+    CalculatePositionRequest, CalculatePositionResponse, CreateSatelliteRequest, SatelliteResponse,
+    UpdateSatelliteStatusRequest,
 };
-use std::env;
 use thiserror::Error;
 use tracing::{error, info, instrument};
 use uuid::Uuid;
 
 #[derive(Error, Debug)]
 pub enum SatelliteServiceError {
-    #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
-    #[error("Middleware error: {0}")]
-    Middleware(#[from] reqwest_middleware::Error),
+    #[error("Satellite service error: {0}")]
+    Client(#[from] ClientError),
     #[error("Satellite not found: {0}")]
     NotFound(String),
-    #[error("Parse error: {0}")]
-    ParseError(String),
 }
 
-fn get_satellite_service_base_url() -> String {
-    env::var("SATELLITE_SERVICE_BASE_URL").unwrap_or_else(|_| "http://localhost:3002".to_string())
+impl From<SatelliteServiceError> for ProblemDetails {
+    fn from(error: SatelliteServiceError) -> Self {
+        match error {
+            SatelliteServiceError::NotFound(id) => ProblemDetails::new(404, "Satellite not found")
+                .with_code("NOT_FOUND")
+                .with_detail(format!("Satellite {} not found", id)),
+            SatelliteServiceError::Client(error) => error.to_problem(),
+        }
+    }
 }
 
-#[instrument]
-pub async fn fetch_satellites() -> Result<Vec<SatelliteResponse>, SatelliteServiceError> {
-    let client = create_client();
-    let base_url = get_satellite_service_base_url();
-    let url = format!("{}{}", base_url, SATELLITES_API_PATH);
-
-    info!("Fetching satellites from: {}", url);
+#[instrument(skip(client))]
+pub async fn fetch_satellites(
+    client: &SatellitesClient,
+) -> Result<Vec<SatelliteResponse>, SatelliteServiceError> {
+    info!("Fetching satellites from: {}", client.base_url());
 
-    let resp = client.get(&url).send().await?;
-    if resp.status().is_success() {
-        let satellites = resp.json::<Vec<SatelliteResponse>>().await?;
-        info!("Successfully fetched {} satellites", satellites.len());
-        Ok(satellites)
-    } else {
-        error!("Failed to fetch satellites: {}", resp.status());
-        Err(SatelliteServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ))
-    }
+    let satellites = client
+        .list()
+        .await
+        .inspect_err(|e| error!("Failed to fetch satellites: {}", e))?;
+    info!("Successfully fetched {} satellites", satellites.len());
+    Ok(satellites)
 }
 
-#[instrument]
+#[instrument(skip(client))]
 pub async fn create_satellite(
+    client: &SatellitesClient,
     req: CreateSatelliteRequest,
 ) -> Result<SatelliteResponse, SatelliteServiceError> {
-    let client = create_client();
-    let base_url = get_satellite_service_base_url();
-    let url = format!("{}{}", base_url, SATELLITES_API_PATH);
-
-    info!("Creating satellite at: {}", url);
-
-    let resp = client.post(&url).json(&req).send().await?;
-    if resp.status().is_success() {
-        let satellite = resp.json::<SatelliteResponse>().await?;
-        info!("Successfully created satellite: {}", satellite.name);
-        Ok(satellite)
-    } else {
-        error!("Failed to create satellite: {}", resp.status());
-        Err(SatelliteServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ))
-    }
+    let satellite = client
+        .create(&req)
+        .await
+        .inspect_err(|e| error!("Failed to create satellite: {}", e))?;
+    info!("Successfully created satellite: {}", satellite.name);
+    Ok(satellite)
 }
 
-#[instrument]
+#[instrument(skip(client))]
 pub async fn update_satellite_status(
+    client: &SatellitesClient,
     id: Uuid,
     req: UpdateSatelliteStatusRequest,
 ) -> Result<SatelliteResponse, SatelliteServiceError> {
-    let client = create_client();
-    let base_url = get_satellite_service_base_url();
-    let url = format!(
-        "{}{}",
-        base_url,
-        SATELLITES_STATUS_API_PATH.replace("{id}", &id.to_string())
-    );
-
-    info!("Updating satellite status at: {}", url);
-
-    let resp = client.put(&url).json(&req).send().await?;
-    if resp.status().is_success() {
-        let satellite = resp.json::<SatelliteResponse>().await?;
-        info!("Successfully updated satellite: {}", satellite.name);
-        Ok(satellite)
-    } else {
-        error!("Failed to update satellite status: {}", resp.status());
-        Err(SatelliteServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ))
+    match client.update_status(id, &req).await {
+        Ok(satellite) => {
+            info!("Successfully updated satellite: {}", satellite.name);
+            Ok(satellite)
+        }
+        Err(e) if e.is_not_found() => Err(SatelliteServiceError::NotFound(id.to_string())),
+        Err(e) => {
+            error!("Failed to update satellite status: {}", e);
+            Err(e.into())
+        }
     }
 }
 
-#[instrument]
+#[instrument(skip(client))]
 pub async fn calculate_position(
+    client: &SatellitesClient,
     req: CalculatePositionRequest,
 ) -> Result<CalculatePositionResponse, SatelliteServiceError> {
-    let client = create_client();
-    let base_url = get_satellite_service_base_url();
-    let url = format!("{}{}", base_url, SATELLITES_POSITION_API_PATH);
-
-    info!("Calculating position at: {}", url);
-
-    let resp = client.post(&url).json(&req).send().await?;
-    if resp.status().is_success() {
-        let response = resp.json::<CalculatePositionResponse>().await?;
-        info!(
-            "Successfully calculated {} positions",
-            response.positions.len()
-        );
-        Ok(response)
-    } else {
-        error!("Failed to calculate position: {}", resp.status());
-        Err(SatelliteServiceError::Network(
-            resp.error_for_status().unwrap_err(),
-        ))
-    }
+    let response = client
+        .calculate_position(&req)
+        .await
+        .inspect_err(|e| error!("Failed to calculate position: {}", e))?;
+    info!(
+        "Successfully calculated {} positions",
+        response.positions.len()
+    );
+    Ok(response)
 }
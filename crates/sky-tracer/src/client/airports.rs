@@ -0,0 +1,97 @@
+use super::{typed_client, ClientError};
+use crate::protocol::airports::{
+    AirportResponse, AutocompleteRequest, AutocompleteResponse, ListAirportsRequest,
+    ListAirportsResponse, NearbyAirportsRequest, NearbyAirportsResponse, SearchAirportsRequest,
+    SearchAirportsResponse, MAX_LIST_LIMIT,
+};
+use crate::protocol::{
+    AIRPORTS_API_PATH, AIRPORTS_AUTOCOMPLETE_API_PATH, AIRPORTS_NEARBY_API_PATH,
+    AIRPORTS_SEARCH_API_PATH, AIRPORT_API_PATH,
+};
+use reqwest::Method;
+
+typed_client!(
+    /// Client of the airport-anywhere API
+    AirportsClient
+);
+
+impl AirportsClient {
+    /// Airport with the given primary code
+    pub async fn get(&self, code: &str) -> Result<AirportResponse, ClientError> {
+        self.api
+            .request_at(Method::GET, AIRPORT_API_PATH, code)
+            .send()
+            .await
+    }
+
+    /// Airport with the given IATA or ICAO code, `None` if there is none
+    pub async fn airport(&self, code: &str) -> Result<Option<AirportResponse>, ClientError> {
+        let request = SearchAirportsRequest {
+            code: Some(code.to_string()),
+            ..Default::default()
+        };
+        Ok(self.search(&request).await?.airports.into_iter().next())
+    }
+
+    pub async fn search(
+        &self,
+        request: &SearchAirportsRequest,
+    ) -> Result<SearchAirportsResponse, ClientError> {
+        self.api
+            .request(Method::GET, AIRPORTS_SEARCH_API_PATH)
+            .query(request)
+            .send()
+            .await
+    }
+
+    /// One page of the airport list
+    pub async fn list(
+        &self,
+        request: &ListAirportsRequest,
+    ) -> Result<ListAirportsResponse, ClientError> {
+        self.api
+            .request(Method::GET, AIRPORTS_API_PATH)
+            .query(request)
+            .send()
+            .await
+    }
+
+    /// All airports, following the list cursors page by page
+    pub async fn list_all(&self) -> Result<Vec<AirportResponse>, ClientError> {
+        let mut request = ListAirportsRequest {
+            limit: Some(MAX_LIST_LIMIT),
+            ..Default::default()
+        };
+        let mut airports = Vec::new();
+        loop {
+            let page = self.list(&request).await?;
+            airports.extend(page.airports);
+            match page.next_cursor {
+                Some(cursor) => request.cursor = Some(cursor),
+                None => return Ok(airports),
+            }
+        }
+    }
+
+    pub async fn nearby(
+        &self,
+        request: &NearbyAirportsRequest,
+    ) -> Result<NearbyAirportsResponse, ClientError> {
+        self.api
+            .request(Method::GET, AIRPORTS_NEARBY_API_PATH)
+            .query(request)
+            .send()
+            .await
+    }
+
+    pub async fn autocomplete(
+        &self,
+        request: &AutocompleteRequest,
+    ) -> Result<AutocompleteResponse, ClientError> {
+        self.api
+            .request(Method::GET, AIRPORTS_AUTOCOMPLETE_API_PATH)
+            .query(request)
+            .send()
+            .await
+    }
+}
@@ -0,0 +1,35 @@
+use super::{typed_client, ClientError};
+use crate::protocol::flights::{FlightPositionResponse, FlightResponse};
+use crate::protocol::{BABEL_AIRPORT_API_PATH, BABEL_POSITION_API_PATH};
+use reqwest::Method;
+
+/// Error code of tower-of-babel for an airport without upcoming flights
+pub const NO_FUTURE_FLIGHTS: &str = "NO_FUTURE_FLIGHTS";
+
+typed_client!(
+    /// Client of the tower-of-babel API
+    BabelClient
+);
+
+impl BabelClient {
+    /// Flights departing from `airport_code` that have not arrived yet
+    pub async fn flights_by_airport(
+        &self,
+        airport_code: &str,
+    ) -> Result<Vec<FlightResponse>, ClientError> {
+        self.api
+            .request_at(Method::GET, BABEL_AIRPORT_API_PATH, airport_code)
+            .send()
+            .await
+    }
+
+    pub async fn flight_position(
+        &self,
+        flight_number: &str,
+    ) -> Result<FlightPositionResponse, ClientError> {
+        self.api
+            .request_at(Method::GET, BABEL_POSITION_API_PATH, flight_number)
+            .send()
+            .await
+    }
+}
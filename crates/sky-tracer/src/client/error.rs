@@ -0,0 +1,214 @@
+use crate::protocol::problem::ProblemDetails;
+use reqwest::StatusCode;
+
+/// Error of a call against one of the Sky Tracer APIs
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    /// The service could not be reached or the connection broke
+    #[error("Request to {url} failed: {message}")]
+    Transport { url: String, message: String },
+    /// The service did not answer within the configured timeout
+    #[error("Request to {url} timed out")]
+    Timeout { url: String },
+    /// The service answered with an error status
+    #[error("{problem} (HTTP {status})")]
+    Api {
+        status: StatusCode,
+        /// Problem details sent by the service, or derived from its answer
+        problem: ProblemDetails,
+    },
+    /// The response body did not match the expected protocol type
+    #[error("Invalid response from {url}: {message}")]
+    Decode { url: String, message: String },
+    /// The circuit breaker of the service is open after repeated failures,
+    /// the request was not sent
+    #[error("Request to {url} not sent, circuit breaker of {upstream} is open")]
+    CircuitOpen { url: String, upstream: String },
+}
+
+impl ClientError {
+    /// HTTP status of an error answer of the service
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Error code of an error answer of the service
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Api { problem, .. } => problem.code.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Problem details of an error answer of the service
+    pub fn problem(&self) -> Option<&ProblemDetails> {
+        match self {
+            Self::Api { problem, .. } => Some(problem),
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// Whether the service could not be reached or did not answer in time
+    pub fn is_unavailable(&self) -> bool {
+        matches!(
+            self,
+            Self::Transport { .. } | Self::Timeout { .. } | Self::CircuitOpen { .. }
+        )
+    }
+
+    pub(crate) fn from_middleware(url: &str, error: reqwest_middleware::Error) -> Self {
+        match error {
+            reqwest_middleware::Error::Reqwest(error) => Self::from_reqwest(url, error),
+            #[cfg(not(target_arch = "wasm32"))]
+            reqwest_middleware::Error::Middleware(error)
+                if error.is::<super::resilience::CircuitOpen>() =>
+            {
+                let upstream = error
+                    .downcast::<super::resilience::CircuitOpen>()
+                    .map(|open| open.upstream)
+                    .unwrap_or_default();
+                Self::CircuitOpen {
+                    url: url.to_string(),
+                    upstream,
+                }
+            }
+            reqwest_middleware::Error::Middleware(error) => Self::Transport {
+                url: url.to_string(),
+                message: error.to_string(),
+            },
+        }
+    }
+
+    pub(crate) fn from_reqwest(url: &str, error: reqwest::Error) -> Self {
+        let url = url.to_string();
+        if error.is_timeout() {
+            Self::Timeout { url }
+        } else if error.is_decode() {
+            Self::Decode {
+                url,
+                message: error.to_string(),
+            }
+        } else {
+            Self::Transport {
+                url,
+                message: error.to_string(),
+            }
+        }
+    }
+
+    /// Error for an unsuccessful answer, using the problem details if the
+    /// service sent them
+    pub(crate) fn from_body(status: StatusCode, body: &str) -> Self {
+        let problem = serde_json::from_str::<ProblemDetails>(body).unwrap_or_else(|_| {
+            let title = status.canonical_reason().unwrap_or("Request failed");
+            let problem = ProblemDetails::new(status.as_u16(), title);
+            match body.trim() {
+                "" => problem,
+                text => problem.with_detail(text),
+            }
+        });
+        Self::Api { status, problem }
+    }
+
+    /// Problem to answer with when this error of an upstream call fails a
+    /// request
+    ///
+    /// Rejections of the request (4xx) are passed on as they are, failures
+    /// of the upstream service become `502 Bad Gateway` and timeouts
+    /// `504 Gateway Timeout`.
+    pub fn to_problem(&self) -> ProblemDetails {
+        match self {
+            Self::Api { status, problem } if status.is_client_error() => ProblemDetails {
+                instance: None,
+                ..problem.clone()
+            },
+            Self::Api { problem, .. } => ProblemDetails {
+                trace_id: problem.trace_id.clone(),
+                ..ProblemDetails::new(502, "Upstream service failed")
+                    .with_code(UPSTREAM_ERROR)
+                    .with_detail(self.to_string())
+            },
+            Self::Timeout { .. } => ProblemDetails::new(504, "Upstream service timed out")
+                .with_code(UPSTREAM_TIMEOUT)
+                .with_detail(self.to_string()),
+            Self::CircuitOpen { .. } => ProblemDetails::new(503, "Upstream service unavailable")
+                .with_code(UPSTREAM_CIRCUIT_OPEN)
+                .with_detail(self.to_string()),
+            Self::Transport { .. } | Self::Decode { .. } => {
+                ProblemDetails::new(502, "Upstream service unavailable")
+                    .with_code(UPSTREAM_UNAVAILABLE)
+                    .with_detail(self.to_string())
+            }
+        }
+    }
+}
+
+/// Problem code of upstream services answering with a server error
+pub const UPSTREAM_ERROR: &str = "UPSTREAM_ERROR";
+/// Problem code of upstream services not answering in time
+pub const UPSTREAM_TIMEOUT: &str = "UPSTREAM_TIMEOUT";
+/// Problem code of upstream services that cannot be reached or understood
+pub const UPSTREAM_UNAVAILABLE: &str = "UPSTREAM_UNAVAILABLE";
+/// Problem code of upstream services not called while their circuit breaker
+/// is open
+pub const UPSTREAM_CIRCUIT_OPEN: &str = "UPSTREAM_CIRCUIT_OPEN";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_body_is_decoded() {
+        let error = ClientError::from_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"type":"urn:sky-tracer:problem:validation-failed","title":"Flight validation failed","status":400,"code":"VALIDATION_FAILED","fields":[{"field":"arrival","code":"UNKNOWN_AIRPORT","message":"unknown airport"}]}"#,
+        );
+
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.code(), Some("VALIDATION_FAILED"));
+        assert_eq!(
+            error.to_string(),
+            "Flight validation failed (arrival: unknown airport) (HTTP 400 Bad Request)"
+        );
+        assert_eq!(error.problem().unwrap().fields[0].field, "arrival");
+    }
+
+    #[test]
+    fn test_plain_error_body_is_kept_as_detail() {
+        let error = ClientError::from_body(StatusCode::BAD_GATEWAY, "upstream down");
+        assert_eq!(error.code(), None);
+        assert_eq!(error.to_string(), "upstream down (HTTP 502 Bad Gateway)");
+
+        let error = ClientError::from_body(StatusCode::NOT_FOUND, "");
+        assert!(error.is_not_found());
+        assert_eq!(error.to_string(), "Not Found (HTTP 404 Not Found)");
+    }
+
+    #[test]
+    fn test_upstream_errors_become_gateway_problems() {
+        let rejected = ClientError::from_body(
+            StatusCode::NOT_FOUND,
+            r#"{"title":"Flight not found","status":404,"code":"NOT_FOUND","instance":"/api/v1/flights/LH400"}"#,
+        );
+        let problem = rejected.to_problem();
+        assert_eq!(problem.status, 404);
+        assert_eq!(problem.code.as_deref(), Some("NOT_FOUND"));
+        assert_eq!(problem.instance, None);
+
+        let failed = ClientError::from_body(StatusCode::INTERNAL_SERVER_ERROR, "");
+        assert_eq!(failed.to_problem().status, 502);
+        assert_eq!(failed.to_problem().code.as_deref(), Some(UPSTREAM_ERROR));
+
+        let timeout = ClientError::Timeout {
+            url: "http://flight-controller:3001".to_string(),
+        };
+        assert_eq!(timeout.to_problem().status, 504);
+    }
+}
@@ -0,0 +1,279 @@
+use super::{typed_client, ClientError};
+use crate::protocol::flights::{
+    AirlineResponse, CreateFlightRequest, FlightPositionResponse, FlightResponse,
+    ListFlightsRequest, PatchFlightRequest, UpdateFlightRequest,
+};
+use crate::protocol::{
+    AIRLINES_API_PATH, FLIGHTS_API_PATH, FLIGHTS_DETAIL_API_PATH, FLIGHTS_POSITION_API_PATH,
+};
+use reqwest::Method;
+
+typed_client!(
+    /// Client of the flight-controller API
+    FlightsClient
+);
+
+impl FlightsClient {
+    /// Flights matching the filters of `request`
+    pub async fn list(
+        &self,
+        request: &ListFlightsRequest,
+    ) -> Result<Vec<FlightResponse>, ClientError> {
+        self.api
+            .request(Method::GET, FLIGHTS_API_PATH)
+            .query(request)
+            .send()
+            .await
+    }
+
+    pub async fn get(&self, flight_number: &str) -> Result<FlightResponse, ClientError> {
+        self.api
+            .request_at(Method::GET, FLIGHTS_DETAIL_API_PATH, flight_number)
+            .send()
+            .await
+    }
+
+    pub async fn create(
+        &self,
+        request: &CreateFlightRequest,
+    ) -> Result<FlightResponse, ClientError> {
+        self.api
+            .request(Method::POST, FLIGHTS_API_PATH)
+            .json(request)
+            .send()
+            .await
+    }
+
+    pub async fn update(
+        &self,
+        flight_number: &str,
+        request: &UpdateFlightRequest,
+    ) -> Result<FlightResponse, ClientError> {
+        self.api
+            .request_at(Method::PUT, FLIGHTS_DETAIL_API_PATH, flight_number)
+            .json(request)
+            .send()
+            .await
+    }
+
+    pub async fn patch(
+        &self,
+        flight_number: &str,
+        request: &PatchFlightRequest,
+    ) -> Result<FlightResponse, ClientError> {
+        self.api
+            .request_at(Method::PATCH, FLIGHTS_DETAIL_API_PATH, flight_number)
+            .json(request)
+            .send()
+            .await
+    }
+
+    pub async fn delete(&self, flight_number: &str) -> Result<(), ClientError> {
+        self.api
+            .request_at(Method::DELETE, FLIGHTS_DETAIL_API_PATH, flight_number)
+            .send_empty()
+            .await
+    }
+
+    /// Current position of a flight, calculated by the orbital beacon
+    pub async fn position(
+        &self,
+        flight_number: &str,
+    ) -> Result<FlightPositionResponse, ClientError> {
+        self.api
+            .request_at(Method::GET, FLIGHTS_POSITION_API_PATH, flight_number)
+            .send()
+            .await
+    }
+
+    pub async fn airlines(&self) -> Result<Vec<AirlineResponse>, ClientError> {
+        self.api
+            .request(Method::GET, AIRLINES_API_PATH)
+            .send()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientConfig;
+    use crate::clock::{FixedClock, SharedClock, SIMULATED_TIME_HEADER};
+    use crate::protocol::flights::FieldError;
+    use crate::protocol::problem::ProblemDetails;
+    use axum::extract::{Path, Query};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use std::time::Duration;
+
+    async fn serve(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn test_error_responses_are_decoded() {
+        let app = Router::new().route(
+            FLIGHTS_DETAIL_API_PATH,
+            get(|Path(flight_number): Path<String>| async move {
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(
+                        ProblemDetails::new(404, "Flight not found")
+                            .with_code("NOT_FOUND")
+                            .with_detail(format!("Flight not found: {}", flight_number))
+                            .with_fields(vec![FieldError::new(
+                                "flight_number",
+                                "unknown_flight",
+                                "no such flight",
+                            )]),
+                    ),
+                )
+            }),
+        );
+        let client = FlightsClient::new(serve(app).await);
+
+        let error = client.get("LH400").await.unwrap_err();
+        assert!(error.is_not_found());
+        assert_eq!(error.code(), Some("NOT_FOUND"));
+        assert_eq!(
+            error.to_string(),
+            "Flight not found: LH400 (flight_number: no such flight) (HTTP 404 Not Found)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_time_of_the_configured_clock_is_sent() {
+        let app = Router::new().route(
+            FLIGHTS_API_PATH,
+            get(|headers: HeaderMap| async move {
+                assert_eq!(headers[SIMULATED_TIME_HEADER], "2025-06-01T12:00:00+00:00");
+                Json(Vec::<FlightResponse>::new())
+            }),
+        );
+        let time = "2025-06-01T12:00:00Z".parse().unwrap();
+        let client = FlightsClient::with_config(
+            ClientConfig::new(serve(app).await).with_clock(SharedClock::new(FixedClock(time))),
+        );
+
+        assert!(client.list(&Default::default()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_sends_filters_as_query() {
+        let app = Router::new().route(
+            FLIGHTS_API_PATH,
+            get(|Query(filter): Query<ListFlightsRequest>| async move {
+                assert_eq!(filter.departure.as_deref(), Some("FRA"));
+                assert_eq!(filter.arrival, None);
+                Json(Vec::<FlightResponse>::new())
+            }),
+        );
+        let client = FlightsClient::new(serve(app).await);
+
+        let request = ListFlightsRequest {
+            departure: Some("FRA".to_string()),
+            ..Default::default()
+        };
+        assert!(client.list(&request).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_slow_service_times_out() {
+        let app = Router::new().route(
+            AIRLINES_API_PATH,
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Json(Vec::<AirlineResponse>::new())
+            }),
+        );
+        let config = super::super::ClientConfig::new(serve(app).await)
+            .with_timeout(Duration::from_millis(50));
+        let client = FlightsClient::with_config(config);
+
+        let error = client.airlines().await.unwrap_err();
+        assert!(matches!(error, ClientError::Timeout { .. }), "{:?}", error);
+        assert!(error.is_unavailable());
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_requests_are_retried() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let app = Router::new().route(
+            AIRLINES_API_PATH,
+            get({
+                let calls = calls.clone();
+                move || async move {
+                    match calls.fetch_add(1, Ordering::SeqCst) {
+                        0 => Err(StatusCode::SERVICE_UNAVAILABLE),
+                        _ => Ok(Json(Vec::<AirlineResponse>::new())),
+                    }
+                }
+            })
+            .post({
+                let calls = calls.clone();
+                move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
+            }),
+        );
+        let base_url = serve(app).await;
+        let client = FlightsClient::new(&base_url);
+
+        assert!(client.airlines().await.unwrap().is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Creating a flight twice would not be safe
+        let response = super::super::ClientConfig::new(&base_url)
+            .http_client()
+            .post(format!("{base_url}{AIRLINES_API_PATH}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_repeated_failures() {
+        use super::super::{CircuitBreakerConfig, CircuitState, UPSTREAM_CIRCUIT_OPEN};
+
+        let app = Router::new().route(
+            AIRLINES_API_PATH,
+            get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+        );
+        let config = super::super::ClientConfig::new(serve(app).await)
+            .without_retry()
+            .with_circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 2,
+                open_for: Duration::from_secs(60),
+            });
+        let client = FlightsClient::with_config(config);
+
+        for _ in 0..2 {
+            let error = client.airlines().await.unwrap_err();
+            assert_eq!(error.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+        }
+        assert_eq!(client.circuit_state(), CircuitState::Open);
+
+        let error = client.airlines().await.unwrap_err();
+        assert!(
+            matches!(error, ClientError::CircuitOpen { .. }),
+            "{:?}",
+            error
+        );
+        assert!(error.is_unavailable());
+        assert_eq!(error.to_problem().status, 503);
+        assert_eq!(
+            error.to_problem().code.as_deref(),
+            Some(UPSTREAM_CIRCUIT_OPEN)
+        );
+    }
+}
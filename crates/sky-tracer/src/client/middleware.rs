@@ -0,0 +1,192 @@
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next, Result};
+use std::time::Duration;
+
+/// Applies a per-request timeout.
+///
+/// The timeout of `reqwest::ClientBuilder` is not available on wasm, the one
+/// of a single request is.
+pub struct TimeoutMiddleware(pub Duration);
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Middleware for TimeoutMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        req.timeout_mut().get_or_insert(self.0);
+        next.run(req, extensions).await
+    }
+}
+
+/// Route template of a request, e.g. `/api/v1/flights/{flight_number}`
+///
+/// Set as request extension by the typed clients so metrics are labelled by
+/// route instead of by the concrete path.
+#[derive(Debug, Clone, Copy)]
+pub struct Route(pub &'static str);
+
+/// Sends the time of the clock along, so the called service follows it.
+///
+/// Without a clock the time of the process is sent.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ClockMiddleware(pub Option<crate::clock::SharedClock>);
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait::async_trait]
+impl Middleware for ClockMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let now = match &self.0 {
+            Some(clock) => clock.now(),
+            None => crate::clock::now(),
+        }
+        .to_rfc3339();
+        if let Ok(value) = reqwest::header::HeaderValue::from_str(&now) {
+            req.headers_mut()
+                .insert(crate::clock::SIMULATED_TIME_HEADER, value);
+        }
+        next.run(req, extensions).await
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use otel::{MetricsMiddleware, OtelMiddleware};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod otel {
+    use super::*;
+    use crate::protocol::metrics::{
+        DURATION_BUCKETS, HTTP_CLIENT_REQUEST_DURATION, UNMATCHED_ROUTE,
+    };
+    use opentelemetry::metrics::Histogram;
+    use opentelemetry::{global, KeyValue};
+    use std::time::Instant;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    struct HeaderInjector<'a>(&'a mut reqwest::header::HeaderMap);
+
+    impl opentelemetry::propagation::Injector for HeaderInjector<'_> {
+        fn set(&mut self, key: &str, value: String) {
+            if let Ok(name) = reqwest::header::HeaderName::from_bytes(key.as_bytes()) {
+                if let Ok(val) = reqwest::header::HeaderValue::from_str(&value) {
+                    self.0.insert(name, val);
+                }
+            }
+        }
+    }
+
+    /// Propagates the current tracing span to the called service
+    pub struct OtelMiddleware;
+
+    #[async_trait::async_trait]
+    impl Middleware for OtelMiddleware {
+        async fn handle(
+            &self,
+            mut req: Request,
+            extensions: &mut Extensions,
+            next: Next<'_>,
+        ) -> Result<Response> {
+            let cx = tracing::Span::current().context();
+            global::get_text_map_propagator(|propagator| {
+                propagator.inject_context(&cx, &mut HeaderInjector(req.headers_mut()));
+            });
+            next.run(req, extensions).await
+        }
+    }
+
+    /// Records upstream, route, status and duration of every call.
+    ///
+    /// The histogram is created once, with the server feature from the
+    /// metrics of the process so calls made before the first request are
+    /// counted too.
+    pub struct MetricsMiddleware {
+        duration: Histogram<f64>,
+    }
+
+    impl MetricsMiddleware {
+        pub fn new() -> Self {
+            #[cfg(feature = "server")]
+            let meter = crate::protocol::metrics::Metrics::global().meter();
+            #[cfg(not(feature = "server"))]
+            let meter = global::meter(crate::protocol::metrics::METER_NAME);
+            Self {
+                duration: meter
+                    .f64_histogram(HTTP_CLIENT_REQUEST_DURATION)
+                    .with_unit("s")
+                    .with_description("Duration of HTTP client requests")
+                    .with_boundaries(DURATION_BUCKETS.to_vec())
+                    .build(),
+            }
+        }
+    }
+
+    impl Default for MetricsMiddleware {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Middleware for MetricsMiddleware {
+        async fn handle(
+            &self,
+            req: Request,
+            extensions: &mut Extensions,
+            next: Next<'_>,
+        ) -> Result<Response> {
+            let mut attributes = vec![
+                KeyValue::new("http.request.method", req.method().to_string()),
+                KeyValue::new(
+                    "server.address",
+                    req.url().host_str().unwrap_or_default().to_string(),
+                ),
+                KeyValue::new(
+                    "http.route",
+                    extensions
+                        .get::<Route>()
+                        .map_or(UNMATCHED_ROUTE, |route| route.0),
+                ),
+            ];
+            if let Some(port) = req.url().port_or_known_default() {
+                attributes.push(KeyValue::new("server.port", i64::from(port)));
+            }
+            // Calls made while a service handles a request belong to it
+            #[cfg(feature = "server")]
+            if let Some(service) = crate::protocol::metrics::current_service() {
+                attributes.push(KeyValue::new(
+                    crate::protocol::metrics::SERVICE_NAME,
+                    service,
+                ));
+            }
+
+            let started = Instant::now();
+            let result = next.run(req, extensions).await;
+            match &result {
+                Ok(response) => attributes.push(KeyValue::new(
+                    "http.response.status_code",
+                    i64::from(response.status().as_u16()),
+                )),
+                Err(e) => attributes.push(KeyValue::new(
+                    "error.type",
+                    if e.is_timeout() {
+                        "timeout"
+                    } else {
+                        "transport"
+                    },
+                )),
+            }
+            self.duration
+                .record(started.elapsed().as_secs_f64(), &attributes);
+            result
+        }
+    }
+}
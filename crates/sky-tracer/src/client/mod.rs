@@ -0,0 +1,459 @@
+//! Typed async clients for the Sky Tracer APIs.
+//!
+//! Each service has its own client speaking the [`protocol`](crate::protocol)
+//! types, e.g. [`AirportsClient`] for airport-anywhere. Clients are cheap to
+//! clone and share one connection pool per client.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), sky_tracer::client::ClientError> {
+//! use sky_tracer::client::{AirportsClient, ClientConfig};
+//!
+//! let airports = AirportsClient::with_config(ClientConfig::from_env(
+//!     "AIRPORT_SERVICE_BASE_URL",
+//!     "http://localhost:3000",
+//! ));
+//! let frankfurt = airports.airport("FRA").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod airports;
+mod babel;
+mod error;
+mod flights;
+pub mod middleware;
+pub mod resilience;
+mod satellites;
+
+pub use airports::AirportsClient;
+pub use babel::{BabelClient, NO_FUTURE_FLIGHTS};
+pub use error::{
+    ClientError, UPSTREAM_CIRCUIT_OPEN, UPSTREAM_ERROR, UPSTREAM_TIMEOUT, UPSTREAM_UNAVAILABLE,
+};
+pub use flights::FlightsClient;
+pub use resilience::{CircuitBreakerConfig, CircuitState, RetryPolicy};
+pub use satellites::SatellitesClient;
+
+use crate::clock::SharedClock;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use reqwest::{Method, Response, StatusCode};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Timeout of a single request unless configured otherwise
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where a client finds its service and how it copes with failures of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientConfig {
+    /// Scheme, host and optional prefix of the service, e.g. `http://localhost:3000`
+    pub base_url: String,
+    /// Timeout of a single attempt, `None` waits forever
+    pub timeout: Option<Duration>,
+    /// Retries of idempotent requests, `None` sends every request once
+    pub retry: Option<RetryPolicy>,
+    /// Circuit breaker of the service, `None` always calls it
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Clock whose time is sent along, `None` sends the time of the process
+    pub clock: Option<SharedClock>,
+}
+
+impl ClientConfig {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            timeout: Some(DEFAULT_TIMEOUT),
+            retry: Some(RetryPolicy::default()),
+            circuit_breaker: Some(CircuitBreakerConfig::default()),
+            clock: None,
+        }
+    }
+
+    /// Base URL from the environment variable `var`, `default` if it is unset.
+    ///
+    /// The other settings are read with the prefix of `var`, e.g.
+    /// `AIRPORT_SERVICE_TIMEOUT_MS` for `AIRPORT_SERVICE_BASE_URL`, see
+    /// [`with_env_overrides`](Self::with_env_overrides).
+    pub fn from_env(var: &str, default: &str) -> Self {
+        Self::new(std::env::var(var).unwrap_or_else(|_| default.to_string()))
+            .with_env_overrides(var.trim_end_matches("_BASE_URL"))
+    }
+
+    /// Settings overridden by the environment variables
+    ///
+    /// - `{prefix}_TIMEOUT_MS`: timeout of a single attempt, `0` waits forever
+    /// - `{prefix}_MAX_RETRIES`: retries of idempotent requests, `0` disables them
+    /// - `{prefix}_CIRCUIT_THRESHOLD`: consecutive failures opening the
+    ///   circuit, `0` disables the circuit breaker
+    pub fn with_env_overrides(mut self, prefix: &str) -> Self {
+        if let Some(timeout_ms) = env_number(&format!("{prefix}_TIMEOUT_MS")) {
+            self.timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        }
+        if let Some(max_retries) = env_number(&format!("{prefix}_MAX_RETRIES")) {
+            self.retry = (max_retries > 0).then(|| RetryPolicy {
+                max_retries: max_retries.try_into().unwrap_or(u32::MAX),
+                ..self.retry.unwrap_or_default()
+            });
+        }
+        if let Some(threshold) = env_number(&format!("{prefix}_CIRCUIT_THRESHOLD")) {
+            self.circuit_breaker = (threshold > 0).then(|| CircuitBreakerConfig {
+                failure_threshold: threshold.try_into().unwrap_or(u32::MAX),
+                ..self.circuit_breaker.unwrap_or_default()
+            });
+        }
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn without_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    pub fn without_retry(mut self) -> Self {
+        self.retry = None;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    pub fn without_circuit_breaker(mut self) -> Self {
+        self.circuit_breaker = None;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// HTTP client applying the timeout to every attempt and, on native
+    /// targets, recording call metrics, retrying idempotent requests,
+    /// guarding the service by its circuit breaker and propagating the
+    /// current trace
+    pub fn http_client(&self) -> ClientWithMiddleware {
+        let mut builder = ClientBuilder::new(reqwest::Client::new());
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            builder = builder.with(middleware::MetricsMiddleware::new());
+            if let Some(retry) = self.retry {
+                builder = builder.with(resilience::RetryMiddleware(retry));
+            }
+            if let Some(circuit_breaker) = self.circuit_breaker {
+                builder = builder.with(resilience::CircuitBreakerMiddleware(circuit_breaker));
+            }
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.with(middleware::TimeoutMiddleware(timeout));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            builder = builder
+                .with(middleware::ClockMiddleware(self.clock.clone()))
+                .with(middleware::OtelMiddleware);
+        }
+        builder.build()
+    }
+}
+
+fn env_number(var: &str) -> Option<u64> {
+    let value = std::env::var(var).ok()?;
+    match value.trim().parse() {
+        Ok(number) => Some(number),
+        Err(_) => {
+            warn!(var, value = %value, "Ignoring invalid number in environment variable");
+            None
+        }
+    }
+}
+
+/// Constructors shared by all typed clients
+macro_rules! typed_client {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone)]
+        pub struct $name {
+            api: $crate::client::ApiClient,
+        }
+
+        impl $name {
+            /// Client with the default timeout
+            pub fn new(base_url: impl Into<String>) -> Self {
+                Self::with_config($crate::client::ClientConfig::new(base_url))
+            }
+
+            pub fn with_config(config: $crate::client::ClientConfig) -> Self {
+                Self {
+                    api: $crate::client::ApiClient::new(config.http_client(), config.base_url),
+                }
+            }
+
+            /// Client sending its requests through `http`, e.g. to share middleware
+            pub fn with_client(
+                http: reqwest_middleware::ClientWithMiddleware,
+                base_url: impl Into<String>,
+            ) -> Self {
+                Self {
+                    api: $crate::client::ApiClient::new(http, base_url.into()),
+                }
+            }
+
+            pub fn base_url(&self) -> &str {
+                &self.api.base_url
+            }
+
+            /// State of the circuit breaker guarding the calls to the service
+            #[cfg(not(target_arch = "wasm32"))]
+            pub fn circuit_state(&self) -> $crate::client::CircuitState {
+                reqwest::Url::parse(&self.api.base_url)
+                    .map(|url| {
+                        $crate::client::resilience::circuit_state(
+                            &$crate::client::resilience::upstream_of(&url),
+                        )
+                    })
+                    .unwrap_or($crate::client::CircuitState::Closed)
+            }
+
+            /// Liveness of the service, used by readiness checks of its callers
+            pub async fn live(
+                &self,
+            ) -> Result<$crate::protocol::health::HealthResponse, $crate::client::ClientError> {
+                self.api
+                    .request(
+                        reqwest::Method::GET,
+                        $crate::protocol::health::HEALTH_LIVE_PATH,
+                    )
+                    .send()
+                    .await
+            }
+        }
+    };
+}
+
+pub(crate) use typed_client;
+
+/// HTTP client bound to the base URL of one service
+#[derive(Clone)]
+pub(crate) struct ApiClient {
+    http: ClientWithMiddleware,
+    base_url: String,
+}
+
+impl ApiClient {
+    pub(crate) fn new(http: ClientWithMiddleware, base_url: String) -> Self {
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub(crate) fn request(&self, method: Method, route: &'static str) -> Call {
+        self.call(method, route, route)
+    }
+
+    /// Request to `route` with its placeholder filled by `value`
+    pub(crate) fn request_at(&self, method: Method, route: &'static str, value: &str) -> Call {
+        let mut call = self.call(method, route, &path(route, value));
+        // URLs resolve dot segments even when encoded, so these values would
+        // address another resource. None is named like this.
+        if value.chars().all(|c| c == '.') {
+            call.rejected = Some(ClientError::from_body(
+                StatusCode::NOT_FOUND,
+                &format!("No resource is named {value:?}"),
+            ));
+        }
+        call
+    }
+
+    fn call(&self, method: Method, route: &'static str, path: &str) -> Call {
+        let url = format!("{}{}", self.base_url, path);
+        Call {
+            builder: self
+                .http
+                .request(method, &url)
+                .with_extension(middleware::Route(route)),
+            url,
+            rejected: None,
+        }
+    }
+}
+
+/// A prepared request remembering its URL for error reporting
+pub(crate) struct Call {
+    url: String,
+    builder: RequestBuilder,
+    /// Error to fail with instead of sending the request
+    rejected: Option<ClientError>,
+}
+
+impl Call {
+    pub(crate) fn query<Q: Serialize + ?Sized>(mut self, query: &Q) -> Self {
+        self.builder = self.builder.query(query);
+        self
+    }
+
+    pub(crate) fn json<B: Serialize + ?Sized>(mut self, body: &B) -> Self {
+        self.builder = self.builder.json(body);
+        self
+    }
+
+    /// Send the request and decode the JSON answer
+    pub(crate) async fn send<T: DeserializeOwned>(self) -> Result<T, ClientError> {
+        let (url, response) = self.response().await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ClientError::from_reqwest(&url, e))?;
+        serde_json::from_str(&body).map_err(|e| ClientError::Decode {
+            url,
+            message: e.to_string(),
+        })
+    }
+
+    /// Send the request, ignoring the body of a successful answer
+    pub(crate) async fn send_empty(self) -> Result<(), ClientError> {
+        self.response().await.map(|_| ())
+    }
+
+    async fn response(self) -> Result<(String, Response), ClientError> {
+        let Call {
+            url,
+            builder,
+            rejected,
+        } = self;
+        if let Some(error) = rejected {
+            return Err(error);
+        }
+        debug!(url = %url, "Calling Sky Tracer API");
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| ClientError::from_middleware(&url, e))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok((url, response));
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let error = ClientError::from_body(status, &body);
+        debug!(url = %url, status = %status, error = %error, "Sky Tracer API call failed");
+        Err(error)
+    }
+}
+
+/// Characters escaped in a path segment: those of the URL path
+/// percent-encode set, the separators `/` and `\`, and `%` itself
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// `template` with its single `{...}` placeholder replaced by `value`,
+/// encoded as one path segment
+pub(crate) fn path(template: &str, value: &str) -> String {
+    match (template.find('{'), template.find('}')) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{}{}",
+            &template[..start],
+            utf8_percent_encode(value, PATH_SEGMENT),
+            &template[end + 1..]
+        ),
+        _ => template.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{BABEL_POSITION_API_PATH, SATELLITES_API_PATH};
+
+    #[test]
+    fn test_path_fills_placeholder() {
+        assert_eq!(
+            path(BABEL_POSITION_API_PATH, "LH400"),
+            "/api/v1/babel/LH400/position"
+        );
+        assert_eq!(path(SATELLITES_API_PATH, "x"), SATELLITES_API_PATH);
+    }
+
+    #[test]
+    fn test_path_value_stays_one_segment() {
+        assert_eq!(
+            path(BABEL_POSITION_API_PATH, "../admin"),
+            "/api/v1/babel/..%2Fadmin/position"
+        );
+        assert_eq!(
+            path(BABEL_POSITION_API_PATH, "X?y=1#z"),
+            "/api/v1/babel/X%3Fy=1%23z/position"
+        );
+        assert_eq!(
+            path(BABEL_POSITION_API_PATH, "a\\b 50%"),
+            "/api/v1/babel/a%5Cb%2050%25/position"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dot_segments_are_not_requested() {
+        let client = BabelClient::new("http://127.0.0.1:9");
+        for value in [".", ".."] {
+            let error = client.flight_position(value).await.unwrap_err();
+            assert!(error.is_not_found(), "{value}: {error:?}");
+        }
+    }
+
+    #[test]
+    fn test_config_trims_base_url() {
+        let config = ClientConfig::new("http://localhost:3000/");
+        assert_eq!(config.base_url, "http://localhost:3000");
+        assert_eq!(config.timeout, Some(DEFAULT_TIMEOUT));
+        assert_eq!(config.without_timeout().timeout, None);
+
+        let client =
+            AirportsClient::with_client(ClientConfig::new("http://a").http_client(), "http://b/");
+        assert_eq!(client.base_url(), "http://b");
+    }
+
+    #[test]
+    fn test_config_is_overridden_per_upstream() {
+        std::env::set_var("TEST_ORBITAL_BEACON_TIMEOUT_MS", "1500");
+        std::env::set_var("TEST_ORBITAL_BEACON_MAX_RETRIES", "0");
+        std::env::set_var("TEST_ORBITAL_BEACON_CIRCUIT_THRESHOLD", "3");
+
+        let config =
+            ClientConfig::from_env("TEST_ORBITAL_BEACON_BASE_URL", "http://orbital-beacon:3002");
+        assert_eq!(config.base_url, "http://orbital-beacon:3002");
+        assert_eq!(config.timeout, Some(Duration::from_millis(1500)));
+        assert_eq!(config.retry, None);
+        assert_eq!(
+            config
+                .circuit_breaker
+                .map(|breaker| breaker.failure_threshold),
+            Some(3)
+        );
+    }
+}
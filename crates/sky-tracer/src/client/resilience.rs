@@ -0,0 +1,374 @@
+//! Retries and circuit breakers of the upstream calls.
+//!
+//! Idempotent requests failing with a transport error, a timeout or a
+//! `502`/`503`/`504` answer are retried with exponential backoff and full
+//! jitter. Every upstream, identified by host and port, has one circuit
+//! breaker shared by all clients of the process: after a number of
+//! consecutive failures it opens and calls fail fast until a trial call
+//! succeeds again.
+
+pub use crate::protocol::health::CircuitState;
+use std::time::Duration;
+
+/// Retries of idempotent requests
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts after the first one
+    pub max_retries: u32,
+    /// Upper bound of the delay before the first retry, doubled per retry
+    pub base_delay: Duration,
+    /// Upper bound of any delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Upper bound of the delay before retry number `retry`, counting from 0
+    pub fn max_delay_before(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay)
+    }
+}
+
+/// When the circuit breaker of an upstream opens and for how long
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures opening the circuit
+    pub failure_threshold: u32,
+    /// Time calls fail fast before a trial call is let through
+    pub open_for: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_for: Duration::from_secs(30),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::upstream_of;
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::{circuit_state, CircuitBreakerMiddleware, CircuitOpen, RetryMiddleware};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use super::{CircuitBreakerConfig, CircuitState, RetryPolicy};
+    use http::Extensions;
+    use reqwest::{Method, Request, Response, StatusCode};
+    use reqwest_middleware::{Middleware, Next, Result};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+    use std::time::Instant;
+    use tracing::{info, warn};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    /// Error of a call the open circuit breaker of its upstream rejected
+    #[derive(thiserror::Error, Debug)]
+    #[error("Circuit breaker of {upstream} is open")]
+    pub struct CircuitOpen {
+        pub upstream: String,
+    }
+
+    #[derive(Debug)]
+    enum Circuit {
+        Closed {
+            failures: u32,
+        },
+        Open {
+            until: Instant,
+        },
+        /// A trial call is under way, all others fail fast. Another trial is
+        /// let through if it does not finish in time, e.g. when cancelled.
+        HalfOpen {
+            trial_started: Instant,
+        },
+    }
+
+    #[derive(Debug)]
+    struct CircuitBreaker {
+        upstream: String,
+        config: CircuitBreakerConfig,
+        circuit: Mutex<Circuit>,
+    }
+
+    impl CircuitBreaker {
+        fn lock(&self) -> MutexGuard<'_, Circuit> {
+            self.circuit.lock().unwrap_or_else(|e| e.into_inner())
+        }
+
+        fn state(&self) -> CircuitState {
+            match *self.lock() {
+                Circuit::Closed { .. } => CircuitState::Closed,
+                Circuit::Open { until } if until <= Instant::now() => CircuitState::HalfOpen,
+                Circuit::Open { .. } => CircuitState::Open,
+                Circuit::HalfOpen { .. } => CircuitState::HalfOpen,
+            }
+        }
+
+        /// Whether a call may be made now
+        fn acquire(&self) -> bool {
+            let mut circuit = self.lock();
+            let now = Instant::now();
+            match *circuit {
+                Circuit::Closed { .. } => true,
+                Circuit::HalfOpen { trial_started }
+                    if now < trial_started + self.config.open_for =>
+                {
+                    false
+                }
+                Circuit::Open { until } if now < until => false,
+                Circuit::Open { .. } | Circuit::HalfOpen { .. } => {
+                    info!(upstream = %self.upstream, "Circuit breaker half-open, sending trial call");
+                    *circuit = Circuit::HalfOpen { trial_started: now };
+                    true
+                }
+            }
+        }
+
+        fn record(&self, success: bool) {
+            let mut circuit = self.lock();
+            match (&*circuit, success) {
+                (Circuit::Closed { .. }, true) => *circuit = Circuit::Closed { failures: 0 },
+                (Circuit::HalfOpen { .. }, true) => {
+                    info!(upstream = %self.upstream, "Circuit breaker closed");
+                    *circuit = Circuit::Closed { failures: 0 };
+                }
+                (Circuit::Closed { failures }, false)
+                    if failures + 1 < self.config.failure_threshold =>
+                {
+                    *circuit = Circuit::Closed {
+                        failures: failures + 1,
+                    };
+                }
+                (Circuit::Closed { .. } | Circuit::HalfOpen { .. }, false) => {
+                    warn!(
+                        upstream = %self.upstream,
+                        open_for = ?self.config.open_for,
+                        "Circuit breaker opened"
+                    );
+                    *circuit = Circuit::Open {
+                        until: Instant::now() + self.config.open_for,
+                    };
+                }
+                // Answers of calls made before the circuit opened
+                (Circuit::Open { .. }, _) => {}
+            }
+        }
+    }
+
+    fn breakers() -> MutexGuard<'static, HashMap<String, Arc<CircuitBreaker>>> {
+        static BREAKERS: OnceLock<Mutex<HashMap<String, Arc<CircuitBreaker>>>> = OnceLock::new();
+        BREAKERS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Circuit breaker of `upstream`, created with `config` by its first user
+    fn breaker(upstream: &str, config: CircuitBreakerConfig) -> Arc<CircuitBreaker> {
+        breakers()
+            .entry(upstream.to_string())
+            .or_insert_with(|| {
+                Arc::new(CircuitBreaker {
+                    upstream: upstream.to_string(),
+                    config,
+                    circuit: Mutex::new(Circuit::Closed { failures: 0 }),
+                })
+            })
+            .clone()
+    }
+
+    /// State of the circuit breaker of `upstream` (`host:port`), closed if
+    /// it was never called
+    pub fn circuit_state(upstream: &str) -> CircuitState {
+        breakers()
+            .get(upstream)
+            .map_or(CircuitState::Closed, |breaker| breaker.state())
+    }
+
+    /// `host:port` of the called service
+    pub(crate) fn upstream_of(url: &reqwest::Url) -> String {
+        format!(
+            "{}:{}",
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or_default()
+        )
+    }
+
+    /// Whether the upstream failed, as opposed to rejecting the request
+    fn is_failure(result: &Result<Response>) -> bool {
+        match result {
+            Ok(response) => response.status().is_server_error(),
+            Err(_) => true,
+        }
+    }
+
+    /// Fails calls fast while the circuit breaker of their upstream is open
+    pub struct CircuitBreakerMiddleware(pub CircuitBreakerConfig);
+
+    #[async_trait::async_trait]
+    impl Middleware for CircuitBreakerMiddleware {
+        async fn handle(
+            &self,
+            req: Request,
+            extensions: &mut Extensions,
+            next: Next<'_>,
+        ) -> Result<Response> {
+            let breaker = breaker(&upstream_of(req.url()), self.0);
+            let span = tracing::Span::current();
+            if !breaker.acquire() {
+                span.set_attribute("circuit_breaker.state", breaker.state().to_string());
+                warn!(upstream = %breaker.upstream, url = %req.url(), "Circuit breaker open, call rejected");
+                return Err(reqwest_middleware::Error::middleware(CircuitOpen {
+                    upstream: breaker.upstream.clone(),
+                }));
+            }
+
+            let result = next.run(req, extensions).await;
+            breaker.record(!is_failure(&result));
+            span.set_attribute("circuit_breaker.state", breaker.state().to_string());
+            result
+        }
+    }
+
+    /// Retries idempotent requests the upstream failed to answer
+    pub struct RetryMiddleware(pub RetryPolicy);
+
+    fn is_idempotent(method: &Method) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+        )
+    }
+
+    fn is_retryable(result: &Result<Response>) -> bool {
+        match result {
+            Ok(response) => matches!(
+                response.status(),
+                StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            // Rejected by an open circuit breaker, retrying would not help
+            Err(reqwest_middleware::Error::Middleware(_)) => false,
+            Err(reqwest_middleware::Error::Reqwest(e)) => {
+                e.is_timeout() || e.is_connect() || e.is_request()
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Middleware for RetryMiddleware {
+        async fn handle(
+            &self,
+            req: Request,
+            extensions: &mut Extensions,
+            next: Next<'_>,
+        ) -> Result<Response> {
+            if !is_idempotent(req.method()) {
+                return next.run(req, extensions).await;
+            }
+
+            let mut retry = 0;
+            loop {
+                // Streaming bodies cannot be sent twice
+                let Some(attempt) = req.try_clone() else {
+                    return next.run(req, extensions).await;
+                };
+                let result = next.clone().run(attempt, extensions).await;
+                if retry >= self.0.max_retries || !is_retryable(&result) {
+                    return result;
+                }
+
+                let delay = self.0.max_delay_before(retry).mul_f64(fastrand::f64());
+                retry += 1;
+                warn!(
+                    url = %req.url(),
+                    retry,
+                    delay = ?delay,
+                    "Upstream call failed, retrying"
+                );
+                tracing::Span::current()
+                    .set_attribute("http.request.resend_count", i64::from(retry));
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::time::Duration;
+
+        fn breaker(threshold: u32, open_for: Duration) -> CircuitBreaker {
+            CircuitBreaker {
+                upstream: "orbital-beacon:3002".to_string(),
+                config: CircuitBreakerConfig {
+                    failure_threshold: threshold,
+                    open_for,
+                },
+                circuit: Mutex::new(Circuit::Closed { failures: 0 }),
+            }
+        }
+
+        #[test]
+        fn test_circuit_opens_after_consecutive_failures() {
+            let breaker = breaker(2, Duration::from_secs(60));
+
+            breaker.record(false);
+            breaker.record(true);
+            breaker.record(false);
+            assert_eq!(breaker.state(), CircuitState::Closed);
+
+            breaker.record(false);
+            assert_eq!(breaker.state(), CircuitState::Open);
+            assert!(!breaker.acquire());
+        }
+
+        #[test]
+        fn test_trial_call_closes_or_reopens_circuit() {
+            let breaker = breaker(1, Duration::from_millis(20));
+
+            breaker.record(false);
+            assert!(!breaker.acquire());
+            std::thread::sleep(Duration::from_millis(20));
+            assert_eq!(breaker.state(), CircuitState::HalfOpen);
+            assert!(breaker.acquire());
+            // Only one trial call at a time
+            assert!(!breaker.acquire());
+            breaker.record(false);
+            assert_eq!(breaker.state(), CircuitState::Open);
+
+            std::thread::sleep(Duration::from_millis(20));
+            assert!(breaker.acquire());
+            breaker.record(true);
+            assert_eq!(breaker.state(), CircuitState::Closed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_max() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_delay_before(0), Duration::from_millis(100));
+        assert_eq!(policy.max_delay_before(2), Duration::from_millis(400));
+        assert_eq!(policy.max_delay_before(10), Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,59 @@
+use super::{typed_client, ClientError};
+use crate::protocol::satellite::{
+    CalculatePositionRequest, CalculatePositionResponse, CreateSatelliteRequest, SatelliteResponse,
+    UpdateSatelliteStatusRequest,
+};
+use crate::protocol::{
+    SATELLITES_API_PATH, SATELLITES_POSITION_API_PATH, SATELLITES_STATUS_API_PATH,
+};
+use reqwest::Method;
+use uuid::Uuid;
+
+typed_client!(
+    /// Client of the orbital-beacon API
+    SatellitesClient
+);
+
+impl SatellitesClient {
+    pub async fn list(&self) -> Result<Vec<SatelliteResponse>, ClientError> {
+        self.api
+            .request(Method::GET, SATELLITES_API_PATH)
+            .send()
+            .await
+    }
+
+    pub async fn create(
+        &self,
+        request: &CreateSatelliteRequest,
+    ) -> Result<SatelliteResponse, ClientError> {
+        self.api
+            .request(Method::POST, SATELLITES_API_PATH)
+            .json(request)
+            .send()
+            .await
+    }
+
+    pub async fn update_status(
+        &self,
+        id: Uuid,
+        request: &UpdateSatelliteStatusRequest,
+    ) -> Result<SatelliteResponse, ClientError> {
+        self.api
+            .request_at(Method::PUT, SATELLITES_STATUS_API_PATH, &id.to_string())
+            .json(request)
+            .send()
+            .await
+    }
+
+    /// Positions of a flight as seen by the active satellites
+    pub async fn calculate_position(
+        &self,
+        request: &CalculatePositionRequest,
+    ) -> Result<CalculatePositionResponse, ClientError> {
+        self.api
+            .request(Method::POST, SATELLITES_POSITION_API_PATH)
+            .json(request)
+            .send()
+            .await
+    }
+}
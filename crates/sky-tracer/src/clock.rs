@@ -0,0 +1,481 @@
+//! Time as the services see it.
+//!
+//! Services read the time from a [`SharedClock`] instead of [`Utc::now`], so
+//! a deployment can run a compressed simulation and tests can pin the time.
+//! The clock of a process is configured with `SIMULATION_SPEED`,
+//! `SIMULATION_START` and `SIMULATION_EPOCH`:
+//!
+//! - none set: the system clock
+//! - `SIMULATION_SPEED=60`: an hour passes every minute. The clock shows
+//!   `SIMULATION_START` at the wall clock time `SIMULATION_EPOCH`, both are
+//!   required for any speed but 1, so services started apart agree on the
+//!   time
+//! - `SIMULATION_SPEED=1` or only `SIMULATION_START`: time passes as usual
+//!   from `SIMULATION_START`, shown at `SIMULATION_EPOCH` or at the start of
+//!   the process
+//! - `SIMULATION_SPEED=0`: time stands still at `SIMULATION_START`, which is
+//!   required
+//!
+//! The speed is at most [`MAX_SIMULATION_SPEED`]. Calls between services
+//! carry the time of the caller in [`SIMULATED_TIME_HEADER`]. While a
+//! simulation is configured, the clocks of the callee continue from it for
+//! the rest of the request.
+
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+/// Header with the RFC 3339 time of the calling service
+pub const SIMULATED_TIME_HEADER: &str = "x-simulated-time";
+pub const SIMULATION_SPEED_ENV: &str = "SIMULATION_SPEED";
+pub const SIMULATION_START_ENV: &str = "SIMULATION_START";
+pub const SIMULATION_EPOCH_ENV: &str = "SIMULATION_EPOCH";
+/// Fastest simulation, about a week per minute
+pub const MAX_SIMULATION_SPEED: f64 = 10_000.0;
+
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Time of the clock when the wall clock shows `wall_time`
+    fn at(&self, wall_time: DateTime<Utc>) -> DateTime<Utc>;
+
+    fn now(&self) -> DateTime<Utc> {
+        self.at(Utc::now())
+    }
+
+    /// Whether the clock shows other than wall clock time
+    fn is_simulated(&self) -> bool {
+        true
+    }
+}
+
+/// Wall clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn at(&self, wall_time: DateTime<Utc>) -> DateTime<Utc> {
+        wall_time
+    }
+
+    fn is_simulated(&self) -> bool {
+        false
+    }
+}
+
+/// Time standing still
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn at(&self, _wall_time: DateTime<Utc>) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Time passing `speed` times as fast as wall clock time, showing `start`
+/// when the wall clock shows `epoch`
+#[derive(Debug, Clone, Copy)]
+pub struct ScaledClock {
+    pub start: DateTime<Utc>,
+    pub epoch: DateTime<Utc>,
+    pub speed: f64,
+}
+
+impl ScaledClock {
+    pub fn new(start: DateTime<Utc>, epoch: DateTime<Utc>, speed: f64) -> Self {
+        Self {
+            start,
+            epoch,
+            speed,
+        }
+    }
+}
+
+impl Clock for ScaledClock {
+    /// Times beyond the range of [`DateTime`] end at its limits
+    fn at(&self, wall_time: DateTime<Utc>) -> DateTime<Utc> {
+        // `as` saturates, so does adding the elapsed time
+        let elapsed = (wall_time - self.epoch).num_milliseconds() as f64 * self.speed;
+        let elapsed = Duration::try_milliseconds(elapsed as i64).unwrap_or(if elapsed < 0.0 {
+            Duration::MIN
+        } else {
+            Duration::MAX
+        });
+        saturating_add(self.start, elapsed)
+    }
+}
+
+fn saturating_add(time: DateTime<Utc>, duration: Duration) -> DateTime<Utc> {
+    time.checked_add_signed(duration)
+        .unwrap_or(if duration < Duration::zero() {
+            DateTime::<Utc>::MIN_UTC
+        } else {
+            DateTime::<Utc>::MAX_UTC
+        })
+}
+
+/// Clock handed to the services, following the time of the calling service
+/// while a request is handled
+#[derive(Clone)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        Self(Arc::new(clock))
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        match caller_time() {
+            // The time of the caller, moved on as far as this clock moved since
+            Some(caller) => saturating_add(caller.time, self.0.now() - self.0.at(caller.received)),
+            None => self.0.now(),
+        }
+    }
+
+    pub fn is_simulated(&self) -> bool {
+        self.0.is_simulated()
+    }
+}
+
+/// The clock of the process
+impl Default for SharedClock {
+    fn default() -> Self {
+        global().clone()
+    }
+}
+
+/// Clocks are equal if they are the same clock
+impl PartialEq for SharedClock {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ClockError {
+    #[error("{SIMULATION_SPEED_ENV} must be a number from 0 to {MAX_SIMULATION_SPEED}, got {0:?}")]
+    InvalidSpeed(String),
+    #[error("{SIMULATION_START_ENV} must be an RFC 3339 time, got {0:?}")]
+    InvalidStart(String),
+    #[error("{SIMULATION_EPOCH_ENV} must be an RFC 3339 time, got {0:?}")]
+    InvalidEpoch(String),
+    #[error("{0} must be set for a simulation not at real speed, so services agree on the time")]
+    MissingSetting(&'static str),
+}
+
+/// Clock for the given simulation settings, empty values count as unset
+pub fn clock_from_settings(
+    speed: Option<&str>,
+    start: Option<&str>,
+    epoch: Option<&str>,
+) -> Result<SharedClock, ClockError> {
+    let speed = match speed.map(str::trim).filter(|speed| !speed.is_empty()) {
+        Some(speed) => Some(
+            speed
+                .parse::<f64>()
+                .ok()
+                .filter(|speed| (0.0..=MAX_SIMULATION_SPEED).contains(speed))
+                .ok_or_else(|| ClockError::InvalidSpeed(speed.to_string()))?,
+        ),
+        None => None,
+    };
+    let start = parse_time(start, ClockError::InvalidStart)?;
+    let epoch = parse_time(epoch, ClockError::InvalidEpoch)?;
+
+    Ok(match (speed, start) {
+        (None, None) => SharedClock::new(SystemClock),
+        (Some(0.0), start) => SharedClock::new(FixedClock(
+            start.ok_or(ClockError::MissingSetting(SIMULATION_START_ENV))?,
+        )),
+        (Some(speed), _) if speed != 1.0 => SharedClock::new(ScaledClock::new(
+            start.ok_or(ClockError::MissingSetting(SIMULATION_START_ENV))?,
+            epoch.ok_or(ClockError::MissingSetting(SIMULATION_EPOCH_ENV))?,
+            speed,
+        )),
+        (speed, start) => {
+            let now = Utc::now();
+            SharedClock::new(ScaledClock::new(
+                start.unwrap_or(now),
+                epoch.unwrap_or(now),
+                speed.unwrap_or(1.0),
+            ))
+        }
+    })
+}
+
+fn parse_time(
+    value: Option<&str>,
+    error: fn(String) -> ClockError,
+) -> Result<Option<DateTime<Utc>>, ClockError> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => Ok(Some(
+            DateTime::parse_from_rfc3339(value)
+                .map_err(|_| error(value.to_string()))?
+                .with_timezone(&Utc),
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Clock configured by `SIMULATION_SPEED`, `SIMULATION_START` and
+/// `SIMULATION_EPOCH`
+pub fn clock_from_env() -> Result<SharedClock, ClockError> {
+    clock_from_settings(
+        std::env::var(SIMULATION_SPEED_ENV).ok().as_deref(),
+        std::env::var(SIMULATION_START_ENV).ok().as_deref(),
+        std::env::var(SIMULATION_EPOCH_ENV).ok().as_deref(),
+    )
+}
+
+static GLOBAL: OnceLock<SharedClock> = OnceLock::new();
+
+/// Configure the clock of the process from the environment. Call it before
+/// the clock is used, later calls keep the first clock.
+pub fn init_from_env() -> Result<&'static SharedClock, ClockError> {
+    let clock = clock_from_env()?;
+    Ok(GLOBAL.get_or_init(|| clock))
+}
+
+/// Clock of the process, the system clock unless configured
+pub fn global() -> &'static SharedClock {
+    GLOBAL.get_or_init(|| SharedClock::new(SystemClock))
+}
+
+/// Current time of the process
+pub fn now() -> DateTime<Utc> {
+    global().now()
+}
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "server", feature = "client")
+))]
+tokio::task_local! {
+    /// Time of the caller of the request being handled
+    static CALLER_TIME: CallerTime;
+}
+
+/// Time the caller sent along and the wall clock time it arrived at
+#[derive(Debug, Clone, Copy)]
+struct CallerTime {
+    time: DateTime<Utc>,
+    received: DateTime<Utc>,
+}
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "server", feature = "client")
+))]
+fn caller_time() -> Option<CallerTime> {
+    CALLER_TIME.try_with(|caller| *caller).ok()
+}
+
+#[cfg(not(all(
+    not(target_arch = "wasm32"),
+    any(feature = "server", feature = "client")
+)))]
+fn caller_time() -> Option<CallerTime> {
+    None
+}
+
+/// Run `future` with the clocks following `caller_time`
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "server", feature = "client")
+))]
+pub async fn follow<F: std::future::Future>(caller_time: DateTime<Utc>, future: F) -> F::Output {
+    let caller = CallerTime {
+        time: caller_time,
+        received: Utc::now(),
+    };
+    CALLER_TIME.scope(caller, future).await
+}
+
+#[cfg(feature = "server")]
+pub use server::follow_caller_time;
+
+#[cfg(feature = "server")]
+mod server {
+    use super::{follow, SharedClock, SIMULATED_TIME_HEADER};
+    use axum::{
+        extract::{Request, State},
+        http::{
+            header::{HeaderName, FORWARDED},
+            HeaderMap,
+        },
+        middleware::Next,
+        response::Response,
+    };
+    use chrono::{DateTime, Utc};
+
+    const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+    /// Middleware making the clocks follow the time of the calling service.
+    ///
+    /// The time is only followed while the service runs a simulation on the
+    /// given clock, so services on the system clock ignore the header of any
+    /// caller. Requests that passed a proxy such as Traefik carry `Forwarded`
+    /// or `X-Forwarded-For` and are not followed either.
+    pub async fn follow_caller_time(
+        State(clock): State<SharedClock>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        match caller_time(request.headers(), clock.is_simulated()) {
+            Some(caller_time) => follow(caller_time, next.run(request)).await,
+            None => next.run(request).await,
+        }
+    }
+
+    /// Time of the caller to follow, if any
+    pub(super) fn caller_time(headers: &HeaderMap, simulated: bool) -> Option<DateTime<Utc>> {
+        let proxied = headers.contains_key(FORWARDED) || headers.contains_key(X_FORWARDED_FOR);
+        headers
+            .get(SIMULATED_TIME_HEADER)
+            .filter(|_| simulated && !proxied)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|time| time.with_timezone(&Utc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().into()
+    }
+
+    #[test]
+    fn test_scaled_clock_compresses_time() {
+        let clock = ScaledClock::new(
+            time("2025-06-01T06:00:00Z"),
+            time("2026-10-18T12:00:00Z"),
+            60.0,
+        );
+
+        assert_eq!(
+            clock.at(time("2026-10-18T12:10:00Z")),
+            time("2025-06-01T16:00:00Z")
+        );
+        assert_eq!(clock.at(clock.epoch), clock.start);
+
+        let racing = ScaledClock::new(clock.start, clock.start, f64::MAX);
+        assert_eq!(
+            racing.at(time("2025-06-01T06:00:01Z")),
+            DateTime::<Utc>::MAX_UTC
+        );
+        assert_eq!(
+            racing.at(time("2025-06-01T05:59:59Z")),
+            DateTime::<Utc>::MIN_UTC
+        );
+    }
+
+    #[test]
+    fn test_settings_select_clock() {
+        let fixed = clock_from_settings(Some("0"), Some("2025-06-01T12:00:00Z"), None).unwrap();
+        assert_eq!(fixed.now(), time("2025-06-01T12:00:00Z"));
+        assert!(fixed.is_simulated());
+
+        let real = clock_from_settings(None, Some(""), None).unwrap();
+        assert!((real.now() - Utc::now()).num_seconds().abs() < 5);
+        assert!(!real.is_simulated());
+
+        // A start in the past, running from an epoch a minute ago
+        let epoch = Utc::now() - Duration::minutes(1);
+        let scaled = clock_from_settings(
+            Some("60"),
+            Some("2025-06-01T06:00:00Z"),
+            Some(&epoch.to_rfc3339()),
+        )
+        .unwrap();
+        let elapsed = scaled.now() - time("2025-06-01T06:00:00Z");
+        assert!(elapsed >= Duration::hours(1), "{elapsed}");
+        assert!(elapsed < Duration::hours(2), "{elapsed}");
+
+        let replay = clock_from_settings(None, Some("2025-06-01T06:00:00Z"), None).unwrap();
+        assert!((replay.now() - time("2025-06-01T06:00:00Z")).num_seconds() < 5);
+
+        assert_eq!(
+            clock_from_settings(Some("0"), None, Some(&epoch.to_rfc3339())).unwrap_err(),
+            ClockError::MissingSetting(SIMULATION_START_ENV)
+        );
+        assert_eq!(
+            clock_from_settings(Some("60"), None, Some(&epoch.to_rfc3339())).unwrap_err(),
+            ClockError::MissingSetting(SIMULATION_START_ENV)
+        );
+        assert_eq!(
+            clock_from_settings(Some("60"), Some("2025-06-01T06:00:00Z"), None).unwrap_err(),
+            ClockError::MissingSetting(SIMULATION_EPOCH_ENV)
+        );
+        assert_eq!(
+            clock_from_settings(Some("0.5"), None, None).unwrap_err(),
+            ClockError::MissingSetting(SIMULATION_START_ENV)
+        );
+        assert_eq!(
+            clock_from_settings(Some("0.5"), Some("2025-06-01T06:00:00Z"), None).unwrap_err(),
+            ClockError::MissingSetting(SIMULATION_EPOCH_ENV)
+        );
+        assert_eq!(
+            clock_from_settings(Some("fast"), None, None).unwrap_err(),
+            ClockError::InvalidSpeed("fast".to_string())
+        );
+        assert!(matches!(
+            clock_from_settings(Some("-1"), None, None),
+            Err(ClockError::InvalidSpeed(_))
+        ));
+        assert!(matches!(
+            clock_from_settings(Some("1e300"), None, None),
+            Err(ClockError::InvalidSpeed(_))
+        ));
+        assert!(matches!(
+            clock_from_settings(None, Some("tomorrow"), None),
+            Err(ClockError::InvalidStart(_))
+        ));
+        assert!(matches!(
+            clock_from_settings(None, None, Some("now")),
+            Err(ClockError::InvalidEpoch(_))
+        ));
+    }
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_clocks_follow_caller_time() {
+        let caller_time = Utc::now() + Duration::days(2);
+        let clock = SharedClock::new(SystemClock);
+
+        let (shared, process) = follow(caller_time, async { (clock.now(), now()) }).await;
+        assert!((shared - caller_time).num_seconds().abs() < 5);
+        assert!((process - caller_time).num_seconds().abs() < 5);
+        assert!((clock.now() - Utc::now()).num_seconds().abs() < 5);
+
+        let stopped = SharedClock::new(FixedClock(time("2025-06-01T12:00:00Z")));
+        let followed = follow(caller_time, async { stopped.now() }).await;
+        assert_eq!(followed, caller_time);
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn test_caller_time_is_followed_in_simulations_only() {
+        use axum::http::{HeaderMap, HeaderValue};
+
+        let caller_time = time("2025-06-01T12:00:00Z");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            SIMULATED_TIME_HEADER,
+            HeaderValue::from_str(&caller_time.to_rfc3339()).unwrap(),
+        );
+
+        assert_eq!(server::caller_time(&headers, true), Some(caller_time));
+        assert_eq!(server::caller_time(&headers, false), None);
+
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7"));
+        assert_eq!(server::caller_time(&headers, true), None);
+    }
+}
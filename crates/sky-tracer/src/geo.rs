@@ -0,0 +1,184 @@
+//! Great-circle (geodesic) helpers on a spherical earth model.
+//!
+//! Positions are `(latitude, longitude)` tuples in decimal degrees, matching
+//! [`Airport::position`](crate::model::Airport::position). Longitudes returned
+//! by this module are normalized to `[-180, 180)`, so routes crossing the
+//! antimeridian (e.g. SFO → HND) stay on the short way around the globe.
+
+/// Mean earth radius in kilometers
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Normalizes a longitude in degrees to the range `[-180, 180)`
+pub fn normalize_longitude(longitude: f64) -> f64 {
+    (longitude + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Central angle between two positions in radians (haversine formula)
+pub fn central_angle(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = lon2 - lon1;
+
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+/// Great-circle distance between two positions in kilometers
+pub fn distance_km(from: (f64, f64), to: (f64, f64)) -> f64 {
+    EARTH_RADIUS_KM * central_angle(from, to)
+}
+
+/// Initial bearing (forward azimuth) from `from` towards `to` in degrees `[0, 360)`
+pub fn initial_bearing(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let dlon = lon2 - lon1;
+
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Position at `fraction` (0.0 = `from`, 1.0 = `to`) along the great circle
+/// between two positions
+pub fn intermediate_point(from: (f64, f64), to: (f64, f64), fraction: f64) -> (f64, f64) {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let delta = central_angle(from, to);
+
+    // Coincident points: nothing to interpolate
+    if delta.abs() < f64::EPSILON {
+        return (from.0, normalize_longitude(from.1));
+    }
+
+    // Antipodal points: every great circle joins them, take the meridian of
+    // `from` over the north pole
+    if delta.sin().abs() < 1e-9 {
+        let latitude = from.0 + fraction * 180.0;
+        return if latitude > 90.0 {
+            (180.0 - latitude, normalize_longitude(from.1 + 180.0))
+        } else {
+            (latitude, normalize_longitude(from.1))
+        };
+    }
+
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+
+    let a = ((1.0 - fraction) * delta).sin() / delta.sin();
+    let b = (fraction * delta).sin() / delta.sin();
+
+    let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+    let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+    let z = a * lat1.sin() + b * lat2.sin();
+
+    let latitude = z.atan2((x * x + y * y).sqrt()).to_degrees();
+    let longitude = y.atan2(x).to_degrees();
+
+    (latitude, normalize_longitude(longitude))
+}
+
+/// Samples `segments + 1` evenly spaced positions along the great circle,
+/// including both end points
+pub fn great_circle_path(from: (f64, f64), to: (f64, f64), segments: usize) -> Vec<(f64, f64)> {
+    let segments = segments.max(1);
+    (0..=segments)
+        .map(|i| intermediate_point(from, to, i as f64 / segments as f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRA: (f64, f64) = (50.033333, 8.570556);
+    const JFK: (f64, f64) = (40.63980103, -73.77890015);
+    const SFO: (f64, f64) = (37.61899948120117, -122.375);
+    const HND: (f64, f64) = (35.552299, 139.779999);
+
+    #[test]
+    fn test_normalize_longitude() {
+        assert_eq!(normalize_longitude(0.0), 0.0);
+        assert_eq!(normalize_longitude(190.0), -170.0);
+        assert_eq!(normalize_longitude(-190.0), 170.0);
+        assert_eq!(normalize_longitude(540.0), -180.0);
+    }
+
+    #[test]
+    fn test_distance_fra_jfk() {
+        let distance = distance_km(FRA, JFK);
+        assert!((distance - 6200.0).abs() < 50.0, "got {distance}");
+    }
+
+    #[test]
+    fn test_distance_same_point_is_zero() {
+        assert_eq!(distance_km(FRA, FRA), 0.0);
+    }
+
+    #[test]
+    fn test_initial_bearing() {
+        // Due north and due east along the equator
+        assert!((initial_bearing((0.0, 0.0), (10.0, 0.0)) - 0.0).abs() < 1e-9);
+        assert!((initial_bearing((0.0, 0.0), (0.0, 10.0)) - 90.0).abs() < 1e-9);
+        // FRA -> JFK departs heading north-west, not south-west
+        let bearing = initial_bearing(FRA, JFK);
+        assert!(bearing > 280.0 && bearing < 310.0, "got {bearing}");
+    }
+
+    #[test]
+    fn test_intermediate_point_end_points() {
+        let start = intermediate_point(FRA, JFK, 0.0);
+        let end = intermediate_point(FRA, JFK, 1.0);
+        assert!((start.0 - FRA.0).abs() < 1e-9 && (start.1 - FRA.1).abs() < 1e-9);
+        assert!((end.0 - JFK.0).abs() < 1e-9 && (end.1 - JFK.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transatlantic_midpoint_is_north_of_linear_interpolation() {
+        let (lat, lon) = intermediate_point(FRA, JFK, 0.5);
+        let linear_lat = (FRA.0 + JFK.0) / 2.0;
+
+        // The great circle bulges towards the pole, roughly over 52°N 35°W
+        assert!(lat > linear_lat + 5.0, "got {lat}");
+        assert!((lon + 35.0).abs() < 5.0, "got {lon}");
+
+        // Both halves must be equally long
+        let first = distance_km(FRA, (lat, lon));
+        let second = distance_km((lat, lon), JFK);
+        assert!((first - second).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_antimeridian_crossing() {
+        let (lat, lon) = intermediate_point(SFO, HND, 0.5);
+
+        // The midpoint lies over the North Pacific close to the antimeridian,
+        // not over the Atlantic as linear interpolation would suggest
+        assert!(lon.abs() > 150.0, "got {lon}");
+        assert!(lat > 40.0, "got {lat}");
+    }
+
+    #[test]
+    fn test_great_circle_path() {
+        let path = great_circle_path(SFO, HND, 10);
+        assert_eq!(path.len(), 11);
+        assert!(path.iter().all(|(_, lon)| (-180.0..180.0).contains(lon)));
+
+        let total: f64 = path.windows(2).map(|w| distance_km(w[0], w[1])).sum();
+        assert!((total - distance_km(SFO, HND)).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_antipodal_points() {
+        let antipode = (-FRA.0, normalize_longitude(FRA.1 + 180.0));
+        let path = great_circle_path(FRA, antipode, 10);
+        assert!(path
+            .iter()
+            .all(|(lat, lon)| lat.is_finite() && (-180.0..180.0).contains(lon)));
+
+        let end = path[10];
+        assert!(distance_km(end, antipode) < 1e-3, "got {end:?}");
+        let total: f64 = path.windows(2).map(|w| distance_km(w[0], w[1])).sum();
+        assert!((total - distance_km(FRA, antipode)).abs() < 1.0);
+    }
+}
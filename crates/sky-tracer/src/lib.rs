@@ -1,8 +1,13 @@
+pub mod clock;
+pub mod geo;
 pub mod model;
 
 #[cfg(feature = "protocol")]
 pub mod protocol;
 
+#[cfg(feature = "client")]
+pub mod client;
+
 pub mod prelude {
     pub use crate::model::airport::Airport;
 }
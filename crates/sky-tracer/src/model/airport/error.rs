@@ -6,6 +6,9 @@ pub enum AirportError {
     #[error("Airport not found with code: {0}")]
     NotFound(String),
 
+    #[error("Airport already exists with code: {0}")]
+    AlreadyExists(String),
+
     #[error("CSV parsing error: {0}")]
     CsvError(#[from] csv::Error),
 
@@ -23,6 +26,9 @@ pub enum AirportError {
 
     #[error("Error loading airports: {0}")]
     LoadError(String),
+
+    #[error("No ids left for added airports")]
+    NoIdsLeft,
 }
 
 impl From<std::string::FromUtf8Error> for AirportError {
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// A radio frequency used at an airport
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Frequency {
+    /// Id of the airport the frequency belongs to
+    pub airport_id: u32,
+    /// Service, e.g. "TWR", "GND" or "ATIS"
+    pub frequency_type: String,
+    /// Free text such as the call sign
+    pub description: String,
+    pub frequency_mhz: f64,
+}
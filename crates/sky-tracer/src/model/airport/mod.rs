@@ -1,10 +1,20 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+use super::identifiers::{IataCode, IcaoCode};
+
 pub mod error;
+pub mod frequency;
+pub mod runway;
 
 pub use error::AirportError;
+pub use frequency::Frequency;
+pub use runway::{Runway, RunwayEnd};
 
 /// Represents an airport with its essential location and identification data
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct Airport {
     /// Unique identifier
     pub id: u32,
@@ -14,22 +24,77 @@ pub struct Airport {
     pub longitude: f64,
     /// Full name of the airport
     pub name: String,
-    /// Combined IATA/ICAO code (e.g., "LAX/KLAX")
+    /// Primary code, the IATA code where assigned and the ICAO code otherwise
     pub code: String,
+    /// Three-letter IATA code (e.g., "LAX")
+    #[serde(default)]
+    pub iata: Option<IataCode>,
+    /// Four-letter ICAO code (e.g., "KLAX")
+    #[serde(default)]
+    pub icao: Option<IcaoCode>,
+    /// City served by the airport
+    #[serde(default)]
+    pub city: String,
+    /// Country the airport is located in
+    #[serde(default)]
+    pub country: String,
+    /// Elevation in feet above mean sea level
+    #[serde(default)]
+    pub elevation_ft: Option<i32>,
+    /// IANA time zone (e.g., "America/Los_Angeles")
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Kind of facility
+    #[serde(default)]
+    pub airport_type: AirportType,
 }
 
-impl Default for Airport {
-    fn default() -> Self {
-        Self {
-            id: 0,
-            latitude: 0.0,
-            longitude: 0.0,
-            name: String::new(),
-            code: String::new(),
+/// Kind of facility, following the OpenFlights classification
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AirportType {
+    #[default]
+    Airport,
+    Station,
+    Port,
+    Unknown,
+}
+
+impl AirportType {
+    pub const ALL: [AirportType; 4] = [
+        AirportType::Airport,
+        AirportType::Station,
+        AirportType::Port,
+        AirportType::Unknown,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AirportType::Airport => "airport",
+            AirportType::Station => "station",
+            AirportType::Port => "port",
+            AirportType::Unknown => "unknown",
         }
     }
 }
 
+impl fmt::Display for AirportType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AirportType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AirportType::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown airport type: {}", s))
+    }
+}
+
 impl Airport {
     /// Creates a new Airport instance
     pub fn new(id: u32, latitude: f64, longitude: f64, name: String, code: String) -> Self {
@@ -39,6 +104,7 @@ impl Airport {
             longitude,
             name,
             code,
+            ..Default::default()
         }
     }
 
@@ -47,26 +113,54 @@ impl Airport {
         (self.latitude, self.longitude)
     }
 
+    /// Whether the given IATA or ICAO code belongs to this airport, ignoring case
+    pub fn has_code(&self, code: &str) -> bool {
+        let code = code.trim();
+        self.code.eq_ignore_ascii_case(code)
+            || self
+                .iata
+                .iter()
+                .map(IataCode::as_str)
+                .chain(self.icao.iter().map(IcaoCode::as_str))
+                .any(|c| c.eq_ignore_ascii_case(code))
+    }
+
     /// Creates Frankfurt Airport instance
     pub fn frankfurt() -> Self {
-        Self::new(
-            340,
-            50.033333,
-            8.570556,
-            "Frankfurt am Main Airport".to_string(),
-            "FRA/EDDF".to_string(),
-        )
+        Self {
+            iata: Some("FRA".parse().unwrap()),
+            icao: Some("EDDF".parse().unwrap()),
+            city: "Frankfurt".to_string(),
+            country: "Germany".to_string(),
+            elevation_ft: Some(364),
+            timezone: Some("Europe/Berlin".to_string()),
+            ..Self::new(
+                340,
+                50.033333,
+                8.570556,
+                "Frankfurt am Main Airport".to_string(),
+                "FRA".to_string(),
+            )
+        }
     }
 
     /// Creates Paris Charles de Gaulle Airport instance
     pub fn paris() -> Self {
-        Self::new(
-            1382,
-            49.012798,
-            2.55,
-            "Charles de Gaulle International Airport".to_string(),
-            "CDG/LFPG".to_string(),
-        )
+        Self {
+            iata: Some("CDG".parse().unwrap()),
+            icao: Some("LFPG".parse().unwrap()),
+            city: "Paris".to_string(),
+            country: "France".to_string(),
+            elevation_ft: Some(392),
+            timezone: Some("Europe/Paris".to_string()),
+            ..Self::new(
+                1382,
+                49.012798,
+                2.55,
+                "Charles de Gaulle International Airport".to_string(),
+                "CDG".to_string(),
+            )
+        }
     }
 }
 
@@ -81,7 +175,7 @@ mod tests {
                 37.61899948120117,
                 -122.375,
                 "San Francisco International Airport".to_string(),
-                "SFO/KSFO".to_string(),
+                "SFO".to_string(),
             )
         }
 
@@ -91,7 +185,7 @@ mod tests {
                 40.63980103,
                 -73.77890015,
                 "John F Kennedy International Airport".to_string(),
-                "JFK/KJFK".to_string(),
+                "JFK".to_string(),
             )
         }
     }
@@ -124,16 +218,19 @@ mod tests {
     #[test]
     fn test_predefined_airports() {
         let fra = Airport::frankfurt();
-        assert_eq!(fra.code, "FRA/EDDF");
+        assert_eq!(fra.code, "FRA");
+        assert_eq!(fra.icao.as_ref().map(IcaoCode::as_str), Some("EDDF"));
+        assert_eq!(fra.timezone.as_deref(), Some("Europe/Berlin"));
 
         let cdg = Airport::paris();
-        assert_eq!(cdg.code, "CDG/LFPG");
+        assert_eq!(cdg.code, "CDG");
+        assert_eq!(cdg.icao.as_ref().map(IcaoCode::as_str), Some("LFPG"));
 
         let sfo = Airport::san_francisco();
-        assert_eq!(sfo.code, "SFO/KSFO");
+        assert_eq!(sfo.code, "SFO");
 
         let jfk = Airport::new_york();
-        assert_eq!(jfk.code, "JFK/KJFK");
+        assert_eq!(jfk.code, "JFK");
     }
 
     #[test]
@@ -143,4 +240,31 @@ mod tests {
         let deserialized: Airport = serde_json::from_str(&json).unwrap();
         assert_eq!(airport, deserialized);
     }
+
+    #[test]
+    fn test_has_code() {
+        let fra = Airport::frankfurt();
+
+        assert!(fra.has_code("FRA"));
+        assert!(fra.has_code("eddf"));
+        assert!(!fra.has_code("EDD"));
+        assert!(!fra.has_code("CDG"));
+    }
+
+    #[test]
+    fn test_airport_type_parsing() {
+        assert_eq!("airport".parse(), Ok(AirportType::Airport));
+        assert_eq!("Station".parse(), Ok(AirportType::Station));
+        assert!("heliport".parse::<AirportType>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_without_details() {
+        let json = r#"{"id":1,"latitude":1.0,"longitude":2.0,"name":"Test","code":"TST"}"#;
+        let airport: Airport = serde_json::from_str(json).unwrap();
+
+        assert_eq!(airport.code, "TST");
+        assert_eq!(airport.iata, None);
+        assert_eq!(airport.airport_type, AirportType::Airport);
+    }
 }
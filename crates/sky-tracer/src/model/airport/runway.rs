@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// A runway of an airport, described from both ends
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Runway {
+    /// Id of the airport the runway belongs to
+    pub airport_id: u32,
+    /// Length in feet
+    pub length_ft: Option<u32>,
+    /// Width in feet
+    pub width_ft: Option<u32>,
+    /// Surface as published, e.g. "ASP" or "CON"
+    pub surface: String,
+    pub lighted: bool,
+    pub closed: bool,
+    /// Low-numbered end, e.g. "07L"
+    pub low_end: RunwayEnd,
+    /// High-numbered end, absent for single-ended runways such as helipads
+    pub high_end: Option<RunwayEnd>,
+}
+
+/// One direction a runway can be used in
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct RunwayEnd {
+    /// Runway designator, e.g. "25R"
+    pub ident: String,
+    /// True heading in degrees
+    pub heading_deg: Option<f64>,
+    /// Threshold latitude in decimal degrees
+    pub latitude: Option<f64>,
+    /// Threshold longitude in decimal degrees
+    pub longitude: Option<f64>,
+    /// Threshold elevation in feet above mean sea level
+    pub elevation_ft: Option<i32>,
+    /// Distance the landing threshold is moved down the runway, in feet
+    pub displaced_threshold_ft: Option<u32>,
+}
+
+impl Runway {
+    /// Both designators, e.g. "07L/25R"
+    pub fn designation(&self) -> String {
+        match &self.high_end {
+            Some(high_end) => format!("{}/{}", self.low_end.ident, high_end.ident),
+            None => self.low_end.ident.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_designation() {
+        let end = |ident: &str| RunwayEnd {
+            ident: ident.to_string(),
+            ..Default::default()
+        };
+        let runway = Runway {
+            low_end: end("07L"),
+            high_end: Some(end("25R")),
+            ..Default::default()
+        };
+        assert_eq!(runway.designation(), "07L/25R");
+
+        let helipad = Runway {
+            low_end: end("H1"),
+            ..Default::default()
+        };
+        assert_eq!(helipad.designation(), "H1");
+    }
+}
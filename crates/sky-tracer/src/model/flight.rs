@@ -1,18 +1,159 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+use super::identifiers::{AircraftRegistration, FlightNumber, IataCode};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Flight {
     /// Unique flight number (e.g., "LH1234")
-    pub flight_number: String,
+    pub flight_number: FlightNumber,
     /// Aircraft registration number
-    pub aircraft_number: String,
+    pub aircraft_number: AircraftRegistration,
     /// Departure airport
-    pub departure: String, // Airport code
+    pub departure: IataCode,
     /// Arrival airport
-    pub arrival: String, // Airport code
+    pub arrival: IataCode,
     /// Scheduled departure time
     pub departure_time: DateTime<Utc>,
     /// Scheduled arrival time
     pub arrival_time: Option<DateTime<Utc>>,
+    /// Operational status
+    #[serde(default)]
+    pub status: FlightStatus,
+}
+
+/// Operational status of a flight
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub enum FlightStatus {
+    #[default]
+    Scheduled,
+    Boarding,
+    Departed,
+    EnRoute,
+    Landed,
+    Cancelled,
+    Diverted,
+}
+
+impl FlightStatus {
+    pub const ALL: [FlightStatus; 7] = [
+        FlightStatus::Scheduled,
+        FlightStatus::Boarding,
+        FlightStatus::Departed,
+        FlightStatus::EnRoute,
+        FlightStatus::Landed,
+        FlightStatus::Cancelled,
+        FlightStatus::Diverted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlightStatus::Scheduled => "Scheduled",
+            FlightStatus::Boarding => "Boarding",
+            FlightStatus::Departed => "Departed",
+            FlightStatus::EnRoute => "EnRoute",
+            FlightStatus::Landed => "Landed",
+            FlightStatus::Cancelled => "Cancelled",
+            FlightStatus::Diverted => "Diverted",
+        }
+    }
+
+    /// No further status changes are possible
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, FlightStatus::Landed | FlightStatus::Cancelled)
+    }
+
+    /// Route and schedule may only change before the aircraft leaves the gate
+    pub fn allows_schedule_changes(&self) -> bool {
+        matches!(self, FlightStatus::Scheduled | FlightStatus::Boarding)
+    }
+
+    /// Check whether a flight in this status may move to `next`.
+    /// Keeping the current status is always allowed.
+    pub fn can_transition_to(&self, next: FlightStatus) -> bool {
+        use FlightStatus::*;
+
+        if *self == next {
+            return true;
+        }
+
+        matches!(
+            (self, next),
+            (Scheduled, Boarding | Cancelled)
+                | (Boarding, Scheduled | Departed | Cancelled)
+                | (Departed, EnRoute | Landed | Diverted)
+                | (EnRoute, Landed | Diverted)
+                | (Diverted, Landed)
+        )
+    }
+}
+
+impl fmt::Display for FlightStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FlightStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FlightStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown flight status: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regular_lifecycle() {
+        let lifecycle = [
+            FlightStatus::Scheduled,
+            FlightStatus::Boarding,
+            FlightStatus::Departed,
+            FlightStatus::EnRoute,
+            FlightStatus::Landed,
+        ];
+
+        for pair in lifecycle.windows(2) {
+            assert!(pair[0].can_transition_to(pair[1]), "{:?}", pair);
+        }
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        assert!(!FlightStatus::Scheduled.can_transition_to(FlightStatus::Landed));
+        assert!(!FlightStatus::EnRoute.can_transition_to(FlightStatus::Cancelled));
+        assert!(!FlightStatus::Departed.can_transition_to(FlightStatus::Boarding));
+    }
+
+    #[test]
+    fn test_terminal_states() {
+        for status in FlightStatus::ALL {
+            let terminal = status.is_terminal();
+            let has_successor = FlightStatus::ALL
+                .into_iter()
+                .any(|next| next != status && status.can_transition_to(next));
+            assert_eq!(terminal, !has_successor, "{}", status);
+        }
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in FlightStatus::ALL {
+            assert_eq!(status.to_string().parse::<FlightStatus>(), Ok(status));
+
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(json, format!("\"{}\"", status));
+        }
+        assert_eq!("enroute".parse::<FlightStatus>(), Ok(FlightStatus::EnRoute));
+        assert!("Delayed".parse::<FlightStatus>().is_err());
+    }
 }
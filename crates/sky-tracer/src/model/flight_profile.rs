@@ -0,0 +1,369 @@
+use crate::geo;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest taxi time at each end of a flight in seconds
+const MAX_TAXI_SECS: f64 = 600.0;
+/// Share of the block time spent taxiing at each end for short flights
+const TAXI_SHARE: f64 = 0.08;
+/// Taxi speed in km/h
+const TAXI_SPEED_KMH: f64 = 30.0;
+/// Altitude at which the approach begins in meters
+const APPROACH_ALTITUDE_M: f64 = 900.0;
+/// Lowest and highest cruise altitudes in meters
+const MIN_CRUISE_ALTITUDE_M: f64 = 3000.0;
+const MAX_CRUISE_ALTITUDE_M: f64 = 11000.0;
+/// Cruise altitude gained per kilometer of route length
+const CRUISE_ALTITUDE_PER_KM: f64 = 20.0;
+/// Vertical rates in m/s
+const CLIMB_RATE_MS: f64 = 12.0;
+const DESCENT_RATE_MS: f64 = 9.0;
+const APPROACH_RATE_MS: f64 = 4.0;
+/// Minimum share of the airborne time spent in cruise
+const MIN_CRUISE_SHARE: f64 = 0.2;
+
+/// Ground speed relative to cruise speed at the phase boundaries
+const CLIMB_START_SPEED: f64 = 0.4;
+const DESCENT_END_SPEED: f64 = 0.6;
+const APPROACH_END_SPEED: f64 = 0.45;
+
+/// Phase of a flight
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum FlightPhase {
+    /// On the ground before departure or after landing
+    Taxi,
+    Climb,
+    /// Positions without a computed profile are assumed to be cruising
+    #[default]
+    Cruise,
+    Descent,
+    Approach,
+}
+
+/// Kinematic state of a flight at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlightState {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Altitude in meters
+    pub altitude: f32,
+    /// True track over ground in degrees
+    pub heading: f32,
+    /// Ground speed in km/h
+    pub ground_speed: f32,
+    /// Vertical rate in m/s, negative while descending
+    pub vertical_rate: f32,
+    pub phase: FlightPhase,
+}
+
+/// Vertical and speed profile of a flight along its great-circle route.
+///
+/// The block time is split into taxi-out, climb, cruise, descent, approach and
+/// taxi-in. Cruise altitude grows with route length; on short flights all
+/// climb and descent segments are scaled down so some cruise time remains.
+/// Ground speed ramps up during climb and down during descent, scaled so the
+/// airborne phases cover exactly the route length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlightProfile {
+    from: (f64, f64),
+    to: (f64, f64),
+    distance_km: f64,
+    taxi_secs: f64,
+    climb_secs: f64,
+    cruise_secs: f64,
+    descent_secs: f64,
+    approach_secs: f64,
+    cruise_altitude: f64,
+    approach_altitude: f64,
+    /// Cruise ground speed in km/s
+    cruise_speed: f64,
+}
+
+impl FlightProfile {
+    /// Builds the profile for a flight between two `(latitude, longitude)`
+    /// positions with the given block time
+    pub fn new(from: (f64, f64), to: (f64, f64), duration: Duration) -> Self {
+        let distance_km = geo::distance_km(from, to);
+        let total_secs = (duration.num_seconds() as f64).max(0.0);
+
+        let taxi_secs = MAX_TAXI_SECS.min(total_secs * TAXI_SHARE);
+        let airborne_secs = total_secs - 2.0 * taxi_secs;
+
+        let mut cruise_altitude = (distance_km * CRUISE_ALTITUDE_PER_KM)
+            .clamp(MIN_CRUISE_ALTITUDE_M, MAX_CRUISE_ALTITUDE_M);
+        let mut approach_altitude = APPROACH_ALTITUDE_M;
+        let mut climb_secs = cruise_altitude / CLIMB_RATE_MS;
+        let mut descent_secs = (cruise_altitude - approach_altitude) / DESCENT_RATE_MS;
+        let mut approach_secs = approach_altitude / APPROACH_RATE_MS;
+
+        // Scale altitudes and durations together so vertical rates stay realistic
+        let vertical_secs = climb_secs + descent_secs + approach_secs;
+        let available_secs = airborne_secs * (1.0 - MIN_CRUISE_SHARE);
+        if vertical_secs > available_secs {
+            let scale = available_secs / vertical_secs;
+            cruise_altitude *= scale;
+            approach_altitude *= scale;
+            climb_secs *= scale;
+            descent_secs *= scale;
+            approach_secs *= scale;
+        }
+        let cruise_secs = airborne_secs - climb_secs - descent_secs - approach_secs;
+
+        // Distance covered per unit of cruise speed, integrating the speed ramps
+        let relative_distance = climb_secs * (CLIMB_START_SPEED + 1.0) / 2.0
+            + cruise_secs
+            + descent_secs * (1.0 + DESCENT_END_SPEED) / 2.0
+            + approach_secs * (DESCENT_END_SPEED + APPROACH_END_SPEED) / 2.0;
+        let cruise_speed = if relative_distance > 0.0 {
+            distance_km / relative_distance
+        } else {
+            0.0
+        };
+
+        Self {
+            from,
+            to,
+            distance_km,
+            taxi_secs,
+            climb_secs,
+            cruise_secs,
+            descent_secs,
+            approach_secs,
+            cruise_altitude,
+            approach_altitude,
+            cruise_speed,
+        }
+    }
+
+    /// Great-circle route length in kilometers
+    pub fn distance_km(&self) -> f64 {
+        self.distance_km
+    }
+
+    /// Cruise altitude in meters
+    pub fn cruise_altitude(&self) -> f64 {
+        self.cruise_altitude
+    }
+
+    /// Flight state at `progress` (0.0 = off-block, 1.0 = on-block) of the block time
+    pub fn state_at(&self, progress: f64) -> FlightState {
+        let total_secs = 2.0 * self.taxi_secs
+            + self.climb_secs
+            + self.cruise_secs
+            + self.descent_secs
+            + self.approach_secs;
+        let mut t = progress.clamp(0.0, 1.0) * total_secs;
+
+        // (phase, altitude in m, vertical rate in m/s, relative speed, relative distance)
+        let (phase, altitude, vertical_rate, speed, distance) = 'phase: {
+            if t < self.taxi_secs {
+                break 'phase (FlightPhase::Taxi, 0.0, 0.0, None, 0.0);
+            }
+            t -= self.taxi_secs;
+
+            let mut covered = 0.0;
+            if t < self.climb_secs {
+                let f = t / self.climb_secs;
+                let speed = CLIMB_START_SPEED + (1.0 - CLIMB_START_SPEED) * f;
+                break 'phase (
+                    FlightPhase::Climb,
+                    self.cruise_altitude * f,
+                    self.cruise_altitude / self.climb_secs,
+                    Some(speed),
+                    t * (CLIMB_START_SPEED + speed) / 2.0,
+                );
+            }
+            t -= self.climb_secs;
+            covered += self.climb_secs * (CLIMB_START_SPEED + 1.0) / 2.0;
+
+            if t < self.cruise_secs {
+                break 'phase (
+                    FlightPhase::Cruise,
+                    self.cruise_altitude,
+                    0.0,
+                    Some(1.0),
+                    covered + t,
+                );
+            }
+            t -= self.cruise_secs;
+            covered += self.cruise_secs;
+
+            let descent_drop = self.cruise_altitude - self.approach_altitude;
+            if t < self.descent_secs {
+                let f = t / self.descent_secs;
+                let speed = 1.0 - (1.0 - DESCENT_END_SPEED) * f;
+                break 'phase (
+                    FlightPhase::Descent,
+                    self.cruise_altitude - descent_drop * f,
+                    -descent_drop / self.descent_secs,
+                    Some(speed),
+                    covered + t * (1.0 + speed) / 2.0,
+                );
+            }
+            t -= self.descent_secs;
+            covered += self.descent_secs * (1.0 + DESCENT_END_SPEED) / 2.0;
+
+            if t < self.approach_secs {
+                let f = t / self.approach_secs;
+                let speed = DESCENT_END_SPEED - (DESCENT_END_SPEED - APPROACH_END_SPEED) * f;
+                break 'phase (
+                    FlightPhase::Approach,
+                    self.approach_altitude * (1.0 - f),
+                    -self.approach_altitude / self.approach_secs,
+                    Some(speed),
+                    covered + t * (DESCENT_END_SPEED + speed) / 2.0,
+                );
+            }
+            covered += self.approach_secs * (DESCENT_END_SPEED + APPROACH_END_SPEED) / 2.0;
+
+            (FlightPhase::Taxi, 0.0, 0.0, None, covered)
+        };
+
+        let fraction = if self.cruise_speed > 0.0 && self.distance_km > 0.0 {
+            (distance * self.cruise_speed / self.distance_km).clamp(0.0, 1.0)
+        } else {
+            progress.clamp(0.0, 1.0)
+        };
+        let (latitude, longitude) = geo::intermediate_point(self.from, self.to, fraction);
+
+        let ground_speed = match speed {
+            Some(speed) => speed * self.cruise_speed * 3600.0,
+            None if self.taxi_secs > 0.0 && progress > 0.0 && progress < 1.0 => TAXI_SPEED_KMH,
+            None => 0.0,
+        };
+
+        FlightState {
+            latitude,
+            longitude,
+            altitude: altitude as f32,
+            heading: self.track_at(fraction) as f32,
+            ground_speed: ground_speed as f32,
+            vertical_rate: vertical_rate as f32,
+            phase,
+        }
+    }
+
+    /// True track at `fraction` of the route length
+    fn track_at(&self, fraction: f64) -> f64 {
+        if fraction < 1.0 {
+            let here = geo::intermediate_point(self.from, self.to, fraction);
+            geo::initial_bearing(here, self.to)
+        } else {
+            // Final track is the reverse of the initial bearing from the destination
+            (geo::initial_bearing(self.to, self.from) + 180.0).rem_euclid(360.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRA: (f64, f64) = (50.033333, 8.570556);
+    const JFK: (f64, f64) = (40.63980103, -73.77890015);
+    const MUC: (f64, f64) = (48.353802, 11.7861);
+
+    fn transatlantic() -> FlightProfile {
+        FlightProfile::new(FRA, JFK, Duration::hours(8) + Duration::minutes(30))
+    }
+
+    #[test]
+    fn test_phase_sequence() {
+        let profile = transatlantic();
+        let phases: Vec<FlightPhase> = [0.0, 0.02, 0.5, 0.95, 0.975, 0.999]
+            .iter()
+            .map(|p| profile.state_at(*p).phase)
+            .collect();
+
+        assert_eq!(
+            phases,
+            vec![
+                FlightPhase::Taxi,
+                FlightPhase::Climb,
+                FlightPhase::Cruise,
+                FlightPhase::Descent,
+                FlightPhase::Approach,
+                FlightPhase::Taxi,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cruise_state() {
+        let state = transatlantic().state_at(0.5);
+
+        assert_eq!(state.altitude, MAX_CRUISE_ALTITUDE_M as f32);
+        assert_eq!(state.vertical_rate, 0.0);
+        assert!(
+            state.ground_speed > 700.0 && state.ground_speed < 900.0,
+            "got {}",
+            state.ground_speed
+        );
+        // Heading west over the Atlantic
+        assert!(
+            state.heading > 240.0 && state.heading < 300.0,
+            "got {}",
+            state.heading
+        );
+    }
+
+    #[test]
+    fn test_vertical_rate_signs() {
+        let profile = transatlantic();
+        assert!(profile.state_at(0.02).vertical_rate > 0.0);
+        assert!(profile.state_at(0.95).vertical_rate < 0.0);
+        assert!(profile.state_at(0.975).vertical_rate < 0.0);
+    }
+
+    #[test]
+    fn test_on_ground_at_block_times() {
+        let profile = transatlantic();
+        let start = profile.state_at(0.0);
+        let end = profile.state_at(1.0);
+
+        assert_eq!(start.altitude, 0.0);
+        assert_eq!(end.altitude, 0.0);
+        assert!((start.latitude - FRA.0).abs() < 1e-6);
+        assert!((end.latitude - JFK.0).abs() < 1e-6);
+        assert!((end.longitude - JFK.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_short_flight_cruises_lower() {
+        let profile = FlightProfile::new(FRA, MUC, Duration::hours(1));
+        assert!(profile.cruise_altitude() < MAX_CRUISE_ALTITUDE_M);
+        assert_eq!(profile.state_at(0.5).phase, FlightPhase::Cruise);
+    }
+
+    #[test]
+    fn test_altitude_is_continuous() {
+        let profile = FlightProfile::new(FRA, MUC, Duration::hours(1));
+        let mut previous = profile.state_at(0.0);
+        for i in 1..=1000 {
+            let state = profile.state_at(i as f64 / 1000.0);
+            assert!((state.altitude - previous.altitude).abs() < 100.0);
+            previous = state;
+        }
+    }
+
+    #[test]
+    fn test_position_advances_monotonically() {
+        let profile = transatlantic();
+        let mut previous = 0.0;
+        for i in 0..=100 {
+            let state = profile.state_at(i as f64 / 100.0);
+            let covered = geo::distance_km(FRA, (state.latitude, state.longitude));
+            assert!(covered + 1e-6 >= previous);
+            previous = covered;
+        }
+        assert!((previous - profile.distance_km()).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_zero_duration() {
+        let state = FlightProfile::new(FRA, JFK, Duration::zero()).state_at(0.5);
+        assert_eq!(state.phase, FlightPhase::Taxi);
+        assert_eq!(state.ground_speed, 0.0);
+    }
+}
@@ -0,0 +1,383 @@
+//! Validated aviation identifiers.
+//!
+//! Each type only holds values of its format, normalized to upper case
+//! without surrounding whitespace. They parse with [`FromStr`], serialize as
+//! plain strings and reject invalid values when deserialized.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+use utoipa::openapi::RefOr;
+
+/// Kind of identifier that failed to parse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentifierKind {
+    IataCode,
+    IcaoCode,
+    FlightNumber,
+    AircraftRegistration,
+}
+
+impl IdentifierKind {
+    /// Description of the accepted format
+    pub fn expected(&self) -> &'static str {
+        match self {
+            Self::IataCode => "three letters or digits, e.g. FRA",
+            Self::IcaoCode => "a letter and three letters or digits, e.g. EDDF",
+            Self::FlightNumber => {
+                "an airline designator, one to four digits and an optional letter, e.g. LH400"
+            }
+            Self::AircraftRegistration => {
+                "two to ten letters, digits and single hyphens, e.g. D-AIBL"
+            }
+        }
+    }
+}
+
+impl fmt::Display for IdentifierKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::IataCode => "IATA code",
+            Self::IcaoCode => "ICAO code",
+            Self::FlightNumber => "flight number",
+            Self::AircraftRegistration => "aircraft registration",
+        })
+    }
+}
+
+/// A value that is not a valid identifier of its kind
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid {kind} {value:?}, expected {}", kind.expected())]
+pub struct IdentifierError {
+    pub kind: IdentifierKind,
+    pub value: String,
+}
+
+impl IdentifierError {
+    fn new(kind: IdentifierKind, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Implements the string conversions, serde and the OpenAPI schema of an
+/// identifier whose `parse` function validates and normalizes
+macro_rules! identifier {
+    ($name:ident, $pattern:literal, $example:literal) => {
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = IdentifierError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = IdentifierError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                Self::parse(&value)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = IdentifierError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                Self::parse(value)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl utoipa::PartialSchema for $name {
+            fn schema() -> RefOr<Schema> {
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .pattern(Some($pattern))
+                    .examples([$example])
+                    .into()
+            }
+        }
+
+        impl utoipa::ToSchema for $name {}
+    };
+}
+
+/// Three character IATA location code, e.g. `FRA`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IataCode(String);
+
+impl IataCode {
+    fn parse(value: &str) -> Result<Self, IdentifierError> {
+        let code = value.trim().to_ascii_uppercase();
+        if code.len() == 3 && code.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            Ok(Self(code))
+        } else {
+            Err(IdentifierError::new(IdentifierKind::IataCode, value))
+        }
+    }
+}
+
+identifier!(IataCode, "^[A-Z0-9]{3}$", "FRA");
+
+/// Four character ICAO location indicator, e.g. `EDDF`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IcaoCode(String);
+
+impl IcaoCode {
+    fn parse(value: &str) -> Result<Self, IdentifierError> {
+        let code = value.trim().to_ascii_uppercase();
+        let valid = code.len() == 4
+            && code.starts_with(|c: char| c.is_ascii_alphabetic())
+            && code.bytes().all(|b| b.is_ascii_alphanumeric());
+        if valid {
+            Ok(Self(code))
+        } else {
+            Err(IdentifierError::new(IdentifierKind::IcaoCode, value))
+        }
+    }
+}
+
+identifier!(IcaoCode, "^[A-Z][A-Z0-9]{3}$", "EDDF");
+
+/// Airline designator, one to four digits and an optional suffix letter.
+///
+/// The designator is the two character IATA (`LH400`) or three letter ICAO
+/// (`DLH400`) code of the airline. Whitespace and leading zeros of the
+/// number are dropped, so `lh 0400` reads as `LH400`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FlightNumber(String);
+
+impl FlightNumber {
+    fn parse(value: &str) -> Result<Self, IdentifierError> {
+        let invalid = || IdentifierError::new(IdentifierKind::FlightNumber, value);
+        let compact: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        if !compact.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(invalid());
+        }
+
+        let designator_len = Self::designator_len(&compact).ok_or_else(invalid)?;
+        let (designator, rest) = compact.split_at(designator_len);
+        let digits = rest.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let suffix = &rest[digits.len()..];
+        if !(1..=4).contains(&digits.len())
+            || !digits.bytes().all(|b| b.is_ascii_digit())
+            || suffix.len() > 1
+        {
+            return Err(invalid());
+        }
+
+        match digits.parse::<u16>() {
+            Ok(number) if number > 0 => Ok(Self(format!("{designator}{number}{suffix}"))),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Three letters before a digit are an ICAO designator, otherwise the
+    /// first two characters are an IATA designator with at least one letter
+    fn designator_len(compact: &str) -> Option<usize> {
+        let bytes = compact.as_bytes();
+        if bytes.len() > 3
+            && bytes[..3].iter().all(u8::is_ascii_alphabetic)
+            && bytes[3].is_ascii_digit()
+        {
+            return Some(3);
+        }
+        (bytes.len() > 2
+            && bytes[..2].iter().any(u8::is_ascii_alphabetic)
+            && bytes[2].is_ascii_digit())
+        .then_some(2)
+    }
+
+    /// IATA or ICAO designator of the airline, e.g. `LH`
+    pub fn designator(&self) -> &str {
+        let len = Self::designator_len(&self.0).expect("validated on parse");
+        &self.0[..len]
+    }
+
+    /// Numeric part without suffix, e.g. `400`
+    pub fn number(&self) -> u16 {
+        self.0[self.designator().len()..]
+            .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+            .parse()
+            .expect("validated on parse")
+    }
+
+    /// Operational suffix letter, e.g. `A` of `TP1234A`
+    pub fn suffix(&self) -> Option<char> {
+        self.0.chars().last().filter(|c| c.is_ascii_alphabetic())
+    }
+}
+
+identifier!(
+    FlightNumber,
+    "^([A-Z]{3}|[A-Z0-9]{2})[0-9]{1,4}[A-Z]?$",
+    "LH400"
+);
+
+/// Aircraft registration (tail number), e.g. `D-AIBL` or `N12345`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AircraftRegistration(String);
+
+impl AircraftRegistration {
+    fn parse(value: &str) -> Result<Self, IdentifierError> {
+        let registration = value.trim().to_ascii_uppercase();
+        let valid = (2..=10).contains(&registration.len())
+            && registration
+                .split('-')
+                .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric()))
+            && registration.bytes().any(|b| b.is_ascii_alphabetic());
+        if valid {
+            Ok(Self(registration))
+        } else {
+            Err(IdentifierError::new(
+                IdentifierKind::AircraftRegistration,
+                value,
+            ))
+        }
+    }
+}
+
+identifier!(AircraftRegistration, "^[A-Z0-9]+(-[A-Z0-9]+)*$", "D-AIBL");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_airport_codes() {
+        assert_eq!(" fra ".parse::<IataCode>().unwrap(), "FRA");
+        assert_eq!("du9".parse::<IataCode>().unwrap(), "DU9");
+        for invalid in ["FR", "FRAX", "F-A", "", "ÄBC"] {
+            assert!(invalid.parse::<IataCode>().is_err(), "{invalid}");
+        }
+
+        assert_eq!("eddf".parse::<IcaoCode>().unwrap(), "EDDF");
+        assert_eq!("CAQ3".parse::<IcaoCode>().unwrap(), "CAQ3");
+        for invalid in ["FRA", "EDDFX", "07FA", "ED-F"] {
+            assert!(invalid.parse::<IcaoCode>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_flight_numbers() {
+        for (input, expected) in [
+            ("LH400", "LH400"),
+            ("lh 0400", "LH400"),
+            ("DLH400", "DLH400"),
+            ("TP1234A", "TP1234A"),
+            ("U21", "U21"),
+            ("9W12", "9W12"),
+        ] {
+            assert_eq!(input.parse::<FlightNumber>().unwrap(), expected, "{input}");
+        }
+        for invalid in [
+            "LH", "LH12345", "LH0", "LH12AB", "LH-12", "12345", "L400", "ABCD12",
+        ] {
+            assert!(invalid.parse::<FlightNumber>().is_err(), "{invalid}");
+        }
+
+        let number: FlightNumber = "TP1234A".parse().unwrap();
+        assert_eq!(number.designator(), "TP");
+        assert_eq!(number.number(), 1234);
+        assert_eq!(number.suffix(), Some('A'));
+        let number: FlightNumber = "DLH7".parse().unwrap();
+        assert_eq!(number.designator(), "DLH");
+        assert_eq!(number.suffix(), None);
+    }
+
+    #[test]
+    fn test_aircraft_registrations() {
+        for (input, expected) in [
+            ("d-aibl", "D-AIBL"),
+            ("N12345", "N12345"),
+            ("HB-JNA", "HB-JNA"),
+        ] {
+            assert_eq!(
+                input.parse::<AircraftRegistration>().unwrap(),
+                expected,
+                "{input}"
+            );
+        }
+        for invalid in [
+            "D--AIBL",
+            "-DAIBL",
+            "D-",
+            "12345",
+            "D AIBL",
+            "X",
+            "ABCDEFGHIJK",
+        ] {
+            assert!(
+                invalid.parse::<AircraftRegistration>().is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let code: IataCode = serde_json::from_str("\"lis\"").unwrap();
+        assert_eq!(serde_json::to_string(&code).unwrap(), "\"LIS\"");
+
+        let error = serde_json::from_str::<IcaoCode>("\"LIS\"").unwrap_err();
+        assert!(error.to_string().contains("Invalid ICAO code \"LIS\""));
+    }
+
+    #[test]
+    fn test_error_message() {
+        let error = "LH".parse::<FlightNumber>().unwrap_err();
+        assert_eq!(error.kind, IdentifierKind::FlightNumber);
+        assert_eq!(
+            error.to_string(),
+            "Invalid flight number \"LH\", expected an airline designator, one to four digits and an optional letter, e.g. LH400"
+        );
+    }
+}
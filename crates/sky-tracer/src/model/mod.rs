@@ -1,9 +1,15 @@
 pub mod airport;
 pub mod flight;
+pub mod flight_profile;
+pub mod identifiers;
 pub mod position;
 pub mod satellite;
 
-pub use airport::{Airport, AirportError};
-pub use flight::Flight;
+pub use airport::{Airport, AirportError, AirportType, Frequency, Runway, RunwayEnd};
+pub use flight::{Flight, FlightStatus};
+pub use flight_profile::{FlightPhase, FlightProfile, FlightState};
+pub use identifiers::{
+    AircraftRegistration, FlightNumber, IataCode, IcaoCode, IdentifierError, IdentifierKind,
+};
 pub use position::Position;
 pub use satellite::{Satellite, SatelliteStatus};
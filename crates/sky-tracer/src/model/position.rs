@@ -1,3 +1,5 @@
+use super::flight_profile::{FlightPhase, FlightState};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -6,18 +8,55 @@ use uuid::Uuid;
 pub struct Position {
     pub latitude: f64,
     pub longitude: f64,
+    /// Altitude in meters
     pub altitude: f32,
+    /// True track over ground in degrees
+    #[serde(default)]
+    pub heading: f32,
+    /// Ground speed in km/h
+    #[serde(default)]
+    pub ground_speed: f32,
+    /// Vertical rate in m/s, negative while descending
+    #[serde(default)]
+    pub vertical_rate: f32,
+    #[serde(default)]
+    pub phase: FlightPhase,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub satellite_id: Uuid,
 }
 
 impl Position {
-    pub fn new(latitude: f64, longitude: f64, altitude: f32, satellite_id: Uuid) -> Self {
+    pub fn new(
+        latitude: f64,
+        longitude: f64,
+        altitude: f32,
+        satellite_id: Uuid,
+        timestamp: DateTime<Utc>,
+    ) -> Self {
         Self {
             latitude,
             longitude,
             altitude,
-            timestamp: chrono::Utc::now(),
+            heading: 0.0,
+            ground_speed: 0.0,
+            vertical_rate: 0.0,
+            phase: FlightPhase::default(),
+            timestamp,
+            satellite_id,
+        }
+    }
+
+    /// Creates a position from a flight state computed for `timestamp`
+    pub fn from_state(state: &FlightState, satellite_id: Uuid, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            latitude: state.latitude,
+            longitude: state.longitude,
+            altitude: state.altitude,
+            heading: state.heading,
+            ground_speed: state.ground_speed,
+            vertical_rate: state.vertical_rate,
+            phase: state.phase,
+            timestamp,
             satellite_id,
         }
     }
@@ -1,3 +1,5 @@
+use crate::model::airport::{Airport, AirportType, Frequency, Runway, RunwayEnd};
+use crate::model::{IataCode, IcaoCode};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
@@ -7,14 +9,35 @@ pub struct AirportResponse {
     pub id: u32,
     /// Airport name
     pub name: String,
-    /// Combined IATA/ICAO code (e.g., "LAX/KLAX")
+    /// Primary code, IATA where assigned and ICAO otherwise (e.g., "LAX")
     pub code: String,
     /// Airport position details
     pub position: Position,
+    /// Three-letter IATA code (e.g., "LAX")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iata: Option<IataCode>,
+    /// Four-letter ICAO code (e.g., "KLAX")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icao: Option<IcaoCode>,
+    /// City served by the airport
+    #[serde(default)]
+    pub city: String,
+    /// Country the airport is located in
+    #[serde(default)]
+    pub country: String,
+    /// Elevation in feet above mean sea level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elevation_ft: Option<i32>,
+    /// IANA time zone (e.g., "America/Los_Angeles")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Kind of facility
+    #[serde(default)]
+    pub airport_type: AirportType,
 }
 
-impl From<&crate::model::airport::Airport> for AirportResponse {
-    fn from(airport: &crate::model::airport::Airport) -> Self {
+impl From<&Airport> for AirportResponse {
+    fn from(airport: &Airport) -> Self {
         let (latitude, longitude) = airport.position();
         Self {
             id: airport.id,
@@ -24,6 +47,32 @@ impl From<&crate::model::airport::Airport> for AirportResponse {
                 latitude,
                 longitude,
             },
+            iata: airport.iata.clone(),
+            icao: airport.icao.clone(),
+            city: airport.city.clone(),
+            country: airport.country.clone(),
+            elevation_ft: airport.elevation_ft,
+            timezone: airport.timezone.clone(),
+            airport_type: airport.airport_type,
+        }
+    }
+}
+
+impl From<AirportResponse> for Airport {
+    fn from(response: AirportResponse) -> Self {
+        Self {
+            id: response.id,
+            latitude: response.position.latitude,
+            longitude: response.position.longitude,
+            name: response.name,
+            code: response.code,
+            iata: response.iata,
+            icao: response.icao,
+            city: response.city,
+            country: response.country,
+            elevation_ft: response.elevation_ft,
+            timezone: response.timezone,
+            airport_type: response.airport_type,
         }
     }
 }
@@ -36,13 +85,26 @@ pub struct Position {
     pub longitude: f64,
 }
 
-#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+/// Default number of airports returned by a text search
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+/// Largest number of airports returned by a text search
+pub const MAX_SEARCH_LIMIT: usize = 1000;
+/// Default number of autocomplete suggestions
+pub const DEFAULT_AUTOCOMPLETE_LIMIT: usize = 8;
+/// Largest number of autocomplete suggestions
+pub const MAX_AUTOCOMPLETE_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct SearchAirportsRequest {
-    /// Optional name to search for
+    /// Free text matched against codes, name, city and country, ranked by relevance
+    pub q: Option<String>,
+    /// Optional name to search for, same matching as `q`
     pub name: Option<String>,
     /// Optional code (IATA/ICAO) for exact match
     pub code: Option<String>,
+    /// Maximum number of airports for text searches, defaults to 50
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -50,18 +112,407 @@ pub struct SearchAirportsResponse {
     pub airports: Vec<AirportResponse>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
-pub struct ErrorResponse {
-    /// Error message
-    pub error: String,
-    /// Error code for categorization
+/// Default page size of the airport list
+pub const DEFAULT_LIST_LIMIT: usize = 100;
+/// Largest page size of the airport list
+pub const MAX_LIST_LIMIT: usize = 1000;
+
+/// Field the airport list is ordered by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AirportSortField {
+    #[default]
+    Id,
+    Name,
+    Code,
+    City,
+    Country,
+    Elevation,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAirportsRequest {
+    /// Number of matching airports to skip, defaults to 0
+    pub offset: Option<usize>,
+    /// Opaque `next_cursor` of a previous page, instead of `offset`
+    pub cursor: Option<String>,
+    /// Page size, defaults to 100 and is capped at 1000. 0 only counts
+    pub limit: Option<usize>,
+    /// Field to order by, defaults to `id`. Names compare case and accent insensitive
+    pub sort: Option<AirportSortField>,
+    /// Sort direction, defaults to `asc`
+    pub order: Option<SortOrder>,
+    /// Only airports in this country (e.g., "Germany"), case and accent insensitive
+    pub country: Option<String>,
+    /// Only airports inside `min_lon,min_lat,max_lon,max_lat`, `min_lon > max_lon` crosses the antimeridian
+    pub bbox: Option<String>,
+    /// Only facilities of this kind
+    #[serde(rename = "type")]
+    pub airport_type: Option<AirportType>,
+}
+
+/// One page of the airport list
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListAirportsResponse {
+    pub airports: Vec<AirportResponse>,
+    /// Number of airports matching the filters
+    pub total: usize,
+    /// Position of the first airport of this page among all matches
+    pub offset: usize,
+    /// Page size that was applied
+    pub limit: usize,
+    /// Offset of the next page, absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+    /// Cursor of the next page, absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AutocompleteRequest {
+    /// Text typed so far, matched like a search
+    pub q: String,
+    /// Maximum number of suggestions, defaults to 8
+    pub limit: Option<usize>,
+}
+
+/// Compact airport entry for input suggestions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AirportSuggestion {
+    /// Primary code to put into the input (e.g., "LAX")
+    pub code: String,
+    /// Airport name
+    pub name: String,
+    /// City served by the airport
+    pub city: String,
+    /// Country the airport is located in
+    pub country: String,
+    /// Display text, e.g. "Frankfurt am Main Airport (FRA) - Frankfurt, Germany"
+    pub label: String,
+}
+
+impl From<&Airport> for AirportSuggestion {
+    fn from(airport: &Airport) -> Self {
+        let place = [airport.city.as_str(), airport.country.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let label = if place.is_empty() {
+            format!("{} ({})", airport.name, airport.code)
+        } else {
+            format!("{} ({}) - {}", airport.name, airport.code, place)
+        };
+
+        Self {
+            code: airport.code.clone(),
+            name: airport.name.clone(),
+            city: airport.city.clone(),
+            country: airport.country.clone(),
+            label,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AutocompleteResponse {
+    /// Suggestions ordered by relevance
+    pub suggestions: Vec<AirportSuggestion>,
+}
+
+/// Default search radius for nearby airports in kilometers
+pub const DEFAULT_NEARBY_RADIUS_KM: f64 = 100.0;
+/// Largest accepted search radius, half the earth's circumference
+pub const MAX_NEARBY_RADIUS_KM: f64 = 20_000.0;
+/// Default number of nearby airports returned
+pub const DEFAULT_NEARBY_LIMIT: usize = 10;
+/// Largest number of nearby airports returned
+pub const MAX_NEARBY_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NearbyAirportsRequest {
+    /// Latitude in decimal degrees
+    pub lat: f64,
+    /// Longitude in decimal degrees
+    pub lon: f64,
+    /// Search radius in kilometers, defaults to 100
+    pub radius_km: Option<f64>,
+    /// Maximum number of airports, defaults to 10
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct NearbyAirportResponse {
+    #[serde(flatten)]
+    pub airport: AirportResponse,
+    /// Great-circle distance from the requested position in kilometers
+    pub distance_km: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NearbyAirportsResponse {
+    /// Airports ordered by distance, nearest first
+    pub airports: Vec<NearbyAirportResponse>,
+}
+
+/// Airport to add to the overlay, or the new data of an existing airport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AirportRequest {
+    /// Airport name
+    pub name: String,
+    /// Primary code the airport is looked up by (e.g., "LAX")
     pub code: String,
+    /// Airport position
+    pub position: Position,
+    /// Three-letter IATA code (e.g., "LAX")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iata: Option<IataCode>,
+    /// Four-letter ICAO code (e.g., "KLAX")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icao: Option<IcaoCode>,
+    /// City served by the airport
+    #[serde(default)]
+    pub city: String,
+    /// Country the airport is located in
+    #[serde(default)]
+    pub country: String,
+    /// Elevation in feet above mean sea level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elevation_ft: Option<i32>,
+    /// IANA time zone (e.g., "America/Los_Angeles")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Kind of facility
+    #[serde(default)]
+    pub airport_type: AirportType,
+}
+
+impl AirportRequest {
+    /// The airport described by the request under `id`
+    pub fn into_airport(self, id: u32) -> Airport {
+        Airport {
+            id,
+            latitude: self.position.latitude,
+            longitude: self.position.longitude,
+            name: self.name,
+            code: self.code,
+            iata: self.iata,
+            icao: self.icao,
+            city: self.city,
+            country: self.country,
+            elevation_ft: self.elevation_ft,
+            timezone: self.timezone,
+            airport_type: self.airport_type,
+        }
+    }
+}
+
+/// Local changes served on top of the airport data
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AirportOverlayResponse {
+    /// Added airports and replacements of airports in the data, by id
+    pub airports: Vec<AirportResponse>,
+    /// Ids of hidden airports of the data
+    pub hidden: Vec<u32>,
+}
+
+/// Media type of GeoJSON documents
+pub const GEOJSON_CONTENT_TYPE: &str = "application/geo+json";
+/// Media type of KML documents
+pub const KML_CONTENT_TYPE: &str = "application/vnd.google-earth.kml+xml";
+
+/// Representation of an airport list for GIS tooling
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// The regular JSON response
+    #[default]
+    Json,
+    /// A GeoJSON `FeatureCollection` of points
+    GeoJson,
+    /// A KML document of placemarks
+    Kml,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::GeoJson => GEOJSON_CONTENT_TYPE,
+            Self::Kml => KML_CONTENT_TYPE,
+        }
+    }
+
+    /// Format for a media type of an `Accept` header, parameters are ignored
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        [Self::Json, Self::GeoJson, Self::Kml]
+            .into_iter()
+            .find(|format| format.content_type().eq_ignore_ascii_case(essence))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportFormatRequest {
+    /// Response format, overrides the `Accept` header. Defaults to `json`
+    pub format: Option<ExportFormat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RunwayResponse {
+    /// Both designators, e.g. "07L/25R"
+    pub designation: String,
+    /// Length in feet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length_ft: Option<u32>,
+    /// Width in feet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width_ft: Option<u32>,
+    /// Surface as published, e.g. "ASP" or "CON"
+    pub surface: String,
+    pub lighted: bool,
+    pub closed: bool,
+    /// Low-numbered end
+    pub low_end: RunwayEndResponse,
+    /// High-numbered end, absent for single-ended runways such as helipads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high_end: Option<RunwayEndResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RunwayEndResponse {
+    /// Runway designator, e.g. "25R"
+    pub ident: String,
+    /// True heading in degrees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading_deg: Option<f64>,
+    /// Threshold position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+    /// Threshold elevation in feet above mean sea level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elevation_ft: Option<i32>,
+    /// Distance the landing threshold is moved down the runway, in feet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displaced_threshold_ft: Option<u32>,
+}
+
+impl From<&RunwayEnd> for RunwayEndResponse {
+    fn from(end: &RunwayEnd) -> Self {
+        Self {
+            ident: end.ident.clone(),
+            heading_deg: end.heading_deg,
+            position: end
+                .latitude
+                .zip(end.longitude)
+                .map(|(latitude, longitude)| Position {
+                    latitude,
+                    longitude,
+                }),
+            elevation_ft: end.elevation_ft,
+            displaced_threshold_ft: end.displaced_threshold_ft,
+        }
+    }
+}
+
+impl From<&Runway> for RunwayResponse {
+    fn from(runway: &Runway) -> Self {
+        Self {
+            designation: runway.designation(),
+            length_ft: runway.length_ft,
+            width_ft: runway.width_ft,
+            surface: runway.surface.clone(),
+            lighted: runway.lighted,
+            closed: runway.closed,
+            low_end: RunwayEndResponse::from(&runway.low_end),
+            high_end: runway.high_end.as_ref().map(RunwayEndResponse::from),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RunwaysResponse {
+    /// Primary code of the airport
+    pub airport: String,
+    pub runways: Vec<RunwayResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FrequencyResponse {
+    /// Service, e.g. "TWR", "GND" or "ATIS"
+    pub frequency_type: String,
+    /// Free text such as the call sign
+    pub description: String,
+    pub frequency_mhz: f64,
+}
+
+impl From<&Frequency> for FrequencyResponse {
+    fn from(frequency: &Frequency) -> Self {
+        Self {
+            frequency_type: frequency.frequency_type.clone(),
+            description: frequency.description.clone(),
+            frequency_mhz: frequency.frequency_mhz,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FrequenciesResponse {
+    /// Primary code of the airport
+    pub airport: String,
+    pub frequencies: Vec<FrequencyResponse>,
+}
+
+/// Largest number of invalid lines listed in a reload report
+pub const MAX_REPORTED_LINE_ERRORS: usize = 100;
+
+/// A data line skipped during a reload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LineErrorResponse {
+    /// Data file the line is in, when several are loaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// 1-based line number in the data file
+    pub line: u64,
+    /// Why the line was skipped
+    pub error: String,
+}
+
+/// Outcome of reloading the airport data
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReloadAirportsResponse {
+    /// Whether the new data replaced the served dataset
+    pub reloaded: bool,
+    /// Data file path, or "embedded"
+    pub source: String,
+    /// Number of airports served after the reload
+    pub airports: usize,
+    /// Dataset version served after the reload, also used as ETag
+    pub version: String,
+    /// Whether the served data differs from before the reload
+    pub changed: bool,
+    /// Number of lines that could not be parsed
+    pub invalid_lines: usize,
+    /// The first invalid lines, at most 100
+    pub errors: Vec<LineErrorResponse>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::airport::Airport;
 
     #[test]
     fn test_airport_response_conversion() {
@@ -74,4 +525,90 @@ mod tests {
         assert_eq!(response.position.latitude, airport.latitude);
         assert_eq!(response.position.longitude, airport.longitude);
     }
+
+    #[test]
+    fn test_airport_round_trip() {
+        let airport = Airport::paris();
+        let json = serde_json::to_string(&AirportResponse::from(&airport)).unwrap();
+        let response: AirportResponse = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(response.icao.as_ref().unwrap(), "LFPG");
+        assert_eq!(response.country, "France");
+        assert_eq!(Airport::from(response), airport);
+    }
+
+    #[test]
+    fn test_nearby_airport_is_flattened() {
+        let nearby = NearbyAirportResponse {
+            airport: AirportResponse::from(&Airport::frankfurt()),
+            distance_km: 12.5,
+        };
+        let json = serde_json::to_value(&nearby).unwrap();
+
+        assert_eq!(json["code"], "FRA");
+        assert_eq!(json["distance_km"], 12.5);
+        assert_eq!(
+            serde_json::from_value::<NearbyAirportResponse>(json).unwrap(),
+            nearby
+        );
+    }
+
+    #[test]
+    fn test_list_request_from_query() {
+        let request: ListAirportsRequest = serde_json::from_value(serde_json::json!({
+            "sort": "elevation",
+            "order": "desc",
+            "type": "station"
+        }))
+        .unwrap();
+
+        assert_eq!(request.sort, Some(AirportSortField::Elevation));
+        assert_eq!(request.order, Some(SortOrder::Desc));
+        assert_eq!(request.airport_type, Some(AirportType::Station));
+    }
+
+    #[test]
+    fn test_list_response_reads_as_search_response() {
+        let page = ListAirportsResponse {
+            airports: vec![AirportResponse::from(&Airport::frankfurt())],
+            total: 2,
+            offset: 0,
+            limit: 1,
+            next_offset: Some(1),
+            next_cursor: Some("abc".to_string()),
+        };
+        let json = serde_json::to_string(&page).unwrap();
+        let search: SearchAirportsResponse = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(search.airports, page.airports);
+    }
+
+    #[test]
+    fn test_export_format_from_media_type() {
+        assert_eq!(
+            ExportFormat::from_media_type("application/geo+json"),
+            Some(ExportFormat::GeoJson)
+        );
+        assert_eq!(
+            ExportFormat::from_media_type(" application/vnd.google-earth.kml+xml; charset=utf-8"),
+            Some(ExportFormat::Kml)
+        );
+        assert_eq!(ExportFormat::from_media_type("text/html"), None);
+        assert_eq!(
+            serde_json::from_str::<ExportFormat>("\"geojson\"").unwrap(),
+            ExportFormat::GeoJson
+        );
+    }
+
+    #[test]
+    fn test_suggestion_label() {
+        let suggestion = AirportSuggestion::from(&Airport::frankfurt());
+        assert_eq!(
+            suggestion.label,
+            "Frankfurt am Main Airport (FRA) - Frankfurt, Germany"
+        );
+
+        let bare = AirportSuggestion::from(&Airport::new(1, 0.0, 0.0, "Test".into(), "TST".into()));
+        assert_eq!(bare.label, "Test (TST)");
+    }
 }
@@ -1,3 +1,6 @@
+use crate::model::{
+    AircraftRegistration, Flight, FlightNumber, FlightPhase, FlightStatus, IataCode,
+};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
@@ -14,36 +17,121 @@ pub fn example_arrival_time() -> String {
     future_time.to_rfc3339()
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateFlightRequest {
     /// Aircraft registration number
-    pub aircraft_number: String,
-    /// Departure airport code
-    pub departure: String,
-    /// Arrival airport code
-    pub arrival: String,
+    pub aircraft_number: AircraftRegistration,
+    /// Departure airport IATA code
+    pub departure: IataCode,
+    /// Arrival airport IATA code
+    pub arrival: IataCode,
     /// Scheduled departure time
     #[schema(value_type = String, format = "date-time", example = example_departure_time)]
     pub departure_time: DateTime<Utc>,
     /// Scheduled arrival time
     #[schema(value_type = String, format = "date-time", example = example_arrival_time)]
     pub arrival_time: Option<DateTime<Utc>>,
+    /// IATA or ICAO designator of the operating airline, the service default when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "LH")]
+    pub airline: Option<String>,
+    /// Explicit flight number such as "LH400", allocated automatically when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flight_number: Option<FlightNumber>,
+}
+
+/// Airline known to the flight registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AirlineResponse {
+    /// Two character IATA designator, used as flight number prefix
+    pub iata: String,
+    /// Three letter ICAO designator
+    pub icao: String,
+    /// Airline name
+    pub name: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct FlightResponse {
     /// Flight number
-    pub flight_number: String,
-    /// Aircraft number
-    pub aircraft_number: String,
-    /// Departure airport code
-    pub departure: String,
-    /// Arrival airport code
-    pub arrival: String,
+    pub flight_number: FlightNumber,
+    /// Aircraft registration number
+    pub aircraft_number: AircraftRegistration,
+    /// Departure airport IATA code
+    pub departure: IataCode,
+    /// Arrival airport IATA code
+    pub arrival: IataCode,
     /// Scheduled departure time
     pub departure_time: DateTime<Utc>,
     /// Scheduled arrival time
     pub arrival_time: Option<DateTime<Utc>>,
+    /// Operational status
+    #[serde(default)]
+    pub status: FlightStatus,
+}
+
+impl From<Flight> for FlightResponse {
+    fn from(flight: Flight) -> Self {
+        Self {
+            flight_number: flight.flight_number,
+            aircraft_number: flight.aircraft_number,
+            departure: flight.departure,
+            arrival: flight.arrival,
+            departure_time: flight.departure_time,
+            arrival_time: flight.arrival_time,
+            status: flight.status,
+        }
+    }
+}
+
+impl From<FlightResponse> for Flight {
+    fn from(response: FlightResponse) -> Self {
+        Self {
+            flight_number: response.flight_number,
+            aircraft_number: response.aircraft_number,
+            departure: response.departure,
+            arrival: response.arrival,
+            departure_time: response.departure_time,
+            arrival_time: response.arrival_time,
+            status: response.status,
+        }
+    }
+}
+
+/// Replaces the schedule of an existing flight
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateFlightRequest {
+    /// Aircraft registration number
+    pub aircraft_number: AircraftRegistration,
+    /// Departure airport IATA code
+    pub departure: IataCode,
+    /// Arrival airport IATA code
+    pub arrival: IataCode,
+    /// Scheduled departure time
+    #[schema(value_type = String, format = "date-time", example = example_departure_time)]
+    pub departure_time: DateTime<Utc>,
+    /// Scheduled arrival time
+    #[schema(value_type = String, format = "date-time", example = example_arrival_time)]
+    pub arrival_time: Option<DateTime<Utc>>,
+}
+
+/// Partially updates a flight; omitted fields keep their value
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct PatchFlightRequest {
+    /// Aircraft registration number
+    pub aircraft_number: Option<AircraftRegistration>,
+    /// Departure airport IATA code
+    pub departure: Option<IataCode>,
+    /// Arrival airport IATA code
+    pub arrival: Option<IataCode>,
+    /// Scheduled departure time
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub departure_time: Option<DateTime<Utc>>,
+    /// Scheduled arrival time
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub arrival_time: Option<DateTime<Utc>>,
+    /// New operational status
+    pub status: Option<FlightStatus>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
@@ -54,11 +142,25 @@ pub struct FlightPositionResponse {
     pub latitude: f64,
     /// Longitude
     pub longitude: f64,
+    /// Altitude in meters
+    pub altitude: f32,
+    /// True track over ground in degrees
+    #[serde(default)]
+    pub heading: f32,
+    /// Ground speed in km/h
+    #[serde(default)]
+    pub ground_speed: f32,
+    /// Vertical rate in m/s, negative while descending
+    #[serde(default)]
+    pub vertical_rate: f32,
+    /// Flight phase
+    #[serde(default)]
+    pub phase: FlightPhase,
     /// Time
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct ListFlightsRequest {
     /// Optional departure airport code
@@ -69,8 +171,61 @@ pub struct ListFlightsRequest {
     pub date: Option<String>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
-pub struct ErrorResponse {
-    pub error: String,
+/// Validation problem with a single request field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    /// Name of the offending field, e.g. "arrival_time"
+    pub field: String,
+    /// Machine readable reason, e.g. "UNKNOWN_AIRPORT"
     pub code: String,
+    /// Human readable description
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Position;
+
+    #[test]
+    fn test_positions_without_flight_profile_decode() {
+        // Positions sent before flights had a profile
+        let response: FlightPositionResponse = serde_json::from_str(
+            r#"{
+                "flight_number": "LH400",
+                "latitude": 50.03,
+                "longitude": 8.57,
+                "altitude": 11000.0,
+                "timestamp": "2025-06-01T12:00:00Z"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(response.heading, 0.0);
+        assert_eq!(response.ground_speed, 0.0);
+        assert_eq!(response.vertical_rate, 0.0);
+        assert_eq!(response.phase, FlightPhase::Cruise);
+
+        let position: Position = serde_json::from_str(
+            r#"{
+                "latitude": 50.03,
+                "longitude": 8.57,
+                "altitude": 11000.0,
+                "timestamp": "2025-06-01T12:00:00Z",
+                "satellite_id": "6f1c2a3e-8e4d-4b5a-9c7f-1d2e3f4a5b6c"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(position.phase, FlightPhase::Cruise);
+        assert_eq!(position.heading, 0.0);
+    }
 }
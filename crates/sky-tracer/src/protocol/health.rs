@@ -0,0 +1,294 @@
+//! Liveness and readiness reports of the Sky Tracer services.
+//!
+//! `/health/live` only tells that the process serves requests,
+//! `/health/ready` also checks the services it depends on and reports the
+//! status and latency of each of them.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use utoipa::ToSchema;
+
+/// Liveness endpoint of every service
+pub const HEALTH_LIVE_PATH: &str = "/health/live";
+/// Readiness endpoint of every service
+pub const HEALTH_READY_PATH: &str = "/health/ready";
+
+/// Time a dependency gets to answer a readiness check
+pub const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+/// State of the circuit breaker guarding the calls to a dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls go through
+    Closed,
+    /// Calls fail fast after repeated failures
+    Open,
+    /// The next call is a trial deciding whether to close the circuit again
+    HalfOpen,
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        })
+    }
+}
+
+/// Result of checking a single dependency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DependencyHealth {
+    /// Name of the dependency, e.g. "airport-anywhere"
+    pub name: String,
+    pub status: HealthStatus,
+    /// Time the check took in milliseconds
+    pub latency_ms: u64,
+    /// Why the dependency is down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Circuit breaker of the calls to the dependency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit: Option<CircuitState>,
+}
+
+impl DependencyHealth {
+    pub fn up(name: &str, latency: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            status: HealthStatus::Up,
+            latency_ms: latency.as_millis() as u64,
+            error: None,
+            circuit: None,
+        }
+    }
+
+    pub fn down(name: &str, latency: Duration, error: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: HealthStatus::Down,
+            latency_ms: latency.as_millis() as u64,
+            error: Some(error.into()),
+            circuit: None,
+        }
+    }
+
+    pub fn with_circuit(mut self, circuit: CircuitState) -> Self {
+        self.circuit = Some(circuit);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({
+    "service": "tower-of-babel",
+    "status": "up",
+    "dependencies": [
+        {"name": "flight-controller", "status": "up", "latency_ms": 3, "circuit": "closed"}
+    ]
+}))]
+pub struct HealthResponse {
+    /// Name of the reporting service
+    pub service: String,
+    /// `down` as soon as one dependency is down
+    pub status: HealthStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyHealth>,
+}
+
+impl HealthResponse {
+    /// Report of a live service, without checking dependencies
+    pub fn live(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            status: HealthStatus::Up,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Report of a service that is ready if all `dependencies` are up
+    pub fn ready(service: &str, dependencies: Vec<DependencyHealth>) -> Self {
+        let status = if dependencies
+            .iter()
+            .all(|dependency| dependency.status == HealthStatus::Up)
+        {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+        Self {
+            service: service.to_string(),
+            status,
+            dependencies,
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.status == HealthStatus::Up
+    }
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use super::{
+        DependencyHealth, HealthResponse, HEALTH_LIVE_PATH, HEALTH_READY_PATH, READINESS_TIMEOUT,
+    };
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use std::fmt::Display;
+    use std::future::Future;
+    use std::time::Instant;
+
+    /// `200 OK` for a service that is up, `503 Service Unavailable` otherwise
+    impl IntoResponse for HealthResponse {
+        fn into_response(self) -> Response {
+            let status = if self.is_up() {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            (status, Json(self)).into_response()
+        }
+    }
+
+    /// Run the check of the dependency `name`, which is down if the check
+    /// fails or takes longer than [`READINESS_TIMEOUT`]
+    pub async fn check_dependency<T, E: Display>(
+        name: &str,
+        check: impl Future<Output = Result<T, E>>,
+    ) -> DependencyHealth {
+        let started = Instant::now();
+        match tokio::time::timeout(READINESS_TIMEOUT, check).await {
+            Ok(Ok(_)) => DependencyHealth::up(name, started.elapsed()),
+            Ok(Err(e)) => {
+                tracing::warn!(dependency = name, error = %e, "Dependency is down");
+                DependencyHealth::down(name, started.elapsed(), e.to_string())
+            }
+            Err(_) => {
+                tracing::warn!(dependency = name, "Dependency did not answer in time");
+                DependencyHealth::down(name, started.elapsed(), "Health check timed out")
+            }
+        }
+    }
+
+    /// Routes for `/health/live` and `/health/ready` of `service`, readiness
+    /// is decided by the dependencies `ready` reports for the state
+    ///
+    /// ```no_run
+    /// use sky_tracer::protocol::health::{health_routes, DependencyHealth};
+    ///
+    /// let app: axum::Router = axum::Router::new()
+    ///     .merge(health_routes("flight-map", |_: ()| async { Vec::<DependencyHealth>::new() }))
+    ///     .with_state(());
+    /// ```
+    pub fn health_routes<S, F, Fut>(service: &'static str, ready: F) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+        F: Fn(S) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Vec<DependencyHealth>> + Send,
+    {
+        Router::new()
+            .route(
+                HEALTH_LIVE_PATH,
+                get(move || async move { HealthResponse::live(service) }),
+            )
+            .route(
+                HEALTH_READY_PATH,
+                get(move |State(state): State<S>| async move {
+                    HealthResponse::ready(service, ready(state).await)
+                }),
+            )
+    }
+}
+
+#[cfg(feature = "server")]
+pub use server::{check_dependency, health_routes};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_is_down_with_one_dependency_down() {
+        let report = HealthResponse::ready(
+            "sky-nexus",
+            vec![
+                DependencyHealth::up("airport-anywhere", Duration::from_millis(3)),
+                DependencyHealth::down(
+                    "orbital-beacon",
+                    Duration::from_millis(2000),
+                    "Health check timed out",
+                ),
+            ],
+        );
+
+        assert!(!report.is_up());
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "service": "sky-nexus",
+                "status": "down",
+                "dependencies": [
+                    {"name": "airport-anywhere", "status": "up", "latency_ms": 3},
+                    {
+                        "name": "orbital-beacon",
+                        "status": "down",
+                        "latency_ms": 2000,
+                        "error": "Health check timed out"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_readiness_answers_503_when_down() {
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use tower::ServiceExt;
+
+        let app = health_routes("tower-of-babel", |_: ()| async {
+            vec![
+                check_dependency("flight-controller", async {
+                    Err::<(), _>("connection refused")
+                })
+                .await,
+            ]
+        })
+        .with_state(());
+
+        let live = app
+            .clone()
+            .oneshot(Request::get(HEALTH_LIVE_PATH).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(live.status(), StatusCode::OK);
+
+        let ready = app
+            .oneshot(Request::get(HEALTH_READY_PATH).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(ready.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(ready.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: HealthResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            report.dependencies[0].error.as_deref(),
+            Some("connection refused")
+        );
+    }
+}
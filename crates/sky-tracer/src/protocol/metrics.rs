@@ -0,0 +1,632 @@
+//! RED metrics of the Sky Tracer services.
+//!
+//! Every service records rate, errors and duration of the requests it serves
+//! per route and of the calls it makes per upstream. They are exposed in the
+//! Prometheus text format at `/metrics` and, with `OTEL_METRICS_EXPORTER=otlp`,
+//! pushed to the OTLP endpoint configured by the usual `OTEL_EXPORTER_OTLP_*`
+//! variables.
+//!
+//! All services of a process share one meter provider. Series carry the
+//! [`SERVICE_NAME`] of the service that served the request or made the call,
+//! and the scrape endpoint of a service only returns its own series, so
+//! services started together by the launcher do not report each other.
+
+/// Prometheus scrape endpoint of every service
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Meter all Sky Tracer instruments are created by
+pub const METER_NAME: &str = "sky-tracer";
+
+/// Duration of the requests a service answers, in seconds
+pub const HTTP_SERVER_REQUEST_DURATION: &str = "http.server.request.duration";
+/// Duration of the calls a service makes to its upstreams, in seconds
+pub const HTTP_CLIENT_REQUEST_DURATION: &str = "http.client.request.duration";
+
+/// Attribute naming the service a series belongs to, `service_name` in
+/// Prometheus
+pub const SERVICE_NAME: &str = "service.name";
+
+/// Route label of requests no route matched, keeps unknown paths from
+/// creating new series
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Histogram buckets of request durations in seconds, as recommended by the
+/// OpenTelemetry HTTP semantic conventions
+pub const DURATION_BUCKETS: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[cfg(feature = "server")]
+mod server {
+    use super::{
+        DURATION_BUCKETS, HTTP_SERVER_REQUEST_DURATION, METER_NAME, METRICS_PATH, PROMETHEUS_TEXT,
+        SERVICE_NAME, UNMATCHED_ROUTE,
+    };
+    use axum::extract::{MatchedPath, Request, State};
+    use axum::http::header;
+    use axum::middleware::{self, Next};
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::get;
+    use axum::Router;
+    use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
+    use opentelemetry::metrics::{Histogram, Meter, MeterProvider};
+    use opentelemetry::{global, KeyValue};
+    use opentelemetry_sdk::error::OTelSdkResult;
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics};
+    use opentelemetry_sdk::metrics::reader::MetricReader;
+    use opentelemetry_sdk::metrics::{
+        InstrumentKind, ManualReader, Pipeline, SdkMeterProvider, Temporality,
+    };
+    use opentelemetry_sdk::Resource;
+    use std::fmt::{Display, Write};
+    use std::future::Future;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, OnceLock, Weak};
+    use std::time::{Duration, Instant};
+    use tracing::{info, warn};
+
+    tokio::task_local! {
+        /// Service handling the request being served
+        static SERVICE: &'static str;
+    }
+
+    /// Service the work of the current task is done for, set while a request
+    /// of a service wrapped by [`observe`] is handled
+    pub fn current_service() -> Option<&'static str> {
+        SERVICE.try_with(|service| *service).ok()
+    }
+
+    /// Run `future` on behalf of `service`, the instruments recorded meanwhile
+    /// are labelled with it
+    pub async fn in_service<F: Future>(service: &'static str, future: F) -> F::Output {
+        SERVICE.scope(service, future).await
+    }
+
+    /// Metrics of this process, the meter provider every instrument reports to
+    pub struct Metrics {
+        provider: SdkMeterProvider,
+        reader: Arc<ManualReader>,
+        server_duration: Histogram<f64>,
+    }
+
+    impl Metrics {
+        /// Metrics of this process, installed as the global meter provider
+        /// on first use
+        pub fn global() -> &'static Metrics {
+            static METRICS: OnceLock<Metrics> = OnceLock::new();
+            METRICS.get_or_init(|| {
+                let metrics = Metrics::from_env();
+                global::set_meter_provider(metrics.provider.clone());
+                metrics
+            })
+        }
+
+        fn from_env() -> Self {
+            let reader = Arc::new(ManualReader::builder().build());
+            let mut builder = SdkMeterProvider::builder()
+                .with_resource(Resource::builder().build())
+                .with_reader(SharedReader(reader.clone()));
+
+            if std::env::var("OTEL_METRICS_EXPORTER").as_deref() == Ok("otlp") {
+                match opentelemetry_otlp::MetricExporter::builder()
+                    .with_http()
+                    .build()
+                {
+                    Ok(exporter) => {
+                        info!("Pushing metrics via OTLP");
+                        builder = builder.with_periodic_exporter(exporter);
+                    }
+                    Err(e) => warn!(error = %e, "Cannot push metrics via OTLP"),
+                }
+            }
+
+            let provider = builder.build();
+            let server_duration = provider
+                .meter(METER_NAME)
+                .f64_histogram(HTTP_SERVER_REQUEST_DURATION)
+                .with_unit("s")
+                .with_description("Duration of HTTP server requests")
+                .with_boundaries(DURATION_BUCKETS.to_vec())
+                .build();
+            Self {
+                provider,
+                reader,
+                server_duration,
+            }
+        }
+
+        /// Meter for the domain instruments of a service
+        pub fn meter(&self) -> Meter {
+            self.provider.meter(METER_NAME)
+        }
+
+        /// Register a gauge of `service` reporting the value last stored in
+        /// the returned cell. Register each gauge once, values are stored
+        /// whenever they change or right before a scrape.
+        pub fn service_gauge(
+            &self,
+            service: &'static str,
+            name: &'static str,
+            unit: &'static str,
+            description: &'static str,
+        ) -> Arc<AtomicU64> {
+            let value = Arc::new(AtomicU64::new(0));
+            let observed = value.clone();
+            // The callback stays registered with the provider
+            self.meter()
+                .u64_observable_gauge(name)
+                .with_unit(unit)
+                .with_description(description)
+                .with_callback(move |observer| {
+                    observer.observe(
+                        observed.load(Ordering::Relaxed),
+                        &[KeyValue::new(SERVICE_NAME, service)],
+                    )
+                })
+                .build();
+            value
+        }
+
+        /// Current values of all instruments in the Prometheus text format
+        pub fn render(&self) -> String {
+            self.collect(None)
+        }
+
+        /// Current values of the series of `service` and of those belonging
+        /// to no service in the Prometheus text format
+        pub fn render_service(&self, service: &str) -> String {
+            self.collect(Some(service))
+        }
+
+        fn collect(&self, service: Option<&str>) -> String {
+            let mut metrics = ResourceMetrics::default();
+            if let Err(e) = self.reader.collect(&mut metrics) {
+                warn!(error = %e, "Cannot collect metrics");
+            }
+            encode_prometheus(&metrics, service)
+        }
+
+        fn record_request(
+            &self,
+            service: &'static str,
+            method: &str,
+            route: String,
+            status: u16,
+            elapsed: Duration,
+        ) {
+            self.server_duration.record(
+                elapsed.as_secs_f64(),
+                &[
+                    KeyValue::new(SERVICE_NAME, service),
+                    KeyValue::new("http.request.method", method.to_string()),
+                    KeyValue::new("http.route", route),
+                    KeyValue::new("http.response.status_code", i64::from(status)),
+                ],
+            );
+        }
+    }
+
+    /// The manual reader is owned by the meter provider, the scrape endpoint
+    /// collects from it through this handle
+    #[derive(Debug)]
+    struct SharedReader(Arc<ManualReader>);
+
+    impl MetricReader for SharedReader {
+        fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+            self.0.register_pipeline(pipeline)
+        }
+
+        fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+            self.0.collect(rm)
+        }
+
+        fn force_flush(&self) -> OTelSdkResult {
+            self.0.force_flush()
+        }
+
+        fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+            self.0.shutdown_with_timeout(timeout)
+        }
+
+        fn temporality(&self, kind: InstrumentKind) -> Temporality {
+            self.0.temporality(kind)
+        }
+    }
+
+    /// Middleware recording method, route template, status and duration of
+    /// every answered request.
+    ///
+    /// Routes are labelled by their template, e.g. `/api/v1/flights/{flight_number}`,
+    /// so flight numbers do not create new series. The request is handled on
+    /// behalf of the service given as state.
+    ///
+    /// ```no_run
+    /// # let router: axum::Router = axum::Router::new();
+    /// let app = router.layer(axum::middleware::from_fn_with_state(
+    ///     "flight-controller",
+    ///     sky_tracer::protocol::metrics::record_metrics,
+    /// ));
+    /// ```
+    pub async fn record_metrics(
+        State(service): State<&'static str>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        let started = Instant::now();
+        let method = request.method().clone();
+        let route = request.extensions().get::<MatchedPath>().map_or_else(
+            || UNMATCHED_ROUTE.to_string(),
+            |path| path.as_str().to_string(),
+        );
+
+        let response = in_service(service, next.run(request)).await;
+        Metrics::global().record_request(
+            service,
+            method.as_str(),
+            route,
+            response.status().as_u16(),
+            started.elapsed(),
+        );
+        response
+    }
+
+    /// Route for `/metrics`, `observe` updates the domain gauges of the state
+    /// right before each scrape. Wrapped by [`observe`], only the series of
+    /// that service are returned.
+    ///
+    /// ```no_run
+    /// use sky_tracer::protocol::metrics::metrics_routes;
+    ///
+    /// let app: axum::Router = axum::Router::new()
+    ///     .merge(metrics_routes(|_: ()| async {}))
+    ///     .with_state(());
+    /// ```
+    pub fn metrics_routes<S, F, Fut>(observe: F) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+        F: Fn(S) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let metrics = Metrics::global();
+        Router::new().route(
+            METRICS_PATH,
+            get(move |State(state): State<S>| async move {
+                observe(state).await;
+                let text = match current_service() {
+                    Some(service) => metrics.render_service(service),
+                    None => metrics.render(),
+                };
+                ([(header::CONTENT_TYPE, PROMETHEUS_TEXT)], text).into_response()
+            }),
+        )
+    }
+
+    /// Traces and records the requests of `router` as those of `service`,
+    /// then adds `probes` such as the health and metrics routes
+    ///
+    /// ```no_run
+    /// use sky_tracer::protocol::metrics::{metrics_routes, observe};
+    ///
+    /// let app: axum::Router = observe(
+    ///     "flight-controller",
+    ///     axum::Router::new(),
+    ///     metrics_routes(|_: ()| async {}),
+    /// )
+    /// .with_state(());
+    /// ```
+    pub fn observe<S>(service: &'static str, router: Router<S>, probes: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        // Health probes and scrapes run often and stay out of traces and
+        // metrics, but their upstream calls and scrapes still belong to the
+        // service
+        let probes = probes.layer(middleware::from_fn_with_state(
+            service,
+            |State(service): State<&'static str>, request: Request, next: Next| {
+                in_service(service, next.run(request))
+            },
+        ));
+        router
+            .layer(OtelInResponseLayer)
+            .layer(OtelAxumLayer::default())
+            .layer(middleware::from_fn_with_state(service, record_metrics))
+            .merge(probes)
+    }
+
+    /// Prometheus name of an instrument, e.g. `http_server_request_duration_seconds`
+    fn prometheus_name(name: &str, unit: &str) -> String {
+        let mut name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        match unit {
+            "s" => name.push_str("_seconds"),
+            "ms" => name.push_str("_milliseconds"),
+            "By" => name.push_str("_bytes"),
+            _ => {}
+        }
+        name
+    }
+
+    /// Labels sorted by name, `extra` such as `le` goes last
+    fn labels<'a>(
+        attributes: impl Iterator<Item = &'a KeyValue>,
+        extra: Option<(&str, String)>,
+    ) -> String {
+        let mut labels: Vec<String> = attributes
+            .map(|kv| {
+                format!(
+                    "{}=\"{}\"",
+                    prometheus_name(kv.key.as_str(), ""),
+                    escape(&kv.value.as_str())
+                )
+            })
+            .collect();
+        labels.sort();
+        if let Some((key, value)) = extra {
+            labels.push(format!("{key}=\"{value}\""));
+        }
+        if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", labels.join(","))
+        }
+    }
+
+    fn escape(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    }
+
+    /// Whether a series with `attributes` is reported on the scrape of
+    /// `service`, all series are when no service is given
+    fn belongs<'a>(
+        mut attributes: impl Iterator<Item = &'a KeyValue>,
+        service: Option<&str>,
+    ) -> bool {
+        let Some(service) = service else {
+            return true;
+        };
+        match attributes.find(|kv| kv.key.as_str() == SERVICE_NAME) {
+            Some(kv) => kv.value.as_str() == service,
+            None => true,
+        }
+    }
+
+    fn encode_data<T: Copy + Display>(
+        out: &mut String,
+        name: &str,
+        description: &str,
+        data: &MetricData<T>,
+        service: Option<&str>,
+    ) {
+        match data {
+            MetricData::Gauge(gauge) => {
+                let points: Vec<_> = gauge
+                    .data_points()
+                    .filter(|point| belongs(point.attributes(), service))
+                    .collect();
+                if points.is_empty() {
+                    return;
+                }
+                let _ = writeln!(out, "# HELP {name} {description}");
+                let _ = writeln!(out, "# TYPE {name} gauge");
+                for point in points {
+                    let _ = writeln!(
+                        out,
+                        "{name}{} {}",
+                        labels(point.attributes(), None),
+                        point.value()
+                    );
+                }
+            }
+            MetricData::Sum(sum) => {
+                let (name, kind) = if sum.is_monotonic() {
+                    (format!("{name}_total"), "counter")
+                } else {
+                    (name.to_string(), "gauge")
+                };
+                let points: Vec<_> = sum
+                    .data_points()
+                    .filter(|point| belongs(point.attributes(), service))
+                    .collect();
+                if points.is_empty() {
+                    return;
+                }
+                let _ = writeln!(out, "# HELP {name} {description}");
+                let _ = writeln!(out, "# TYPE {name} {kind}");
+                for point in points {
+                    let _ = writeln!(
+                        out,
+                        "{name}{} {}",
+                        labels(point.attributes(), None),
+                        point.value()
+                    );
+                }
+            }
+            MetricData::Histogram(histogram) => {
+                let points: Vec<_> = histogram
+                    .data_points()
+                    .filter(|point| belongs(point.attributes(), service))
+                    .collect();
+                if points.is_empty() {
+                    return;
+                }
+                let _ = writeln!(out, "# HELP {name} {description}");
+                let _ = writeln!(out, "# TYPE {name} histogram");
+                for point in points {
+                    let mut cumulative = 0;
+                    for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+                        cumulative += count;
+                        let bucket = labels(point.attributes(), Some(("le", bound.to_string())));
+                        let _ = writeln!(out, "{name}_bucket{bucket} {cumulative}");
+                    }
+                    let bucket = labels(point.attributes(), Some(("le", "+Inf".to_string())));
+                    let _ = writeln!(out, "{name}_bucket{bucket} {}", point.count());
+                    let series = labels(point.attributes(), None);
+                    let _ = writeln!(out, "{name}_sum{series} {}", point.sum());
+                    let _ = writeln!(out, "{name}_count{series} {}", point.count());
+                }
+            }
+            // Not created by any Sky Tracer instrument
+            MetricData::ExponentialHistogram(_) => {}
+        }
+    }
+
+    fn encode_prometheus(metrics: &ResourceMetrics, service: Option<&str>) -> String {
+        let mut out = String::new();
+        for scope in metrics.scope_metrics() {
+            for metric in scope.metrics() {
+                let name = prometheus_name(metric.name(), metric.unit());
+                let description = metric.description();
+                match metric.data() {
+                    AggregatedMetrics::F64(data) => {
+                        encode_data(&mut out, &name, description, data, service)
+                    }
+                    AggregatedMetrics::U64(data) => {
+                        encode_data(&mut out, &name, description, data, service)
+                    }
+                    AggregatedMetrics::I64(data) => {
+                        encode_data(&mut out, &name, description, data, service)
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_prometheus_name_carries_unit() {
+            assert_eq!(
+                prometheus_name("http.server.request.duration", "s"),
+                "http_server_request_duration_seconds"
+            );
+            assert_eq!(
+                prometheus_name("sky_tracer.flights.airborne", "{flight}"),
+                "sky_tracer_flights_airborne"
+            );
+        }
+
+        #[test]
+        fn test_label_values_are_escaped() {
+            let attributes = [KeyValue::new("http.route", "/a\"b")];
+            assert_eq!(
+                labels(attributes.iter(), Some(("le", "0.5".to_string()))),
+                r#"{http_route="/a\"b",le="0.5"}"#
+            );
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+pub use server::{current_service, in_service, metrics_routes, observe, record_metrics, Metrics};
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_scrape_reports_requests_per_route_template() {
+        let gauge = Metrics::global().service_gauge(
+            "test-flights",
+            "sky_tracer.test.gauge",
+            "{flight}",
+            "Test gauge",
+        );
+        let service = |name: &'static str| {
+            let gauge = gauge.clone();
+            observe(
+                name,
+                Router::new().route("/flights/{flight_number}", get(|| async { "ok" })),
+                metrics_routes(move |_: ()| {
+                    let gauge = gauge.clone();
+                    async move { gauge.store(7, std::sync::atomic::Ordering::Relaxed) }
+                }),
+            )
+            .with_state(())
+        };
+        let app = service("test-flights");
+        let other = service("test-other");
+
+        for app in [&app, &other] {
+            let response = app
+                .clone()
+                .oneshot(Request::get("/flights/LH400").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = app
+            .oneshot(Request::get(METRICS_PATH).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            PROMETHEUS_TEXT
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(text.contains("# TYPE http_server_request_duration_seconds histogram"));
+        assert!(text.contains(
+            r#"http_server_request_duration_seconds_count{http_request_method="GET",http_response_status_code="200",http_route="/flights/{flight_number}",service_name="test-flights"} 1"#
+        ));
+        assert!(text.contains(r#"sky_tracer_test_gauge{service_name="test-flights"} 7"#));
+        // Services sharing the process do not report each other
+        assert!(!text.contains("test-other"));
+        assert!(!text.contains("LH400"));
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn test_client_calls_are_recorded_per_route_template() {
+        use crate::client::FlightsClient;
+
+        let upstream = Router::new().route(
+            crate::protocol::FLIGHTS_DETAIL_API_PATH,
+            get(|| async { StatusCode::NOT_FOUND }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+        let metrics = Metrics::global();
+        let flights = FlightsClient::new(format!("http://{address}"));
+        assert!(flights.get("LH400").await.is_err());
+
+        let text = metrics.render();
+        assert!(text.contains(&format!(
+            r#"http_client_request_duration_seconds_count{{http_request_method="GET",http_response_status_code="404",http_route="/api/v1/flights/{{flight_number}}",server_address="127.0.0.1",server_port="{}"}} 1"#,
+            address.port()
+        )));
+
+        // Calls made while a service handles a request belong to it
+        assert!(in_service("test-babel", flights.get("LH400"))
+            .await
+            .is_err());
+        let text = metrics.render_service("test-babel");
+        assert!(text.contains(&format!(
+            r#"http_client_request_duration_seconds_count{{http_request_method="GET",http_response_status_code="404",http_route="/api/v1/flights/{{flight_number}}",server_address="127.0.0.1",server_port="{}",service_name="test-babel"}} 1"#,
+            address.port()
+        )));
+    }
+}
@@ -1,11 +1,23 @@
 pub mod airports;
 pub mod flights;
+pub mod health;
+pub mod metrics;
+pub mod problem;
 pub mod satellite;
 
 /// API base paths (const str)
 pub const AIRPORTS_API_PATH: &str = "/api/v1/airports";
+pub const AIRPORT_API_PATH: &str = "/api/v1/airports/{code}";
 pub const AIRPORTS_SEARCH_API_PATH: &str = "/api/v1/airports/search";
+pub const AIRPORTS_NEARBY_API_PATH: &str = "/api/v1/airports/nearby";
+pub const AIRPORTS_AUTOCOMPLETE_API_PATH: &str = "/api/v1/airports/autocomplete";
+pub const AIRPORTS_RELOAD_API_PATH: &str = "/api/v1/airports/reload";
+pub const AIRPORTS_OVERLAY_API_PATH: &str = "/api/v1/airports/overlay";
+pub const AIRPORTS_RUNWAYS_API_PATH: &str = "/api/v1/airports/{code}/runways";
+pub const AIRPORTS_FREQUENCIES_API_PATH: &str = "/api/v1/airports/{code}/frequencies";
+pub const AIRLINES_API_PATH: &str = "/api/v1/airlines";
 pub const FLIGHTS_API_PATH: &str = "/api/v1/flights";
+pub const FLIGHTS_DETAIL_API_PATH: &str = "/api/v1/flights/{flight_number}";
 pub const FLIGHTS_POSITION_API_PATH: &str = "/api/v1/flights/{flight_number}/position";
 pub const SATELLITES_API_PATH: &str = "/api/v1/satellites";
 pub const SATELLITES_POSITION_API_PATH: &str = "/api/v1/satellites/position";
@@ -0,0 +1,377 @@
+//! Problem details for HTTP APIs ([RFC 7807]), the error body of every
+//! Sky Tracer service.
+//!
+//! Besides the standard members a problem carries the trace id of the failed
+//! request, a machine readable `code` and, for rejected requests, the
+//! problems of single fields.
+//!
+//! [RFC 7807]: https://www.rfc-editor.org/rfc/rfc7807
+
+use super::flights::FieldError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Media type of problem details
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Problem type of problems without semantics beyond their status
+pub const ABOUT_BLANK: &str = "about:blank";
+
+/// Prefix of the problem types derived from problem codes
+pub const PROBLEM_TYPE_PREFIX: &str = "urn:sky-tracer:problem:";
+
+fn about_blank() -> String {
+    ABOUT_BLANK.to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({
+    "type": "urn:sky-tracer:problem:not-found",
+    "title": "Flight not found",
+    "status": 404,
+    "detail": "Flight LH400 does not exist",
+    "instance": "/api/v1/flights/LH400",
+    "trace_id": "4bf92f3577b34da6a3ce929d0e0e4736",
+    "code": "NOT_FOUND"
+}))]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type
+    #[serde(rename = "type", default = "about_blank")]
+    pub problem_type: String,
+    /// Short summary of the problem type
+    pub title: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Explanation of this occurrence of the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Path of the request that failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Trace id of the failed request, for looking it up in Jaeger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Machine readable problem code, e.g. "NOT_FOUND"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Per-field problems of a rejected request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+}
+
+impl ProblemDetails {
+    pub fn new(status: u16, title: impl Into<String>) -> Self {
+        Self {
+            problem_type: about_blank(),
+            title: title.into(),
+            status,
+            detail: None,
+            instance: None,
+            trace_id: None,
+            code: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Set the problem code and the problem type derived from it, e.g.
+    /// `urn:sky-tracer:problem:not-found` for `NOT_FOUND`
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.problem_type = format!(
+            "{}{}",
+            PROBLEM_TYPE_PREFIX,
+            code.to_lowercase().replace('_', "-")
+        );
+        self.code = Some(code.to_string());
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    pub fn with_fields(mut self, fields: Vec<FieldError>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// The detail of this occurrence, the title if there is none
+    pub fn message(&self) -> &str {
+        self.detail.as_deref().unwrap_or(&self.title)
+    }
+}
+
+/// The message followed by the field problems, if any
+impl fmt::Display for ProblemDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+        if !self.fields.is_empty() {
+            let fields: Vec<String> = self
+                .fields
+                .iter()
+                .map(|field| format!("{}: {}", field.field, field.message))
+                .collect();
+            write!(f, " ({})", fields.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+mod server {
+    use super::{ProblemDetails, PROBLEM_JSON};
+    use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+    use axum::extract::{FromRequest, FromRequestParts, Request};
+    use axum::http::{header, HeaderValue, StatusCode};
+    use axum::middleware::Next;
+    use axum::response::{IntoResponse, Response};
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    /// Trace id of the current span, if it is part of a trace
+    fn current_trace_id() -> Option<String> {
+        let context = tracing::Span::current().context();
+        let span = context.span();
+        let span_context = span.span_context();
+        span_context
+            .is_valid()
+            .then(|| span_context.trace_id().to_string())
+    }
+
+    /// Answer with the problem as `application/problem+json`, adding the
+    /// trace id of the current request
+    impl IntoResponse for ProblemDetails {
+        fn into_response(mut self) -> Response {
+            if self.trace_id.is_none() {
+                self.trace_id = current_trace_id();
+            }
+            let status =
+                StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+            let mut response = match serde_json::to_vec(&self) {
+                Ok(body) => (status, body).into_response(),
+                Err(e) => {
+                    tracing::error!(error = %e, "Cannot serialize problem details");
+                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                }
+            };
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+            response.extensions_mut().insert(self);
+            response
+        }
+    }
+
+    /// Unreadable request bodies keep the status axum rejects them with,
+    /// e.g. `415` without a JSON content type or `422` for missing fields
+    impl From<JsonRejection> for ProblemDetails {
+        fn from(rejection: JsonRejection) -> Self {
+            ProblemDetails::new(rejection.status().as_u16(), "Invalid request body")
+                .with_code("INVALID_BODY")
+                .with_detail(rejection.body_text())
+        }
+    }
+
+    impl From<QueryRejection> for ProblemDetails {
+        fn from(rejection: QueryRejection) -> Self {
+            ProblemDetails::new(rejection.status().as_u16(), "Invalid parameter")
+                .with_code("INVALID_PARAMETER")
+                .with_detail(rejection.body_text())
+        }
+    }
+
+    impl From<PathRejection> for ProblemDetails {
+        fn from(rejection: PathRejection) -> Self {
+            ProblemDetails::new(rejection.status().as_u16(), "Invalid parameter")
+                .with_code("INVALID_PARAMETER")
+                .with_detail(rejection.body_text())
+        }
+    }
+
+    /// [`axum::Json`] rejecting unreadable bodies with problem details
+    #[derive(Debug, Clone, Copy, Default, FromRequest)]
+    #[from_request(via(axum::Json), rejection(ProblemDetails))]
+    pub struct ProblemJson<T>(pub T);
+
+    /// [`axum::extract::Query`] rejecting invalid parameters with problem details
+    #[derive(Debug, Clone, Copy, Default, FromRequestParts)]
+    #[from_request(via(axum::extract::Query), rejection(ProblemDetails))]
+    pub struct ProblemQuery<T>(pub T);
+
+    /// [`axum::extract::Path`] rejecting invalid segments with problem details
+    #[derive(Debug, Clone, Copy, Default, FromRequestParts)]
+    #[from_request(via(axum::extract::Path), rejection(ProblemDetails))]
+    pub struct ProblemPath<T>(pub T);
+
+    /// Middleware setting the `instance` of problem answers to the request path
+    ///
+    /// ```no_run
+    /// # let router: axum::Router = axum::Router::new();
+    /// let app = router.layer(axum::middleware::from_fn(sky_tracer::protocol::problem::problem_instance));
+    /// ```
+    pub async fn problem_instance(request: Request, next: Next) -> Response {
+        let path = request.uri().path().to_string();
+        let mut response = next.run(request).await;
+
+        match response.extensions_mut().remove::<ProblemDetails>() {
+            Some(problem) if problem.instance.is_none() => {
+                let (mut parts, _) = response.into_parts();
+                parts.headers.remove(header::CONTENT_LENGTH);
+                let problem = problem.with_instance(path);
+                let mut response = problem.into_response();
+                *response.headers_mut() = parts.headers;
+                response
+            }
+            _ => response,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+pub use server::{problem_instance, ProblemJson, ProblemPath, ProblemQuery};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_serializes_rfc_7807_members() {
+        let problem = ProblemDetails::new(404, "Flight not found")
+            .with_code("NOT_FOUND")
+            .with_detail("Flight LH400 does not exist");
+
+        let json = serde_json::to_value(&problem).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "urn:sky-tracer:problem:not-found",
+                "title": "Flight not found",
+                "status": 404,
+                "detail": "Flight LH400 does not exist",
+                "code": "NOT_FOUND"
+            })
+        );
+    }
+
+    #[test]
+    fn test_minimal_problem_is_decoded() {
+        let problem: ProblemDetails =
+            serde_json::from_str(r#"{"title":"Not Found","status":404}"#).unwrap();
+
+        assert_eq!(problem.problem_type, ABOUT_BLANK);
+        assert_eq!(problem.message(), "Not Found");
+        assert!(problem.fields.is_empty());
+    }
+
+    #[test]
+    fn test_display_lists_field_problems() {
+        let problem =
+            ProblemDetails::new(400, "Invalid flight").with_fields(vec![FieldError::new(
+                "arrival",
+                "UNKNOWN_AIRPORT",
+                "unknown airport",
+            )]);
+
+        assert_eq!(
+            problem.to_string(),
+            "Invalid flight (arrival: unknown airport)"
+        );
+    }
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_problem_response_gets_instance() {
+        use axum::body::Body;
+        use axum::http::{header, Request, StatusCode};
+        use axum::routing::get;
+        use axum::Router;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route(
+                "/api/v1/flights/{flight_number}",
+                get(|| async {
+                    ProblemDetails::new(404, "Flight not found").with_code("NOT_FOUND")
+                }),
+            )
+            .layer(axum::middleware::from_fn(problem_instance));
+
+        let response = app
+            .oneshot(
+                Request::get("/api/v1/flights/LH400")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.instance.as_deref(), Some("/api/v1/flights/LH400"));
+        assert_eq!(problem.code.as_deref(), Some("NOT_FOUND"));
+    }
+
+    #[cfg(feature = "server")]
+    #[tokio::test]
+    async fn test_rejections_are_problems() {
+        use axum::body::Body;
+        use axum::http::{header, Request, StatusCode};
+        use axum::routing::post;
+        use axum::Router;
+        use std::collections::HashMap;
+        use tower::ServiceExt;
+
+        let app = Router::new().route(
+            "/flights/{count}",
+            post(
+                |ProblemPath(_): ProblemPath<u32>,
+                 ProblemQuery(_): ProblemQuery<HashMap<String, u32>>,
+                 ProblemJson(_): ProblemJson<HashMap<String, u32>>| async {},
+            ),
+        );
+        let problem = |uri: &str, body: &str| {
+            let request = Request::post(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<ProblemDetails>(&body).unwrap()
+            }
+        };
+
+        let path = problem("/flights/many", "{}").await;
+        assert_eq!(path.status, StatusCode::BAD_REQUEST.as_u16());
+        assert_eq!(path.code.as_deref(), Some("INVALID_PARAMETER"));
+
+        let query = problem("/flights/1?limit=all", "{}").await;
+        assert_eq!(query.status, StatusCode::BAD_REQUEST.as_u16());
+        assert_eq!(query.code.as_deref(), Some("INVALID_PARAMETER"));
+
+        let body = problem("/flights/1", "{\"limit\": \"all\"}").await;
+        assert_eq!(body.status, StatusCode::UNPROCESSABLE_ENTITY.as_u16());
+        assert_eq!(body.code.as_deref(), Some("INVALID_BODY"));
+        assert!(body.detail.is_some());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{openapi, routes, services::BabelService};
-use axum::{routing::get, Router};
-use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
+use axum::{middleware, routing::get, Router};
+use sky_tracer::clock::follow_caller_time;
+use sky_tracer::protocol::health::health_routes;
+use sky_tracer::protocol::metrics::{metrics_routes, observe};
+use sky_tracer::protocol::problem::problem_instance;
 use sky_tracer::protocol::{BABEL_AIRPORT_API_PATH, BABEL_POSITION_API_PATH};
 use tower_http::cors::{Any, CorsLayer};
 
@@ -9,16 +12,58 @@ pub fn app(babel_service: BabelService) -> Router {
         .route(BABEL_AIRPORT_API_PATH, get(routes::get_flights_by_airport))
         .route(BABEL_POSITION_API_PATH, get(routes::get_flight_position));
 
-    Router::new()
+    let app = Router::new()
         .merge(openapi::routes()) // Now works with generic state
         .merge(api_routes) // This has the BabelService state
-        .layer(OtelInResponseLayer::default())
-        .layer(OtelAxumLayer::default())
+        .layer(middleware::from_fn_with_state(
+            babel_service.clock().clone(),
+            follow_caller_time,
+        ))
+        .layer(middleware::from_fn(problem_instance))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
                 .allow_headers(Any),
-        )
-        .with_state(babel_service) // Apply state at the end
+        );
+    let probes = health_routes("tower-of-babel", |service: BabelService| async move {
+        service.readiness().await
+    })
+    .merge(metrics_routes(|_: BabelService| async {}));
+    observe("tower-of-babel", app, probes).with_state(babel_service) // Apply state at the end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+    };
+    use sky_tracer::client::FlightsClient;
+    use sky_tracer::protocol::problem::{ProblemDetails, PROBLEM_JSON};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_invalid_airport_code_is_a_problem() {
+        let app = app(BabelService::new(FlightsClient::new("http://127.0.0.1:9")));
+
+        let response = app
+            .oneshot(
+                Request::get("/api/v1/babel/%FF")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()[CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code.as_deref(), Some("INVALID_PARAMETER"));
+        assert_eq!(problem.instance.as_deref(), Some("/api/v1/babel/%FF"));
+    }
 }
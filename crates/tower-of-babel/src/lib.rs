@@ -1,10 +1,7 @@
 pub mod app;
-pub mod client;
 pub mod openapi;
 pub mod routes;
 pub mod services;
 
-pub use client::create_client;
-
 // Re-export for backward compatibility
 pub use services::BabelService;
@@ -1,10 +1,13 @@
+use sky_tracer::client::{ClientConfig, FlightsClient};
 use std::env;
-use tower_of_babel::{app::app, create_client, services::BabelService};
+use tower_of_babel::{app::app, services::BabelService};
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
     let _guard = init_tracing_opentelemetry::tracing_subscriber_ext::init_subscribers()?;
+    let clock = sky_tracer::clock::init_from_env()?;
+    info!(clock = ?clock, "Configured clock");
 
     // Get service configuration
     let service_port = env::var("PORT")
@@ -14,14 +17,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
 
     let service_name = env::var("SERVICE_NAME").unwrap_or_else(|_| "tower-of-babel".to_string());
 
-    let flight_controller_base_url = env::var("FLIGHT_CONTROLLER_BASE_URL")
-        .unwrap_or_else(|_| "http://flight-controller:3001".to_string());
+    let flights = FlightsClient::with_config(ClientConfig::from_env(
+        "FLIGHT_CONTROLLER_BASE_URL",
+        "http://flight-controller:3001",
+    ));
 
     info!("Starting {} service on port {}", service_name, service_port);
-    info!(flight_controller_base_url = %flight_controller_base_url, "Configured Flight Controller base URL");
+    info!(flight_controller_base_url = %flights.base_url(), "Configured Flight Controller base URL");
 
-    let client = create_client();
-    let babel_service = BabelService::new(client, flight_controller_base_url);
+    let babel_service = BabelService::new(flights);
     let app = app(babel_service);
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", service_port)).await?;
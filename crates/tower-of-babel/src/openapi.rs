@@ -1,5 +1,6 @@
 use axum::{response::Redirect, routing::get, Router};
-use sky_tracer::protocol::flights::ErrorResponse;
+use sky_tracer::protocol::flights::FieldError;
+use sky_tracer::protocol::problem::ProblemDetails;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -13,7 +14,8 @@ use utoipa_swagger_ui::SwaggerUi;
         schemas(
             sky_tracer::protocol::flights::FlightResponse,
             sky_tracer::protocol::flights::FlightPositionResponse,
-            ErrorResponse
+            ProblemDetails,
+            FieldError
         )
     ),
     tags(
@@ -1,102 +1,119 @@
-use chrono::Utc;
-use reqwest_middleware::ClientWithMiddleware;
-use sky_tracer::protocol::{
-    flights::{FlightPositionResponse, FlightResponse},
-    FLIGHTS_API_PATH, FLIGHTS_POSITION_API_PATH,
-};
+use sky_tracer::client::{ClientError, FlightsClient, NO_FUTURE_FLIGHTS};
+use sky_tracer::clock::SharedClock;
+use sky_tracer::protocol::flights::{FlightPositionResponse, FlightResponse, ListFlightsRequest};
+use sky_tracer::protocol::health::{check_dependency, DependencyHealth};
+use sky_tracer::protocol::problem::ProblemDetails;
 use thiserror::Error;
 use tracing::{debug, error, info, instrument, warn};
 
 #[derive(Error, Debug)]
 pub enum BabelServiceError {
-    #[error("HTTP request failed: {0}")]
-    HttpError(#[from] reqwest_middleware::Error),
-    #[error("Failed to parse response: {0}")]
-    ParseError(#[from] reqwest::Error),
-    #[error("Service unavailable: {0}")]
-    ServiceUnavailable(String),
+    #[error("Flight controller request failed: {0}")]
+    Client(#[from] ClientError),
     #[error("Not found: {0}")]
     NotFound(String),
     #[error("No future flights found for airport: {0}")]
     NoFutureFlights(String),
 }
 
+impl From<BabelServiceError> for ProblemDetails {
+    fn from(error: BabelServiceError) -> Self {
+        match error {
+            BabelServiceError::NotFound(message) => ProblemDetails::new(404, "Not found")
+                .with_code("NOT_FOUND")
+                .with_detail(message),
+            BabelServiceError::NoFutureFlights(airport) => {
+                ProblemDetails::new(404, "No future flights")
+                    .with_code(NO_FUTURE_FLIGHTS)
+                    .with_detail(format!("No future flights found for airport {}", airport))
+            }
+            BabelServiceError::Client(error) => error.to_problem(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct BabelService {
-    client: ClientWithMiddleware,
-    flight_controller_base_url: String,
+    flights: FlightsClient,
+    clock: SharedClock,
 }
 
 impl BabelService {
-    pub fn new(client: ClientWithMiddleware, flight_controller_base_url: String) -> Self {
+    pub fn new(flights: FlightsClient) -> Self {
         Self {
-            client,
-            flight_controller_base_url,
+            flights,
+            clock: SharedClock::default(),
         }
     }
 
+    /// Tell future flights from past ones with the given clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Clock of the service
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Health of the flight controller the flights are translated from
+    pub async fn readiness(&self) -> Vec<DependencyHealth> {
+        vec![check_dependency("flight-controller", self.flights.live())
+            .await
+            .with_circuit(self.flights.circuit_state())]
+    }
+
     /// List flights by airport with future arrival filtering
     #[instrument(skip(self), fields(airport_code = %airport_code))]
     pub async fn list_flights_by_airport(
         &self,
         airport_code: &str,
     ) -> Result<Vec<FlightResponse>, BabelServiceError> {
-        let url = format!(
-            "{}{}?departure={}",
-            self.flight_controller_base_url, FLIGHTS_API_PATH, airport_code
+        debug!(airport = %airport_code, "Fetching flights");
+
+        let request = ListFlightsRequest {
+            departure: Some(airport_code.to_string()),
+            ..Default::default()
+        };
+        let all_flights = match self.flights.list(&request).await {
+            Ok(flights) => flights,
+            Err(e) if e.is_not_found() => {
+                warn!(airport = %airport_code, "No flights found");
+                return Err(BabelServiceError::NotFound(format!(
+                    "No flights found for airport {}",
+                    airport_code
+                )));
+            }
+            Err(e) => {
+                error!(error = %e, airport = %airport_code, "Error from flight-controller");
+                return Err(e.into());
+            }
+        };
+
+        // Filter flights with future arrival times
+        let now = self.clock.now();
+        let future_flights: Vec<FlightResponse> = all_flights
+            .into_iter()
+            .filter(|flight| {
+                flight
+                    .arrival_time
+                    .map(|arrival| arrival > now)
+                    .unwrap_or(true) // Include flights with no arrival time
+            })
+            .collect();
+
+        info!(
+            total_flights = future_flights.len(),
+            airport = %airport_code,
+            "Successfully retrieved future flights"
         );
 
-        debug!(url = %url, airport = %airport_code, "Fetching flights");
-
-        let response = self.client.get(&url).send().await?;
-        let status = response.status();
-        debug!(status = %status, "Received response");
-
-        if response.status().is_success() {
-            let all_flights: Vec<FlightResponse> = response.json().await?;
-
-            // Filter flights with future arrival times
-            let now = Utc::now();
-            let future_flights: Vec<FlightResponse> = all_flights
-                .into_iter()
-                .filter(|flight| {
-                    flight
-                        .arrival_time
-                        .map(|arrival| arrival > now)
-                        .unwrap_or(true) // Include flights with no arrival time
-                })
-                .collect();
-
-            info!(
-                total_flights = future_flights.len(),
-                airport = %airport_code,
-                "Successfully retrieved future flights"
-            );
-
-            if future_flights.is_empty() {
-                warn!(airport = %airport_code, "No future flights found");
-                Err(BabelServiceError::NoFutureFlights(airport_code.to_string()))
-            } else {
-                Ok(future_flights)
-            }
-        } else if response.status().as_u16() == 404 {
-            warn!(airport = %airport_code, "No flights found");
-            Err(BabelServiceError::NotFound(format!(
-                "No flights found for airport {}",
-                airport_code
-            )))
+        if future_flights.is_empty() {
+            warn!(airport = %airport_code, "No future flights found");
+            Err(BabelServiceError::NoFutureFlights(airport_code.to_string()))
         } else {
-            let error_message = response.text().await.unwrap_or_default();
-            error!(
-                status = %status,
-                error = %error_message,
-                airport = %airport_code,
-                "Error from flight-controller"
-            );
-            Err(BabelServiceError::ServiceUnavailable(format!(
-                "Flight controller returned an error: {}",
-                error_message
-            )))
+            Ok(future_flights)
         }
     }
 
@@ -106,51 +123,33 @@ impl BabelService {
         &self,
         flight_number: &str,
     ) -> Result<FlightPositionResponse, BabelServiceError> {
-        let url = format!(
-            "{}{}",
-            self.flight_controller_base_url,
-            FLIGHTS_POSITION_API_PATH.replace("{flight_number}", flight_number)
-        );
-
-        debug!(
-            url = %url,
-            flight_number = %flight_number,
-            "Fetching flight position"
-        );
-
-        let response = self.client.get(&url).send().await?;
-        let status = response.status();
-        debug!(status = %status, "Received response");
-
-        if response.status().is_success() {
-            let position = response.json::<FlightPositionResponse>().await?;
-
-            info!(
-                flight_number = %flight_number,
-                latitude = position.latitude,
-                longitude = position.longitude,
-                "Successfully retrieved flight position"
-            );
-
-            Ok(position)
-        } else if response.status().as_u16() == 404 {
-            warn!(flight_number = %flight_number, "Flight not found");
-            Err(BabelServiceError::NotFound(format!(
-                "Flight not found: {}",
-                flight_number
-            )))
-        } else {
-            let error_message = response.text().await.unwrap_or_default();
-            error!(
-                status = %status,
-                error = %error_message,
-                flight_number = %flight_number,
-                "Error from flight-controller"
-            );
-            Err(BabelServiceError::ServiceUnavailable(format!(
-                "Flight controller returned an error: {}",
-                error_message
-            )))
+        debug!(flight_number = %flight_number, "Fetching flight position");
+
+        match self.flights.position(flight_number).await {
+            Ok(position) => {
+                info!(
+                    flight_number = %flight_number,
+                    latitude = position.latitude,
+                    longitude = position.longitude,
+                    "Successfully retrieved flight position"
+                );
+                Ok(position)
+            }
+            Err(e) if e.is_not_found() => {
+                warn!(flight_number = %flight_number, "Flight not found");
+                Err(BabelServiceError::NotFound(format!(
+                    "Flight not found: {}",
+                    flight_number
+                )))
+            }
+            Err(e) => {
+                error!(
+                    error = %e,
+                    flight_number = %flight_number,
+                    "Error from flight-controller"
+                );
+                Err(e.into())
+            }
         }
     }
 }
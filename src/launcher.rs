@@ -0,0 +1,525 @@
+//! All services in one process for local development.
+//!
+//! Every service listens on its own port, as it does in Docker Compose, and
+//! calls the others through their base URLs. A gateway port mounts all of
+//! them under the path prefixes Traefik routes, so the demo works without
+//! containers.
+
+use airport_anywhere::{
+    admin::AdminToken,
+    models::{overlay_path_from_env, AirportSource},
+    services::AirportService,
+    watcher,
+};
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Router,
+};
+use delay_orama::services::DelayService;
+use flight_controller::services::{FlightService, HttpAirportDirectory};
+use orbital_beacon::repository::{self, SatelliteSeed, SATELLITE_SEED_PATH_ENV};
+use orbital_beacon::services::SatelliteService;
+use sky_nexus::services::{NexusState, Upstreams};
+use sky_tracer::client::{
+    AirportsClient, BabelClient, ClientConfig, FlightsClient, SatellitesClient,
+};
+use sky_tracer::clock::SharedClock;
+use std::future::IntoFuture;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tower::ServiceExt;
+use tower_of_babel::services::BabelService;
+use tracing::{info, warn};
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Services started by the launcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    Airports,
+    Flights,
+    Satellites,
+    Babel,
+    Delays,
+    Nexus,
+}
+
+impl Service {
+    pub const ALL: [Service; 6] = [
+        Service::Airports,
+        Service::Flights,
+        Service::Satellites,
+        Service::Babel,
+        Service::Delays,
+        Service::Nexus,
+    ];
+
+    /// Name used for the path prefix, the docs and the port variable
+    pub fn name(self) -> &'static str {
+        match self {
+            Service::Airports => "airports",
+            Service::Flights => "flights",
+            Service::Satellites => "satellites",
+            Service::Babel => "babel",
+            Service::Delays => "delays",
+            Service::Nexus => "nexus",
+        }
+    }
+
+    /// Port the service has in Docker Compose
+    pub fn default_port(self) -> u16 {
+        match self {
+            Service::Airports => 3000,
+            Service::Flights => 3001,
+            Service::Satellites => 3002,
+            Service::Babel => 3003,
+            Service::Delays => 3004,
+            Service::Nexus => 8080,
+        }
+    }
+
+    /// Variable overriding the port, e.g. `FLIGHTS_PORT`
+    pub fn port_env(self) -> String {
+        format!("{}_PORT", self.name().to_uppercase())
+    }
+
+    /// Prefix stripped before requests reach the service
+    pub fn path_prefix(self) -> String {
+        format!("/{}", self.name())
+    }
+
+    /// Prefixes passed on unchanged, as Traefik routes the APIs and docs
+    fn unstripped_prefixes(self) -> Vec<String> {
+        let api: &[&str] = match self {
+            Service::Airports => &["/api/v1/airports"],
+            Service::Flights => &["/api/v1/flights", "/api/v1/airlines"],
+            Service::Satellites => &["/api/v1/satellites"],
+            Service::Babel => &["/api/v1/babel"],
+            Service::Delays => &[],
+            Service::Nexus => &["/api/v1/nexus", "/mcp"],
+        };
+        api.iter()
+            .map(|prefix| prefix.to_string())
+            .chain([format!("{}/docs", self.path_prefix())])
+            .collect()
+    }
+}
+
+/// Ports of the gateway and the services, and the clock they share
+#[derive(Debug, Clone)]
+pub struct LauncherConfig {
+    pub bind_address: IpAddr,
+    pub gateway_port: u16,
+    ports: Vec<(Service, u16)>,
+    /// Clock of all services, the one configured by `SIMULATION_SPEED`,
+    /// `SIMULATION_START` and `SIMULATION_EPOCH` if `None`
+    pub clock: Option<SharedClock>,
+}
+
+impl Default for LauncherConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            gateway_port: 8000,
+            ports: Service::ALL
+                .iter()
+                .map(|service| (*service, service.default_port()))
+                .collect(),
+            clock: None,
+        }
+    }
+}
+
+impl LauncherConfig {
+    /// Configuration from `BIND_ADDRESS`, `PORT` for the gateway and
+    /// `<SERVICE>_PORT` per service, defaulting to the Docker Compose ports
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(address) = env_parsed("BIND_ADDRESS") {
+            config.bind_address = address;
+        }
+        if let Some(port) = env_parsed("PORT") {
+            config.gateway_port = port;
+        }
+        for service in Service::ALL {
+            if let Some(port) = env_parsed(&service.port_env()) {
+                config = config.with_port(service, port);
+            }
+        }
+        config
+    }
+
+    pub fn with_port(mut self, service: Service, port: u16) -> Self {
+        for (configured, configured_port) in &mut self.ports {
+            if *configured == service {
+                *configured_port = port;
+            }
+        }
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub fn port(&self, service: Service) -> u16 {
+        self.ports
+            .iter()
+            .find(|(configured, _)| *configured == service)
+            .map_or(service.default_port(), |(_, port)| *port)
+    }
+
+    /// All ports picked by the operating system, for tests
+    pub fn ephemeral() -> Self {
+        Self {
+            gateway_port: 0,
+            ports: Service::ALL.iter().map(|service| (*service, 0)).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// URL other services call `service` at
+    pub fn base_url(&self, service: Service) -> String {
+        base_url(SocketAddr::new(self.bind_address, self.port(service)))
+    }
+}
+
+fn env_parsed<T: std::str::FromStr>(var: &str) -> Option<T> {
+    let value = std::env::var(var).ok()?;
+    match value.parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            warn!(var, value, "Ignoring invalid launcher setting");
+            None
+        }
+    }
+}
+
+fn base_url(address: SocketAddr) -> String {
+    let ip = match address.ip() {
+        ip if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        ip => ip,
+    };
+    format!("http://{}", SocketAddr::new(ip, address.port()))
+}
+
+/// Running services
+pub struct Launched {
+    pub gateway_address: SocketAddr,
+    addresses: Vec<(Service, SocketAddr)>,
+    servers: JoinSet<std::io::Result<()>>,
+    /// Reloads airport data on file changes while the services run
+    _watcher: Option<watcher::DataWatcher>,
+}
+
+impl Launched {
+    pub fn address(&self, service: Service) -> SocketAddr {
+        self.addresses
+            .iter()
+            .find(|(started, _)| *started == service)
+            .map(|(_, address)| *address)
+            .expect("all services are started")
+    }
+
+    pub fn base_url(&self, service: Service) -> String {
+        base_url(self.address(service))
+    }
+
+    pub fn gateway_url(&self) -> String {
+        base_url(self.gateway_address)
+    }
+
+    /// Serve until a server fails
+    pub async fn wait(mut self) -> Result<(), Error> {
+        match self.servers.join_next().await {
+            Some(result) => Ok(result??),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Bind all ports, build the services and serve them in the background
+pub async fn launch(config: &LauncherConfig) -> Result<Launched, Error> {
+    let clock = match &config.clock {
+        Some(clock) => clock.clone(),
+        None => sky_tracer::clock::init_from_env()?.clone(),
+    };
+    info!(clock = ?clock, "Configured clock");
+
+    let mut listeners = Vec::new();
+    for service in Service::ALL {
+        let address = SocketAddr::new(config.bind_address, config.port(service));
+        listeners.push((service, TcpListener::bind(address).await?));
+    }
+    let gateway_listener =
+        TcpListener::bind(SocketAddr::new(config.bind_address, config.gateway_port)).await?;
+
+    let addresses = listeners
+        .iter()
+        .map(|(service, listener)| Ok((*service, listener.local_addr()?)))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let url = |service: Service| {
+        base_url(
+            addresses
+                .iter()
+                .find(|(started, _)| *started == service)
+                .map(|(_, address)| *address)
+                .expect("all services are bound"),
+        )
+    };
+
+    // Clients of the services send the time of the shared clock along
+    let client = |service, prefix: &str| {
+        ClientConfig::new(url(service))
+            .with_env_overrides(prefix)
+            .with_clock(clock.clone())
+    };
+
+    let (airports_app, watcher) = airports_app()?;
+    let satellites_app = satellites_app(url(Service::Airports), &clock).await?;
+    let flights_app = flights_app(
+        url(Service::Airports),
+        client(Service::Satellites, "ORBITAL_BEACON"),
+        &clock,
+    )?;
+    let babel_app = tower_of_babel::app::app(
+        BabelService::new(FlightsClient::with_config(client(
+            Service::Flights,
+            "FLIGHT_CONTROLLER",
+        )))
+        .with_clock(clock.clone()),
+    );
+    let delays_app = delay_orama::app::app(
+        DelayService::new(
+            BabelClient::with_config(client(Service::Babel, "TOWER_BABEL")),
+            AirportsClient::with_config(client(Service::Airports, "AIRPORT_SERVICE")),
+        )
+        .with_clock(clock.clone()),
+    );
+    let nexus_app = sky_nexus::app(NexusState {
+        upstreams: Upstreams {
+            airports: AirportsClient::with_config(client(Service::Airports, "AIRPORT_SERVICE")),
+            flights: FlightsClient::with_config(client(Service::Flights, "FLIGHT_SERVICE")),
+            satellites: SatellitesClient::with_config(client(
+                Service::Satellites,
+                "SATELLITE_SERVICE",
+            )),
+            babel: BabelClient::with_config(client(Service::Babel, "BABEL_SERVICE")),
+        },
+        clock: clock.clone(),
+    });
+    let apps = vec![
+        (Service::Airports, airports_app),
+        (Service::Flights, flights_app),
+        (Service::Satellites, satellites_app),
+        (Service::Babel, babel_app),
+        (Service::Delays, delays_app),
+        (Service::Nexus, nexus_app),
+    ];
+
+    let mut servers = JoinSet::new();
+    for ((service, listener), (_, app)) in listeners.into_iter().zip(&apps) {
+        info!(service = service.name(), url = %url(service), "Service started");
+        servers.spawn(axum::serve(listener, app.clone()).into_future());
+    }
+    let gateway_address = gateway_listener.local_addr()?;
+    servers.spawn(axum::serve(gateway_listener, gateway(apps)).into_future());
+    info!(url = %base_url(gateway_address), "Gateway started");
+
+    Ok(Launched {
+        gateway_address,
+        addresses,
+        servers,
+        _watcher: watcher,
+    })
+}
+
+fn airports_app() -> Result<(Router, Option<watcher::DataWatcher>), Error> {
+    let source = AirportSource::from_env();
+    let mut service = AirportService::from_source(source.clone())?;
+    match overlay_path_from_env() {
+        Some(path) => service = service.with_overlay_file(path)?,
+        None => warn!("AIRPORTS_OVERLAY_PATH is not set, airport changes are kept in memory only"),
+    }
+    info!(source = %source, airports = service.airports().len(), "Airport data loaded");
+
+    let watcher = watcher::watch(service.clone()).unwrap_or_else(|e| {
+        warn!(error = %e, "Cannot watch airport data file, reload it via the API");
+        None
+    });
+    let admin_token = AdminToken::from_env();
+    if !admin_token.is_configured() {
+        warn!("AIRPORTS_ADMIN_TOKEN is not set, admin endpoints are disabled");
+    }
+    let app = airport_anywhere::web_app(service, admin_token);
+    Ok((app, watcher))
+}
+
+async fn satellites_app(airports_url: String, clock: &SharedClock) -> Result<Router, Error> {
+    let satellite_repository = repository::from_env()?;
+    if let Ok(seed_path) = std::env::var(SATELLITE_SEED_PATH_ENV) {
+        info!(seed_path = %seed_path, "Loading satellite seed");
+        let seed = SatelliteSeed::from_file(&seed_path)?;
+        repository::apply_seed(satellite_repository.as_ref(), &seed).await?;
+    }
+    // Links in the pages point below the gateway prefix
+    let service = SatelliteService::with_repository(airports_url, satellite_repository)
+        .with_path_prefix(Service::Satellites.path_prefix())
+        .with_clock(clock.clone());
+    Ok(orbital_beacon::app::app(service))
+}
+
+fn flights_app(
+    airports_url: String,
+    satellites: ClientConfig,
+    clock: &SharedClock,
+) -> Result<Router, Error> {
+    let store = flight_controller::storage::from_env()?;
+    let service = FlightService::with_store(store)
+        .with_airport_directory(Arc::new(HttpAirportDirectory::new(airports_url)))
+        .with_satellites_client(SatellitesClient::with_config(satellites))
+        .with_clock(clock.clone());
+    Ok(flight_controller::app::app_with_service(service))
+}
+
+/// Service a request is routed to, with the path it reaches the service at
+struct Mount {
+    prefix: String,
+    strip: bool,
+    app: Router,
+}
+
+impl Mount {
+    fn matches(&self, path: &str) -> bool {
+        path.strip_prefix(&self.prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+
+    fn rewrite(&self, uri: &Uri) -> Uri {
+        if !self.strip {
+            return uri.clone();
+        }
+        let rest = &uri.path()[self.prefix.len()..];
+        let path = if rest.is_empty() { "/" } else { rest };
+        let path_and_query = match uri.query() {
+            Some(query) => format!("{path}?{query}"),
+            None => path.to_string(),
+        };
+        path_and_query.parse().unwrap_or_else(|_| uri.clone())
+    }
+}
+
+/// Router dispatching by path prefix like the Traefik rules in
+/// `compose.yml`: APIs and docs keep their path, everything else below a
+/// service prefix has it stripped.
+pub fn gateway(apps: Vec<(Service, Router)>) -> Router {
+    let mut mounts = Vec::new();
+    for (service, app) in apps {
+        for prefix in service.unstripped_prefixes() {
+            mounts.push(Mount {
+                prefix,
+                strip: false,
+                app: app.clone(),
+            });
+        }
+        mounts.push(Mount {
+            prefix: service.path_prefix(),
+            strip: true,
+            app,
+        });
+    }
+    // Like Traefik rule priorities, the most specific prefix wins
+    mounts.sort_by_key(|mount| std::cmp::Reverse(mount.prefix.len()));
+
+    Router::new()
+        .fallback(dispatch)
+        .with_state(Arc::new(mounts))
+}
+
+async fn dispatch(State(mounts): State<Arc<Vec<Mount>>>, mut request: Request) -> Response {
+    let Some(mount) = mounts
+        .iter()
+        .find(|mount| mount.matches(request.uri().path()))
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    *request.uri_mut() = mount.rewrite(request.uri());
+    // Like requests through Traefik, outside callers do not set the clocks
+    request
+        .headers_mut()
+        .insert("x-forwarded-for", HeaderValue::from_static("127.0.0.1"));
+    match mount.app.clone().oneshot(request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get};
+
+    fn echo(name: &'static str) -> Router {
+        Router::new().fallback(get(move |uri: Uri| async move { format!("{name} {uri}") }))
+    }
+
+    async fn route(gateway: &Router, uri: &str) -> (StatusCode, String) {
+        let response = gateway
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_gateway_routes_like_traefik() {
+        let gateway = gateway(
+            Service::ALL
+                .iter()
+                .map(|service| (*service, echo(service.name())))
+                .collect(),
+        );
+
+        let cases = [
+            ("/airports", "airports /"),
+            ("/airports/?q=frankfurt", "airports /?q=frankfurt"),
+            ("/airports/docs/", "airports /airports/docs/"),
+            ("/api/v1/airports/FRA", "airports /api/v1/airports/FRA"),
+            ("/api/v1/airlines", "flights /api/v1/airlines"),
+            ("/satellites/launch", "satellites /launch"),
+            ("/babel/health/ready", "babel /health/ready"),
+            ("/delays/FRA", "delays /FRA"),
+            ("/mcp/flights", "nexus /mcp/flights"),
+            ("/nexus/docs", "nexus /nexus/docs"),
+        ];
+        for (uri, expected) in cases {
+            assert_eq!(
+                route(&gateway, uri).await,
+                (StatusCode::OK, expected.to_string())
+            );
+        }
+
+        let (status, _) = route(&gateway, "/airportsandmore").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_config_defaults_to_compose_ports() {
+        let config = LauncherConfig::default().with_port(Service::Nexus, 8083);
+
+        assert_eq!(config.base_url(Service::Airports), "http://127.0.0.1:3000");
+        assert_eq!(config.base_url(Service::Delays), "http://127.0.0.1:3004");
+        assert_eq!(config.base_url(Service::Nexus), "http://127.0.0.1:8083");
+        assert_eq!(Service::Satellites.port_env(), "SATELLITES_PORT");
+    }
+}
@@ -1 +1 @@
-
+pub mod launcher;
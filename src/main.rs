@@ -0,0 +1,23 @@
+use sky_tracer_demo::launcher::{self, LauncherConfig, Service};
+use tracing::info;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let _guard = init_tracing_opentelemetry::TracingConfig::development().init_subscriber()?;
+
+    let launched = launcher::launch(&LauncherConfig::from_env()).await?;
+    let gateway_url = launched.gateway_url();
+    info!("All services running on {}", gateway_url);
+    for service in Service::ALL {
+        info!(
+            "- {:<10} {}{}  (direct: {})",
+            service.name(),
+            gateway_url,
+            service.path_prefix(),
+            launched.base_url(service)
+        );
+    }
+    info!("- {:<10} {}/mcp", "mcp", gateway_url);
+
+    launched.wait().await
+}
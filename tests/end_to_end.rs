@@ -0,0 +1,315 @@
+//! Scenarios of `sky-tracer-workspace/features` against all services
+//! running in-process, each calling the others over HTTP.
+
+mod harness;
+
+use chrono::{Duration, Utc};
+use harness::{Harness, McpSession};
+use reqwest::StatusCode;
+use serde_json::json;
+use sky_tracer::client::{ClientConfig, FlightsClient, SatellitesClient};
+use sky_tracer::clock::{FixedClock, SharedClock};
+use sky_tracer::model::satellite::SatelliteStatus;
+use sky_tracer::protocol::airports::SearchAirportsRequest;
+use sky_tracer::protocol::flights::FlightPositionResponse;
+use sky_tracer::protocol::health::{HealthResponse, HealthStatus};
+use sky_tracer::protocol::satellite::{CreateSatelliteRequest, UpdateSatelliteStatusRequest};
+use sky_tracer_demo::launcher::{self, LauncherConfig, Service};
+
+#[tokio::test]
+async fn airport_lookup() {
+    let harness = Harness::get();
+
+    let airport = harness.airports().get("FRA").await.unwrap();
+    assert!(airport.name.contains("Frankfurt"), "{}", airport.name);
+
+    let request = SearchAirportsRequest {
+        code: Some("JFK".to_string()),
+        ..Default::default()
+    };
+    let found = harness.airports().search(&request).await.unwrap();
+    assert!(found.airports.iter().any(|airport| airport.code == "JFK"));
+
+    let (status, page) = harness.get_text("/airports/?q=Frankfurt").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains("Frankfurt"));
+
+    // Sky Nexus answers through airport-anywhere
+    let (status, body) = harness.get_text("/api/v1/nexus/airports/FRA").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Frankfurt"), "{body}");
+}
+
+#[tokio::test]
+async fn flight_tracking_with_satellite_positioning() {
+    let harness = Harness::get();
+    harness.active_satellite("Tracking-1").await;
+    let flight = harness.airborne_flight("FRA", "JFK").await;
+    let flight_number = flight.flight_number.to_string();
+
+    // flight-controller → orbital-beacon → airport-anywhere
+    let position = harness.flights().position(&flight_number).await.unwrap();
+    assert_eq!(position.flight_number, flight_number);
+    assert!((35.0..70.0).contains(&position.latitude), "{position:?}");
+    assert!((-75.0..10.0).contains(&position.longitude), "{position:?}");
+
+    // tower-of-babel → flight-controller
+    let departing = harness.babel().flights_by_airport("FRA").await.unwrap();
+    assert!(departing
+        .iter()
+        .any(|departing| departing.flight_number == flight.flight_number));
+    let tracked = harness
+        .babel()
+        .flight_position(&flight_number)
+        .await
+        .unwrap();
+    assert_eq!(tracked.flight_number, flight_number);
+}
+
+#[tokio::test]
+async fn services_follow_the_time_of_the_caller() {
+    let harness = Harness::get();
+    harness.active_satellite("Clock-1").await;
+    let departure_time = Utc::now() + Duration::days(1);
+    let flight = harness
+        .create_flight(
+            "BCN",
+            "LIS",
+            departure_time,
+            departure_time + Duration::hours(2),
+        )
+        .await;
+    let flight_number = flight.flight_number.to_string();
+
+    // Not yet departed on the clock of this process
+    assert!(harness.flights().position(&flight_number).await.is_err());
+
+    // flight-controller hands the time of the caller on to orbital-beacon
+    let caller_time = departure_time + Duration::hours(1);
+    let url = format!(
+        "{}/api/v1/flights/{flight_number}/position",
+        harness.url(Service::Flights)
+    );
+    let response = harness.get_at(&url, caller_time).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let position: FlightPositionResponse = response.json().await.unwrap();
+    assert!(
+        (position.timestamp - caller_time).num_seconds().abs() < 30,
+        "{position:?}"
+    );
+}
+
+#[tokio::test]
+async fn services_run_on_the_configured_clock() {
+    let now = Utc::now() + Duration::days(30);
+    let clock = SharedClock::new(FixedClock(now));
+    // Services of their own next to the shared ones, stopped with this test
+    let launched = launcher::launch(&LauncherConfig::ephemeral().with_clock(clock.clone()))
+        .await
+        .unwrap();
+    let client = |service| ClientConfig::new(launched.base_url(service)).with_clock(clock.clone());
+
+    let satellites = SatellitesClient::with_config(client(Service::Satellites));
+    let satellite = satellites
+        .create(&CreateSatelliteRequest {
+            name: "Clock-2".to_string(),
+        })
+        .await
+        .unwrap();
+    satellites
+        .update_status(
+            satellite.id,
+            &UpdateSatelliteStatusRequest {
+                status: SatelliteStatus::Active,
+            },
+        )
+        .await
+        .unwrap();
+    let flights = FlightsClient::with_config(client(Service::Flights));
+    let mut request = harness::flight_request("OPO", "MAD", now - Duration::hours(1));
+    request.arrival_time = Some(now + Duration::hours(1));
+    let flight = flights.create(&request).await.unwrap();
+
+    let position = flights
+        .position(flight.flight_number.as_str())
+        .await
+        .unwrap();
+    assert_eq!(position.timestamp, now);
+}
+
+#[tokio::test]
+async fn unknown_airports_are_rejected_by_flight_controller() {
+    let harness = Harness::get();
+
+    let error = harness
+        .flights()
+        .create(&harness::flight_request("FRA", "QQQ", Utc::now()))
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST), "{error}");
+}
+
+#[tokio::test]
+async fn delay_monitoring() {
+    let harness = Harness::get();
+    harness.active_satellite("Delays-1").await;
+    let flight = harness.airborne_flight("MUC", "LHR").await;
+
+    // delay-orama → tower-of-babel → flight-controller → orbital-beacon
+    let (status, page) = harness.get_text("/delays/MUC").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains(flight.flight_number.as_str()), "{page}");
+}
+
+#[tokio::test]
+async fn ai_integration_over_mcp() {
+    let harness = Harness::get();
+    harness.active_satellite("Nexus-1").await;
+    let flight = harness.airborne_flight("CDG", "AMS").await;
+    let flight_number = flight.flight_number.to_string();
+    let mut mcp = harness.mcp().await;
+
+    let tools = mcp.request("tools/list", json!({})).await;
+    let names = tools["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect::<Vec<_>>();
+    assert!(names.contains(&"get_airport"), "{names:?}");
+
+    let airport = mcp.call_tool("get_airport", json!({"code": "CDG"})).await;
+    assert!(airport.contains("Code: CDG"), "{airport}");
+
+    let departing = mcp
+        .call_tool("get_flights_by_airport", json!({"airport_code": "CDG"}))
+        .await;
+    assert!(departing.contains(&flight_number), "{departing}");
+
+    let position = mcp
+        .call_tool(
+            "get_flight_position",
+            json!({"flight_number": flight_number}),
+        )
+        .await;
+    assert!(position.contains("Current Location"), "{position}");
+}
+
+/// Distances of the airports near London the tool lists as
+/// `Name (CODE) - 12.3 km - ...`
+async fn nearest_to_london(mcp: &mut McpSession, limit: usize) -> Vec<f64> {
+    let listed = mcp
+        .call_tool(
+            "find_nearest_airports",
+            json!({
+                "latitude": 51.5074,
+                "longitude": -0.1278,
+                "radius_km": 100.0,
+                "limit": limit
+            }),
+        )
+        .await;
+    listed
+        .lines()
+        .map(|line| {
+            let distance = line.split(" - ").nth(1).expect("distance is listed");
+            distance.trim_end_matches(" km").parse().unwrap()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn nearest_airports_over_mcp() {
+    let harness = Harness::get();
+    let mut mcp = harness.mcp().await;
+
+    let all = nearest_to_london(&mut mcp, 20).await;
+    assert!(all.len() > 3, "{all:?}");
+    assert!(all.windows(2).all(|pair| pair[0] <= pair[1]), "{all:?}");
+    assert!(all.iter().all(|distance| *distance <= 100.0), "{all:?}");
+
+    let limited = nearest_to_london(&mut mcp, 3).await;
+    assert_eq!(limited, all[..3]);
+}
+
+/// Pixel of the flight map at a geo-point, projected as the `flight-map`
+/// crate does onto 1000 × 500 px with a 30 px border
+fn map_pixel(latitude: f64, longitude: f64) -> (usize, usize) {
+    let x = 30.0 + (longitude + 180.0) / 360.0 * 940.0;
+    let y = 30.0 + (90.0 - latitude) / 180.0 * 440.0;
+    (x as usize, y as usize)
+}
+
+#[tokio::test]
+async fn flight_map_over_mcp() {
+    let harness = Harness::get();
+    harness.active_satellite("Map-1").await;
+    let now = Utc::now();
+    let flight = harness
+        .create_flight(
+            "HEL",
+            "ATH",
+            now - Duration::hours(3),
+            now + Duration::hours(1),
+        )
+        .await;
+    let mut mcp = harness.mcp().await;
+
+    let png = mcp
+        .call_tool_image(
+            "generate_flight_map",
+            json!({"airport_code": "HEL"}),
+            "image/png",
+        )
+        .await;
+    let mut reader = png::Decoder::new(std::io::Cursor::new(png))
+        .read_info()
+        .unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!((frame.width, frame.height), (1000, 500));
+    assert_eq!(frame.color_type, png::ColorType::Rgba);
+    let color = |(x, y): (usize, usize)| {
+        let offset = (y * frame.width as usize + x) * 4;
+        pixels[offset..offset + 3].to_vec()
+    };
+
+    // Both airports of the route are pinned
+    for code in ["HEL", "ATH"] {
+        let airport = harness.airports().get(code).await.unwrap();
+        assert_eq!(
+            color(map_pixel(
+                airport.position.latitude,
+                airport.position.longitude
+            )),
+            [0x60, 0xa5, 0xfa],
+            "pin of {code}"
+        );
+    }
+    // The flight is shown where it is, three quarters along its route
+    let position = harness
+        .flights()
+        .position(flight.flight_number.as_str())
+        .await
+        .unwrap();
+    assert_eq!(
+        color(map_pixel(position.latitude, position.longitude)),
+        [0xfb, 0xbf, 0x24],
+        "position of {}",
+        flight.flight_number
+    );
+}
+
+#[tokio::test]
+async fn all_dependencies_are_ready() {
+    let harness = Harness::get();
+
+    let (status, body) = harness.get_text("/nexus/health/ready").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let report: HealthResponse = serde_json::from_str(&body).unwrap();
+    assert_eq!(report.dependencies.len(), 4);
+    assert!(report
+        .dependencies
+        .iter()
+        .all(|dependency| dependency.status == HealthStatus::Up));
+}
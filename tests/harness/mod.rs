@@ -0,0 +1,299 @@
+//! Services of the demo started in-process on ephemeral ports.
+//!
+//! All tests of a binary share one set of services, so they start once. They
+//! run on a runtime of their own, so they outlive the runtime of the test
+//! that started them. Tests keep apart by using their own airports.
+
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use sky_tracer::client::{AirportsClient, BabelClient, FlightsClient, SatellitesClient};
+use sky_tracer::clock::{ScaledClock, SharedClock, SIMULATED_TIME_HEADER};
+use sky_tracer::model::satellite::SatelliteStatus;
+use sky_tracer::protocol::flights::{CreateFlightRequest, FlightResponse};
+use sky_tracer::protocol::satellite::{
+    CreateSatelliteRequest, SatelliteResponse, UpdateSatelliteStatusRequest,
+};
+use sky_tracer_demo::launcher::{self, LauncherConfig, Service};
+use std::sync::OnceLock;
+
+pub struct Harness {
+    gateway_url: String,
+    urls: Vec<(Service, String)>,
+    http: reqwest::Client,
+}
+
+impl Harness {
+    /// Services of this test binary, started by the first caller
+    pub fn get() -> &'static Harness {
+        static HARNESS: OnceLock<Harness> = OnceLock::new();
+        HARNESS.get_or_init(Self::start)
+    }
+
+    fn start() -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().expect("runtime starts");
+            runtime.block_on(async move {
+                // A simulation at real speed, so services follow the time
+                // of their callers
+                let now = Utc::now();
+                let clock = SharedClock::new(ScaledClock::new(now, now, 1.0));
+                let launched = launcher::launch(&LauncherConfig::ephemeral().with_clock(clock))
+                    .await
+                    .expect("services start");
+                let urls = Service::ALL
+                    .iter()
+                    .map(|service| (*service, launched.base_url(*service)))
+                    .collect();
+                sender
+                    .send((launched.gateway_url(), urls))
+                    .expect("harness waits");
+                launched.wait().await.expect("services keep running");
+            });
+        });
+        let (gateway_url, urls) = receiver.recv().expect("services start");
+
+        Self {
+            gateway_url,
+            urls,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// URL the service listens on directly
+    pub fn url(&self, service: Service) -> &str {
+        self.urls
+            .iter()
+            .find(|(started, _)| *started == service)
+            .map(|(_, url)| url.as_str())
+            .expect("all services are started")
+    }
+
+    pub fn airports(&self) -> AirportsClient {
+        AirportsClient::new(self.url(Service::Airports))
+    }
+
+    pub fn flights(&self) -> FlightsClient {
+        FlightsClient::new(self.url(Service::Flights))
+    }
+
+    pub fn satellites(&self) -> SatellitesClient {
+        SatellitesClient::new(self.url(Service::Satellites))
+    }
+
+    pub fn babel(&self) -> BabelClient {
+        BabelClient::new(self.url(Service::Babel))
+    }
+
+    /// Satellite that is active, so flight positions can be calculated
+    pub async fn active_satellite(&self, name: &str) -> SatelliteResponse {
+        let satellites = self.satellites();
+        let satellite = satellites
+            .create(&CreateSatelliteRequest {
+                name: name.to_string(),
+            })
+            .await
+            .expect("satellite is created");
+        satellites
+            .update_status(
+                satellite.id,
+                &UpdateSatelliteStatusRequest {
+                    status: SatelliteStatus::Active,
+                },
+            )
+            .await
+            .expect("satellite is activated")
+    }
+
+    pub async fn create_flight(
+        &self,
+        departure: &str,
+        arrival: &str,
+        departure_time: DateTime<Utc>,
+        arrival_time: DateTime<Utc>,
+    ) -> FlightResponse {
+        let mut request = flight_request(departure, arrival, departure_time);
+        request.arrival_time = Some(arrival_time);
+        self.flights()
+            .create(&request)
+            .await
+            .expect("flight is created")
+    }
+
+    /// Flight that departed an hour ago and is still in the air
+    pub async fn airborne_flight(&self, departure: &str, arrival: &str) -> FlightResponse {
+        let now = Utc::now();
+        self.create_flight(
+            departure,
+            arrival,
+            now - Duration::hours(1),
+            now + Duration::hours(6),
+        )
+        .await
+    }
+
+    /// Response of `url` to a caller whose clock shows `time`
+    pub async fn get_at(&self, url: &str, time: DateTime<Utc>) -> reqwest::Response {
+        self.http
+            .get(url)
+            .header(SIMULATED_TIME_HEADER, time.to_rfc3339())
+            .send()
+            .await
+            .expect("service answers")
+    }
+
+    /// Page or document served by the gateway at `path`
+    pub async fn get_text(&self, path: &str) -> (StatusCode, String) {
+        let response = self
+            .http
+            .get(format!("{}{}", self.gateway_url, path))
+            .send()
+            .await
+            .expect("gateway answers");
+        let status = response.status();
+        (status, response.text().await.expect("body is text"))
+    }
+
+    /// Initialized session with the MCP endpoint of the gateway
+    pub async fn mcp(&self) -> McpSession {
+        let mut session = McpSession {
+            url: format!("{}/mcp", self.gateway_url),
+            http: self.http.clone(),
+            session_id: None,
+            next_id: 1,
+        };
+        session
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": "2025-03-26",
+                    "capabilities": {},
+                    "clientInfo": {"name": "end-to-end", "version": "0.1.0"}
+                }),
+            )
+            .await;
+        session.notify("notifications/initialized").await;
+        session
+    }
+}
+
+/// Lufthansa flight the flight controller numbers itself
+pub fn flight_request(
+    departure: &str,
+    arrival: &str,
+    departure_time: DateTime<Utc>,
+) -> CreateFlightRequest {
+    CreateFlightRequest {
+        aircraft_number: "D-AIBL".parse().unwrap(),
+        departure: departure.parse().unwrap(),
+        arrival: arrival.parse().unwrap(),
+        departure_time,
+        arrival_time: None,
+        airline: None,
+        flight_number: None,
+    }
+}
+
+/// JSON-RPC over the streamable HTTP transport of MCP
+pub struct McpSession {
+    url: String,
+    http: reqwest::Client,
+    session_id: Option<String>,
+    next_id: u64,
+}
+
+impl McpSession {
+    fn post(&self, body: Value) -> reqwest::RequestBuilder {
+        let request = self
+            .http
+            .post(&self.url)
+            .header(ACCEPT, "application/json, text/event-stream")
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body);
+        match &self.session_id {
+            Some(session_id) => request.header("mcp-session-id", session_id),
+            None => request,
+        }
+    }
+
+    /// Result of a request, answered as JSON or as server-sent events
+    pub async fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        let response = self
+            .post(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+            .send()
+            .await
+            .expect("MCP endpoint answers");
+        assert!(
+            response.status().is_success(),
+            "{method}: {}",
+            response.status()
+        );
+        if let Some(session_id) = response.headers().get("mcp-session-id") {
+            self.session_id = Some(session_id.to_str().unwrap().to_string());
+        }
+
+        let body = response.text().await.expect("body is text");
+        let message = std::iter::once(body.as_str())
+            .chain(body.lines().filter_map(|line| line.strip_prefix("data:")))
+            .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+            .find(|message| message["id"] == id)
+            .unwrap_or_else(|| panic!("no answer to {method} in {body}"));
+        assert!(message["error"].is_null(), "{method}: {}", message["error"]);
+        message["result"].clone()
+    }
+
+    async fn notify(&self, method: &str) {
+        let response = self
+            .post(json!({"jsonrpc": "2.0", "method": method}))
+            .send()
+            .await
+            .expect("MCP endpoint answers");
+        assert!(
+            response.status().is_success(),
+            "{method}: {}",
+            response.status()
+        );
+    }
+
+    /// Content the tool answers with
+    async fn call_tool_content(&mut self, name: &str, arguments: Value) -> Vec<Value> {
+        let result = self
+            .request("tools/call", json!({"name": name, "arguments": arguments}))
+            .await;
+        assert_ne!(result["isError"], true, "{name}: {result}");
+        result["content"].as_array().cloned().unwrap_or_default()
+    }
+
+    /// Text the tool answers with
+    pub async fn call_tool(&mut self, name: &str, arguments: Value) -> String {
+        self.call_tool_content(name, arguments)
+            .await
+            .iter()
+            .filter_map(|content| content["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Decoded image of type `mime_type` the tool answers with
+    pub async fn call_tool_image(
+        &mut self,
+        name: &str,
+        arguments: Value,
+        mime_type: &str,
+    ) -> Vec<u8> {
+        let content = self.call_tool_content(name, arguments).await;
+        let data = content
+            .iter()
+            .find(|content| content["type"] == "image" && content["mimeType"] == mime_type)
+            .and_then(|content| content["data"].as_str())
+            .unwrap_or_else(|| panic!("{name} answers without {mime_type} image: {content:?}"));
+        base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .expect("image is base64")
+    }
+}